@@ -255,12 +255,16 @@ pub fn is_timewait_command(elm: i32) -> bool {
 }
 
 pub fn is_display_capture_stub(elm: i32) -> bool {
-    elm == crate::elm::global::ELM_GLOBAL_DISP
-        || elm == crate::elm::global::ELM_GLOBAL_FRAME
-        || elm == crate::elm::global::ELM_GLOBAL_CAPTURE
+    elm == crate::elm::global::ELM_GLOBAL_DISP || elm == crate::elm::global::ELM_GLOBAL_FRAME
+}
+
+pub fn is_capture_command(elm: i32) -> bool {
+    elm == crate::elm::global::ELM_GLOBAL_CAPTURE
         || elm == crate::elm::global::ELM_GLOBAL_CAPTURE_FROM_FILE
         || elm == crate::elm::global::ELM_GLOBAL_CAPTURE_FREE
+        || elm == crate::elm::global::ELM_GLOBAL_CAPTURE_FOR_OBJECT
         || elm == crate::elm::global::ELM_GLOBAL_CAPTURE_FOR_OBJECT_FREE
+        || elm == crate::elm::global::ELM_GLOBAL_CAPTURE_FOR_LOCAL_SAVE
         || elm == crate::elm::global::ELM_GLOBAL_CAPTURE_FOR_TWEET
         || elm == crate::elm::global::ELM_GLOBAL_CAPTURE_FREE_FOR_TWEET
 }
@@ -459,11 +463,6 @@ pub fn is_iapp_dummy(elm: i32) -> bool {
         || elm == crate::elm::global::ELM_GLOBAL__IAPP_DUMMY2_STR
 }
 
-pub fn is_capture_stub_extra(elm: i32) -> bool {
-    elm == crate::elm::global::ELM_GLOBAL_CAPTURE_FOR_OBJECT
-        || elm == crate::elm::global::ELM_GLOBAL_CAPTURE_FOR_LOCAL_SAVE
-}
-
 pub fn is_global_syscom(elm: i32) -> bool {
     elm == crate::elm::global::ELM_GLOBAL_SYSCOM
 }
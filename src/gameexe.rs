@@ -5,6 +5,11 @@ use std::path::{Path, PathBuf};
 use anyhow::{Context, Result, bail};
 use encoding_rs::SHIFT_JIS;
 
+mod button;
+mod mwnd;
+pub use button::*;
+pub use mwnd::*;
+
 const GAMEEXE_DAT_ANGOU_CODE: [u8; 256] = hex_literal::hex!(
     "d829b9163d1a76d0879b2d0c7bd1a919229f91736a35b17ed1b5e7e6d5f506d6babff3453ff161dd4c676a6f74ec7a6f26740edb274ca5f10e2d70c4405d4fda9ec5497bbde8dfeecaf492dee47610dd2a52dc734e548c303d9ab29bb8932955fa7ac9da1097e5b62302dd384c9b1f9ad549e9340f282d1b52395c368956a79614be2ec53e085f47a9df889fd4cc691f309fe7cd8045f3e72a1d16b2f154c86c2b0dd465f7e336d4a53bd1794c54f02ab4b256452eab7b88c5fa74ad03b89ed5f56fdcfa444931f68332ffc2b1e9e1983d6f310dacb108839d0d10d141f900ba1acf1371e486212f2365c345a0c392489deadd312ce9e21022aae1ad2cc42d7f"
 );
@@ -31,6 +36,10 @@ pub struct GameexeConfig {
     pub dummy_check_str: Option<String>,
     pub dummy_check_ok_str: Option<String>,
     pub user_config: GameexeUserConfig,
+    /// `#MWND` / `#WAKU` message window layout.
+    pub mwnd: GameexeMwndConfig,
+    /// `#BUTTON` templates and `#SE` system sounds.
+    pub button: GameexeButtonConfig,
     /// Parsed normalized entries for all directives (including repeated keys).
     pub entries: Vec<GameexeEntry>,
     /// Fast index for directive lookups; values are indexes into `entries`.
@@ -271,6 +280,9 @@ fn parse_gameexe_ini(text: &str) -> Result<GameexeConfig> {
         }
     }
 
+    let mwnd = parse_mwnd_config(&entries);
+    let button = parse_button_config(&entries);
+
     Ok(GameexeConfig {
         game_id,
         game_name,
@@ -291,6 +303,8 @@ fn parse_gameexe_ini(text: &str) -> Result<GameexeConfig> {
         dummy_check_str,
         dummy_check_ok_str,
         user_config,
+        mwnd,
+        button,
         entries,
         entry_index,
     })
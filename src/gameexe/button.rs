@@ -0,0 +1,351 @@
+use std::collections::BTreeMap;
+
+use super::mwnd::{GAMEEXE_LIST_MAX, group_indexed, int_at, set_int, set_pair};
+use super::{GameexeEntry, parse_text};
+
+/// Button look picked from a `#BUTTON.ACTION` entry, in C++
+/// `TNM_BUTTON_STATE_*` order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameexeButtonState {
+    Normal,
+    Hit,
+    Push,
+    Select,
+    Disable,
+}
+
+impl GameexeButtonState {
+    const KEYS: [&'static str; 5] = ["NORMAL", "HIT", "PUSH", "SELECT", "DISABLE"];
+}
+
+/// One state of a `#BUTTON.ACTION.NNN` entry:
+/// `= rep_pat_no, rep_x, rep_y, rep_tr, rep_bright, rep_dark`.
+///
+/// C++ reference: tnm_ini.cpp `S_tnm_ini_button_action_pat`. The pattern and
+/// position are added to the object's own, `tr` scales its opacity (255 =
+/// unchanged) and bright/dark are added to its color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GameexeButtonActionPat {
+    pub pat_no: i32,
+    pub pos: (i32, i32),
+    pub tr: i32,
+    pub bright: i32,
+    pub dark: i32,
+}
+
+impl Default for GameexeButtonActionPat {
+    fn default() -> Self {
+        Self {
+            pat_no: 0,
+            pos: (0, 0),
+            tr: 255,
+            bright: 0,
+            dark: 0,
+        }
+    }
+}
+
+/// `#BUTTON.ACTION.NNN.{NORMAL,HIT,PUSH,SELECT,DISABLE}`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct GameexeButtonAction {
+    pub pats: [GameexeButtonActionPat; 5],
+}
+
+impl GameexeButtonAction {
+    pub fn pat(&self, state: GameexeButtonState) -> &GameexeButtonActionPat {
+        &self.pats[state as usize]
+    }
+}
+
+/// `#BUTTON.SE.NNN.{HIT,PUSH,DECIDE}`: `#SE` numbers played as the cursor
+/// enters, presses and decides a button (-1 = silent).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GameexeButtonSe {
+    pub hit: i32,
+    pub push: i32,
+    pub decide: i32,
+}
+
+impl Default for GameexeButtonSe {
+    fn default() -> Self {
+        Self {
+            hit: -1,
+            push: -1,
+            decide: -1,
+        }
+    }
+}
+
+/// `#BTNSELITEM.NNN.*`: button settings of selection-button items.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct GameexeBtnSelItem {
+    pub action_no: i32,
+    pub se_no: i32,
+}
+
+/// `#SELBTN.NNN.*` selection button template.
+///
+/// C++ reference: tnm_ini.cpp `S_tnm_ini_sel_btn`. Items are laid out from
+/// `base_pos` in `rep_pos` steps; the item buttons use `#BTNSELITEM` of the
+/// same number for their looks and sounds. Colors are `#COLOR_TABLE` indices.
+///
+/// Keys a template leaves out stay 0 (empty for `base_file`): the engine's
+/// defaults are not known here.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct GameexeSelBtn {
+    /// Item background g00 (empty = text only).
+    pub base_file: String,
+    pub base_pos: (i32, i32),
+    pub rep_pos: (i32, i32),
+    /// 0 = items start at `base_pos.x`, 1 = centered on it, 2 = end at it.
+    pub x_align: i32,
+    /// Same for the item block against `base_pos.y`.
+    pub y_align: i32,
+    /// Text position inside an item.
+    pub moji_pos: (i32, i32),
+    pub moji_size: i32,
+    /// 0 = text starts at `moji_pos.x`, 1 = centered in the item.
+    pub moji_x_align: i32,
+    pub moji_color: i32,
+    pub moji_hit_color: i32,
+    pub moji_disable_color: i32,
+    /// Open/close animation keys; parsed but not drawn, as their types are
+    /// not known here.
+    pub open_anime_type: i32,
+    pub open_anime_time: i32,
+    pub close_anime_type: i32,
+    pub close_anime_time: i32,
+}
+
+/// Button templates and system sounds of one Gameexe.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct GameexeButtonConfig {
+    pub actions: Vec<GameexeButtonAction>,
+    pub ses: Vec<GameexeButtonSe>,
+    /// `#SE.NNN` system sound files.
+    pub se_files: Vec<String>,
+    pub sel_items: Vec<GameexeBtnSelItem>,
+    pub selbtns: Vec<GameexeSelBtn>,
+}
+
+impl GameexeButtonConfig {
+    pub fn action(&self, action_no: i32) -> Option<&GameexeButtonAction> {
+        usize::try_from(action_no)
+            .ok()
+            .and_then(|i| self.actions.get(i))
+    }
+
+    pub fn se(&self, se_no: i32) -> Option<&GameexeButtonSe> {
+        usize::try_from(se_no).ok().and_then(|i| self.ses.get(i))
+    }
+
+    /// `#SE.NNN` file name; `None` when undefined or empty.
+    pub fn se_file(&self, sys_se_no: i32) -> Option<&str> {
+        usize::try_from(sys_se_no)
+            .ok()
+            .and_then(|i| self.se_files.get(i))
+            .map(String::as_str)
+            .filter(|s| !s.is_empty())
+    }
+
+    pub fn sel_item(&self, item_no: i32) -> Option<&GameexeBtnSelItem> {
+        usize::try_from(item_no)
+            .ok()
+            .and_then(|i| self.sel_items.get(i))
+    }
+
+    pub fn selbtn(&self, template_no: i32) -> Option<&GameexeSelBtn> {
+        usize::try_from(template_no)
+            .ok()
+            .and_then(|i| self.selbtns.get(i))
+    }
+}
+
+fn parse_action(fields: &BTreeMap<&str, &[String]>) -> GameexeButtonAction {
+    let mut a = GameexeButtonAction::default();
+    for (pat, key) in a.pats.iter_mut().zip(GameexeButtonState::KEYS) {
+        let Some(v) = fields.get(key) else {
+            continue;
+        };
+        pat.pat_no = int_at(v, 0).unwrap_or(pat.pat_no);
+        pat.pos = (
+            int_at(v, 1).unwrap_or(pat.pos.0),
+            int_at(v, 2).unwrap_or(pat.pos.1),
+        );
+        pat.tr = int_at(v, 3).unwrap_or(pat.tr);
+        pat.bright = int_at(v, 4).unwrap_or(pat.bright);
+        pat.dark = int_at(v, 5).unwrap_or(pat.dark);
+    }
+    a
+}
+
+fn parse_se(fields: &BTreeMap<&str, &[String]>) -> GameexeButtonSe {
+    let mut se = GameexeButtonSe::default();
+    set_int(fields, "HIT", &mut se.hit);
+    set_int(fields, "PUSH", &mut se.push);
+    set_int(fields, "DECIDE", &mut se.decide);
+    se
+}
+
+fn parse_sel_item(fields: &BTreeMap<&str, &[String]>) -> GameexeBtnSelItem {
+    let mut item = GameexeBtnSelItem::default();
+    set_int(fields, "ACTION_NO", &mut item.action_no);
+    set_int(fields, "SE_NO", &mut item.se_no);
+    item
+}
+
+fn parse_selbtn(fields: &BTreeMap<&str, &[String]>) -> GameexeSelBtn {
+    let mut t = GameexeSelBtn {
+        base_file: fields
+            .get("BASE_FILE")
+            .and_then(|v| v.first())
+            .map(|s| parse_text(s))
+            .unwrap_or_default(),
+        ..Default::default()
+    };
+    set_pair(fields, "BASE_POS", &mut t.base_pos);
+    set_pair(fields, "REP_POS", &mut t.rep_pos);
+    set_int(fields, "X_ALIGN", &mut t.x_align);
+    set_int(fields, "Y_ALIGN", &mut t.y_align);
+    set_pair(fields, "MOJI_POS", &mut t.moji_pos);
+    set_int(fields, "MOJI_SIZE", &mut t.moji_size);
+    set_int(fields, "MOJI_X_ALIGN", &mut t.moji_x_align);
+    set_int(fields, "MOJI_COLOR", &mut t.moji_color);
+    set_int(fields, "MOJI_HIT_COLOR", &mut t.moji_hit_color);
+    set_int(fields, "MOJI_DISABLE_COLOR", &mut t.moji_disable_color);
+    set_int(fields, "OPEN_ANIME_TYPE", &mut t.open_anime_type);
+    set_int(fields, "OPEN_ANIME_TIME", &mut t.open_anime_time);
+    set_int(fields, "CLOSE_ANIME_TYPE", &mut t.close_anime_type);
+    set_int(fields, "CLOSE_ANIME_TIME", &mut t.close_anime_time);
+    t
+}
+
+/// Parse the `#BUTTON.ACTION`, `#BUTTON.SE`, `#SE`, `#BTNSELITEM` and
+/// `#SELBTN` directives.
+///
+/// Lists are sized by their `.CNT` key when present, otherwise by the highest
+/// defined index, up to `GAMEEXE_LIST_MAX`.
+pub(super) fn parse_button_config(entries: &[GameexeEntry]) -> GameexeButtonConfig {
+    let count = |key: &str, defined: Option<usize>| {
+        entries
+            .iter()
+            .rev()
+            .find(|e| e.key == key)
+            .and_then(|e| int_at(&e.values, 0))
+            .map(|n| n.max(0) as usize)
+            .unwrap_or_else(|| defined.map(|i| i + 1).unwrap_or(0))
+            .min(GAMEEXE_LIST_MAX)
+    };
+    fn indexed<T: Default>(
+        fields: &BTreeMap<usize, BTreeMap<&str, &[String]>>,
+        cnt: usize,
+        parse: fn(&BTreeMap<&str, &[String]>) -> T,
+    ) -> Vec<T> {
+        (0..cnt)
+            .map(|i| fields.get(&i).map(parse).unwrap_or_default())
+            .collect()
+    }
+
+    let action_fields = group_indexed(entries, "BUTTON.ACTION.");
+    let action_cnt = count(
+        "BUTTON.ACTION.CNT",
+        action_fields.keys().next_back().copied(),
+    );
+    let se_fields = group_indexed(entries, "BUTTON.SE.");
+    let se_cnt = count("BUTTON.SE.CNT", se_fields.keys().next_back().copied());
+    let item_fields = group_indexed(entries, "BTNSELITEM.");
+    let item_cnt = count("BTNSELITEM.CNT", item_fields.keys().next_back().copied());
+    let selbtn_fields = group_indexed(entries, "SELBTN.");
+    let selbtn_cnt = count("SELBTN.CNT", selbtn_fields.keys().next_back().copied());
+
+    let mut se_files = Vec::new();
+    for e in entries {
+        let Some(idx) = e
+            .key
+            .strip_prefix("SE.")
+            .and_then(|n| n.parse::<usize>().ok())
+        else {
+            continue;
+        };
+        if idx >= GAMEEXE_LIST_MAX {
+            continue;
+        }
+        if se_files.len() <= idx {
+            se_files.resize(idx + 1, String::new());
+        }
+        se_files[idx] = e.values.first().map(|v| parse_text(v)).unwrap_or_default();
+    }
+
+    GameexeButtonConfig {
+        actions: indexed(&action_fields, action_cnt, parse_action),
+        ses: indexed(&se_fields, se_cnt, parse_se),
+        se_files,
+        sel_items: indexed(&item_fields, item_cnt, parse_sel_item),
+        selbtns: indexed(&selbtn_fields, selbtn_cnt, parse_selbtn),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(key: &str, values: &[&str]) -> GameexeEntry {
+        GameexeEntry {
+            key: key.to_string(),
+            raw_value: values.join(","),
+            values: values.iter().map(|v| v.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn parses_actions_ses_and_templates() {
+        let cfg = parse_button_config(&[
+            entry("BUTTON.ACTION.1.HIT", &["1", "2", "-3", "128", "40"]),
+            entry("BUTTON.SE.0.DECIDE", &["5"]),
+            entry("SE.002", &["\"sys_ok\""]),
+            entry("BTNSELITEM.0.ACTION_NO", &["1"]),
+            entry("SELBTN.0.BASE_FILE", &["\"sel_base\""]),
+            entry("SELBTN.0.REP_POS", &["0", "64"]),
+            entry("SELBTN.0.MOJI_COLOR", &["3"]),
+        ]);
+        assert_eq!(cfg.actions.len(), 2);
+        let hit = cfg.action(1).unwrap().pat(GameexeButtonState::Hit);
+        assert_eq!(
+            *hit,
+            GameexeButtonActionPat {
+                pat_no: 1,
+                pos: (2, -3),
+                tr: 128,
+                bright: 40,
+                dark: 0,
+            }
+        );
+        assert_eq!(
+            *cfg.action(1).unwrap().pat(GameexeButtonState::Normal),
+            GameexeButtonActionPat::default()
+        );
+        assert_eq!(cfg.se(0).map(|se| (se.hit, se.decide)), Some((-1, 5)));
+        assert_eq!(cfg.se_file(2), Some("sys_ok"));
+        assert_eq!(cfg.se_file(0), None);
+        assert_eq!(cfg.sel_item(0).map(|item| item.action_no), Some(1));
+
+        let selbtn = cfg.selbtn(0).unwrap();
+        assert_eq!(selbtn.base_file, "sel_base");
+        assert_eq!(selbtn.rep_pos, (0, 64));
+        assert_eq!(selbtn.moji_color, 3);
+        // Keys the template leaves out stay 0.
+        assert_eq!((selbtn.moji_hit_color, selbtn.moji_size), (0, 0));
+    }
+
+    #[test]
+    fn huge_counts_and_indices_are_bounded() {
+        let cfg = parse_button_config(&[
+            entry("BUTTON.ACTION.CNT", &["2000000000"]),
+            entry("BUTTON.SE.99999999.HIT", &["1"]),
+            entry("SE.99999999", &["\"boom\""]),
+        ]);
+        assert_eq!(cfg.actions.len(), GAMEEXE_LIST_MAX);
+        assert!(cfg.ses.is_empty());
+        assert!(cfg.se_files.is_empty());
+    }
+}
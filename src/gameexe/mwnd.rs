@@ -0,0 +1,376 @@
+use std::collections::BTreeMap;
+
+use super::{GameexeEntry, parse_int, parse_text};
+
+/// `#MWND.NNN.*` message window definition.
+///
+/// C++ reference: tnm_ini.cpp `S_tnm_ini_mwnd`. Positions are stage pixels;
+/// `message_pos`, `name_window_pos` are relative to the window, `name_msg_pos`
+/// to the name window. Colors are `#COLOR_TABLE` indices.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameexeMwnd {
+    pub waku_no: i32,
+    pub window_pos: (i32, i32),
+    /// `(0, 0)` = size of the waku image.
+    pub window_size: (i32, i32),
+    pub message_pos: (i32, i32),
+    /// Characters per line / lines per page.
+    pub moji_cnt: (i32, i32),
+    pub moji_size: i32,
+    pub moji_space: (i32, i32),
+    pub moji_color: i32,
+    pub shadow_color: i32,
+    pub fuchi_color: i32,
+    /// 0 = no name, 1 = name window, 2 = `【name】` at the head of the message.
+    pub name_disp_mode: i32,
+    /// Bracket the name (`【】`) when it is drawn in the name window.
+    pub name_bracket: bool,
+    /// -1 = no name window frame.
+    pub name_waku_no: i32,
+    pub name_window_pos: (i32, i32),
+    pub name_window_size: (i32, i32),
+    pub name_msg_pos: (i32, i32),
+    pub name_moji_size: i32,
+    pub name_moji_space: (i32, i32),
+    pub name_moji_color: i32,
+    /// Hide the name window while a face graphic is shown.
+    pub face_hide_name: bool,
+    pub open_anime_type: i32,
+    pub open_anime_time: i32,
+    pub close_anime_type: i32,
+    pub close_anime_time: i32,
+}
+
+impl Default for GameexeMwnd {
+    fn default() -> Self {
+        Self {
+            waku_no: 0,
+            window_pos: (0, 0),
+            window_size: (0, 0),
+            message_pos: (32, 32),
+            moji_cnt: (26, 3),
+            moji_size: 24,
+            moji_space: (0, 8),
+            moji_color: 0,
+            shadow_color: 1,
+            fuchi_color: 1,
+            name_disp_mode: 1,
+            name_bracket: false,
+            name_waku_no: -1,
+            name_window_pos: (24, -40),
+            name_window_size: (0, 0),
+            name_msg_pos: (16, 8),
+            name_moji_size: 24,
+            name_moji_space: (0, 0),
+            name_moji_color: 0,
+            face_hide_name: false,
+            open_anime_type: 0,
+            open_anime_time: 0,
+            close_anime_type: 0,
+            close_anime_time: 0,
+        }
+    }
+}
+
+/// `#WAKU.NNN.*` message window frame (C++ tnm_ini.cpp `S_tnm_ini_waku`).
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct GameexeWaku {
+    /// Frame image drawn at the window position (empty = none).
+    pub waku_file: String,
+    /// Translucent backing image under the frame (empty = plain `filter_color`).
+    pub filter_file: String,
+    /// Filter inset from the window rect: left, top, right, bottom.
+    pub filter_margin: (i32, i32, i32, i32),
+    /// RGBA; alpha 0 disables the plain filter.
+    pub filter_color: [u8; 4],
+    /// Face graphic positions, relative to the window, by face number.
+    pub face_pos: Vec<(i32, i32)>,
+}
+
+/// Message window definitions of one Gameexe.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct GameexeMwndConfig {
+    pub mwnds: Vec<GameexeMwnd>,
+    pub wakus: Vec<GameexeWaku>,
+    /// `#COLOR_TABLE.NNN` RGB entries.
+    pub color_table: Vec<[u8; 3]>,
+    /// `#DEFAULT_MWND_NO`: window used while the script has not chosen one.
+    pub default_mwnd_no: i32,
+}
+
+impl GameexeMwndConfig {
+    pub fn mwnd(&self, mwnd_no: i32) -> Option<&GameexeMwnd> {
+        usize::try_from(mwnd_no)
+            .ok()
+            .and_then(|i| self.mwnds.get(i))
+    }
+
+    pub fn waku(&self, waku_no: i32) -> Option<&GameexeWaku> {
+        usize::try_from(waku_no)
+            .ok()
+            .and_then(|i| self.wakus.get(i))
+    }
+
+    /// `#COLOR_TABLE` entry; index 0 defaults to white and others to black.
+    pub fn color(&self, color_no: i32) -> [u8; 3] {
+        usize::try_from(color_no)
+            .ok()
+            .and_then(|i| self.color_table.get(i).copied())
+            .unwrap_or(if color_no == 0 {
+                [255, 255, 255]
+            } else {
+                [0, 0, 0]
+            })
+    }
+
+    /// Resolve `-1` (no window chosen yet) to `default_mwnd_no`.
+    pub fn resolve_mwnd_no(&self, mwnd_no: i32) -> i32 {
+        if mwnd_no < 0 {
+            self.default_mwnd_no
+        } else {
+            mwnd_no
+        }
+    }
+}
+
+/// Upper bound on indices and `.CNT` sizes of Gameexe lists, so a corrupt or
+/// hostile Gameexe.dat cannot make the parser allocate without limit.
+pub(super) const GAMEEXE_LIST_MAX: usize = 1024;
+
+/// Collect `#PREFIX.NNN.FIELD` entries into per-index maps of `FIELD -> values`.
+/// Indices at or above `GAMEEXE_LIST_MAX` are dropped.
+pub(super) fn group_indexed<'a>(
+    entries: &'a [GameexeEntry],
+    prefix: &str,
+) -> BTreeMap<usize, BTreeMap<&'a str, &'a [String]>> {
+    let mut out: BTreeMap<usize, BTreeMap<&str, &[String]>> = BTreeMap::new();
+    for e in entries {
+        let Some(rest) = e.key.strip_prefix(prefix) else {
+            continue;
+        };
+        let Some((idx, field)) = rest.split_once('.') else {
+            continue;
+        };
+        let Ok(idx) = idx.parse::<usize>() else {
+            continue;
+        };
+        if idx >= GAMEEXE_LIST_MAX {
+            continue;
+        }
+        out.entry(idx)
+            .or_default()
+            .insert(field, e.values.as_slice());
+    }
+    out
+}
+
+pub(super) fn int_at(values: &[String], i: usize) -> Option<i32> {
+    values.get(i).and_then(|v| parse_int(v))
+}
+
+pub(super) fn set_int(fields: &BTreeMap<&str, &[String]>, key: &str, dst: &mut i32) {
+    if let Some(v) = fields.get(key).and_then(|v| int_at(v, 0)) {
+        *dst = v;
+    }
+}
+
+pub(super) fn set_pair(fields: &BTreeMap<&str, &[String]>, key: &str, dst: &mut (i32, i32)) {
+    if let Some(v) = fields.get(key) {
+        dst.0 = int_at(v, 0).unwrap_or(dst.0);
+        dst.1 = int_at(v, 1).unwrap_or(dst.1);
+    }
+}
+
+fn set_bool(fields: &BTreeMap<&str, &[String]>, key: &str, dst: &mut bool) {
+    if let Some(v) = fields.get(key).and_then(|v| int_at(v, 0)) {
+        *dst = v != 0;
+    }
+}
+
+fn parse_mwnd(fields: &BTreeMap<&str, &[String]>) -> GameexeMwnd {
+    let mut m = GameexeMwnd::default();
+    set_int(fields, "WAKU_NO", &mut m.waku_no);
+    set_pair(fields, "WINDOW_POS", &mut m.window_pos);
+    set_pair(fields, "WINDOW_SIZE", &mut m.window_size);
+    set_pair(fields, "MSG_POS", &mut m.message_pos);
+    set_pair(fields, "MESSAGE_POS", &mut m.message_pos);
+    set_pair(fields, "MOJI_CNT", &mut m.moji_cnt);
+    set_int(fields, "MOJI_SIZE", &mut m.moji_size);
+    set_pair(fields, "MOJI_SPACE", &mut m.moji_space);
+    set_int(fields, "MOJI_COLOR", &mut m.moji_color);
+    set_int(fields, "SHADOW_COLOR", &mut m.shadow_color);
+    set_int(fields, "FUCHI_COLOR", &mut m.fuchi_color);
+    set_int(fields, "NAME_DISP_MODE", &mut m.name_disp_mode);
+    set_bool(fields, "NAME_BRACKET", &mut m.name_bracket);
+    set_int(fields, "NAME_WAKU_NO", &mut m.name_waku_no);
+    set_pair(fields, "NAME_WINDOW_POS", &mut m.name_window_pos);
+    set_pair(fields, "NAME_WINDOW_SIZE", &mut m.name_window_size);
+    set_pair(fields, "NAME_MSG_POS", &mut m.name_msg_pos);
+    set_int(fields, "NAME_MOJI_SIZE", &mut m.name_moji_size);
+    set_pair(fields, "NAME_MOJI_SPACE", &mut m.name_moji_space);
+    set_int(fields, "NAME_MOJI_COLOR", &mut m.name_moji_color);
+    set_bool(fields, "FACE_HIDE_NAME", &mut m.face_hide_name);
+    set_int(fields, "OPEN_ANIME_TYPE", &mut m.open_anime_type);
+    set_int(fields, "OPEN_ANIME_TIME", &mut m.open_anime_time);
+    set_int(fields, "CLOSE_ANIME_TYPE", &mut m.close_anime_type);
+    set_int(fields, "CLOSE_ANIME_TIME", &mut m.close_anime_time);
+    m
+}
+
+fn parse_waku(fields: &BTreeMap<&str, &[String]>) -> GameexeWaku {
+    let text = |key: &str| {
+        fields
+            .get(key)
+            .and_then(|v| v.first())
+            .map(|s| parse_text(s))
+            .unwrap_or_default()
+    };
+    let mut w = GameexeWaku {
+        waku_file: text("WAKU_FILE"),
+        filter_file: text("FILTER_FILE"),
+        ..Default::default()
+    };
+    if let Some(v) = fields.get("FILTER_MARGIN") {
+        w.filter_margin = (
+            int_at(v, 0).unwrap_or(0),
+            int_at(v, 1).unwrap_or(0),
+            int_at(v, 2).unwrap_or(0),
+            int_at(v, 3).unwrap_or(0),
+        );
+    }
+    if let Some(v) = fields.get("FILTER_COLOR") {
+        for (i, c) in w.filter_color.iter_mut().enumerate() {
+            *c = int_at(v, i).unwrap_or(0).clamp(0, 255) as u8;
+        }
+    }
+    // `FACE_POS = x, y` (face 0) or `FACE_POS.NNN = x, y`.
+    for (key, v) in fields {
+        let no = match key.strip_prefix("FACE_POS") {
+            Some("") => 0,
+            Some(rest) => match rest.strip_prefix('.').and_then(|n| n.parse::<usize>().ok()) {
+                Some(n) => n,
+                None => continue,
+            },
+            None => continue,
+        };
+        if no >= GAMEEXE_LIST_MAX {
+            continue;
+        }
+        if w.face_pos.len() <= no {
+            w.face_pos.resize(no + 1, (0, 0));
+        }
+        w.face_pos[no] = (int_at(v, 0).unwrap_or(0), int_at(v, 1).unwrap_or(0));
+    }
+    w
+}
+
+/// Parse the `#MWND`, `#WAKU`, `#COLOR_TABLE` and `#DEFAULT_MWND_NO` directives.
+///
+/// `#MWND.CNT` / `#WAKU.CNT` size the lists when present; otherwise the highest
+/// defined index does, up to `GAMEEXE_LIST_MAX`. Undefined entries keep the
+/// defaults.
+pub(super) fn parse_mwnd_config(entries: &[GameexeEntry]) -> GameexeMwndConfig {
+    let count = |key: &str, defined: Option<usize>| {
+        entries
+            .iter()
+            .rev()
+            .find(|e| e.key == key)
+            .and_then(|e| int_at(&e.values, 0))
+            .map(|n| n.max(0) as usize)
+            .unwrap_or_else(|| defined.map(|i| i + 1).unwrap_or(0))
+            .min(GAMEEXE_LIST_MAX)
+    };
+
+    let mwnd_fields = group_indexed(entries, "MWND.");
+    let mwnd_cnt = count("MWND.CNT", mwnd_fields.keys().next_back().copied());
+    let mwnds = (0..mwnd_cnt)
+        .map(|i| mwnd_fields.get(&i).map(parse_mwnd).unwrap_or_default())
+        .collect();
+
+    let waku_fields = group_indexed(entries, "WAKU.");
+    let waku_cnt = count("WAKU.CNT", waku_fields.keys().next_back().copied());
+    let wakus = (0..waku_cnt)
+        .map(|i| waku_fields.get(&i).map(parse_waku).unwrap_or_default())
+        .collect();
+
+    let mut color_table = Vec::new();
+    for e in entries {
+        let Some(idx) = e
+            .key
+            .strip_prefix("COLOR_TABLE.")
+            .and_then(|n| n.parse::<usize>().ok())
+        else {
+            continue;
+        };
+        if idx >= GAMEEXE_LIST_MAX {
+            continue;
+        }
+        if color_table.len() <= idx {
+            color_table.resize(idx + 1, [0, 0, 0]);
+        }
+        let c = |i| int_at(&e.values, i).unwrap_or(0).clamp(0, 255) as u8;
+        color_table[idx] = [c(0), c(1), c(2)];
+    }
+
+    let default_mwnd_no = entries
+        .iter()
+        .rev()
+        .find(|e| e.key == "DEFAULT_MWND_NO")
+        .and_then(|e| int_at(&e.values, 0))
+        .unwrap_or(0);
+
+    GameexeMwndConfig {
+        mwnds,
+        wakus,
+        color_table,
+        default_mwnd_no,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(key: &str, values: &[&str]) -> GameexeEntry {
+        GameexeEntry {
+            key: key.to_string(),
+            raw_value: values.join(","),
+            values: values.iter().map(|v| v.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn parses_windows_wakus_and_colors() {
+        let cfg = parse_mwnd_config(&[
+            entry("MWND.1.WAKU_NO", &["2"]),
+            entry("MWND.1.MOJI_SIZE", &["26"]),
+            entry("MWND.1.MOJI_SPACE", &["-1", "6"]),
+            entry("WAKU.2.FACE_POS.003", &["10", "20"]),
+            entry("COLOR_TABLE.002", &["255", "300", "-4"]),
+            entry("DEFAULT_MWND_NO", &["1"]),
+        ]);
+        assert_eq!(cfg.mwnds.len(), 2);
+        assert_eq!(cfg.mwnds[0], GameexeMwnd::default());
+        assert_eq!(cfg.mwnds[1].waku_no, 2);
+        assert_eq!(cfg.mwnds[1].moji_size, 26);
+        assert_eq!(cfg.mwnds[1].moji_space, (-1, 6));
+        assert_eq!(cfg.wakus.len(), 3);
+        assert_eq!(cfg.wakus[2].face_pos[3], (10, 20));
+        assert_eq!(cfg.color(2), [255, 255, 0]);
+        assert_eq!(cfg.resolve_mwnd_no(-1), 1);
+    }
+
+    #[test]
+    fn huge_counts_and_indices_are_bounded() {
+        let cfg = parse_mwnd_config(&[
+            entry("MWND.CNT", &["2000000000"]),
+            entry("WAKU.99999999.WAKU_FILE", &["waku"]),
+            entry("WAKU.0.FACE_POS.99999999", &["1", "1"]),
+            entry("COLOR_TABLE.99999999", &["1", "2", "3"]),
+        ]);
+        assert_eq!(cfg.mwnds.len(), GAMEEXE_LIST_MAX);
+        assert_eq!(cfg.wakus.len(), 1);
+        assert!(cfg.wakus[0].face_pos.is_empty());
+        assert!(cfg.color_table.is_empty());
+    }
+}
@@ -0,0 +1,118 @@
+impl GuiApp {
+    /// The stage as the software compositor sees it: plane backgrounds and
+    /// object images at their current placement, weather particles, and gan
+    /// patterns, world cameras and quakes resolved on the VM clock. Message windows, the
+    /// toolbar, dialogs and the wipe overlay are drawn by egui on top and are
+    /// not part of it.
+    fn stage_render_scene(&self) -> Option<siglus::render::RenderScene> {
+        use siglus::render::{RenderBlend, RenderObject, RenderRect};
+
+        let (w, h) = self.scene_size.filter(|&(w, h)| w > 0 && h > 0)?;
+        let mut scene = siglus::render::RenderScene::new(w as u32, h as u32);
+        scene.backgrounds = self
+            .background_images
+            .iter()
+            .map(|(plane, image)| (plane.render_plane(), image.clone()))
+            .collect();
+        let now = self.vm_clock.now_ms();
+        scene.worlds = self
+            .worlds
+            .iter()
+            .map(|(plane, worlds)| {
+                let cameras = worlds.iter().map(|w| w.camera_at(now)).collect();
+                (plane.render_plane(), cameras)
+            })
+            .collect();
+        (scene.quakes, scene.shake) = self.stage_quakes();
+
+        let mut keys: Vec<(StagePlane, i32)> = self
+            .object_images
+            .keys()
+            .chain(self.object_weather.keys())
+            .copied()
+            .collect();
+        keys.sort();
+        keys.dedup();
+        let keys = self.object_draw_order(keys);
+        for (seq, key) in keys.into_iter().enumerate() {
+            let (mut pos, mut render, visible) = self.world_object_placement(key);
+            if !visible {
+                continue;
+            }
+            let mut image = self.object_images.get(&key).cloned();
+            if let Some((view, pat)) = self.object_gan_pat(key)
+                && let Some(pat_image) = view.images.get(&pat.pat_no)
+            {
+                image = Some(pat_image.clone());
+                pos += egui::vec2(pat.x as f32, pat.y as f32);
+                render.z += pat.z as f32;
+                render.alpha *= pat.tr.clamp(0, 255) as f32 / 255.0;
+            }
+            let (order, layer, _seq) = self.object_sort.get(&key).copied().unwrap_or((0, 0, 0));
+            let object = RenderObject {
+                plane: key.0.render_plane(),
+                index: key.1,
+                seq: seq as u64,
+                image,
+                x: pos.x + render.center_rep_x,
+                y: pos.y + render.center_rep_y,
+                z: render.z,
+                center_x: render.center_x + render.center_rep_x,
+                center_y: render.center_y + render.center_rep_y,
+                scale_x: render.scale_x,
+                scale_y: render.scale_y,
+                rotate_x_deg: render.rotate_x_deg,
+                rotate_y_deg: render.rotate_y_deg,
+                rotate_z_deg: render.rotate_z_deg,
+                world: render.world,
+                billboard: render.billboard,
+                alpha: render.alpha,
+                order,
+                layer,
+                src_clip: render.src_clip_use.then(|| {
+                    RenderRect::new(
+                        render.src_clip_left,
+                        render.src_clip_top,
+                        render.src_clip_right,
+                        render.src_clip_bottom,
+                    )
+                }),
+                dst_clip: render.dst_clip_use.then(|| {
+                    RenderRect::new(
+                        render.dst_clip_left,
+                        render.dst_clip_top,
+                        render.dst_clip_right,
+                        render.dst_clip_bottom,
+                    )
+                }),
+                // Color, tone curve, mask and screen effects are already in
+                // the image; screen and multiply arrive shaped for
+                // source-over by `bake_blend_for_egui`.
+                blend: match render.blend {
+                    RenderBlend::Add | RenderBlend::Sub => render.blend,
+                    _ => RenderBlend::Normal,
+                },
+                reps: self.object_reps.get(&key).cloned().unwrap_or_default(),
+                ..RenderObject::default()
+            };
+            // A weather object draws its particles in place of its image.
+            match self.object_weather.get(&key) {
+                Some(view) => scene.objects.extend(
+                    view.sim
+                        .render_objects(&object, &|pat_no| view.images.get(&pat_no).cloned()),
+                ),
+                None => scene.objects.push(object),
+            }
+        }
+        Some(scene)
+    }
+
+    /// Hand the VM thread the stage just drawn, for captures
+    /// (`Host::on_stage_scene`).
+    fn publish_stage_scene(&self) {
+        let scene = self.stage_render_scene();
+        if let Ok(mut slot) = self.stage_scene.lock() {
+            *slot = scene;
+        }
+    }
+}
@@ -0,0 +1,76 @@
+/// A gan-animated object's set, its start time and one texture per pattern
+/// (with the pixels, for composed captures).
+struct ObjectGanView {
+    set: siglus::resource::GanSet,
+    playback: siglus::resource::GanPlayback,
+    textures: BTreeMap<i32, egui::TextureHandle>,
+    images: BTreeMap<i32, Arc<image::RgbaImage>>,
+}
+
+impl GuiApp {
+    fn set_object_gan(&mut self, ctx: &egui::Context, key: (StagePlane, i32), gan: ObjectGanEvent) {
+        let textures = gan
+            .patterns
+            .iter()
+            .map(|(pat_no, image)| {
+                let size = [image.width() as usize, image.height() as usize];
+                let color_image = egui::ColorImage::from_rgba_unmultiplied(
+                    size,
+                    image.as_flat_samples().as_slice(),
+                );
+                let name = format!("gan_{:?}_{}_{pat_no}", key.0, key.1);
+                (
+                    *pat_no,
+                    ctx.load_texture(name, color_image, egui::TextureOptions::LINEAR),
+                )
+            })
+            .collect();
+        self.object_gans.insert(
+            key,
+            ObjectGanView {
+                set: gan.set,
+                playback: gan.playback,
+                textures,
+                images: gan.patterns.into_iter().collect(),
+            },
+        );
+    }
+
+    /// The gan pattern an object shows now on the VM clock, with its view.
+    fn object_gan_pat(
+        &self,
+        key: (StagePlane, i32),
+    ) -> Option<(&ObjectGanView, &siglus::resource::GanPat)> {
+        let view = self.object_gans.get(&key)?;
+        let elapsed = self
+            .vm_clock
+            .now_ms()
+            .saturating_sub(view.playback.start_ms);
+        Some((view, view.set.pat_at(elapsed, view.playback.loop_flag)?))
+    }
+
+    /// The gan pattern an object shows now, with its texture.
+    fn object_gan_frame(
+        &self,
+        key: (StagePlane, i32),
+    ) -> Option<(&egui::TextureHandle, &siglus::resource::GanPat)> {
+        let (view, pat) = self.object_gan_pat(key)?;
+        Some((view.textures.get(&pat.pat_no)?, pat))
+    }
+
+    /// Keep repainting while any gan is still stepping.
+    fn tick_gans(&self, ctx: &egui::Context) {
+        let now = self.vm_clock.now_ms();
+        let running = self.object_gans.values().any(|view| {
+            view.set
+                .pat_index_at(
+                    now.saturating_sub(view.playback.start_ms),
+                    view.playback.loop_flag,
+                )
+                .is_some_and(|(_, running)| running)
+        });
+        if running {
+            ctx.request_repaint();
+        }
+    }
+}
@@ -3,6 +3,7 @@ impl GuiApp {
         event_rx: mpsc::Receiver<HostEvent>,
         selection_tx: mpsc::Sender<i32>,
         return_to_menu_warning_tx: mpsc::Sender<bool>,
+        stage_scene: Arc<Mutex<Option<siglus::render::RenderScene>>>,
         advance_tx: mpsc::Sender<AdvanceSignal>,
         movie_event_tx: mpsc::Sender<MoviePlaybackEvent>,
         skip_mode: Arc<AtomicBool>,
@@ -16,11 +17,17 @@ impl GuiApp {
         movie_backends: Vec<String>,
         quake_ref_csv: Option<PathBuf>,
         quake_ref_report: PathBuf,
+        hot_reload: siglus::hot_reload::HotReloadHandle,
+        fonts: Arc<siglus::render::FontSet>,
+        mwnd_config: Arc<siglus::gameexe::GameexeMwndConfig>,
+        vm_clock: siglus::vm::SharedVmClock,
+        koe: Arc<audio::KoePlayback>,
     ) -> Self {
         let mut app = Self {
             event_rx,
             selection_tx,
             return_to_menu_warning_tx,
+            stage_scene,
             advance_tx,
             movie_event_tx,
             skip_mode,
@@ -33,6 +40,7 @@ impl GuiApp {
             message_window_visible: false,
             pending_options: Vec::new(),
             pending_selbtn: None,
+            selbtn_view: None,
             backlog: Vec::new(),
             done: false,
             show_backlog: false,
@@ -59,13 +67,21 @@ impl GuiApp {
             vm_error_sort: VmErrorSort::TimeDesc,
             background_texture: None,
             background_textures: BTreeMap::new(),
+            background_images: BTreeMap::new(),
             missing_background_names: BTreeMap::new(),
             object_textures: BTreeMap::new(),
+            object_images: BTreeMap::new(),
             missing_object_names: BTreeMap::new(),
             object_pos: BTreeMap::new(),
             object_visible: BTreeMap::new(),
             object_sort: BTreeMap::new(),
             object_render: BTreeMap::new(),
+            object_reps: BTreeMap::new(),
+            object_parent: BTreeMap::new(),
+            object_child_sort_test: BTreeSet::new(),
+            object_weather: BTreeMap::new(),
+            object_gans: BTreeMap::new(),
+            sub_blend: SubBlendPainter::default(),
             base_title: base_title.clone(),
             location_scene_title: String::new(),
             location_scene: String::new(),
@@ -73,12 +89,19 @@ impl GuiApp {
             location_pc: 0,
             last_window_title: format!("{} - Siglus", base_title),
             scene_size,
-            wipe_started_at: None,
+            wipe_started_ms: None,
             wipe_duration_ms: 0,
             wipe_type: 0,
+            wipe_speed_mode: 0,
+            wipe_option: Vec::new(),
             wipe_direction: WipeDirection::Normal,
+            wipe_from: None,
+            wipe_range: None,
+            wipe_pass: None,
+            wipe_mask_grid: None,
 
             start_time: Instant::now(),
+            vm_clock,
             audio_manager,
             input_state,
             base_dir,
@@ -87,8 +110,21 @@ impl GuiApp {
             movie_stop_flags: Arc::new(Mutex::new(BTreeMap::new())),
             quake_ref_csv,
             quake_ref_report,
-            quake_started_at: None,
-            quake_request: None,
+            quakes: BTreeMap::new(),
+            shake: None,
+            worlds: BTreeMap::new(),
+            hot_reload,
+            message_font: game_font_family(&fonts, &siglus::vm::VmFontStyle::default()),
+            message_font_style: siglus::vm::VmFontStyle::default(),
+            fonts,
+            mwnd_config,
+            cur_mwnd: -1,
+            mwnd_views: BTreeMap::new(),
+            mwnd_textures: BTreeMap::new(),
+            message_speed: siglus::vm::VmMessageSpeed::default(),
+            text_reveal: None,
+            koe,
+            koe_serial: 0,
         };
         app.run_quake_reference_validation();
         app
@@ -155,23 +191,45 @@ impl GuiApp {
     fn consume_events(&mut self, ctx: &egui::Context) {
         while let Ok(event) = self.event_rx.try_recv() {
             match event {
-                HostEvent::Name(name) => self.current_name = name,
-                HostEvent::Text { text } => {
+                HostEvent::Name(name) => {
+                    let no = self.mwnd_config.resolve_mwnd_no(self.cur_mwnd);
+                    self.mwnd_views.entry(no).or_default().name = name.clone();
+                    self.current_name = name;
+                }
+                HostEvent::Text { text, wait } => {
                     self.message_window_visible = true;
+                    let no = self.mwnd_config.resolve_mwnd_no(self.cur_mwnd);
+                    self.mwnd_views.entry(no).or_default().text = text.clone();
                     self.current_text = text.clone();
-                    let row = if self.current_name.is_empty() {
-                        text
-                    } else {
-                        format!("{}：{}", self.current_name, text)
+                    let row = BacklogRow {
+                        text: if self.current_name.is_empty() {
+                            text
+                        } else {
+                            format!("{}：{}", self.current_name, text)
+                        },
+                        message: self.mwnd_views.get(&no).and_then(|v| v.message.clone()),
+                    };
+                    // Text continuing the same page replaces its history entry.
+                    let same_page = |last: &BacklogRow| match (&last.message, &row.message) {
+                        (Some(a), Some(b)) => a.page_serial == b.page_serial,
+                        _ => false,
                     };
+                    if self.backlog.last().is_some_and(same_page) {
+                        self.backlog.pop();
+                    }
                     self.backlog.push(row);
                     if self.backlog.len() > 500 {
                         self.backlog.remove(0);
                     }
+                    let message = self.mwnd_views.get(&no).and_then(|v| v.message.as_ref());
+                    let page_serial = message.map(|m| m.page_serial);
+                    let total = message
+                        .map_or_else(|| self.current_text.chars().count(), |m| m.char_count());
+                    self.start_text_reveal(page_serial, total, !wait);
                     // VM waits for advance unless skip is on.
                     // Keep a small click-stock so very quick repeated clicks can
                     // continue advancing text like the C++ input stock behavior.
-                    if !self.skip_mode.load(Ordering::Relaxed) {
+                    if wait {
                         if self.queued_advance_stock > 0 {
                             self.queued_advance_stock -= 1;
                             self.waiting_for_click = false;
@@ -184,8 +242,7 @@ impl GuiApp {
                     if req.options.is_empty() {
                         let _ = self.selection_tx.send(0);
                     } else {
-                        self.pending_selbtn = req.selbtn.clone();
-                        self.pending_options = req.options;
+                        self.open_selection(ctx, req);
                     }
                 }
                 HostEvent::SelBtnSyncCheckpoint {
@@ -230,18 +287,22 @@ impl GuiApp {
                 }
                 HostEvent::LoadPlaneImage { stage, image } => {
                     let size = [image.width() as usize, image.height() as usize];
-                    let pixels = image.to_rgba8().into_flat_samples();
-                    let color_image =
-                        egui::ColorImage::from_rgba_unmultiplied(size, pixels.as_slice());
+                    let rgba = image.to_rgba8();
+                    let color_image = egui::ColorImage::from_rgba_unmultiplied(
+                        size,
+                        rgba.as_flat_samples().as_slice(),
+                    );
                     let tex_name = format!("background_{:?}", stage);
                     self.background_textures.insert(
                         stage,
                         ctx.load_texture(tex_name, color_image, egui::TextureOptions::LINEAR),
                     );
+                    self.background_images.insert(stage, Arc::new(rgba));
                     self.missing_background_names.remove(&stage);
                 }
                 HostEvent::MissingPlaneImage { stage, name } => {
                     self.background_textures.remove(&stage);
+                    self.background_images.remove(&stage);
                     self.missing_background_names.insert(stage, name);
                 }
                 HostEvent::UpsertObjectImage {
@@ -250,19 +311,33 @@ impl GuiApp {
                     image,
                 } => {
                     let size = [image.width() as usize, image.height() as usize];
-                    let pixels = image.to_rgba8().into_flat_samples();
-                    let color_image =
-                        egui::ColorImage::from_rgba_unmultiplied(size, pixels.as_slice());
+                    let rgba = image.to_rgba8();
+                    let color_image = egui::ColorImage::from_rgba_unmultiplied(
+                        size,
+                        rgba.as_flat_samples().as_slice(),
+                    );
 
                     let tex_name = format!("obj_{:?}_{}", stage, index);
                     self.object_textures.insert(
                         (stage, index),
                         ctx.load_texture(tex_name, color_image, egui::TextureOptions::LINEAR),
                     );
+                    self.object_images.insert((stage, index), Arc::new(rgba));
                     self.missing_object_names.remove(&(stage, index));
+                    self.object_gans.remove(&(stage, index));
+                }
+                HostEvent::ObjectGan { stage, index, gan } => {
+                    self.set_object_gan(ctx, (stage, index), gan)
                 }
+                HostEvent::ObjectWeather {
+                    stage,
+                    index,
+                    weather,
+                } => self.set_object_weather(ctx, (stage, index), weather),
                 HostEvent::MissingObjectImage { stage, index, name } => {
                     self.object_textures.remove(&(stage, index));
+                    self.object_images.remove(&(stage, index));
+                    self.object_gans.remove(&(stage, index));
                     self.missing_object_names.insert((stage, index), name);
                 }
                 HostEvent::SetObjectPos { stage, index, x, y } => {
@@ -284,23 +359,54 @@ impl GuiApp {
                 } => {
                     self.object_sort.insert((stage, index), (order, layer, seq));
                 }
+                HostEvent::SetObjectParent {
+                    stage,
+                    index,
+                    parent,
+                } => match parent {
+                    Some(parent) => {
+                        self.object_parent.insert((stage, index), parent);
+                    }
+                    None => {
+                        self.object_parent.remove(&(stage, index));
+                    }
+                },
+                HostEvent::SetObjectChildSort { stage, index, test } => {
+                    if test {
+                        self.object_child_sort_test.insert((stage, index));
+                    } else {
+                        self.object_child_sort_test.remove(&(stage, index));
+                    }
+                }
                 HostEvent::RemoveObject { stage, index } => {
                     self.object_textures.remove(&(stage, index));
+                    self.object_images.remove(&(stage, index));
                     self.missing_object_names.remove(&(stage, index));
                     self.object_pos.remove(&(stage, index));
                     self.object_visible.remove(&(stage, index));
                     self.object_sort.remove(&(stage, index));
                     self.object_render.remove(&(stage, index));
+                    self.object_reps.remove(&(stage, index));
+                    self.object_weather.remove(&(stage, index));
+                    self.object_gans.remove(&(stage, index));
                 }
                 HostEvent::SetObjectRenderState {
                     stage,
                     index,
                     center_x,
                     center_y,
+                    center_rep_x,
+                    center_rep_y,
                     scale_x,
                     scale_y,
                     rotate_z_deg,
+                    z,
+                    rotate_x_deg,
+                    rotate_y_deg,
+                    world,
+                    billboard,
                     alpha,
+                    button_offset,
                     dst_clip_use,
                     dst_clip_left,
                     dst_clip_top,
@@ -311,16 +417,31 @@ impl GuiApp {
                     src_clip_top,
                     src_clip_right,
                     src_clip_bottom,
+                    blend,
+                    reps,
                 } => {
+                    if reps.is_empty() {
+                        self.object_reps.remove(&(stage, index));
+                    } else {
+                        self.object_reps.insert((stage, index), reps);
+                    }
                     self.object_render.insert(
                         (stage, index),
                         ObjectRenderState {
                             center_x,
                             center_y,
+                            center_rep_x,
+                            center_rep_y,
                             scale_x,
                             scale_y,
                             rotate_z_deg,
+                            z,
+                            rotate_x_deg,
+                            rotate_y_deg,
+                            world,
+                            billboard,
                             alpha,
+                            button_offset,
                             dst_clip_use,
                             dst_clip_left,
                             dst_clip_top,
@@ -331,16 +452,19 @@ impl GuiApp {
                             src_clip_top,
                             src_clip_right,
                             src_clip_bottom,
+                            blend,
                         },
                     );
                 }
                 HostEvent::ClearPlaneObjects { stage } => {
                     self.object_textures.retain(|(s, _), _| *s != stage);
+                    self.object_images.retain(|(s, _), _| *s != stage);
                     self.missing_object_names.retain(|(s, _), _| *s != stage);
                     self.object_pos.retain(|(s, _), _| *s != stage);
                     self.object_visible.retain(|(s, _), _| *s != stage);
                     self.object_sort.retain(|(s, _), _| *s != stage);
                     self.object_render.retain(|(s, _), _| *s != stage);
+                    self.object_reps.retain(|(s, _), _| *s != stage);
                 }
                 HostEvent::Location {
                     scene_title,
@@ -382,12 +506,30 @@ impl GuiApp {
                 HostEvent::StartWipe {
                     duration_ms,
                     wipe_type,
+                    speed_mode,
+                    option,
+                    mask,
                     wipe_direction,
+                    range,
                 } => {
                     self.wipe_duration_ms = duration_ms.max(1);
                     self.wipe_type = wipe_type;
+                    self.wipe_speed_mode = speed_mode;
+                    self.wipe_option = option;
                     self.wipe_direction = wipe_direction;
-                    self.wipe_started_at = Some(Instant::now());
+                    self.wipe_started_ms = Some(self.vm_clock.now_ms());
+                    self.wipe_mask_grid = mask.map(|m| m.grid(MASK_WIPE_COLS, MASK_WIPE_ROWS));
+                    // Back and front are what is on screen; objects prepared on
+                    // `next` only show in the incoming frame.
+                    self.wipe_from = (wipe_direction == WipeDirection::Normal)
+                        .then(|| self.stage_snapshot(|plane| plane != StagePlane::Next));
+                    self.wipe_range = range;
+                }
+                HostEvent::EndWipe => {
+                    self.wipe_started_ms = None;
+                    self.wipe_from = None;
+                    self.wipe_range = None;
+                    self.wipe_mask_grid = None;
                 }
                 HostEvent::SetCursorPos { x, y } => {
                     let viewport_rect = ctx.input(|i| i.viewport_rect());
@@ -418,6 +560,23 @@ impl GuiApp {
                         am.stop_se();
                     }
                 }
+                HostEvent::PlayKoe { koe_no, serial } => {
+                    let started = self
+                        .audio_manager
+                        .as_mut()
+                        .is_some_and(|am| am.play_koe(koe_no));
+                    self.koe.finish(self.koe_serial);
+                    self.koe_serial = if started { serial } else { 0 };
+                    if !started {
+                        self.koe.finish(serial);
+                    }
+                }
+                HostEvent::StopKoe => {
+                    if let Some(am) = &mut self.audio_manager {
+                        am.stop_koe();
+                    }
+                    self.koe_serial = 0;
+                }
                 HostEvent::PlayPcm {
                     ch,
                     name,
@@ -448,14 +607,77 @@ impl GuiApp {
                 } => {
                     self.request_stop_movie_process(stage, index, generation);
                 }
-                HostEvent::StartQuake { req, started_at } => {
-                    self.quake_request = Some(req);
-                    self.quake_started_at = Some(started_at);
+                HostEvent::StartQuake {
+                    quake_no,
+                    req,
+                    started_at,
+                } => {
+                    self.quakes.insert(quake_no, (req, started_at));
+                }
+                HostEvent::EndQuake { quake_no } => {
+                    self.quakes.remove(&quake_no);
+                }
+                HostEvent::StartShake { steps, started_at } => {
+                    self.shake = Some((steps, started_at));
+                }
+                HostEvent::EndShake => {
+                    self.shake = None;
                 }
-                HostEvent::EndQuake => {
-                    self.quake_request = None;
-                    self.quake_started_at = None;
+                HostEvent::SetWorlds { stage, worlds } => {
+                    self.worlds.insert(stage, worlds);
                 }
+                HostEvent::MessageSpeed(speed) => self.message_speed = speed,
+                HostEvent::FontStyle(style) => {
+                    self.message_font = game_font_family(&self.fonts, &style);
+                    self.message_font_style = style;
+                }
+                HostEvent::CurMwnd(mwnd_no) => self.cur_mwnd = mwnd_no,
+                HostEvent::Message {
+                    mwnd_no,
+                    mut message,
+                    from_part,
+                } => {
+                    let view = self.mwnd_views.entry(mwnd_no).or_default();
+                    if let Some(shown) = view.message.take()
+                        && from_part > 0
+                        && shown.page_serial == message.page_serial
+                    {
+                        let mut parts = shown.parts;
+                        parts.truncate(from_part);
+                        parts.append(&mut message.parts);
+                        message.parts = parts;
+                    }
+                    view.message = Some(message);
+                }
+                HostEvent::MwndState { mwnd_no, state } => {
+                    self.mwnd_views.entry(mwnd_no).or_default().state = state;
+                }
+                HostEvent::MwndClear(mwnd_no) => {
+                    if let Some(view) = self.mwnd_views.get_mut(&mwnd_no) {
+                        view.name.clear();
+                        view.text.clear();
+                    }
+                }
+                HostEvent::MwndImage {
+                    mwnd_no,
+                    slot,
+                    image,
+                } => match image {
+                    Some(image) => {
+                        let size = [image.width() as usize, image.height() as usize];
+                        let pixels = image.to_rgba8().into_flat_samples();
+                        let color_image =
+                            egui::ColorImage::from_rgba_unmultiplied(size, pixels.as_slice());
+                        let tex_name = format!("mwnd_{mwnd_no}_{slot:?}");
+                        self.mwnd_textures.insert(
+                            (mwnd_no, slot),
+                            ctx.load_texture(tex_name, color_image, egui::TextureOptions::LINEAR),
+                        );
+                    }
+                    None => {
+                        self.mwnd_textures.remove(&(mwnd_no, slot));
+                    }
+                },
             }
         }
     }
@@ -596,9 +818,7 @@ impl GuiApp {
                 .map(|s| s.cancel_enable)
                 .unwrap_or(false);
             if cancel_enable && (secondary_clicked || escape_pressed) {
-                let _ = self.selection_tx.send(-1);
-                self.pending_options.clear();
-                self.pending_selbtn = None;
+                self.decide_selection(-1);
             }
             return;
         }
@@ -614,6 +834,7 @@ impl GuiApp {
         if ctrl_held {
             self.skip_mode.store(true, Ordering::Relaxed);
             // If we're waiting for click, auto-advance
+            self.complete_text_reveal();
             self.advance();
         } else if self.skip_mode.load(Ordering::Relaxed) {
             self.skip_mode.store(false, Ordering::Relaxed);
@@ -635,7 +856,7 @@ impl GuiApp {
             && self.pending_options.is_empty()
             && wheel_down
         {
-            self.advance();
+            self.click_advance();
         }
 
         // Mouse wheel down → close backlog if scrolled to bottom
@@ -644,7 +865,7 @@ impl GuiApp {
         // Click anywhere → advance text (when message window is showing and not in backlog/selection)
         if self.waiting_for_click && !self.show_backlog && self.pending_options.is_empty() {
             if primary_clicked {
-                self.advance();
+                self.click_advance();
             }
         }
     }
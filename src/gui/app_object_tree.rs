@@ -0,0 +1,196 @@
+impl ObjectRenderState {
+    fn transform(&self, pos: egui::Pos2) -> ObjectTransform {
+        ObjectTransform {
+            x: pos.x,
+            y: pos.y,
+            scale_x: self.scale_x,
+            scale_y: self.scale_y,
+            rotate_z_deg: self.rotate_z_deg,
+            alpha: self.alpha,
+        }
+    }
+
+    /// Seen through a camera instead of drawn flat (see `RenderObject::is_3d`).
+    fn is_3d(&self) -> bool {
+        self.world >= 0
+            || self.billboard
+            || self.z != 0.0
+            || self.rotate_x_deg != 0.0
+            || self.rotate_y_deg != 0.0
+    }
+
+    /// The object at stage position `pos` as a 3D sprite of `size` source pixels.
+    fn sprite3d(&self, pos: egui::Pos2, size: egui::Vec2) -> siglus::render::Sprite3d {
+        siglus::render::Sprite3d {
+            x: pos.x + self.center_rep_x,
+            y: pos.y + self.center_rep_y,
+            z: self.z,
+            center_x: self.center_x + self.center_rep_x,
+            center_y: self.center_y + self.center_rep_y,
+            width: size.x,
+            height: size.y,
+            scale_x: self.scale_x,
+            scale_y: self.scale_y,
+            rotate_x_deg: self.rotate_x_deg,
+            rotate_y_deg: self.rotate_y_deg,
+            rotate_z_deg: self.rotate_z_deg,
+            billboard: self.billboard,
+            in_world: self.world >= 0,
+        }
+    }
+
+    fn dst_clip(&self) -> Option<(f32, f32, f32, f32)> {
+        self.dst_clip_use.then_some((
+            self.dst_clip_left,
+            self.dst_clip_top,
+            self.dst_clip_right,
+            self.dst_clip_bottom,
+        ))
+    }
+}
+
+impl GuiApp {
+    /// Keys in draw order: top-level objects by their stage sort key, each
+    /// followed depth first by its children in the parent's child sort
+    /// order. Parents without an image of their own still place children.
+    fn object_draw_order(&self, keys: Vec<(StagePlane, i32)>) -> Vec<(StagePlane, i32)> {
+        let drawn: BTreeSet<(StagePlane, i32)> = keys.iter().copied().collect();
+        let mut nodes = drawn.clone();
+        for key in &keys {
+            let mut cur = *key;
+            while let Some(parent) = self.object_parent.get(&cur) {
+                cur = (cur.0, *parent);
+                if !nodes.insert(cur) {
+                    break;
+                }
+            }
+        }
+        let mut children: BTreeMap<(StagePlane, i32), Vec<(StagePlane, i32)>> = BTreeMap::new();
+        let mut roots = Vec::new();
+        for key in &nodes {
+            match self.object_parent.get(key) {
+                Some(parent) => children.entry((key.0, *parent)).or_default().push(*key),
+                None => roots.push(*key),
+            }
+        }
+        let sort_key = |key: &(StagePlane, i32)| {
+            let (order, layer, seq) = self.object_sort.get(key).copied().unwrap_or((0, 0, 0));
+            (order, layer, key.0 as i32, key.1, seq)
+        };
+        // Within one order/layer, farther objects draw first.
+        let sort_cmp = |a: &(StagePlane, i32), b: &(StagePlane, i32)| {
+            let (ka, kb) = (sort_key(a), sort_key(b));
+            (ka.0, ka.1)
+                .cmp(&(kb.0, kb.1))
+                .then(self.object_depth(*b).total_cmp(&self.object_depth(*a)))
+                .then(ka.cmp(&kb))
+        };
+        roots.sort_by(sort_cmp);
+        for (parent, list) in children.iter_mut() {
+            // Child indices grow with the child number, so index order is list order.
+            if self.object_child_sort_test.contains(parent) {
+                list.sort_by_key(|key| key.1);
+            } else {
+                list.sort_by(sort_cmp);
+            }
+        }
+
+        fn walk(
+            key: (StagePlane, i32),
+            children: &BTreeMap<(StagePlane, i32), Vec<(StagePlane, i32)>>,
+            drawn: &BTreeSet<(StagePlane, i32)>,
+            out: &mut Vec<(StagePlane, i32)>,
+        ) {
+            if drawn.contains(&key) {
+                out.push(key);
+            }
+            for child in children.get(&key).into_iter().flatten() {
+                walk(*child, children, drawn, out);
+            }
+        }
+        let mut out = Vec::with_capacity(keys.len());
+        for root in roots {
+            walk(root, &children, &drawn, &mut out);
+        }
+        out
+    }
+
+    /// Camera an object of `stage` is seen through: its world's, or the
+    /// stage camera.
+    fn object_camera(
+        &self,
+        stage: StagePlane,
+        render: &ObjectRenderState,
+    ) -> siglus::render::WorldCamera {
+        match usize::try_from(render.world) {
+            Ok(no) => self
+                .worlds
+                .get(&stage)
+                .and_then(|worlds| worlds.get(no))
+                .map_or_else(Default::default, |w| w.camera_at(self.vm_clock.now_ms())),
+            Err(_) => {
+                let height = self.scene_size.map_or(0, |(_, h)| h);
+                siglus::render::WorldCamera::stage(height as f32)
+            }
+        }
+    }
+
+    /// Camera-space depth of an object's anchor; flat objects share the
+    /// stage plane's depth.
+    fn object_depth(&self, key: (StagePlane, i32)) -> f32 {
+        let render = self.object_render.get(&key).copied().unwrap_or_default();
+        let camera = self.object_camera(key.0, &render);
+        if !render.is_3d() {
+            return camera.depth_of([0.0; 3]);
+        }
+        let pos = self.object_stage_pos(key, &render);
+        let (w, h) = self.scene_size.unwrap_or_default();
+        let anchor = render
+            .sprite3d(pos, egui::Vec2::ZERO)
+            .anchor(w as f32, h as f32);
+        camera.depth_of(anchor)
+    }
+
+    /// An object's own position plus its button look's offset.
+    fn object_stage_pos(&self, key: (StagePlane, i32), render: &ObjectRenderState) -> egui::Pos2 {
+        let pos = self.object_pos.get(&key).copied().unwrap_or(egui::Pos2::ZERO);
+        pos + egui::vec2(render.button_offset.0, render.button_offset.1)
+    }
+
+    /// Stage position (unscaled stage pixels), render state and visibility of
+    /// an object with its parents' placement, opacity and clip applied.
+    fn world_object_placement(
+        &self,
+        key: (StagePlane, i32),
+    ) -> (egui::Pos2, ObjectRenderState, bool) {
+        let mut render = self.object_render.get(&key).copied().unwrap_or_default();
+        let pos = self.object_stage_pos(key, &render);
+        let visible = self.object_visible.get(&key).copied().unwrap_or(true);
+        let Some(parent) = self.object_parent.get(&key) else {
+            return (pos, render, visible);
+        };
+        let (parent_pos, parent_render, parent_visible) =
+            self.world_object_placement((key.0, *parent));
+        let world = parent_render
+            .transform(parent_pos)
+            .then(&render.transform(pos));
+        render.scale_x = world.scale_x;
+        render.scale_y = world.scale_y;
+        render.rotate_z_deg = world.rotate_z_deg;
+        render.alpha = world.alpha;
+        if let Some((left, top, right, bottom)) =
+            intersect_clip(parent_render.dst_clip(), render.dst_clip())
+        {
+            render.dst_clip_use = true;
+            render.dst_clip_left = left;
+            render.dst_clip_top = top;
+            render.dst_clip_right = right;
+            render.dst_clip_bottom = bottom;
+        }
+        (
+            egui::pos2(world.x, world.y),
+            render,
+            visible && parent_visible,
+        )
+    }
+}
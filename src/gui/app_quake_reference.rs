@@ -101,7 +101,8 @@ impl GuiApp {
                 center_x,
                 center_y: parse_i32(10).unwrap_or(0),
             };
-            let (x, y, scale, cx, cy) = Self::quake_transform_for_order_at(&req, elapsed_ms, order);
+            let q = siglus::render::quake_transform_at(&req, elapsed_ms, order);
+            let (x, y, scale, cx, cy) = (q.x, q.y, q.scale, q.center_x, q.center_y);
             let x = x * stage_ratio_x;
             let y = y * stage_ratio_y;
             let err = (x - exp_x).abs()
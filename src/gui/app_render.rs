@@ -1,5 +1,9 @@
 impl GuiApp {
     fn draw_background(&self, ui: &mut egui::Ui) {
+        // The stage background sits at order 0, layer 0 for ranged wipes.
+        if !self.in_wipe_pass(0, 0) {
+            return;
+        }
         let screen = ui.max_rect();
         let (mut rect, stage_scale_x, stage_scale_y) = self.stage_transform(screen);
         let (qx, qy, qz, quake_cx, quake_cy) = self.quake_transform_for_order(0);
@@ -71,21 +75,30 @@ impl GuiApp {
             .keys()
             .copied()
             .chain(self.missing_object_names.keys().copied())
+            .chain(self.object_weather.keys().copied())
             .collect();
         keys.sort();
         keys.dedup();
-        let mut entries: Vec<_> = keys;
-        entries.sort_by_key(|key| {
-            let (order, layer, seq) = self.object_sort.get(key).copied().unwrap_or((0, 0, 0));
-            let (plane, idx) = *key;
-            (order, layer, plane as i32, idx, seq)
-        });
+        let entries = self.object_draw_order(keys);
 
         for key in entries {
-            if !self.object_visible.get(&key).copied().unwrap_or(true) {
+            let (mut pos, mut render, visible) = self.world_object_placement(key);
+            if !visible {
                 continue;
             }
+            // A playing gan swaps in its current pattern, offset and faded
+            // by that pattern's x/y/z and tr.
+            let gan = self.object_gan_frame(key);
+            if let Some((_, pat)) = gan {
+                pos += egui::vec2(pat.x as f32, pat.y as f32);
+                render.z += pat.z as f32;
+                render.alpha *= pat.tr.clamp(0, 255) as f32 / 255.0;
+            }
+            let stage_pos = pos;
             let (order, layer, _seq) = self.object_sort.get(&key).copied().unwrap_or((0, 0, 0));
+            if !self.in_wipe_pass(order, layer) {
+                continue;
+            }
             let (qx, qy, qz, quake_cx, quake_cy) = self.quake_transform_for_order(order);
             let quake_center = egui::pos2(
                 base_stage_rect.center().x + qx + (1.0 - qz) * quake_cx * stage_scale_x,
@@ -95,42 +108,22 @@ impl GuiApp {
                 quake_center,
                 egui::vec2(base_stage_rect.width() * qz, base_stage_rect.height() * qz),
             );
-            let _ = layer;
 
-            let render = self
-                .object_render
-                .get(&key)
-                .copied()
-                .unwrap_or(ObjectRenderState {
-                    center_x: 0.0,
-                    center_y: 0.0,
-                    scale_x: 1.0,
-                    scale_y: 1.0,
-                    rotate_z_deg: 0.0,
-                    alpha: 1.0,
-                    dst_clip_use: false,
-                    dst_clip_left: 0.0,
-                    dst_clip_top: 0.0,
-                    dst_clip_right: 0.0,
-                    dst_clip_bottom: 0.0,
-                    src_clip_use: false,
-                    src_clip_left: 0.0,
-                    src_clip_top: 0.0,
-                    src_clip_right: 0.0,
-                    src_clip_bottom: 0.0,
-                });
-
-            let pos = self
-                .object_pos
-                .get(&key)
-                .copied()
-                .unwrap_or_else(|| egui::pos2(0.0, 0.0));
             let pos = egui::pos2(
                 stage_rect.min.x + pos.x * stage_scale_x,
                 stage_rect.min.y + pos.y * stage_scale_y,
             );
 
-            let Some(texture) = self.object_textures.get(&key) else {
+            if let Some(weather) = self.object_weather.get(&key) {
+                let stage_scale = egui::vec2(stage_scale_x, stage_scale_y);
+                self.draw_weather(ui, weather, pos, stage_scale, &render);
+                continue;
+            }
+
+            let Some(texture) = gan
+                .map(|(texture, _)| texture)
+                .or_else(|| self.object_textures.get(&key))
+            else {
                 if let Some(name) = self.missing_object_names.get(&key) {
                     let rect = egui::Rect::from_min_size(
                         pos,
@@ -188,17 +181,7 @@ impl GuiApp {
                 render.scale_y
             };
 
-            // C++-like: object position is transformed anchor, drawing origin is (pos - center).
-            let left = pos.x - render.center_x * scale_x * stage_scale_x;
-            let top = pos.y - render.center_y * scale_y * stage_scale_y;
-            let right = left + src_size.x * scale_x * stage_scale_x;
-            let bottom = top + src_size.y * scale_y * stage_scale_y;
-            let rect = egui::Rect::from_min_max(
-                egui::pos2(left.min(right), top.min(bottom)),
-                egui::pos2(left.max(right), top.max(bottom)),
-            );
-
-            let uv = egui::Rect::from_min_max(
+            let uv_base = egui::Rect::from_min_max(
                 egui::pos2(
                     src_min.x / tex_size.x.max(1.0),
                     src_min.y / tex_size.y.max(1.0),
@@ -208,20 +191,8 @@ impl GuiApp {
                     src_max.y / tex_size.y.max(1.0),
                 ),
             );
-            let uv = egui::Rect::from_min_max(
-                egui::pos2(
-                    if scale_x < 0.0 { uv.max.x } else { uv.min.x },
-                    if scale_y < 0.0 { uv.max.y } else { uv.min.y },
-                ),
-                egui::pos2(
-                    if scale_x < 0.0 { uv.min.x } else { uv.max.x },
-                    if scale_y < 0.0 { uv.min.y } else { uv.max.y },
-                ),
-            );
-            let mut rect = rect;
-            let mut uv = uv;
-            if render.dst_clip_use {
-                let clip_rect = egui::Rect::from_min_max(
+            let dst_clip_rect = render.dst_clip_use.then(|| {
+                egui::Rect::from_min_max(
                     egui::pos2(
                         stage_rect.min.x + render.dst_clip_left * stage_scale_x,
                         stage_rect.min.y + render.dst_clip_top * stage_scale_y,
@@ -230,73 +201,180 @@ impl GuiApp {
                         stage_rect.min.x + render.dst_clip_right * stage_scale_x,
                         stage_rect.min.y + render.dst_clip_bottom * stage_scale_y,
                     ),
-                );
-                let clipped = rect.intersect(clip_rect);
-                if clipped.width() <= 0.0 || clipped.height() <= 0.0 {
+                )
+            });
+
+            // Rep lists draw the object once per entry, shifted and faded;
+            // `center_rep` moves the scale pivot while keeping the image put.
+            let single = [siglus::render::RenderRep::IDENTITY];
+            let reps = self
+                .object_reps
+                .get(&key)
+                .map_or(&single[..], Vec::as_slice);
+            for rep in reps {
+                let alpha = ((render.alpha * rep.alpha).clamp(0.0, 1.0) * 255.0) as u8;
+                // Non-normal blends arrive shaped by `bake_blend_for_egui`; a zero
+                // tint alpha turns egui's source-over into an add.
+                let tint = match render.blend {
+                    siglus::render::RenderBlend::Normal => {
+                        egui::Color32::from_rgba_premultiplied(255, 255, 255, alpha)
+                    }
+                    siglus::render::RenderBlend::Add => {
+                        egui::Color32::from_rgba_premultiplied(alpha, alpha, alpha, 0)
+                    }
+                    _ => egui::Color32::from_rgba_premultiplied(alpha, alpha, alpha, alpha),
+                };
+
+                if render.is_3d() || rep.z != 0.0 {
+                    // Project the corners on the unscaled stage, then place them
+                    // like any other stage point.
+                    let stage_w = base_stage_rect.width() / stage_scale_x;
+                    let stage_h = base_stage_rect.height() / stage_scale_y;
+                    let mut sprite = render.sprite3d(
+                        egui::pos2(stage_pos.x + rep.x, stage_pos.y + rep.y),
+                        src_size,
+                    );
+                    sprite.z += rep.z;
+                    let Some(quad) = sprite.project(&self.object_camera(key.0, &render), stage_w, stage_h)
+                    else {
+                        continue;
+                    };
+                    let corners = quad.corners.map(|[x, y]| {
+                        egui::pos2(
+                            stage_rect.min.x + x * stage_scale_x,
+                            stage_rect.min.y + y * stage_scale_y,
+                        )
+                    });
+                    let painter = match dst_clip_rect {
+                        Some(clip) => ui.painter().with_clip_rect(clip.intersect(ui.clip_rect())),
+                        None => ui.painter().clone(),
+                    };
+                    if let Some(mesh) = projected_image_mesh(texture.id(), corners, uv_base, tint) {
+                        self.paint_object_mesh(&painter, mesh, render.blend);
+                    }
                     continue;
                 }
-                let dx0 = (clipped.min.x - rect.min.x) / rect.width().max(1.0);
-                let dx1 = (clipped.max.x - rect.min.x) / rect.width().max(1.0);
-                let dy0 = (clipped.min.y - rect.min.y) / rect.height().max(1.0);
-                let dy1 = (clipped.max.y - rect.min.y) / rect.height().max(1.0);
-                let ux0 = uv.min.x + (uv.max.x - uv.min.x) * dx0;
-                let ux1 = uv.min.x + (uv.max.x - uv.min.x) * dx1;
-                let uy0 = uv.min.y + (uv.max.y - uv.min.y) * dy0;
-                let uy1 = uv.min.y + (uv.max.y - uv.min.y) * dy1;
-                rect = clipped;
-                uv = egui::Rect::from_min_max(egui::pos2(ux0, uy0), egui::pos2(ux1, uy1));
-            }
 
-            let alpha = (render.alpha.clamp(0.0, 1.0) * 255.0) as u8;
-            let tint = egui::Color32::from_rgba_premultiplied(255, 255, 255, alpha);
+                // C++-like: object position is transformed anchor, drawing origin is (pos - center).
+                let pos = egui::pos2(
+                    pos.x + (rep.x + render.center_rep_x) * stage_scale_x,
+                    pos.y + (rep.y + render.center_rep_y) * stage_scale_y,
+                );
+                let center_x = render.center_x + render.center_rep_x;
+                let center_y = render.center_y + render.center_rep_y;
+                let left = pos.x - center_x * scale_x * stage_scale_x;
+                let top = pos.y - center_y * scale_y * stage_scale_y;
+                let right = left + src_size.x * scale_x * stage_scale_x;
+                let bottom = top + src_size.y * scale_y * stage_scale_y;
+                let rect = egui::Rect::from_min_max(
+                    egui::pos2(left.min(right), top.min(bottom)),
+                    egui::pos2(left.max(right), top.max(bottom)),
+                );
 
-            if render.rotate_z_deg.abs() < f32::EPSILON {
-                ui.painter().image(texture.id(), rect, uv, tint);
-            } else {
-                let mut mesh = egui::epaint::Mesh::with_texture(texture.id());
-                let center = rect.center();
-                let rad = render.rotate_z_deg.to_radians();
-                let (s, c) = rad.sin_cos();
-
-                let rot = |p: egui::Pos2| {
-                    let dx = p.x - center.x;
-                    let dy = p.y - center.y;
-                    egui::pos2(center.x + dx * c - dy * s, center.y + dx * s + dy * c)
-                };
+                let uv = uv_base;
+                let uv = egui::Rect::from_min_max(
+                    egui::pos2(
+                        if scale_x < 0.0 { uv.max.x } else { uv.min.x },
+                        if scale_y < 0.0 { uv.max.y } else { uv.min.y },
+                    ),
+                    egui::pos2(
+                        if scale_x < 0.0 { uv.min.x } else { uv.max.x },
+                        if scale_y < 0.0 { uv.min.y } else { uv.max.y },
+                    ),
+                );
+                let mut rect = rect;
+                let mut uv = uv;
+                if let Some(clip_rect) = dst_clip_rect {
+                    let clipped = rect.intersect(clip_rect);
+                    if clipped.width() <= 0.0 || clipped.height() <= 0.0 {
+                        continue;
+                    }
+                    let dx0 = (clipped.min.x - rect.min.x) / rect.width().max(1.0);
+                    let dx1 = (clipped.max.x - rect.min.x) / rect.width().max(1.0);
+                    let dy0 = (clipped.min.y - rect.min.y) / rect.height().max(1.0);
+                    let dy1 = (clipped.max.y - rect.min.y) / rect.height().max(1.0);
+                    let ux0 = uv.min.x + (uv.max.x - uv.min.x) * dx0;
+                    let ux1 = uv.min.x + (uv.max.x - uv.min.x) * dx1;
+                    let uy0 = uv.min.y + (uv.max.y - uv.min.y) * dy0;
+                    let uy1 = uv.min.y + (uv.max.y - uv.min.y) * dy1;
+                    rect = clipped;
+                    uv = egui::Rect::from_min_max(egui::pos2(ux0, uy0), egui::pos2(ux1, uy1));
+                }
 
-                let p0 = rot(rect.left_top());
-                let p1 = rot(rect.right_top());
-                let p2 = rot(rect.right_bottom());
-                let p3 = rot(rect.left_bottom());
-
-                mesh.vertices.push(egui::epaint::Vertex {
-                    pos: p0,
-                    uv: uv.left_top(),
-                    color: tint,
-                });
-                mesh.vertices.push(egui::epaint::Vertex {
-                    pos: p1,
-                    uv: uv.right_top(),
-                    color: tint,
-                });
-                mesh.vertices.push(egui::epaint::Vertex {
-                    pos: p2,
-                    uv: uv.right_bottom(),
-                    color: tint,
-                });
-                mesh.vertices.push(egui::epaint::Vertex {
-                    pos: p3,
-                    uv: uv.left_bottom(),
-                    color: tint,
-                });
-                mesh.indices.extend_from_slice(&[0, 1, 2, 0, 2, 3]);
-                ui.painter().add(egui::Shape::mesh(mesh));
+                if render.blend == siglus::render::RenderBlend::Sub {
+                    let mut mesh = egui::epaint::Mesh::with_texture(texture.id());
+                    mesh.add_rect_with_uv(rect, uv, tint);
+                    let rot = egui::emath::Rot2::from_angle(render.rotate_z_deg.to_radians());
+                    mesh.rotate(rot, rect.center());
+                    self.paint_object_mesh(ui.painter(), mesh, render.blend);
+                } else if render.rotate_z_deg.abs() < f32::EPSILON {
+                    ui.painter().image(texture.id(), rect, uv, tint);
+                } else {
+                    let mut mesh = egui::epaint::Mesh::with_texture(texture.id());
+                    let center = rect.center();
+                    let rad = render.rotate_z_deg.to_radians();
+                    let (s, c) = rad.sin_cos();
+
+                    let rot = |p: egui::Pos2| {
+                        let dx = p.x - center.x;
+                        let dy = p.y - center.y;
+                        egui::pos2(center.x + dx * c - dy * s, center.y + dx * s + dy * c)
+                    };
+
+                    let p0 = rot(rect.left_top());
+                    let p1 = rot(rect.right_top());
+                    let p2 = rot(rect.right_bottom());
+                    let p3 = rot(rect.left_bottom());
+
+                    mesh.vertices.push(egui::epaint::Vertex {
+                        pos: p0,
+                        uv: uv.left_top(),
+                        color: tint,
+                    });
+                    mesh.vertices.push(egui::epaint::Vertex {
+                        pos: p1,
+                        uv: uv.right_top(),
+                        color: tint,
+                    });
+                    mesh.vertices.push(egui::epaint::Vertex {
+                        pos: p2,
+                        uv: uv.right_bottom(),
+                        color: tint,
+                    });
+                    mesh.vertices.push(egui::epaint::Vertex {
+                        pos: p3,
+                        uv: uv.left_bottom(),
+                        color: tint,
+                    });
+                    mesh.indices.extend_from_slice(&[0, 1, 2, 0, 2, 3]);
+                    ui.painter().add(egui::Shape::mesh(mesh));
+                }
             }
         }
     }
 
     fn draw_message_window(&self, ui: &mut egui::Ui) {
+        if !self.mwnd_config.mwnds.is_empty() {
+            self.draw_gameexe_mwnds(ui);
+            return;
+        }
         let screen = ui.max_rect();
+        // No `#MWND` table: size text like the Gameexe default window.
+        let def = siglus::gameexe::GameexeMwnd::default();
+        let (_, sx, sy) = self.stage_transform(screen);
+        let color = |no: i32| {
+            let [r, g, b] = self.mwnd_config.color(no);
+            egui::Color32::from_rgb(r, g, b)
+        };
+        let deco = |moji_size: i32| {
+            self.message_decoration(
+                moji_size,
+                sx,
+                sy,
+                color(def.shadow_color),
+                color(def.fuchi_color),
+            )
+        };
 
         let mw_height = screen.height() * MSG_WINDOW_HEIGHT_RATIO;
         let mw_rect = egui::Rect::from_min_max(
@@ -324,7 +402,10 @@ impl GuiApp {
 
         // ── Name plate ──
         if !self.current_name.is_empty() {
-            let name_font = egui::FontId::proportional(17.0);
+            let name_font = egui::FontId::new(
+                def.name_moji_size.max(1) as f32 * sy,
+                self.message_font.clone(),
+            );
             let name_galley = ui.painter().layout_no_wrap(
                 self.current_name.clone(),
                 name_font,
@@ -348,13 +429,15 @@ impl GuiApp {
                 ),
                 egui::StrokeKind::Outside,
             );
-            ui.painter().galley(
+            paint_decorated_galley(
+                ui.painter(),
                 egui::pos2(
                     name_rect.left() + NAME_PLATE_PADDING_X,
                     name_rect.center().y - name_galley.size().y / 2.0,
                 ),
-                name_galley,
+                &name_galley,
                 egui::Color32::WHITE,
+                Some(&deco(def.name_moji_size)),
             );
         }
 
@@ -370,16 +453,49 @@ impl GuiApp {
             ),
         );
 
-        if !self.current_text.is_empty() {
-            let text_font = egui::FontId::proportional(18.0);
-            let text_galley = ui.painter().layout(
-                self.current_text.clone(),
-                text_font.clone(),
+        let cur_message = self
+            .mwnd_views
+            .get(&self.mwnd_config.resolve_mwnd_no(self.cur_mwnd))
+            .and_then(|v| v.message.as_ref())
+            .filter(|m| !m.parts.is_empty());
+        let shown = self.revealed_chars();
+        if let Some(msg) = cur_message {
+            let msg = &msg.revealed(shown);
+            let st = MessageTextStyle {
+                family: self.message_font.clone(),
+                size: def.moji_size.max(1) as f32 * sy,
+                letter_spacing: def.moji_space.0 as f32 * sx,
+                line_height: (def.moji_size + def.moji_space.1).max(1) as f32 * sy,
+                wrap_width: text_rect.width(),
+                scale: sy,
+                color: egui::Color32::from_rgb(230, 235, 245),
+            };
+            let rich = layout_rich_message(ui.painter(), "", msg, &st, &color);
+            paint_rich_message(
+                ui.painter(),
+                text_rect.left_top(),
+                &rich,
+                &st,
+                Some(&deco(def.moji_size)),
+            );
+        } else if !self.current_text.is_empty() {
+            let text_galley = self.mwnd_text_galley(
+                ui,
+                &self.current_text.chars().take(shown).collect::<String>(),
+                def.moji_size,
+                def.moji_space,
+                Some(text_rect.width()),
+                sx,
+                sy,
                 egui::Color32::from_rgb(230, 235, 245),
-                text_rect.width(),
             );
-            ui.painter()
-                .galley(text_rect.left_top(), text_galley, egui::Color32::WHITE);
+            paint_decorated_galley(
+                ui.painter(),
+                text_rect.left_top(),
+                &text_galley,
+                egui::Color32::WHITE,
+                Some(&deco(def.moji_size)),
+            );
         } else if !self.done {
             let text_font = egui::FontId::proportional(16.0);
             let waiting_galley = ui.painter().layout_no_wrap(
@@ -392,7 +508,7 @@ impl GuiApp {
         }
 
         // ── Click-wait indicator (▼ blinking) ──
-        if self.waiting_for_click && self.pending_options.is_empty() {
+        if self.waiting_for_click && self.text_fully_shown() && self.pending_options.is_empty() {
             let elapsed = self.start_time.elapsed().as_secs_f32();
             let alpha = ((elapsed * 3.0).sin() * 0.5 + 0.5) * 200.0 + 55.0;
             let indicator_color =
@@ -424,6 +540,10 @@ impl GuiApp {
         if self.pending_options.is_empty() {
             return;
         }
+        if self.selbtn_view.is_some() {
+            self.draw_selbtn(ui);
+            return;
+        }
 
         let screen = ui.max_rect();
 
@@ -476,18 +596,14 @@ impl GuiApp {
                 egui::StrokeKind::Outside,
             );
 
-            let text_color = if option.color >= 0 {
-                egui::Color32::from_rgb(
-                    ((option.color >> 16) & 0xff) as u8,
-                    ((option.color >> 8) & 0xff) as u8,
-                    (option.color & 0xff) as u8,
-                )
+            let text_color = if let Some(color) = self.selection_item_color(option) {
+                color
             } else if enabled {
                 egui::Color32::from_rgb(220, 230, 250)
             } else {
                 egui::Color32::from_rgb(160, 160, 170)
             };
-            let font = egui::FontId::proportional(17.0);
+            let font = egui::FontId::proportional(SEL_BUTTON_FONT_SIZE);
             let galley = ui.painter().layout_no_wrap(option.text.clone(), font, text_color);
             ui.painter().galley(
                 egui::pos2(
@@ -504,9 +620,7 @@ impl GuiApp {
         }
 
         if let Some(idx) = clicked_index {
-            let _ = self.selection_tx.send(idx as i32);
-            self.pending_options.clear();
-            self.pending_selbtn = None;
+            self.decide_selection(idx as i32);
         }
     }
 
@@ -590,11 +704,47 @@ impl GuiApp {
             .stick_to_bottom(true)
             .show(&mut child_ui, |ui| {
                 let line_font = egui::FontId::proportional(15.0);
-                for line in &self.backlog {
-                    ui.label(
-                        egui::RichText::new(line)
-                            .font(line_font.clone())
-                            .color(egui::Color32::from_rgb(190, 200, 215)),
+                let color_of = |no: i32| {
+                    let [r, g, b] = self.mwnd_config.color(no);
+                    egui::Color32::from_rgb(r, g, b)
+                };
+                for row in &self.backlog {
+                    let Some(msg) = &row.message else {
+                        ui.label(
+                            egui::RichText::new(&row.text)
+                                .font(line_font.clone())
+                                .color(egui::Color32::from_rgb(190, 200, 215)),
+                        );
+                        ui.add_space(4.0);
+                        continue;
+                    };
+                    let st = MessageTextStyle {
+                        family: self.message_font.clone(),
+                        size: 15.0,
+                        letter_spacing: 0.0,
+                        line_height: 24.0,
+                        wrap_width: ui.available_width(),
+                        scale: 15.0 / 24.0,
+                        color: egui::Color32::from_rgb(190, 200, 215),
+                    };
+                    let prefix = if msg.name.is_empty() {
+                        String::new()
+                    } else {
+                        format!("{}：", msg.name)
+                    };
+                    let rich = layout_rich_message(ui.painter(), &prefix, msg, &st, &color_of);
+                    // Leave room above the first line for its ruby.
+                    let ruby_room = if rich.ruby.is_empty() { 0.0 } else { rich.ruby_size };
+                    let (rect, _) = ui.allocate_exact_size(
+                        rich.galley.size() + egui::vec2(0.0, ruby_room),
+                        egui::Sense::hover(),
+                    );
+                    paint_rich_message(
+                        ui.painter(),
+                        rect.min + egui::vec2(0.0, ruby_room),
+                        &rich,
+                        &st,
+                        None,
                     );
                     ui.add_space(4.0);
                 }
@@ -692,3 +842,43 @@ impl GuiApp {
 
     impl_app_render_overlay!();
 }
+
+/// Cells per side of the mesh a projected image is cut into; egui maps
+/// textures affinely per triangle, so finer cells follow the perspective.
+const PROJECTED_MESH_CELLS: usize = 8;
+
+/// Draw `uv` of a texture onto the quad `corners` (top-left, top-right,
+/// bottom-right, bottom-left) with perspective.
+fn projected_image_mesh(
+    texture_id: egui::TextureId,
+    corners: [egui::Pos2; 4],
+    uv: egui::Rect,
+    tint: egui::Color32,
+) -> Option<egui::Mesh> {
+    let map = siglus::render::Homography::square_to_quad(&corners.map(|p| [p.x, p.y]))?;
+    let n = PROJECTED_MESH_CELLS;
+    let mut mesh = egui::epaint::Mesh::with_texture(texture_id);
+    for j in 0..=n {
+        for i in 0..=n {
+            let (u, v) = (i as f32 / n as f32, j as f32 / n as f32);
+            let (x, y) = map.apply(u, v)?;
+            mesh.vertices.push(egui::epaint::Vertex {
+                pos: egui::pos2(x, y),
+                uv: egui::pos2(
+                    uv.min.x + (uv.max.x - uv.min.x) * u,
+                    uv.min.y + (uv.max.y - uv.min.y) * v,
+                ),
+                color: tint,
+            });
+        }
+    }
+    let row = (n + 1) as u32;
+    for j in 0..n as u32 {
+        for i in 0..n as u32 {
+            let a = j * row + i;
+            mesh.indices
+                .extend_from_slice(&[a, a + 1, a + row + 1, a, a + row + 1, a + row]);
+        }
+    }
+    Some(mesh)
+}
@@ -0,0 +1,215 @@
+/// Base font, spacing and colors a rich message is laid out with.
+///
+/// Sizes are screen pixels; `scale` converts `VmTextStyle::size` (stage
+/// pixels) to screen pixels.
+struct MessageTextStyle {
+    family: egui::FontFamily,
+    size: f32,
+    letter_spacing: f32,
+    line_height: f32,
+    wrap_width: f32,
+    scale: f32,
+    color: egui::Color32,
+}
+
+/// A laid-out message plus the ruby to draw above its base text.
+struct RichMessageGalley {
+    galley: Arc<egui::Galley>,
+    /// Ruby text and the galley-relative rect of its base text, per row.
+    ruby: Vec<(String, egui::Rect)>,
+    ruby_size: f32,
+}
+
+/// Lay out a `VmMessage` page with egui sections per text run.
+///
+/// `prefix` (an inline name) is laid out in the base style first. Indents are
+/// measured in base-size character cells, as Siglus' fixed-pitch windows do.
+fn layout_rich_message(
+    painter: &egui::Painter,
+    prefix: &str,
+    msg: &siglus::vm::VmMessage,
+    st: &MessageTextStyle,
+    color_of: &dyn Fn(i32) -> egui::Color32,
+) -> RichMessageGalley {
+    use siglus::vm::{VmMessagePart, VmMessageWait};
+
+    let base = egui::TextFormat {
+        font_id: egui::FontId::new(st.size, st.family.clone()),
+        extra_letter_spacing: st.letter_spacing,
+        line_height: Some(st.line_height),
+        color: st.color,
+        ..Default::default()
+    };
+    let cell = st.size + st.letter_spacing;
+    let mut job = egui::text::LayoutJob::default();
+    job.wrap.max_width = st.wrap_width;
+    let mut ruby_ranges: Vec<(String, std::ops::Range<usize>)> = Vec::new();
+    let mut char_count = 0usize;
+    let mut line_chars = 0usize;
+    let mut indent_px = 0.0f32;
+    let mut leading = 0.0f32;
+
+    if !prefix.is_empty() {
+        job.append(prefix, 0.0, base.clone());
+        char_count += prefix.chars().count();
+        line_chars += prefix.chars().count();
+    }
+    for part in &msg.parts {
+        match part {
+            VmMessagePart::Text { text, style, ruby } => {
+                let mut format = base.clone();
+                if let Some(color) = style.color {
+                    format.color = color_of(color);
+                }
+                if let Some(size) = style.size {
+                    format.font_id.size = size.max(1) as f32 * st.scale;
+                }
+                let n = text.chars().count();
+                if let Some(ruby) = ruby {
+                    ruby_ranges.push((ruby.clone(), char_count..char_count + n));
+                }
+                job.append(text, std::mem::take(&mut leading), format);
+                char_count += n;
+                line_chars += n;
+            }
+            VmMessagePart::NewLine { .. } | VmMessagePart::Wait(VmMessageWait::R) => {
+                job.append("\n", 0.0, base.clone());
+                char_count += 1;
+                line_chars = 0;
+                if matches!(part, VmMessagePart::NewLine { indent: true }) {
+                    leading = indent_px;
+                }
+            }
+            VmMessagePart::Indent => indent_px = line_chars as f32 * cell,
+            VmMessagePart::ClearIndent => indent_px = 0.0,
+            VmMessagePart::Wait(_) | VmMessagePart::RepPos { .. } => {}
+        }
+    }
+
+    let galley = painter.layout_job(job);
+    let mut ruby = Vec::new();
+    for (text, range) in ruby_ranges {
+        // One rect per row the base text spans.
+        let mut span: Option<egui::Rect> = None;
+        for ci in range {
+            let a = galley.pos_from_cursor(egui::text::CCursor::new(ci));
+            let b = galley.pos_from_cursor(egui::text::CCursor::new(ci + 1));
+            if (a.top() - b.top()).abs() > 0.5 {
+                continue;
+            }
+            let glyph = egui::Rect::from_min_max(a.min, egui::pos2(b.left(), a.bottom()));
+            span = match span {
+                Some(s) if (s.top() - glyph.top()).abs() <= 0.5 => Some(s.union(glyph)),
+                Some(s) => {
+                    ruby.push((text.clone(), s));
+                    Some(glyph)
+                }
+                None => Some(glyph),
+            };
+        }
+        if let Some(s) = span {
+            ruby.push((text, s));
+        }
+    }
+    RichMessageGalley {
+        galley,
+        ruby,
+        ruby_size: (st.size * 0.5).max(6.0),
+    }
+}
+
+/// Script font decoration for message text, in screen pixels.
+///
+/// Mirrors `siglus::render::render_text`: the shadow is the (outlined) text
+/// offset down-right, the outline surrounds it, and faux bold widens every
+/// layer by one pixel when the face has no bold weight.
+#[derive(Clone, Copy)]
+struct TextDecoration {
+    decoration: siglus::vm::FontDecoration,
+    shadow_color: egui::Color32,
+    outline_color: egui::Color32,
+    /// Shadow offset and outline width.
+    px: egui::Vec2,
+    faux_bold: bool,
+}
+
+const OUTLINE_DIRS: [(f32, f32); 8] = [
+    (-1.0, -1.0),
+    (0.0, -1.0),
+    (1.0, -1.0),
+    (-1.0, 0.0),
+    (1.0, 0.0),
+    (-1.0, 1.0),
+    (0.0, 1.0),
+    (1.0, 1.0),
+];
+
+/// Paint `galley` at `pos` over its shadow and outline passes.
+fn paint_decorated_galley(
+    painter: &egui::Painter,
+    pos: egui::Pos2,
+    galley: &Arc<egui::Galley>,
+    color: egui::Color32,
+    deco: Option<&TextDecoration>,
+) {
+    let Some(deco) = deco else {
+        painter.galley(pos, galley.clone(), color);
+        return;
+    };
+    let draw = |at: egui::Pos2, over: Option<egui::Color32>| {
+        let passes = if deco.faux_bold { 2 } else { 1 };
+        for i in 0..passes {
+            let at = at + egui::vec2(i as f32, 0.0);
+            match over {
+                Some(c) => painter.galley_with_override_text_color(at, galley.clone(), c),
+                None => painter.galley(at, galley.clone(), color),
+            }
+        }
+    };
+    let ring: &[(f32, f32)] = if deco.decoration.has_outline() {
+        &OUTLINE_DIRS
+    } else {
+        &[(0.0, 0.0)]
+    };
+    if deco.decoration.has_shadow() {
+        for (dx, dy) in ring {
+            draw(
+                pos + deco.px + egui::vec2(dx * deco.px.x, dy * deco.px.y),
+                Some(deco.shadow_color),
+            );
+        }
+    }
+    if deco.decoration.has_outline() {
+        for (dx, dy) in ring {
+            draw(
+                pos + egui::vec2(dx * deco.px.x, dy * deco.px.y),
+                Some(deco.outline_color),
+            );
+        }
+    }
+    draw(pos, None);
+}
+
+/// Paint a rich message at `pos` with an optional decoration; ruby is
+/// centred above its base text.
+fn paint_rich_message(
+    painter: &egui::Painter,
+    pos: egui::Pos2,
+    rich: &RichMessageGalley,
+    st: &MessageTextStyle,
+    deco: Option<&TextDecoration>,
+) {
+    for (text, rect) in &rich.ruby {
+        let galley = painter.layout_no_wrap(
+            text.clone(),
+            egui::FontId::new(rich.ruby_size, st.family.clone()),
+            st.color,
+        );
+        let ruby_pos = egui::pos2(
+            pos.x + rect.center().x - galley.size().x / 2.0,
+            pos.y + rect.top() - galley.size().y * 0.8,
+        );
+        paint_decorated_galley(painter, ruby_pos, &galley, st.color, deco);
+    }
+    paint_decorated_galley(painter, pos, &rich.galley, st.color, deco);
+}
@@ -0,0 +1,323 @@
+impl GuiApp {
+    /// Draw every visible message window as laid out by Gameexe `#MWND.*` /
+    /// `#WAKU.*` (C++ elm_mwnd.cpp `C_elm_mwnd::frame` + `C_elm_mwnd_waku`).
+    fn draw_gameexe_mwnds(&self, ui: &mut egui::Ui) {
+        let cur = self.mwnd_config.resolve_mwnd_no(self.cur_mwnd);
+        for mwnd_no in 0..self.mwnd_config.mwnds.len() as i32 {
+            let view = self.mwnd_views.get(&mwnd_no);
+            let open = view.is_some_and(|v| v.state.open);
+            if mwnd_no != cur && !open {
+                continue;
+            }
+            // Only the current window's text is still appearing.
+            let shown = if mwnd_no == cur {
+                self.revealed_chars()
+            } else {
+                usize::MAX
+            };
+            let message = view
+                .and_then(|v| v.message.as_ref())
+                .map(|msg| msg.revealed(shown));
+            let (name, text) = if let Some(msg) = &message {
+                (msg.name.as_str(), String::new())
+            } else if mwnd_no == cur {
+                (
+                    self.current_name.as_str(),
+                    self.current_text.chars().take(shown).collect(),
+                )
+            } else {
+                view.map(|v| (v.name.as_str(), v.text.clone()))
+                    .unwrap_or_default()
+            };
+            self.draw_gameexe_mwnd(ui, mwnd_no, name, &text, message.as_ref(), mwnd_no == cur);
+        }
+    }
+
+    fn draw_gameexe_mwnd(
+        &self,
+        ui: &mut egui::Ui,
+        mwnd_no: i32,
+        name: &str,
+        text: &str,
+        message: Option<&siglus::vm::VmMessage>,
+        is_cur: bool,
+    ) {
+        let Some(def) = self.mwnd_config.mwnd(mwnd_no) else {
+            return;
+        };
+        let state = self
+            .mwnd_views
+            .get(&mwnd_no)
+            .map(|v| v.state.clone())
+            .unwrap_or_default();
+        let waku = self
+            .mwnd_config
+            .waku(state.waku_no.unwrap_or(def.waku_no))
+            .cloned()
+            .unwrap_or_default();
+        let (stage_rect, sx, sy) = self.stage_transform(ui.max_rect());
+        let to_screen = |x: i32, y: i32| {
+            egui::pos2(
+                stage_rect.left() + x as f32 * sx,
+                stage_rect.top() + y as f32 * sy,
+            )
+        };
+        let texture = |slot: MwndImageSlot| self.mwnd_textures.get(&(mwnd_no, slot));
+        let full_uv = egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0));
+
+        // A zero window size means "as large as the waku image".
+        let pos = state.window_pos.unwrap_or(def.window_pos);
+        let mut size = state.window_size.unwrap_or(def.window_size);
+        if (size.0 <= 0 || size.1 <= 0)
+            && let Some(tex) = texture(MwndImageSlot::Waku)
+        {
+            let [w, h] = tex.size();
+            size = (w as i32, h as i32);
+        }
+        let window_rect = egui::Rect::from_min_size(
+            to_screen(pos.0, pos.1),
+            egui::vec2(size.0 as f32 * sx, size.1 as f32 * sy),
+        );
+
+        // ── Filter (translucent backing), then the waku frame ──
+        if let Some(tex) = texture(MwndImageSlot::Filter) {
+            ui.painter()
+                .image(tex.id(), window_rect, full_uv, egui::Color32::WHITE);
+        } else if waku.filter_color[3] > 0 {
+            let (l, t, r, b) = waku.filter_margin;
+            let filter_rect = egui::Rect::from_min_max(
+                window_rect.min + egui::vec2(l as f32 * sx, t as f32 * sy),
+                window_rect.max - egui::vec2(r as f32 * sx, b as f32 * sy),
+            );
+            let [r, g, b, a] = waku.filter_color;
+            ui.painter().rect_filled(
+                filter_rect,
+                0.0,
+                egui::Color32::from_rgba_unmultiplied(r, g, b, a),
+            );
+        }
+        if let Some(tex) = texture(MwndImageSlot::Waku) {
+            ui.painter()
+                .image(tex.id(), window_rect, full_uv, egui::Color32::WHITE);
+        }
+
+        // ── Face graphic at the waku's FACE_POS ──
+        let face_shown = if let (Some(tex), Some((_, face_no))) =
+            (texture(MwndImageSlot::Face), state.face.as_ref())
+        {
+            let (fx, fy) = waku
+                .face_pos
+                .get(*face_no as usize)
+                .or(waku.face_pos.first())
+                .copied()
+                .unwrap_or((0, 0));
+            let [w, h] = tex.size();
+            let face_rect = egui::Rect::from_min_size(
+                window_rect.min + egui::vec2(fx as f32 * sx, fy as f32 * sy),
+                egui::vec2(w as f32 * sx, h as f32 * sy),
+            );
+            ui.painter()
+                .image(tex.id(), face_rect, full_uv, egui::Color32::WHITE);
+            true
+        } else {
+            false
+        };
+
+        let color = |no: i32| {
+            let [r, g, b] = self.mwnd_config.color(no);
+            egui::Color32::from_rgb(r, g, b)
+        };
+        let deco = |moji_size: i32| {
+            self.message_decoration(
+                moji_size,
+                sx,
+                sy,
+                color(def.shadow_color),
+                color(def.fuchi_color),
+            )
+        };
+
+        // ── Name: own window (mode 1) or inline before the text (mode 2) ──
+        let show_name = !name.is_empty() && !(def.face_hide_name && face_shown);
+        let name_label = if def.name_bracket {
+            format!("【{name}】")
+        } else {
+            name.to_string()
+        };
+        if show_name && def.name_disp_mode == 1 {
+            let name_pos = window_rect.min
+                + egui::vec2(
+                    def.name_window_pos.0 as f32 * sx,
+                    def.name_window_pos.1 as f32 * sy,
+                );
+            let mut name_size = egui::vec2(
+                def.name_window_size.0 as f32 * sx,
+                def.name_window_size.1 as f32 * sy,
+            );
+            if let Some(tex) = texture(MwndImageSlot::NameWaku) {
+                if name_size.x <= 0.0 || name_size.y <= 0.0 {
+                    let [w, h] = tex.size();
+                    name_size = egui::vec2(w as f32 * sx, h as f32 * sy);
+                }
+                ui.painter().image(
+                    tex.id(),
+                    egui::Rect::from_min_size(name_pos, name_size),
+                    full_uv,
+                    egui::Color32::WHITE,
+                );
+            }
+            let galley = self.mwnd_text_galley(
+                ui,
+                &name_label,
+                def.name_moji_size,
+                def.name_moji_space,
+                None,
+                sx,
+                sy,
+                color(def.name_moji_color),
+            );
+            let text_pos = name_pos
+                + egui::vec2(
+                    def.name_msg_pos.0 as f32 * sx,
+                    def.name_msg_pos.1 as f32 * sy,
+                );
+            paint_decorated_galley(
+                ui.painter(),
+                text_pos,
+                &galley,
+                egui::Color32::WHITE,
+                Some(&deco(def.name_moji_size)),
+            );
+        }
+
+        // ── Message text ──
+        let inline_name = if show_name && def.name_disp_mode == 2 {
+            name_label.as_str()
+        } else {
+            ""
+        };
+        let body = format!("{inline_name}{text}");
+        let moji_cnt = state.moji_cnt.unwrap_or(def.moji_cnt);
+        let text_pos = window_rect.min
+            + egui::vec2(def.message_pos.0 as f32 * sx, def.message_pos.1 as f32 * sy);
+        let text_size = egui::vec2(
+            (moji_cnt.0 * (def.moji_size + def.moji_space.0)) as f32 * sx,
+            (moji_cnt.1 * (def.moji_size + def.moji_space.1)) as f32 * sy,
+        );
+        if let Some(msg) = message {
+            let st = MessageTextStyle {
+                family: self.message_font.clone(),
+                size: def.moji_size.max(1) as f32 * sy,
+                letter_spacing: def.moji_space.0 as f32 * sx,
+                line_height: (def.moji_size + def.moji_space.1).max(1) as f32 * sy,
+                wrap_width: text_size.x.max(1.0),
+                scale: sy,
+                color: color(def.moji_color),
+            };
+            let rich = layout_rich_message(ui.painter(), inline_name, msg, &st, &color);
+            paint_rich_message(
+                ui.painter(),
+                text_pos,
+                &rich,
+                &st,
+                Some(&deco(def.moji_size)),
+            );
+        } else if !body.is_empty() {
+            let galley = self.mwnd_text_galley(
+                ui,
+                &body,
+                def.moji_size,
+                def.moji_space,
+                Some(text_size.x),
+                sx,
+                sy,
+                color(def.moji_color),
+            );
+            paint_decorated_galley(
+                ui.painter(),
+                text_pos,
+                &galley,
+                egui::Color32::WHITE,
+                Some(&deco(def.moji_size)),
+            );
+        }
+
+        // ── Click-wait indicator at the end of the text area ──
+        if is_cur
+            && self.waiting_for_click
+            && self.text_fully_shown()
+            && self.pending_options.is_empty()
+        {
+            let elapsed = self.start_time.elapsed().as_secs_f32();
+            let alpha = ((elapsed * 3.0).sin() * 0.5 + 0.5) * 200.0 + 55.0;
+            let [r, g, b] = self.mwnd_config.color(def.moji_color);
+            let indicator_color = egui::Color32::from_rgba_unmultiplied(r, g, b, alpha as u8);
+            let tip = text_pos + text_size;
+            let half = CLICK_INDICATOR_SIZE / 2.0;
+            let tri = vec![
+                egui::pos2(tip.x - half * 2.0, tip.y - CLICK_INDICATOR_SIZE),
+                egui::pos2(tip.x, tip.y - CLICK_INDICATOR_SIZE),
+                egui::pos2(tip.x - half, tip.y),
+            ];
+            ui.painter().add(egui::Shape::convex_polygon(
+                tri,
+                indicator_color,
+                egui::Stroke::NONE,
+            ));
+        }
+    }
+
+    /// Lay out window text with Gameexe moji size/spacing; `wrap_width` wraps
+    /// at `moji_cnt.x` characters.
+    #[allow(clippy::too_many_arguments)]
+    fn mwnd_text_galley(
+        &self,
+        ui: &egui::Ui,
+        text: &str,
+        moji_size: i32,
+        moji_space: (i32, i32),
+        wrap_width: Option<f32>,
+        sx: f32,
+        sy: f32,
+        color: egui::Color32,
+    ) -> Arc<egui::Galley> {
+        let format = egui::TextFormat {
+            font_id: egui::FontId::new(moji_size.max(1) as f32 * sy, self.message_font.clone()),
+            extra_letter_spacing: moji_space.0 as f32 * sx,
+            line_height: Some((moji_size + moji_space.1).max(1) as f32 * sy),
+            color,
+            ..Default::default()
+        };
+        let mut job = egui::text::LayoutJob::single_section(text.to_string(), format);
+        if let Some(width) = wrap_width {
+            job.wrap.max_width = width.max(1.0);
+        }
+        ui.painter().layout_job(job)
+    }
+
+    /// Decoration for `moji_size` text under the current script font style;
+    /// shadow and outline grow with the size, as in `render_text`.
+    fn message_decoration(
+        &self,
+        moji_size: i32,
+        sx: f32,
+        sy: f32,
+        shadow_color: egui::Color32,
+        outline_color: egui::Color32,
+    ) -> TextDecoration {
+        let style = &self.message_font_style;
+        let unit = (moji_size as f32 / 18.0).round().max(1.0);
+        let faux_bold = style.bold
+            && self
+                .fonts
+                .resolve(&style.name, true)
+                .is_none_or(|idx| self.fonts.faces()[idx].weight < 600);
+        TextDecoration {
+            decoration: style.decoration,
+            shadow_color,
+            outline_color,
+            px: egui::vec2((unit * sx).max(1.0), (unit * sy).max(1.0)),
+            faux_bold,
+        }
+    }
+}
@@ -52,6 +52,52 @@ macro_rules! impl_app_render_overlay {
                 self.show_vm_error_panel = !self.show_vm_error_panel;
             }
 
+            // Hot reload: toggle watching, or force a reload of every cached scene.
+            let hot_on = self.hot_reload.is_enabled();
+            let hot_rect = egui::Rect::from_min_size(
+                egui::pos2(screen.left() + 116.0, screen.top() + 12.0),
+                egui::vec2(44.0, 28.0),
+            );
+            let hot_resp = ui.allocate_rect(hot_rect, egui::Sense::click());
+            let hot_bg = match (hot_on, hot_resp.hovered()) {
+                (true, true) => egui::Color32::from_rgba_premultiplied(60, 110, 70, 200),
+                (true, false) => egui::Color32::from_rgba_premultiplied(40, 90, 50, 160),
+                (false, true) => egui::Color32::from_rgba_premultiplied(60, 80, 120, 160),
+                (false, false) => egui::Color32::from_rgba_premultiplied(30, 40, 60, 100),
+            };
+            ui.painter().rect_filled(hot_rect, 4.0, hot_bg);
+            ui.painter().text(
+                hot_rect.center(),
+                egui::Align2::CENTER_CENTER,
+                "HOT",
+                egui::FontId::proportional(12.0),
+                egui::Color32::from_rgba_premultiplied(180, 190, 210, 180),
+            );
+            if hot_resp.clicked() {
+                self.hot_reload.set_enabled(!hot_on);
+            }
+            let reload_rect = egui::Rect::from_min_size(
+                egui::pos2(screen.left() + 166.0, screen.top() + 12.0),
+                egui::vec2(28.0, 28.0),
+            );
+            let reload_resp = ui.allocate_rect(reload_rect, egui::Sense::click());
+            let reload_bg = if reload_resp.hovered() {
+                egui::Color32::from_rgba_premultiplied(60, 80, 120, 160)
+            } else {
+                egui::Color32::from_rgba_premultiplied(30, 40, 60, 100)
+            };
+            ui.painter().rect_filled(reload_rect, 4.0, reload_bg);
+            ui.painter().text(
+                reload_rect.center(),
+                egui::Align2::CENTER_CENTER,
+                "🔄",
+                egui::FontId::proportional(14.0),
+                egui::Color32::from_rgba_premultiplied(180, 190, 210, 180),
+            );
+            if reload_resp.clicked() {
+                self.hot_reload.request_reload();
+            }
+
             if self.skip_mode.load(Ordering::Relaxed) {
                 let skip_font = egui::FontId::proportional(13.0);
                 let skip_galley = ui.painter().layout_no_wrap(
@@ -424,11 +470,11 @@ macro_rules! impl_app_render_overlay {
         }
 
         fn draw_wipe_overlay(&self, ui: &mut egui::Ui) {
-            let Some(started) = self.wipe_started_at else {
+            let Some(started_ms) = self.wipe_started_ms else {
                 return;
             };
             let duration = self.wipe_duration_ms.max(1) as f32;
-            let elapsed_ms = (Instant::now() - started).as_secs_f32() * 1000.0;
+            let elapsed_ms = self.vm_clock.now_ms().saturating_sub(started_ms) as f32;
             if elapsed_ms >= duration {
                 return;
             }
@@ -436,29 +482,14 @@ macro_rules! impl_app_render_overlay {
             let ramp = match self.wipe_direction {
                 WipeDirection::SystemIn => 1.0 - p,
                 WipeDirection::SystemOut => p,
-                WipeDirection::Normal => {
-                    if p < 0.5 {
-                        p * 2.0
-                    } else {
-                        (1.0 - p) * 2.0
-                    }
-                }
+                // Script wipes are plane-to-plane transitions drawn by `draw_stage`.
+                WipeDirection::Normal => return,
             };
             let alpha = (ramp * 255.0).clamp(0.0, 255.0) as u8;
             if alpha == 0 {
                 return;
             }
-            let color = match self.wipe_direction {
-                WipeDirection::SystemIn | WipeDirection::SystemOut => {
-                    egui::Color32::from_rgba_premultiplied(0, 0, 0, alpha)
-                }
-                WipeDirection::Normal => match self.wipe_type.rem_euclid(4) {
-                    0 => egui::Color32::from_rgba_premultiplied(0, 0, 0, alpha),
-                    1 => egui::Color32::from_rgba_premultiplied(255, 255, 255, alpha),
-                    2 => egui::Color32::from_rgba_premultiplied(0, 0, 64, alpha),
-                    _ => egui::Color32::from_rgba_premultiplied(32, 0, 0, alpha),
-                },
-            };
+            let color = egui::Color32::from_rgba_premultiplied(0, 0, 0, alpha);
             ui.painter().rect_filled(ui.max_rect(), 0.0, color);
         }
     };
@@ -0,0 +1,253 @@
+use eframe::egui_glow;
+use eframe::glow::{self, HasContext};
+
+/// Paints `blend` 2 (subtract) meshes. egui's premultiplied source-over
+/// cannot take the source away from the frame, so these go through a glow
+/// callback drawing with `FUNC_REVERSE_SUBTRACT`: `D - S·α` per color
+/// channel, with the frame's alpha kept.
+#[derive(Clone, Default)]
+struct SubBlendPainter {
+    state: Arc<Mutex<SubBlendState>>,
+}
+
+#[derive(Default)]
+enum SubBlendState {
+    #[default]
+    Unbuilt,
+    /// The shaders did not build; sub meshes are skipped.
+    Failed,
+    Ready(SubBlendProgram),
+}
+
+struct SubBlendProgram {
+    program: glow::Program,
+    vbo: glow::Buffer,
+    ebo: glow::Buffer,
+    vao: Option<glow::VertexArray>,
+    u_rect: Option<glow::UniformLocation>,
+    u_sampler: Option<glow::UniformLocation>,
+    a_pos: u32,
+    a_tc: u32,
+    a_color: u32,
+}
+
+impl SubBlendPainter {
+    fn paint(&self, painter: &egui::Painter, mesh: egui::Mesh) {
+        if mesh.is_empty() {
+            return;
+        }
+        let state = self.state.clone();
+        let callback = egui_glow::CallbackFn::new(move |info, glow_painter| {
+            let Ok(mut state) = state.lock() else {
+                return;
+            };
+            let gl = glow_painter.gl();
+            if matches!(*state, SubBlendState::Unbuilt) {
+                *state = match unsafe { SubBlendProgram::build(gl) } {
+                    Ok(program) => SubBlendState::Ready(program),
+                    Err(e) => {
+                        error!("sub blend shaders: {e}");
+                        SubBlendState::Failed
+                    }
+                };
+            }
+            let SubBlendState::Ready(program) = &*state else {
+                return;
+            };
+            let Some(texture) = glow_painter.texture(mesh.texture_id) else {
+                return;
+            };
+            unsafe { program.draw(gl, &info, texture, &mesh) };
+        });
+        painter.add(egui::PaintCallback {
+            rect: painter.clip_rect(),
+            callback: Arc::new(callback),
+        });
+    }
+
+    fn destroy(&self, gl: &glow::Context) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+        if let SubBlendState::Ready(program) = std::mem::take(&mut *state) {
+            unsafe { program.destroy(gl) };
+        }
+    }
+}
+
+impl GuiApp {
+    /// Paint an object mesh; subtract goes through `SubBlendPainter`, every
+    /// other blend arrives pre-shaped by `bake_blend_for_egui`.
+    fn paint_object_mesh(
+        &self,
+        painter: &egui::Painter,
+        mesh: egui::Mesh,
+        blend: siglus::render::RenderBlend,
+    ) {
+        if blend == siglus::render::RenderBlend::Sub {
+            self.sub_blend.paint(painter, mesh);
+        } else {
+            painter.add(egui::Shape::mesh(mesh));
+        }
+    }
+}
+
+impl SubBlendProgram {
+    /// egui's own mesh shaders, placed in the callback viewport.
+    unsafe fn build(gl: &glow::Context) -> Result<Self, String> {
+        let version = egui_glow::ShaderVersion::get(gl);
+        let precision = if version.is_embedded() {
+            "precision mediump float;\n"
+        } else {
+            ""
+        };
+        let (vin, vout, fin) = if version.is_new_shader_interface() {
+            ("in", "out", "in")
+        } else {
+            ("attribute", "varying", "varying")
+        };
+        let (frag_decl, frag_out, sample) = if version.is_new_shader_interface() {
+            ("out vec4 f_color;\n", "f_color", "texture")
+        } else {
+            ("", "gl_FragColor", "texture2D")
+        };
+        let vertex = format!(
+            "{}{precision}uniform vec4 u_rect;\n\
+             {vin} vec2 a_pos;\n{vin} vec4 a_color;\n{vin} vec2 a_tc;\n\
+             {vout} vec4 v_color;\n{vout} vec2 v_tc;\n\
+             void main() {{\n\
+               gl_Position = vec4(2.0 * (a_pos.x - u_rect.x) / u_rect.z - 1.0,\n\
+                                  1.0 - 2.0 * (a_pos.y - u_rect.y) / u_rect.w, 0.0, 1.0);\n\
+               v_color = a_color;\n\
+               v_tc = a_tc;\n\
+             }}\n",
+            version.version_declaration()
+        );
+        let fragment = format!(
+            "{}{precision}uniform sampler2D u_sampler;\n\
+             {fin} vec4 v_color;\n{fin} vec2 v_tc;\n{frag_decl}\
+             void main() {{ {frag_out} = v_color * {sample}(u_sampler, v_tc); }}\n",
+            version.version_declaration()
+        );
+
+        unsafe {
+            let program = gl.create_program()?;
+            let mut shaders = Vec::new();
+            for (kind, source) in [
+                (glow::VERTEX_SHADER, vertex),
+                (glow::FRAGMENT_SHADER, fragment),
+            ] {
+                let shader = gl.create_shader(kind)?;
+                gl.shader_source(shader, &source);
+                gl.compile_shader(shader);
+                if !gl.get_shader_compile_status(shader) {
+                    let log = gl.get_shader_info_log(shader);
+                    gl.delete_shader(shader);
+                    gl.delete_program(program);
+                    return Err(log);
+                }
+                gl.attach_shader(program, shader);
+                shaders.push(shader);
+            }
+            gl.link_program(program);
+            for shader in shaders {
+                gl.detach_shader(program, shader);
+                gl.delete_shader(shader);
+            }
+            if !gl.get_program_link_status(program) {
+                let log = gl.get_program_info_log(program);
+                gl.delete_program(program);
+                return Err(log);
+            }
+            let attrib = |name: &str| gl.get_attrib_location(program, name).unwrap_or(0);
+            Ok(Self {
+                u_rect: gl.get_uniform_location(program, "u_rect"),
+                u_sampler: gl.get_uniform_location(program, "u_sampler"),
+                a_pos: attrib("a_pos"),
+                a_tc: attrib("a_tc"),
+                a_color: attrib("a_color"),
+                program,
+                vbo: gl.create_buffer()?,
+                ebo: gl.create_buffer()?,
+                vao: if version.is_new_shader_interface() {
+                    Some(gl.create_vertex_array()?)
+                } else {
+                    None
+                },
+            })
+        }
+    }
+
+    unsafe fn draw(
+        &self,
+        gl: &glow::Context,
+        info: &egui::PaintCallbackInfo,
+        texture: glow::Texture,
+        mesh: &egui::Mesh,
+    ) {
+        // `egui::epaint::Vertex` layout: pos (2×f32), uv (2×f32), color (4×u8).
+        const STRIDE: i32 = 20;
+        let mut vertices = Vec::with_capacity(mesh.vertices.len() * STRIDE as usize);
+        for v in &mesh.vertices {
+            for f in [v.pos.x, v.pos.y, v.uv.x, v.uv.y] {
+                vertices.extend_from_slice(&f.to_ne_bytes());
+            }
+            vertices.extend_from_slice(&v.color.to_array());
+        }
+        let indices: Vec<u8> = mesh.indices.iter().flat_map(|i| i.to_ne_bytes()).collect();
+        let rect = info.viewport;
+
+        unsafe {
+            gl.use_program(Some(self.program));
+            gl.uniform_4_f32(
+                self.u_rect.as_ref(),
+                rect.min.x,
+                rect.min.y,
+                rect.width(),
+                rect.height(),
+            );
+            gl.uniform_1_i32(self.u_sampler.as_ref(), 0);
+            gl.active_texture(glow::TEXTURE0);
+            gl.bind_texture(glow::TEXTURE_2D, Some(texture));
+
+            gl.bind_vertex_array(self.vao);
+            gl.bind_buffer(glow::ARRAY_BUFFER, Some(self.vbo));
+            gl.buffer_data_u8_slice(glow::ARRAY_BUFFER, &vertices, glow::STREAM_DRAW);
+            gl.bind_buffer(glow::ELEMENT_ARRAY_BUFFER, Some(self.ebo));
+            gl.buffer_data_u8_slice(glow::ELEMENT_ARRAY_BUFFER, &indices, glow::STREAM_DRAW);
+            for (loc, size, ty, normalized, offset) in [
+                (self.a_pos, 2, glow::FLOAT, false, 0),
+                (self.a_tc, 2, glow::FLOAT, false, 8),
+                (self.a_color, 4, glow::UNSIGNED_BYTE, true, 16),
+            ] {
+                gl.enable_vertex_attrib_array(loc);
+                gl.vertex_attrib_pointer_f32(loc, size, ty, normalized, STRIDE, offset);
+            }
+
+            gl.blend_equation_separate(glow::FUNC_REVERSE_SUBTRACT, glow::FUNC_ADD);
+            gl.blend_func_separate(glow::ONE, glow::ONE, glow::ZERO, glow::ONE);
+            gl.draw_elements(
+                glow::TRIANGLES,
+                mesh.indices.len() as i32,
+                glow::UNSIGNED_INT,
+                0,
+            );
+
+            for loc in [self.a_pos, self.a_tc, self.a_color] {
+                gl.disable_vertex_attrib_array(loc);
+            }
+            gl.bind_vertex_array(None);
+        }
+    }
+
+    unsafe fn destroy(self, gl: &glow::Context) {
+        unsafe {
+            gl.delete_program(self.program);
+            gl.delete_buffer(self.vbo);
+            gl.delete_buffer(self.ebo);
+            if let Some(vao) = self.vao {
+                gl.delete_vertex_array(vao);
+            }
+        }
+    }
+}
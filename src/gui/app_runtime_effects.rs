@@ -218,12 +218,12 @@ impl GuiApp {
                                 exit_fail += 1;
                                 let unrecoverable = Self::is_unrecoverable_exit_status(status);
                                 log::warn!(
-                                        "movie backend '{}' exited with status {} for {}, category=exit-code, unrecoverable={}, trying fallback",
-                                        backend,
-                                        status,
-                                        path.display(),
-                                        unrecoverable
-                                    );
+                                    "movie backend '{}' exited with status {} for {}, category=exit-code, unrecoverable={}, trying fallback",
+                                    backend,
+                                    status,
+                                    path.display(),
+                                    unrecoverable
+                                );
                                 if unrecoverable {
                                     let _ = tx.send(MoviePlaybackEvent::ObjectFailed {
                                         stage,
@@ -249,12 +249,12 @@ impl GuiApp {
                                 wait_fail += 1;
                                 let unrecoverable = Self::is_unrecoverable_wait_error(&err);
                                 log::warn!(
-                                        "movie backend '{}' wait failed for {}: {}, category=wait, unrecoverable={}, trying fallback",
-                                        backend,
-                                        path.display(),
-                                        err,
-                                        unrecoverable
-                                    );
+                                    "movie backend '{}' wait failed for {}: {}, category=wait, unrecoverable={}, trying fallback",
+                                    backend,
+                                    path.display(),
+                                    err,
+                                    unrecoverable
+                                );
                                 if unrecoverable {
                                     let _ = tx.send(MoviePlaybackEvent::ObjectFailed {
                                         stage,
@@ -381,207 +381,30 @@ impl GuiApp {
         );
     }
 
-    fn quake_speed_up_limit(
-        cur_time: f32,
-        start_time: f32,
-        start_value: f32,
-        end_time: f32,
-        end_value: f32,
-    ) -> f32 {
-        if (start_time - end_time).abs() < f32::EPSILON {
-            return end_value;
-        }
-        let mut ct = cur_time;
-        if start_time < end_time {
-            ct = ct.clamp(start_time, end_time);
-        } else {
-            ct = ct.clamp(end_time, start_time);
-        }
-        let t = (ct - start_time) / (end_time - start_time);
-        t * t * (end_value - start_value) + start_value
-    }
-
-    fn quake_speed_down_limit(
-        cur_time: f32,
-        start_time: f32,
-        start_value: f32,
-        end_time: f32,
-        end_value: f32,
-    ) -> f32 {
-        if (start_time - end_time).abs() < f32::EPSILON {
-            return end_value;
-        }
-        let mut ct = cur_time;
-        if start_time < end_time {
-            ct = ct.clamp(start_time, end_time);
-        } else {
-            ct = ct.clamp(end_time, start_time);
-        }
-        let t = (ct - end_time) / (end_time - start_time);
-        -(t * t) * (end_value - start_value) + end_value
-    }
-
-    fn quake_linear_limit(
-        cur_time: f32,
-        start_time: f32,
-        start_value: f32,
-        end_time: f32,
-        end_value: f32,
-    ) -> f32 {
-        if (start_time - end_time).abs() < f32::EPSILON {
-            return end_value;
-        }
-        if cur_time <= start_time {
-            return start_value;
-        }
-        if cur_time >= end_time {
-            return end_value;
-        }
-        (end_value - start_value) * (cur_time - start_time) / (end_time - start_time) + start_value
-    }
-
-    fn quake_transform_for_order_at(
-        req: &siglus::vm::VmQuakeRequest,
-        elapsed: f32,
-        order: i32,
-    ) -> (f32, f32, f32, f32, f32) {
-        // C++对照: cmd_effect.cpp 的 begin/end_order 裁剪分支。
-        // 当 order 不在区间内时直接返回恒等变换，后续所有位移/缩放分支都不参与。
-        if order < req.begin_order || order > req.end_order {
-            return (0.0, 0.0, 1.0, 0.0, 0.0);
-        }
-
-        let total = req.time_ms.max(1) as f32;
-        let total_quake_time = total * (req.cnt + req.end_cnt).max(0) as f32;
-        if elapsed >= total_quake_time {
-            return (0.0, 0.0, 1.0, 0.0, 0.0);
-        }
-
-        let quarter = (total / 4.0).max(1.0);
-        let jump_cur_time = elapsed % total;
-        let mut pos_x = 0.0f32;
-        let mut pos_y = 0.0f32;
-        let mut scale = 1.0f32;
-        let mut center_x = 0.0f32;
-        let mut center_y = 0.0f32;
-
-        match req.kind {
-            siglus::vm::VmQuakeKind::Vec => {
-                // C++对照: vec 分支按 1/4 周期做加速→减速→反向加速→反向减速。
-                // 保持与原分段时序一致，避免在 wait/check 轮询时出现相位偏移。
-                let rad = (req.vec as f32).to_radians();
-                let x_sign = rad.cos();
-                let y_sign = rad.sin();
-                let power = req.power as f32;
-                if jump_cur_time < quarter {
-                    pos_x =
-                        Self::quake_speed_up_limit(jump_cur_time, 0.0, 0.0, quarter, power / 2.0)
-                            * x_sign;
-                    pos_y =
-                        Self::quake_speed_up_limit(jump_cur_time, 0.0, 0.0, quarter, power / 2.0)
-                            * y_sign;
-                } else if jump_cur_time < quarter * 2.0 {
-                    let t = jump_cur_time - quarter;
-                    pos_x =
-                        Self::quake_speed_down_limit(t, 0.0, power / 2.0, quarter, power) * x_sign;
-                    pos_y =
-                        Self::quake_speed_down_limit(t, 0.0, power / 2.0, quarter, power) * y_sign;
-                } else if jump_cur_time < quarter * 3.0 {
-                    let t = jump_cur_time - quarter * 2.0;
-                    pos_x =
-                        Self::quake_speed_up_limit(t, 0.0, power, quarter, power / 2.0) * x_sign;
-                    pos_y =
-                        Self::quake_speed_up_limit(t, 0.0, power, quarter, power / 2.0) * y_sign;
-                } else {
-                    let t = jump_cur_time - quarter * 3.0;
-                    pos_x =
-                        Self::quake_speed_down_limit(t, 0.0, power / 2.0, quarter, 0.0) * x_sign;
-                    pos_y =
-                        Self::quake_speed_down_limit(t, 0.0, power / 2.0, quarter, 0.0) * y_sign;
-                }
-            }
-            siglus::vm::VmQuakeKind::Dir => {
-                // C++对照: dir 分支先离散化 8 方向，再复用同一套 1/4 周期速度曲线。
-                let (x_sign, y_sign) = match req.vec {
-                    0 => (0.0, -1.0),
-                    1 => (1.0, -1.0),
-                    2 => (1.0, 0.0),
-                    3 => (1.0, 1.0),
-                    4 => (0.0, 1.0),
-                    5 => (-1.0, 1.0),
-                    6 => (-1.0, 0.0),
-                    7 => (-1.0, -1.0),
-                    _ => (0.0, 0.0),
-                };
-                let power = req.power as f32;
-                if jump_cur_time < quarter {
-                    pos_x =
-                        Self::quake_speed_down_limit(jump_cur_time, 0.0, 0.0, quarter, power / 2.0)
-                            * x_sign;
-                    pos_y =
-                        Self::quake_speed_down_limit(jump_cur_time, 0.0, 0.0, quarter, power / 2.0)
-                            * y_sign;
-                } else if jump_cur_time < quarter * 2.0 {
-                    let t = jump_cur_time - quarter;
-                    pos_x = Self::quake_speed_up_limit(t, 0.0, power / 2.0, quarter, 0.0) * x_sign;
-                    pos_y = Self::quake_speed_up_limit(t, 0.0, power / 2.0, quarter, 0.0) * y_sign;
-                } else if jump_cur_time < quarter * 3.0 {
-                    let t = jump_cur_time - quarter * 2.0;
-                    pos_x =
-                        Self::quake_speed_down_limit(t, 0.0, 0.0, quarter, -power / 2.0) * x_sign;
-                    pos_y =
-                        Self::quake_speed_down_limit(t, 0.0, 0.0, quarter, -power / 2.0) * y_sign;
-                } else {
-                    let t = jump_cur_time - quarter * 3.0;
-                    pos_x = Self::quake_speed_up_limit(t, 0.0, -power / 2.0, quarter, 0.0) * x_sign;
-                    pos_y = Self::quake_speed_up_limit(t, 0.0, -power / 2.0, quarter, 0.0) * y_sign;
-                }
-            }
-            siglus::vm::VmQuakeKind::Zoom => {
-                // C++对照: zoom 分支只改 scale + center，不直接改 pos。
-                // center_x/center_y 在脚本可见，必须与 C++ 的参数回放一致。
-                let power = req.power.clamp(0, 255) as f32;
-                let scale_peak = 256.0 / (256.0 - power);
-                let half_scale = (scale_peak - 1.0) / 2.0 + 1.0;
-                center_x = req.center_x as f32;
-                center_y = req.center_y as f32;
-                if jump_cur_time < quarter {
-                    scale =
-                        Self::quake_speed_up_limit(jump_cur_time, 0.0, 1.0, quarter, half_scale);
-                } else if jump_cur_time < quarter * 2.0 {
-                    let t = jump_cur_time - quarter;
-                    scale = Self::quake_speed_down_limit(t, 0.0, half_scale, quarter, scale_peak);
-                } else if jump_cur_time < quarter * 3.0 {
-                    let t = jump_cur_time - quarter * 2.0;
-                    scale = Self::quake_speed_up_limit(t, 0.0, scale_peak, quarter, half_scale);
-                } else {
-                    let t = jump_cur_time - quarter * 3.0;
-                    scale = Self::quake_speed_down_limit(t, 0.0, half_scale, quarter, 1.0);
-                }
-            }
-        }
-
-        // C++对照: end_cnt 衰减段线性收敛到单位变换；该段对 vec/dir/zoom 共用。
-        if elapsed >= total * req.cnt.max(0) as f32 {
-            let fade = Self::quake_linear_limit(
-                elapsed,
-                total * req.cnt.max(0) as f32,
-                1.0,
-                total_quake_time,
-                0.0,
-            );
-            pos_x *= fade;
-            pos_y *= fade;
-            scale = (scale - 1.0) * fade + 1.0;
-        }
-
-        (pos_x, pos_y, scale, center_x, center_y)
+    /// Running quakes and the shake offset at the current VM time.
+    fn stage_quakes(&self) -> (Vec<siglus::render::RenderQuake>, (f32, f32)) {
+        let now = self.vm_clock.now_ms();
+        let quakes = self
+            .quakes
+            .values()
+            .map(|(req, started_at)| siglus::render::RenderQuake {
+                req: *req,
+                elapsed_ms: now.saturating_sub(*started_at) as f32,
+            })
+            .collect();
+        let shake = self
+            .shake
+            .as_ref()
+            .and_then(|(steps, started_at)| {
+                siglus::render::shake_offset_at(steps, now.saturating_sub(*started_at))
+            })
+            .unwrap_or((0.0, 0.0));
+        (quakes, shake)
     }
 
     fn quake_transform_for_order(&self, order: i32) -> (f32, f32, f32, f32, f32) {
-        let (Some(req), Some(started_at)) = (self.quake_request, self.quake_started_at) else {
-            return (0.0, 0.0, 1.0, 0.0, 0.0);
-        };
-        Self::quake_transform_for_order_at(&req, started_at.elapsed().as_millis() as f32, order)
+        let (quakes, shake) = self.stage_quakes();
+        let q = siglus::render::quake_transform_for_order(&quakes, shake, order);
+        (q.x, q.y, q.scale, q.center_x, q.center_y)
     }
 }
@@ -0,0 +1,207 @@
+/// A `#SELBTN` selection on screen and its template textures.
+///
+/// The template's open/close anime keys are not drawn: their types are not
+/// known here, so selections appear and close at once.
+struct SelBtnView {
+    template: SelBtnTemplate,
+    /// Item backgrounds in `GameexeButtonState` order.
+    textures: [Option<egui::TextureHandle>; 5],
+    /// Item under the cursor, for the hit SE.
+    hit: Option<usize>,
+}
+
+impl GuiApp {
+    /// Show a selection, loading the item textures of its `#SELBTN` template.
+    fn open_selection(&mut self, ctx: &egui::Context, req: SelectionRequest) {
+        self.pending_selbtn = req.selbtn.clone();
+        self.pending_options = req.options;
+        self.selbtn_view = req.template.map(|template| {
+            let textures = std::array::from_fn(|i| {
+                let look = template.looks[i].as_ref()?;
+                let size = [look.image.width() as usize, look.image.height() as usize];
+                let pixels = look.image.to_rgba8().into_flat_samples();
+                let color_image = egui::ColorImage::from_rgba_unmultiplied(size, pixels.as_slice());
+                Some(ctx.load_texture(
+                    format!("selbtn_{}_{i}", req.template_no),
+                    color_image,
+                    egui::TextureOptions::LINEAR,
+                ))
+            });
+            SelBtnView {
+                template,
+                textures,
+                hit: None,
+            }
+        });
+    }
+
+    fn close_selection(&mut self) {
+        self.pending_options.clear();
+        self.pending_selbtn = None;
+        self.selbtn_view = None;
+    }
+
+    /// Hand a choice (or -1 for cancel) to the VM.
+    fn decide_selection(&mut self, result: i32) {
+        if result >= 0
+            && let Some(view) = self.selbtn_view.as_ref()
+            && let (Some(name), Some(am)) = (&view.template.decide_se, &mut self.audio_manager)
+        {
+            am.play_se(name);
+        }
+        let _ = self.selection_tx.send(result);
+        self.close_selection();
+    }
+
+    /// Text color of a selection item; item colors are `#COLOR_TABLE` indices.
+    fn selection_item_color(&self, option: &SelectionOption) -> Option<egui::Color32> {
+        (option.color >= 0).then(|| {
+            let [r, g, b] = self.mwnd_config.color(option.color);
+            egui::Color32::from_rgb(r, g, b)
+        })
+    }
+
+    /// Draw the selection from its `#SELBTN` template; items are laid out in
+    /// stage pixels from `base_pos` in `rep_pos` steps.
+    ///
+    /// C++ reference: elm_sel_btn.cpp `C_elm_sel_btn::frame`.
+    fn draw_selbtn(&mut self, ui: &mut egui::Ui) {
+        use siglus::gameexe::GameexeButtonState;
+
+        let Some(view) = self.selbtn_view.as_ref() else {
+            return;
+        };
+        let layout = &view.template.layout;
+        let (stage_rect, sx, sy) = self.stage_transform(ui.max_rect());
+
+        let item_size = view.textures[GameexeButtonState::Normal as usize]
+            .as_ref()
+            .map(|tex| {
+                let [w, h] = tex.size();
+                (w as f32, h as f32)
+            })
+            .unwrap_or((SEL_BUTTON_WIDTH, SEL_BUTTON_HEIGHT));
+        let n = self.pending_options.len() as f32;
+        let rep = (layout.rep_pos.0 as f32, layout.rep_pos.1 as f32);
+        let block = (
+            (n - 1.0) * rep.0 + item_size.0,
+            (n - 1.0) * rep.1 + item_size.1,
+        );
+        let align = |a: i32| match a {
+            1 => 0.5,
+            2 => 1.0,
+            _ => 0.0,
+        };
+        let origin = (
+            layout.base_pos.0 as f32 - block.0 * align(layout.x_align),
+            layout.base_pos.1 as f32 - block.1 * align(layout.y_align),
+        );
+        let to_screen =
+            |x: f32, y: f32| egui::pos2(stage_rect.left() + x * sx, stage_rect.top() + y * sy);
+        let full_uv = egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0));
+        let text_color = |no: i32| {
+            let [r, g, b] = self.mwnd_config.color(no);
+            egui::Color32::from_rgb(r, g, b)
+        };
+        let pointer_down = ui.input(|i| i.pointer.primary_down());
+
+        let mut hit = None;
+        let mut clicked = None;
+        for (i, option) in self.pending_options.iter().enumerate() {
+            let pos = (origin.0 + i as f32 * rep.0, origin.1 + i as f32 * rep.1);
+            let item_rect = egui::Rect::from_min_size(
+                to_screen(pos.0, pos.1),
+                egui::vec2(item_size.0 * sx, item_size.1 * sy),
+            );
+            let enabled = option.item_type != 0;
+            let sense = if enabled {
+                egui::Sense::click()
+            } else {
+                egui::Sense::hover()
+            };
+            let resp = ui.allocate_rect(item_rect, sense);
+            let hovered = enabled && resp.hovered();
+            if hovered {
+                hit = Some(i);
+            }
+            if enabled && resp.clicked() {
+                clicked = Some(i);
+            }
+            let state = if !enabled {
+                GameexeButtonState::Disable
+            } else if hovered && pointer_down {
+                GameexeButtonState::Push
+            } else if hovered {
+                GameexeButtonState::Hit
+            } else {
+                GameexeButtonState::Normal
+            };
+
+            let look = view.template.looks[state as usize]
+                .as_ref()
+                .zip(view.textures[state as usize].as_ref())
+                .or_else(|| {
+                    view.template.looks[GameexeButtonState::Normal as usize]
+                        .as_ref()
+                        .zip(view.textures[GameexeButtonState::Normal as usize].as_ref())
+                });
+            if let Some((look, tex)) = look {
+                let [w, h] = tex.size();
+                let rect = egui::Rect::from_min_size(
+                    to_screen(pos.0 + look.offset.0, pos.1 + look.offset.1),
+                    egui::vec2(w as f32 * sx, h as f32 * sy),
+                );
+                ui.painter().image(
+                    tex.id(),
+                    rect,
+                    full_uv,
+                    egui::Color32::from_white_alpha((look.alpha * 255.0) as u8),
+                );
+            }
+
+            let color = self
+                .selection_item_color(option)
+                .unwrap_or_else(|| match state {
+                    GameexeButtonState::Disable => text_color(layout.moji_disable_color),
+                    GameexeButtonState::Hit | GameexeButtonState::Push => {
+                        text_color(layout.moji_hit_color)
+                    }
+                    _ => text_color(layout.moji_color),
+                });
+            // Templates without `MOJI_SIZE` use the plain selection text size.
+            let font_size = if layout.moji_size > 0 {
+                layout.moji_size as f32 * sy
+            } else {
+                SEL_BUTTON_FONT_SIZE
+            };
+            let font = egui::FontId::proportional(font_size);
+            let galley = ui
+                .painter()
+                .layout_no_wrap(option.text.clone(), font, color);
+            let moji = (layout.moji_pos.0 as f32 * sx, layout.moji_pos.1 as f32 * sy);
+            let text_pos = if layout.moji_x_align == 1 {
+                egui::pos2(
+                    item_rect.center().x - galley.size().x / 2.0 + moji.0,
+                    item_rect.center().y - galley.size().y / 2.0 + moji.1,
+                )
+            } else {
+                item_rect.min + egui::vec2(moji.0, moji.1)
+            };
+            ui.painter().galley(text_pos, galley, color);
+        }
+
+        if let Some(view) = self.selbtn_view.as_mut()
+            && view.hit != hit
+        {
+            if hit.is_some()
+                && let (Some(name), Some(am)) = (&view.template.hit_se, &mut self.audio_manager)
+            {
+                am.play_se(name);
+            }
+            view.hit = hit;
+        }
+        if let Some(idx) = clicked {
+            self.decide_selection(idx as i32);
+        }
+    }
+}
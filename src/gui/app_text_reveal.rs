@@ -0,0 +1,127 @@
+/// Typewriter state of the current window's text (C++ elm_mwnd.cpp
+/// `C_elm_mwnd::frame` advancing the shown moji count by the message speed).
+struct TextReveal {
+    page_serial: Option<u32>,
+    /// Characters already shown when this text started (a continued page).
+    from: usize,
+    total: usize,
+    started: Instant,
+    /// Set when a click or skip showed the rest at once.
+    completed_at: Option<Instant>,
+}
+
+impl TextReveal {
+    fn shown(&self, speed: &siglus::vm::VmMessageSpeed) -> usize {
+        if self.completed_at.is_some() {
+            return self.total;
+        }
+        let elapsed = self.started.elapsed().as_millis() as u64;
+        self.from + speed.revealed_chars(elapsed, self.total.saturating_sub(self.from))
+    }
+
+    /// Milliseconds since every character appeared; `None` while revealing.
+    fn since_complete_ms(&self, speed: &siglus::vm::VmMessageSpeed) -> Option<u64> {
+        let done_at = self.completed_at.unwrap_or_else(|| {
+            let ms = speed.reveal_ms(self.total.saturating_sub(self.from));
+            self.started + std::time::Duration::from_millis(ms)
+        });
+        Instant::now()
+            .checked_duration_since(done_at)
+            .map(|d| d.as_millis() as u64)
+    }
+}
+
+impl GuiApp {
+    /// Start revealing `total` characters of the current window. Text that
+    /// continues the previous page starts after what was already shown.
+    fn start_text_reveal(&mut self, page_serial: Option<u32>, total: usize, instant: bool) {
+        let from = match &self.text_reveal {
+            Some(prev) if page_serial.is_some() && prev.page_serial == page_serial => {
+                prev.total.min(total)
+            }
+            _ => 0,
+        };
+        let now = Instant::now();
+        self.text_reveal = Some(TextReveal {
+            page_serial,
+            from,
+            total,
+            started: now,
+            completed_at: instant.then_some(now),
+        });
+    }
+
+    /// Characters of the current window's text to draw.
+    fn revealed_chars(&self) -> usize {
+        self.text_reveal
+            .as_ref()
+            .map_or(usize::MAX, |r| r.shown(&self.message_speed))
+    }
+
+    fn text_fully_shown(&self) -> bool {
+        self.text_reveal
+            .as_ref()
+            .is_none_or(|r| r.since_complete_ms(&self.message_speed).is_some())
+    }
+
+    /// Show the rest of the text at once; returns whether anything was
+    /// still appearing.
+    fn complete_text_reveal(&mut self) -> bool {
+        if self.text_fully_shown() {
+            return false;
+        }
+        if let Some(reveal) = self.text_reveal.as_mut() {
+            reveal.completed_at = Some(Instant::now());
+        }
+        true
+    }
+
+    /// A click while text is still appearing completes it; otherwise it
+    /// advances the VM.
+    fn click_advance(&mut self) {
+        if !self.complete_text_reveal() {
+            self.advance();
+        }
+    }
+
+    /// Keep the typewriter animating and advance on its own in auto mode
+    /// (C++ `tnm_msg_proc_auto_mode`).
+    fn tick_text_reveal(&mut self, ctx: &egui::Context) {
+        let Some(reveal) = &self.text_reveal else {
+            return;
+        };
+        let speed = self.message_speed;
+        let Some(since) = reveal.since_complete_ms(&speed) else {
+            ctx.request_repaint();
+            return;
+        };
+        if !speed.auto_mode
+            || !self.waiting_for_click
+            || self.show_backlog
+            || !self.pending_options.is_empty()
+        {
+            return;
+        }
+        let chars = reveal.total.saturating_sub(reveal.from);
+        if speed.auto_advance_ready(chars, since, self.koe.is_playing()) {
+            self.advance();
+        } else {
+            ctx.request_repaint_after(std::time::Duration::from_millis(50));
+        }
+    }
+
+    /// Report the current voice finished once the audio manager drained it.
+    fn tick_koe(&mut self) {
+        if self.koe_serial == 0 {
+            return;
+        }
+        if !self
+            .audio_manager
+            .as_ref()
+            .is_some_and(AudioManager::koe_playing)
+        {
+            self.koe.finish(self.koe_serial);
+            self.koe_serial = 0;
+        }
+    }
+}
@@ -0,0 +1,98 @@
+/// A weather object's running particle simulation, its start on the VM
+/// clock and one texture per pattern (with the pixels, for composed captures).
+struct ObjectWeatherView {
+    sim: siglus::render::WeatherSim,
+    started_ms: u64,
+    textures: BTreeMap<i32, egui::TextureHandle>,
+    images: BTreeMap<i32, Arc<image::RgbaImage>>,
+}
+
+impl GuiApp {
+    fn set_object_weather(
+        &mut self,
+        ctx: &egui::Context,
+        key: (StagePlane, i32),
+        weather: Option<ObjectWeatherEvent>,
+    ) {
+        let Some(weather) = weather else {
+            self.object_weather.remove(&key);
+            return;
+        };
+        let (w, h) = self.scene_size.unwrap_or((1280, 720));
+        let textures = weather
+            .patterns
+            .iter()
+            .map(|(pat_no, image)| {
+                let size = [image.width() as usize, image.height() as usize];
+                let color_image = egui::ColorImage::from_rgba_unmultiplied(
+                    size,
+                    image.as_flat_samples().as_slice(),
+                );
+                let name = format!("weather_{:?}_{}_{pat_no}", key.0, key.1);
+                (
+                    *pat_no,
+                    ctx.load_texture(name, color_image, egui::TextureOptions::LINEAR),
+                )
+            })
+            .collect();
+        self.object_weather.insert(
+            key,
+            ObjectWeatherView {
+                sim: siglus::render::WeatherSim::new(
+                    weather.params,
+                    w.max(1) as u32,
+                    h.max(1) as u32,
+                    weather.seed,
+                ),
+                started_ms: self.vm_clock.now_ms(),
+                textures,
+                images: weather.patterns.into_iter().collect(),
+            },
+        );
+    }
+
+    /// Step every weather to now; particles keep moving, so keep repainting.
+    fn tick_weather(&mut self, ctx: &egui::Context) {
+        if self.object_weather.is_empty() {
+            return;
+        }
+        let now = self.vm_clock.now_ms();
+        for view in self.object_weather.values_mut() {
+            view.sim.advance_to(now.saturating_sub(view.started_ms));
+        }
+        ctx.request_repaint();
+    }
+
+    /// Draw a weather object's particles around its stage position `pos`
+    /// (screen space) in place of its single image.
+    fn draw_weather(
+        &self,
+        ui: &egui::Ui,
+        view: &ObjectWeatherView,
+        pos: egui::Pos2,
+        stage_scale: egui::Vec2,
+        render: &ObjectRenderState,
+    ) {
+        for pt in view.sim.particles() {
+            let Some(texture) = view.textures.get(&pt.pat_no) else {
+                continue;
+            };
+            let alpha = (render.alpha * pt.alpha).clamp(0.0, 1.0);
+            if alpha <= 0.0 {
+                continue;
+            }
+            let size = texture.size_vec2()
+                * egui::vec2(render.scale_x, render.scale_y)
+                * pt.scale
+                * stage_scale;
+            let center = pos + egui::vec2(pt.x, pt.y) * stage_scale;
+            let tint = egui::Color32::from_white_alpha((alpha * 255.0) as u8);
+            ui.painter().image(
+                texture.id(),
+                egui::Rect::from_center_size(center, size.abs()),
+                egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0)),
+                tint,
+            );
+        }
+    }
+}
@@ -0,0 +1,252 @@
+/// Cell grid used to approximate mask wipes (egui has no per-pixel masking).
+const MASK_WIPE_COLS: u32 = 48;
+const MASK_WIPE_ROWS: u32 = 27;
+
+/// Objects one pass of a ranged wipe draws.
+///
+/// C++ cmd_wipe.cpp: only objects inside `begin_order..=end_order` and
+/// `begin_layer..=end_layer` (plus lower orders with `with_low_order`) take part
+/// in the transition; the rest stay live.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WipePass {
+    /// Untouched objects below the wipe range.
+    Below,
+    /// Objects inside the range: drawn as old and new frame.
+    Covered,
+    /// Untouched objects above the wipe range.
+    Above,
+}
+
+impl GuiApp {
+    /// Copy of the stage draw inputs of the planes `keep` accepts.
+    fn stage_snapshot(&self, keep: impl Fn(StagePlane) -> bool) -> StageSnapshot {
+        fn planes<V: Clone>(
+            map: &BTreeMap<(StagePlane, i32), V>,
+            keep: &impl Fn(StagePlane) -> bool,
+        ) -> BTreeMap<(StagePlane, i32), V> {
+            map.iter()
+                .filter(|((plane, _), _)| keep(*plane))
+                .map(|(k, v)| (*k, v.clone()))
+                .collect()
+        }
+        StageSnapshot {
+            background_texture: self.background_texture.clone(),
+            background_textures: self
+                .background_textures
+                .iter()
+                .filter(|(plane, _)| keep(**plane))
+                .map(|(k, v)| (*k, v.clone()))
+                .collect(),
+            missing_background_names: self
+                .missing_background_names
+                .iter()
+                .filter(|(plane, _)| keep(**plane))
+                .map(|(k, v)| (*k, v.clone()))
+                .collect(),
+            object_textures: planes(&self.object_textures, &keep),
+            missing_object_names: planes(&self.missing_object_names, &keep),
+            object_pos: planes(&self.object_pos, &keep),
+            object_visible: planes(&self.object_visible, &keep),
+            object_sort: planes(&self.object_sort, &keep),
+            object_render: planes(&self.object_render, &keep),
+            object_reps: planes(&self.object_reps, &keep),
+            object_parent: planes(&self.object_parent, &keep),
+            object_child_sort_test: self
+                .object_child_sort_test
+                .iter()
+                .filter(|(plane, _)| keep(*plane))
+                .copied()
+                .collect(),
+        }
+    }
+
+    /// Pass of a ranged wipe an object at `order`/`layer` belongs to.
+    ///
+    /// Objects sort by order, then layer, so an object whose order is in range
+    /// but whose layer is not sits below or above the covered block by layer.
+    fn wipe_pass_of(&self, order: i32, layer: i32) -> WipePass {
+        let Some(range) = &self.wipe_range else {
+            return WipePass::Covered;
+        };
+        if range.covers(order, layer) {
+            return WipePass::Covered;
+        }
+        let below_order = !range.with_low_order && order < range.begin_order;
+        let in_order =
+            (range.with_low_order || order >= range.begin_order) && order <= range.end_order;
+        if below_order || (in_order && layer < range.begin_layer) {
+            WipePass::Below
+        } else {
+            WipePass::Above
+        }
+    }
+
+    /// Whether `draw_objects` draws an object at `order`/`layer` in the current pass.
+    fn in_wipe_pass(&self, order: i32, layer: i32) -> bool {
+        self.wipe_pass
+            .is_none_or(|pass| self.wipe_pass_of(order, layer) == pass)
+    }
+
+    /// Exchange the live stage draw inputs with `snap` (call twice to restore).
+    fn swap_stage(&mut self, snap: &mut StageSnapshot) {
+        std::mem::swap(&mut self.background_texture, &mut snap.background_texture);
+        std::mem::swap(&mut self.background_textures, &mut snap.background_textures);
+        std::mem::swap(
+            &mut self.missing_background_names,
+            &mut snap.missing_background_names,
+        );
+        std::mem::swap(&mut self.object_textures, &mut snap.object_textures);
+        std::mem::swap(
+            &mut self.missing_object_names,
+            &mut snap.missing_object_names,
+        );
+        std::mem::swap(&mut self.object_pos, &mut snap.object_pos);
+        std::mem::swap(&mut self.object_visible, &mut snap.object_visible);
+        std::mem::swap(&mut self.object_sort, &mut snap.object_sort);
+        std::mem::swap(&mut self.object_render, &mut snap.object_render);
+        std::mem::swap(&mut self.object_reps, &mut snap.object_reps);
+        std::mem::swap(&mut self.object_parent, &mut snap.object_parent);
+        std::mem::swap(
+            &mut self.object_child_sort_test,
+            &mut snap.object_child_sort_test,
+        );
+    }
+
+    /// Kind and eased progress of the running script wipe, if any.
+    fn script_wipe_state(&self) -> Option<(siglus::render::WipeKind, f32)> {
+        if self.wipe_direction != WipeDirection::Normal {
+            return None;
+        }
+        let started_ms = self.wipe_started_ms?;
+        let elapsed_ms = self.vm_clock.now_ms().saturating_sub(started_ms);
+        if elapsed_ms >= self.wipe_duration_ms {
+            return None;
+        }
+        let t =
+            siglus::render::wipe_progress(elapsed_ms, self.wipe_duration_ms, self.wipe_speed_mode);
+        let kind = if self.wipe_mask_grid.is_some() {
+            siglus::render::WipeKind::mask_from_type(self.wipe_type, &self.wipe_option)
+        } else {
+            siglus::render::WipeKind::from_type(self.wipe_type)
+        };
+        Some((kind, t))
+    }
+
+    /// Draw one frame (live stage, or `frame` when given) shifted by `offset`,
+    /// restricted to `clips` and faded by `opacity`.
+    fn draw_stage_layer(
+        &mut self,
+        ui: &mut egui::Ui,
+        frame: Option<&mut StageSnapshot>,
+        offset: egui::Vec2,
+        clips: &[egui::Rect],
+        opacity: f32,
+    ) {
+        let mut frame = frame;
+        if let Some(snap) = frame.as_deref_mut() {
+            self.swap_stage(snap);
+        }
+        let max_rect = ui.max_rect();
+        for clip in clips {
+            let clip = clip.intersect(ui.clip_rect());
+            if clip.width() <= 0.0 || clip.height() <= 0.0 {
+                continue;
+            }
+            let mut child =
+                ui.new_child(egui::UiBuilder::new().max_rect(max_rect.translate(offset)));
+            child.set_clip_rect(clip);
+            child.multiply_opacity(opacity);
+            self.draw_background(&mut child);
+            self.draw_objects(&mut child);
+        }
+        if let Some(snap) = frame {
+            self.swap_stage(snap);
+        }
+    }
+
+    /// Stage drawing with script wipes as old → new frame transitions.
+    ///
+    /// Plain wipes crossfade (see `siglus::render::WipeKind`); mask wipes are
+    /// resolved per grid cell.
+    fn draw_stage(&mut self, ui: &mut egui::Ui) {
+        let wipe = self.script_wipe_state();
+        let (Some((kind, t)), Some(mut old)) = (wipe, self.wipe_from.take()) else {
+            if wipe.is_none() {
+                self.wipe_from = None;
+            }
+            self.draw_background(ui);
+            self.draw_objects(ui);
+            return;
+        };
+        use siglus::render::WipeKind;
+
+        let screen = ui.max_rect();
+        let (stage_rect, _, _) = self.stage_transform(screen);
+        let full = [screen];
+
+        // Objects outside the wipe range are drawn live around the transition.
+        let ranged = self.wipe_range.is_some();
+        if ranged {
+            self.wipe_pass = Some(WipePass::Below);
+            self.draw_stage_layer(ui, None, egui::Vec2::ZERO, &full, 1.0);
+            self.wipe_pass = Some(WipePass::Covered);
+        }
+
+        match kind {
+            WipeKind::Mask { soft, reverse } if self.wipe_mask_grid.is_some() => {
+                let grid = self.wipe_mask_grid.take().unwrap_or_default();
+                let cell_w = stage_rect.width() / MASK_WIPE_COLS as f32;
+                let cell_h = stage_rect.height() / MASK_WIPE_ROWS as f32;
+                let cell = |col: u32, row: u32, cols: u32| {
+                    egui::Rect::from_min_size(
+                        stage_rect.min + egui::vec2(col as f32 * cell_w, row as f32 * cell_h),
+                        egui::vec2(cell_w * cols as f32, cell_h),
+                    )
+                };
+                // Fully switched cells merge into row runs; fading ones draw alone.
+                let mut full_runs = Vec::new();
+                let mut partial = Vec::new();
+                for row in 0..MASK_WIPE_ROWS {
+                    let mut run_start = None;
+                    for col in 0..=MASK_WIPE_COLS {
+                        let cov = if col < MASK_WIPE_COLS {
+                            let m = grid
+                                .get((row * MASK_WIPE_COLS + col) as usize)
+                                .copied()
+                                .unwrap_or(0.0);
+                            siglus::render::mask_wipe_coverage(m, t, soft, reverse)
+                        } else {
+                            0.0
+                        };
+                        if cov >= 1.0 {
+                            run_start.get_or_insert(col);
+                            continue;
+                        }
+                        if let Some(start) = run_start.take() {
+                            full_runs.push(cell(start, row, col - start));
+                        }
+                        if cov > 0.0 {
+                            partial.push((cell(col, row, 1), cov));
+                        }
+                    }
+                }
+                self.draw_stage_layer(ui, Some(&mut old), egui::Vec2::ZERO, &full, 1.0);
+                self.draw_stage_layer(ui, None, egui::Vec2::ZERO, &full_runs, 1.0);
+                for (rect, cov) in partial {
+                    self.draw_stage_layer(ui, None, egui::Vec2::ZERO, &[rect], cov);
+                }
+                self.wipe_mask_grid = Some(grid);
+            }
+            WipeKind::Crossfade | WipeKind::Mask { .. } => {
+                self.draw_stage_layer(ui, Some(&mut old), egui::Vec2::ZERO, &full, 1.0);
+                self.draw_stage_layer(ui, None, egui::Vec2::ZERO, &full, t);
+            }
+        }
+        if ranged {
+            self.wipe_pass = Some(WipePass::Above);
+            self.draw_stage_layer(ui, None, egui::Vec2::ZERO, &full, 1.0);
+            self.wipe_pass = None;
+        }
+        self.wipe_from = Some(old);
+    }
+}
@@ -1,5 +1,37 @@
 use anyhow::Result;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+/// Voice playback state shared by the VM thread, which starts and stops
+/// voices, and the UI thread, which plays them.
+///
+/// Each request gets a serial; a voice counts as playing from the request
+/// until the UI thread reports that serial finished (or failed to start), so
+/// `koe_wait` cannot slip past a voice the UI has not picked up yet.
+#[derive(Default)]
+pub struct KoePlayback {
+    requested: AtomicU32,
+    finished: AtomicU32,
+}
+
+impl KoePlayback {
+    /// Serial for a new voice.
+    pub fn request(&self) -> u32 {
+        self.requested.fetch_add(1, Ordering::SeqCst) + 1
+    }
+
+    pub fn finish(&self, serial: u32) {
+        self.finished.fetch_max(serial, Ordering::SeqCst);
+    }
+
+    pub fn stop(&self) {
+        self.finish(self.requested.load(Ordering::SeqCst));
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.requested.load(Ordering::SeqCst) != self.finished.load(Ordering::SeqCst)
+    }
+}
 
 #[cfg(feature = "rodio-audio")]
 mod backend {
@@ -10,12 +42,19 @@ mod backend {
     use std::fs::File;
     use std::io::BufReader;
 
+    /// Archive and entry of a voice: `KOE/zNNNN.ovk` holds the voices of
+    /// `koe_no / 100000`, keyed by `koe_no % 100000`.
+    fn koe_archive(koe_no: i32) -> (String, i32) {
+        (format!("z{:04}.ovk", koe_no / 100_000), koe_no % 100_000)
+    }
+
     pub struct AudioManager {
         _stream: OutputStream,
         stream_handle: OutputStreamHandle,
         bgm_sink: Option<Sink>,
         se_sinks: Vec<Sink>,
         pcm_sinks: HashMap<i32, Sink>,
+        koe_sink: Option<Sink>,
         base_dir: PathBuf,
     }
 
@@ -28,6 +67,7 @@ mod backend {
                 bgm_sink: None,
                 se_sinks: Vec::new(),
                 pcm_sinks: HashMap::new(),
+                koe_sink: None,
                 base_dir,
             })
         }
@@ -156,6 +196,58 @@ mod backend {
                 info!("Stopped PCM CH{}", ch);
             }
         }
+
+        /// Play voice `koe_no` from its OVK archive, replacing the current
+        /// one; false when it cannot be found or decoded.
+        pub fn play_koe(&mut self, koe_no: i32) -> bool {
+            self.stop_koe();
+            let (file, entry_no) = koe_archive(koe_no);
+            let Some(path) = ["KOE", "koe"]
+                .iter()
+                .map(|dir| self.base_dir.join(dir).join(&file))
+                .find(|p| p.exists())
+            else {
+                warn!("KOE archive not found: {}", file);
+                return false;
+            };
+            let Ok(ovk) = siglus::resource::read_ovk(&path) else {
+                error!("Failed to read KOE archive: {}", path.display());
+                return false;
+            };
+            let Some(entry) = ovk.entries.iter().find(|e| e.entry_no == entry_no) else {
+                warn!("KOE {} not in {}", koe_no, path.display());
+                return false;
+            };
+            let Ok(data) = std::fs::read(&path) else {
+                return false;
+            };
+            let start = entry.offset as usize;
+            let Some(bytes) = data.get(start..start.saturating_add(entry.size as usize)) else {
+                error!("KOE {} out of range in {}", koe_no, path.display());
+                return false;
+            };
+            let Ok(decoder) = Decoder::new(std::io::Cursor::new(bytes.to_vec())) else {
+                error!("Failed to decode KOE {}", koe_no);
+                return false;
+            };
+            let Ok(sink) = Sink::try_new(&self.stream_handle) else {
+                return false;
+            };
+            sink.append(decoder);
+            sink.play();
+            self.koe_sink = Some(sink);
+            true
+        }
+
+        pub fn stop_koe(&mut self) {
+            if let Some(sink) = self.koe_sink.take() {
+                sink.stop();
+            }
+        }
+
+        pub fn koe_playing(&self) -> bool {
+            self.koe_sink.as_ref().is_some_and(|s| !s.empty())
+        }
     }
 }
 
@@ -176,6 +268,13 @@ mod backend {
         pub fn stop_se(&mut self) {}
         pub fn play_pcmch(&mut self, _ch: i32, _name: &str, _loop_flag: bool) {}
         pub fn stop_pcmch(&mut self, _ch: i32) {}
+        pub fn play_koe(&mut self, _koe_no: i32) -> bool {
+            false
+        }
+        pub fn stop_koe(&mut self) {}
+        pub fn koe_playing(&self) -> bool {
+            false
+        }
     }
 }
 
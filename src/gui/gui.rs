@@ -19,6 +19,7 @@ mod stage;
 use gui_assets::*;
 use gui_config::{RunConfig, load_run_config};
 use stage::{
+    BUTTON_HIT_STATE_HIT, BUTTON_HIT_STATE_NONE, BUTTON_HIT_STATE_PUSH, ObjectButtonState,
     is_visual_or_flow_command, looks_like_stage_object_path, parse_stage_object_command,
     parse_stage_object_prop, parse_stage_plane_command, summarize_props,
 };
@@ -52,6 +53,7 @@ const SEL_BUTTON_WIDTH: f32 = 520.0;
 const SEL_BUTTON_HEIGHT: f32 = 48.0;
 const SEL_BUTTON_SPACING: f32 = 10.0;
 const SEL_BUTTON_ROUNDING: f32 = 8.0;
+const SEL_BUTTON_FONT_SIZE: f32 = 17.0;
 const SEL_BUTTON_BG: egui::Color32 = egui::Color32::from_rgba_premultiplied(40, 60, 110, 220);
 const SEL_BUTTON_HOVER_BG: egui::Color32 =
     egui::Color32::from_rgba_premultiplied(60, 100, 180, 240);
@@ -79,6 +81,7 @@ enum VmErrorSort {
 include!("movie_events.rs");
 
 include!("stage_plane.rs");
+include!("object_tree.rs");
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum MovieWaitState {
@@ -88,7 +91,6 @@ enum MovieWaitState {
     Interrupted,
 }
 
-
 #[derive(Debug, Clone)]
 struct HostObjectState {
     file_name: String,
@@ -97,14 +99,28 @@ struct HostObjectState {
     y: f32,
     center_x: f32,
     center_y: f32,
+    /// `center_rep_x/y`: moves the scale/rotation pivot without moving the
+    /// unscaled image.
+    center_rep_x: f32,
+    center_rep_y: f32,
     visible: bool,
     order: i32,
     layer: i32,
     scale_x: f32,
     scale_y: f32,
     rotate_z_deg: f32,
+    /// `z`, `rotate_x/y`, `world` and billboards project the object through a
+    /// camera (`siglus::render::Sprite3d`).
+    z: f32,
+    rotate_x_deg: f32,
+    rotate_y_deg: f32,
+    world: i32,
+    billboard: bool,
     alpha: f32,
-    alpha_blend: bool,
+    /// `blend` mode; the image is pre-shaped for it (`bake_blend_for_egui`).
+    blend: siglus::render::RenderBlend,
+    /// `tonecurve_no`, -1 for none.
+    tonecurve_no: i32,
     dst_clip_use: bool,
     dst_clip_left: f32,
     dst_clip_top: f32,
@@ -126,11 +142,25 @@ struct HostObjectState {
     dark: f32,
     mono: f32,
     reverse: bool,
+    /// `mask[n]` slot masking this object, -1 for none.
+    mask_no: i32,
+    /// Colored pixels before the mask, so moving the object or its mask only
+    /// re-applies the mask instead of decoding the image again.
+    mask_base: Option<Arc<image::DynamicImage>>,
     movie_auto_init: bool,
     movie_real_time: bool,
     movie_ready_only: bool,
     emote_rep_x: i32,
     emote_rep_y: i32,
+    /// `x_rep` / `y_rep` / `z_rep` / `tr_rep`: the object is drawn once per
+    /// index of the longest list.
+    x_rep: Vec<i32>,
+    y_rep: Vec<i32>,
+    z_rep: Vec<i32>,
+    tr_rep: Vec<i32>,
+    /// Loaded `.gan` and the set stepping `pat_no`, if started.
+    gan: Option<Arc<siglus::resource::GanData>>,
+    gan_play: Option<siglus::resource::GanPlayback>,
     seq: u64,
 }
 
@@ -138,10 +168,19 @@ struct HostObjectState {
 struct ObjectRenderState {
     center_x: f32,
     center_y: f32,
+    center_rep_x: f32,
+    center_rep_y: f32,
     scale_x: f32,
     scale_y: f32,
     rotate_z_deg: f32,
+    z: f32,
+    rotate_x_deg: f32,
+    rotate_y_deg: f32,
+    world: i32,
+    billboard: bool,
     alpha: f32,
+    /// Added to the object's position while a button look moves it.
+    button_offset: (f32, f32),
     dst_clip_use: bool,
     dst_clip_left: f32,
     dst_clip_top: f32,
@@ -152,6 +191,56 @@ struct ObjectRenderState {
     src_clip_top: f32,
     src_clip_right: f32,
     src_clip_bottom: f32,
+    blend: siglus::render::RenderBlend,
+}
+
+impl Default for ObjectRenderState {
+    fn default() -> Self {
+        Self {
+            center_x: 0.0,
+            center_y: 0.0,
+            center_rep_x: 0.0,
+            center_rep_y: 0.0,
+            scale_x: 1.0,
+            scale_y: 1.0,
+            rotate_z_deg: 0.0,
+            z: 0.0,
+            rotate_x_deg: 0.0,
+            rotate_y_deg: 0.0,
+            world: -1,
+            billboard: false,
+            alpha: 1.0,
+            button_offset: (0.0, 0.0),
+            dst_clip_use: false,
+            dst_clip_left: 0.0,
+            dst_clip_top: 0.0,
+            dst_clip_right: 0.0,
+            dst_clip_bottom: 0.0,
+            src_clip_use: false,
+            src_clip_left: 0.0,
+            src_clip_top: 0.0,
+            src_clip_right: 0.0,
+            src_clip_bottom: 0.0,
+            blend: siglus::render::RenderBlend::Normal,
+        }
+    }
+}
+
+/// Stage draw inputs captured before a wipe, drawn as the outgoing frame.
+#[derive(Clone, Default)]
+struct StageSnapshot {
+    background_texture: Option<egui::TextureHandle>,
+    background_textures: BTreeMap<StagePlane, egui::TextureHandle>,
+    missing_background_names: BTreeMap<StagePlane, String>,
+    object_textures: BTreeMap<(StagePlane, i32), egui::TextureHandle>,
+    missing_object_names: BTreeMap<(StagePlane, i32), String>,
+    object_pos: BTreeMap<(StagePlane, i32), egui::Pos2>,
+    object_visible: BTreeMap<(StagePlane, i32), bool>,
+    object_sort: BTreeMap<(StagePlane, i32), (i32, i32, u64)>,
+    object_render: BTreeMap<(StagePlane, i32), ObjectRenderState>,
+    object_reps: BTreeMap<(StagePlane, i32), Vec<siglus::render::RenderRep>>,
+    object_parent: BTreeMap<(StagePlane, i32), i32>,
+    object_child_sort_test: BTreeSet<(StagePlane, i32)>,
 }
 
 #[derive(Debug, Clone)]
@@ -197,8 +286,6 @@ impl Default for HostGroupState {
     }
 }
 
-include!("int_event_state.rs");
-
 // ── VM Host implementation ──────────────────────────────────────────────
 
 #[derive(Debug, Clone, Default)]
@@ -208,12 +295,16 @@ struct HostCaptureBuffer {
     origin_x: i32,
     origin_y: i32,
     png_path: String,
+    /// Captured pixels (`capture_to_capture_buffer`), `width` x `height`.
+    image: Option<Arc<image::RgbaImage>>,
 }
 
 struct GuiHost {
     event_tx: mpsc::Sender<HostEvent>,
     selection_rx: mpsc::Receiver<i32>,
     return_to_menu_warning_rx: mpsc::Receiver<bool>,
+    /// The stage the GUI last drew (`Host::on_stage_scene`).
+    stage_scene: Arc<Mutex<Option<siglus::render::RenderScene>>>,
     advance_rx: mpsc::Receiver<AdvanceSignal>,
     movie_event_rx: mpsc::Receiver<MoviePlaybackEvent>,
     skip_mode: Arc<AtomicBool>,
@@ -225,6 +316,8 @@ struct GuiHost {
     stage_object_sizes: BTreeMap<StagePlane, i32>,
     stage_group_sizes: BTreeMap<StagePlane, i32>,
     groups: BTreeMap<(StagePlane, i32), HostGroupState>,
+    /// VM clock time group button looks were last updated at.
+    group_looks_ms: u64,
     movie_playing_objects: std::collections::BTreeSet<(StagePlane, i32)>,
     movie_ready_objects: std::collections::BTreeSet<(StagePlane, i32)>,
     movie_auto_free_ms: BTreeMap<(StagePlane, i32), i32>,
@@ -234,14 +327,40 @@ struct GuiHost {
     next_movie_generation: u64,
     global_mov_playing: bool,
     mwnd_list_size: i32,
-    world_list_size: i32,
     effect_list_size: i32,
     quake_list_size: i32,
     int_event_list_sizes: BTreeMap<i32, i32>,
-    quake_active_until: Option<Instant>,
-    last_quake_request: Option<siglus::vm::VmQuakeRequest>,
     next_object_seq: u64,
-    int_events: std::collections::HashMap<i32, IntEventState>,
+    mask_slots: BTreeMap<usize, siglus::vm::MaskSlotState>,
+    /// Offset each mask slot was last baked at, to catch `x_eve`/`y_eve` moves.
+    mask_baked_pos: BTreeMap<usize, (i32, i32)>,
+    /// Running object `*_eve` events by (stage, object, event id), with the
+    /// value last applied to the object.
+    object_int_events: BTreeMap<(StagePlane, i32, i32), (siglus::vm::IntEvent, i32)>,
+    /// Decoded mask images by file name (`None` = failed to load, don't retry).
+    mask_images: std::collections::HashMap<String, Option<Arc<image::GrayImage>>>,
+    /// Decoded object images by file name, so pattern changes (gan, button
+    /// looks, number digits, hit tests) don't decode the file again.
+    stage_cuts: std::cell::RefCell<std::collections::HashMap<String, Arc<StageCuts>>>,
+    /// Tone curves indexed by an object's `tonecurve_no` (empty: no file loader yet).
+    tone_curves: Arc<siglus::resource::ToneCurveTable>,
+    object_tree: ObjectTree,
+    screen_effects: Vec<siglus::vm::VmScreenEffect>,
+    /// Game/configured TTF faces for string objects (empty = bitmap glyphs).
+    fonts: Arc<siglus::render::FontSet>,
+    font_style: siglus::vm::VmFontStyle,
+    mwnd_config: Arc<siglus::gameexe::GameexeMwndConfig>,
+    /// `#BUTTON` looks and sounds of button objects.
+    button_config: Arc<siglus::gameexe::GameexeButtonConfig>,
+    mwnd_states: BTreeMap<i32, MwndState>,
+    /// Image file last sent per window slot ("" = none).
+    mwnd_image_files: BTreeMap<(i32, MwndImageSlot), String>,
+    message_speed: siglus::vm::VmMessageSpeed,
+    /// Whether the page the next text belongs to was read before.
+    message_read: bool,
+    /// Effect values currently baked into object images, and when they were resolved.
+    applied_effects: Vec<siglus::render::RenderEffect>,
+    effects_resolved_at: u64,
     input_state: Arc<Mutex<SharedInputState>>,
     cancel_se_map: BTreeMap<i32, String>,
     vm_scene: String,
@@ -250,6 +369,13 @@ struct GuiHost {
     vm_element: Vec<i32>,
     capture_buffer: Option<HostCaptureBuffer>,
     pending_selbtn_request: Option<SelectionRequest>,
+    /// The selection the next SELBTN command waits on is already on screen
+    /// (shown for its `sel_start_call`).
+    selection_shown: bool,
+    vm_clock: siglus::vm::SharedVmClock,
+    /// Frame-action counter deltas on the VM clock.
+    frame_delta: siglus::vm::ClockFrameDelta,
+    koe: Arc<audio::KoePlayback>,
 }
 
 include!("prop_int_ext.rs");
@@ -257,11 +383,14 @@ include!("host_impl_quake_macro.rs");
 include!("cancel_se_map.rs");
 include!("app_render_overlay_macro.rs");
 
+include!("host_mwnd.rs");
 include!("host_impl.rs");
 struct GuiApp {
     event_rx: mpsc::Receiver<HostEvent>,
     selection_tx: mpsc::Sender<i32>,
     return_to_menu_warning_tx: mpsc::Sender<bool>,
+    /// The stage as last drawn, read by the VM thread for captures.
+    stage_scene: Arc<Mutex<Option<siglus::render::RenderScene>>>,
     advance_tx: mpsc::Sender<AdvanceSignal>,
     movie_event_tx: mpsc::Sender<MoviePlaybackEvent>,
     skip_mode: Arc<AtomicBool>,
@@ -275,7 +404,8 @@ struct GuiApp {
     message_window_visible: bool,
     pending_options: Vec<SelectionOption>,
     pending_selbtn: Option<SelBtnNamedArgs>,
-    backlog: Vec<String>,
+    selbtn_view: Option<SelBtnView>,
+    backlog: Vec<BacklogRow>,
     done: bool,
     show_backlog: bool,
     msg_back_display_enabled: bool,
@@ -301,13 +431,26 @@ struct GuiApp {
     vm_error_sort: VmErrorSort,
     background_texture: Option<egui::TextureHandle>,
     background_textures: BTreeMap<StagePlane, egui::TextureHandle>,
+    /// Pixels behind `background_textures`, for composed captures.
+    background_images: BTreeMap<StagePlane, Arc<image::RgbaImage>>,
     missing_background_names: BTreeMap<StagePlane, String>,
     object_textures: BTreeMap<(StagePlane, i32), egui::TextureHandle>,
+    /// Pixels behind `object_textures`, for composed captures.
+    object_images: BTreeMap<(StagePlane, i32), Arc<image::RgbaImage>>,
     missing_object_names: BTreeMap<(StagePlane, i32), String>,
     object_pos: BTreeMap<(StagePlane, i32), egui::Pos2>,
     object_visible: BTreeMap<(StagePlane, i32), bool>,
     object_sort: BTreeMap<(StagePlane, i32), (i32, i32, u64)>,
     object_render: BTreeMap<(StagePlane, i32), ObjectRenderState>,
+    /// Rep-list repeats of objects that have any (see `RenderRep::from_lists`).
+    object_reps: BTreeMap<(StagePlane, i32), Vec<siglus::render::RenderRep>>,
+    /// Child object slots and their parent object (child indices are host-allocated).
+    object_parent: BTreeMap<(StagePlane, i32), i32>,
+    /// Parents drawing their children in child-number order.
+    object_child_sort_test: BTreeSet<(StagePlane, i32)>,
+    object_weather: BTreeMap<(StagePlane, i32), ObjectWeatherView>,
+    object_gans: BTreeMap<(StagePlane, i32), ObjectGanView>,
+    sub_blend: SubBlendPainter,
     base_title: String,
     location_scene_title: String,
     location_scene: String,
@@ -315,12 +458,22 @@ struct GuiApp {
     location_pc: usize,
     last_window_title: String,
     scene_size: Option<(i32, i32)>,
-    wipe_started_at: Option<Instant>,
+    wipe_started_ms: Option<u64>,
     wipe_duration_ms: u64,
     wipe_type: i32,
+    wipe_speed_mode: i32,
+    wipe_option: Vec<i32>,
     wipe_direction: WipeDirection,
+    wipe_from: Option<StageSnapshot>,
+    wipe_range: Option<siglus::vm::VmWipeRequest>,
+    /// Objects `draw_objects` keeps while a ranged wipe draws in passes.
+    wipe_pass: Option<WipePass>,
+    /// Per-cell mask values of the running `mask_wipe` (`MASK_WIPE_COLS`×`MASK_WIPE_ROWS`).
+    wipe_mask_grid: Option<Vec<f32>>,
 
     start_time: Instant,
+    /// The VM's clock, for effects the VM times (quakes, shakes).
+    vm_clock: siglus::vm::SharedVmClock,
     audio_manager: Option<AudioManager>,
     input_state: Arc<Mutex<SharedInputState>>,
     base_dir: PathBuf,
@@ -329,19 +482,74 @@ struct GuiApp {
     movie_stop_flags: Arc<Mutex<BTreeMap<(StagePlane, i32, u64), Arc<AtomicBool>>>>,
     quake_ref_csv: Option<PathBuf>,
     quake_ref_report: PathBuf,
-    quake_started_at: Option<Instant>,
-    quake_request: Option<siglus::vm::VmQuakeRequest>,
+    /// Running quakes by `screen.quake` index, with their start on the VM clock.
+    quakes: BTreeMap<usize, (siglus::vm::VmQuakeRequest, u64)>,
+    shake: Option<(Vec<siglus::render::ShakeStep>, u64)>,
+    /// `stage[n].world` lists, for objects placed in a world; cameras
+    /// resolve on the VM clock.
+    worlds: BTreeMap<StagePlane, Vec<siglus::vm::VmWorld>>,
+    hot_reload: siglus::hot_reload::HotReloadHandle,
+    fonts: Arc<siglus::render::FontSet>,
+    /// egui family for message text, following the VM's script font.
+    message_font: egui::FontFamily,
+    /// Script font style behind `message_font` (bold and decoration).
+    message_font_style: siglus::vm::VmFontStyle,
+    mwnd_config: Arc<siglus::gameexe::GameexeMwndConfig>,
+    /// Window that receives text and names (`set_mwnd`; -1 = Gameexe default).
+    cur_mwnd: i32,
+    mwnd_views: BTreeMap<i32, MwndView>,
+    mwnd_textures: BTreeMap<(i32, MwndImageSlot), egui::TextureHandle>,
+    message_speed: siglus::vm::VmMessageSpeed,
+    text_reveal: Option<TextReveal>,
+    koe: Arc<audio::KoePlayback>,
+    /// Serial of the voice the audio manager is playing (0 = none).
+    koe_serial: u32,
+}
+
+/// What one message window shows: host state plus its latest name and text.
+#[derive(Debug, Clone, Default)]
+struct MwndView {
+    state: MwndState,
+    name: String,
+    text: String,
+    /// Structured page from the VM; preferred over `name`/`text` once sent.
+    message: Option<siglus::vm::VmMessage>,
+}
+
+/// One text history entry; `message` keeps ruby and colors when known.
+#[derive(Debug, Clone)]
+struct BacklogRow {
+    text: String,
+    message: Option<siglus::vm::VmMessage>,
 }
 include!("app_logic.rs");
 include!("app_quake_reference.rs");
 include!("app_runtime_effects.rs");
 include!("app_render.rs");
+include!("app_object_tree.rs");
+include!("app_render_mwnd.rs");
+include!("app_render_message.rs");
+include!("app_render_sub_blend.rs");
+include!("app_text_reveal.rs");
+include!("app_weather.rs");
+include!("app_gan.rs");
+include!("app_wipe.rs");
 include!("app_tweet_dialog.rs");
+include!("app_capture.rs");
+include!("app_selbtn.rs");
 
 impl eframe::App for GuiApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         self.consume_events(ctx);
+        self.tick_koe();
         self.handle_input(ctx);
+        self.tick_text_reveal(ctx);
+        self.tick_weather(ctx);
+        self.tick_gans(ctx);
+        let now = self.vm_clock.now_ms();
+        if self.worlds.values().flatten().any(|w| w.is_animating(now)) {
+            ctx.request_repaint();
+        }
         let title = self.compose_window_title();
         if title != self.last_window_title {
             ctx.send_viewport_cmd(egui::ViewportCommand::Title(title.clone()));
@@ -355,8 +563,7 @@ impl eframe::App for GuiApp {
                 ..Default::default()
             })
             .show(ctx, |ui| {
-                self.draw_background(ui);
-                self.draw_objects(ui);
+                self.draw_stage(ui);
 
                 if self.message_window_visible && self.show_backlog {
                     self.draw_backlog(ui);
@@ -377,11 +584,15 @@ impl eframe::App for GuiApp {
                 self.draw_vm_error_overlay(ui);
                 self.draw_wipe_overlay(ui);
             });
+        self.publish_stage_scene();
 
         ctx.request_repaint();
     }
 
-    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+    fn on_exit(&mut self, gl: Option<&eframe::glow::Context>) {
+        if let Some(gl) = gl {
+            self.sub_blend.destroy(gl);
+        }
         // Signal the VM thread to shut down
         self.shutdown.store(true, Ordering::Relaxed);
         let _ = self.advance_tx.send(AdvanceSignal::Shutdown);
@@ -392,7 +603,7 @@ impl eframe::App for GuiApp {
 
 // ── Configuration loading ───────────────────────────────────────────────
 
-fn setup_multilingual_fonts(ctx: &egui::Context) {
+fn setup_multilingual_fonts(ctx: &egui::Context, game_fonts: &siglus::render::FontSet) {
     let mut fonts = egui::FontDefinitions::default();
     for (idx, path) in [
         "/usr/share/fonts/opentype/noto/NotoSansCJK-Regular.ttc",
@@ -422,9 +633,46 @@ fn setup_multilingual_fonts(ctx: &egui::Context) {
                 .push(font_name);
         }
     }
+    // Game faces: the first one leads the default family so message text is
+    // readable without system CJK fonts; each also gets its own named family
+    // (backed by the default chain) for `script.set_font_name`.
+    let fallback = fonts
+        .families
+        .get(&egui::FontFamily::Proportional)
+        .cloned()
+        .unwrap_or_default();
+    for (idx, face) in game_fonts.faces().iter().enumerate() {
+        let font_name = format!("game-{idx}");
+        let mut data = egui::FontData::from_owned(face.data.to_vec());
+        data.index = face.index;
+        fonts.font_data.insert(font_name.clone(), data.into());
+        let mut chain = vec![font_name.clone()];
+        chain.extend(fallback.iter().cloned());
+        fonts
+            .families
+            .insert(egui::FontFamily::Name(font_name.clone().into()), chain);
+        if idx == 0 {
+            fonts
+                .families
+                .entry(egui::FontFamily::Proportional)
+                .or_default()
+                .insert(0, font_name);
+        }
+    }
     ctx.set_fonts(fonts);
 }
 
+/// egui family registered by `setup_multilingual_fonts` for the VM font.
+fn game_font_family(
+    game_fonts: &siglus::render::FontSet,
+    style: &siglus::vm::VmFontStyle,
+) -> egui::FontFamily {
+    match game_fonts.find(&style.name, style.bold) {
+        Some(idx) => egui::FontFamily::Name(format!("game-{idx}").into()),
+        None => egui::FontFamily::Proportional,
+    }
+}
+
 fn load_persistent_state(path: &Path) -> Result<Option<siglus::vm::VmPersistentState>> {
     if !path.exists() {
         return Ok(None);
@@ -475,16 +723,33 @@ fn run_gui(args: RunConfig) -> Result<()> {
     let (event_tx, event_rx) = mpsc::channel::<HostEvent>();
     let (selection_tx, selection_rx) = mpsc::channel::<i32>();
     let (return_to_menu_warning_tx, return_to_menu_warning_rx) = mpsc::channel::<bool>();
+    let stage_scene = Arc::new(Mutex::new(None));
     let (advance_tx, advance_rx) = mpsc::channel::<AdvanceSignal>();
     let (movie_event_tx, movie_event_rx) = mpsc::channel::<MoviePlaybackEvent>();
     let skip_mode = Arc::new(AtomicBool::new(false));
     let shutdown = Arc::new(AtomicBool::new(false));
     let input_state = Arc::new(Mutex::new(SharedInputState::default()));
+    let hot_reload_options = siglus::hot_reload::HotReloadOptions::default();
+    let hot_reload = siglus::hot_reload::HotReloadHandle::new(hot_reload_options);
+    hot_reload.set_enabled(
+        std::env::var("SIGLUS_HOT_RELOAD")
+            .map(|v| matches!(v.trim(), "1" | "true" | "on"))
+            .unwrap_or(false),
+    );
 
+    let vm_clock = siglus::vm::real_clock();
+    let worker_clock = vm_clock.clone();
     let worker_event_tx = event_tx.clone();
     let worker_skip = skip_mode.clone();
     let worker_shutdown = shutdown.clone();
+    let worker_stage_scene = stage_scene.clone();
     let worker_input_state = input_state.clone();
+    let worker_hot_reload = hot_reload.clone();
+    let fonts = Arc::new(siglus::render::FontSet::load_paths(&args.font_paths));
+    info!("Loaded {} font face(s)", fonts.faces().len());
+    let worker_fonts = fonts.clone();
+    let mwnd_config = Arc::new(args.mwnd_config.clone());
+    let worker_mwnd_config = mwnd_config.clone();
 
     let base_dir = args
         .pck
@@ -492,6 +757,8 @@ fn run_gui(args: RunConfig) -> Result<()> {
         .unwrap_or(&PathBuf::from("."))
         .to_path_buf();
     let audio_manager = audio::AudioManager::new(base_dir.clone()).ok();
+    let koe = Arc::new(audio::KoePlayback::default());
+    let worker_koe = koe.clone();
     let app_append_dirs = args.append_search_dirs.clone();
     let cancel_se_map = load_cancel_se_map_from_gameexe(&args.gameexe);
 
@@ -500,11 +767,17 @@ fn run_gui(args: RunConfig) -> Result<()> {
             let pack = siglus::pck::read_file(&args.pck)
                 .with_context(|| format!("failed to read pack: {}", args.pck.display()))?;
             let mut rt = siglus::runtime::Runtime::new(pack)?;
+            for dir in &args.scene_override_dirs {
+                info!("Scene override dir: {}", dir.display());
+                rt.add_override_dir(dir.clone());
+            }
+            rt.enable_hot_reload_with_handle(&args.pck, hot_reload_options, worker_hot_reload);
 
             let mut host = GuiHost {
                 event_tx: worker_event_tx,
                 selection_rx,
                 return_to_menu_warning_rx,
+                stage_scene: worker_stage_scene,
                 advance_rx,
                 movie_event_rx,
                 skip_mode: worker_skip,
@@ -520,6 +793,7 @@ fn run_gui(args: RunConfig) -> Result<()> {
                 stage_object_sizes: BTreeMap::new(),
                 stage_group_sizes: BTreeMap::new(),
                 groups: BTreeMap::new(),
+                group_looks_ms: 0,
                 movie_playing_objects: std::collections::BTreeSet::new(),
                 movie_ready_objects: std::collections::BTreeSet::new(),
                 movie_auto_free_ms: BTreeMap::new(),
@@ -528,15 +802,29 @@ fn run_gui(args: RunConfig) -> Result<()> {
                 movie_interrupted_objects: std::collections::BTreeSet::new(),
                 next_movie_generation: 1,
                 global_mov_playing: false,
-                mwnd_list_size: 1,
-                world_list_size: 1,
+                mwnd_list_size: args.mwnd_config.mwnds.len().max(1) as i32,
                 effect_list_size: 0,
                 quake_list_size: 0,
                 int_event_list_sizes: BTreeMap::new(),
-                quake_active_until: None,
-                last_quake_request: None,
                 next_object_seq: 1,
-                int_events: std::collections::HashMap::new(),
+                mask_slots: BTreeMap::new(),
+                mask_baked_pos: BTreeMap::new(),
+                object_int_events: BTreeMap::new(),
+                mask_images: std::collections::HashMap::new(),
+                stage_cuts: std::cell::RefCell::new(std::collections::HashMap::new()),
+                tone_curves: Arc::new(args.tone_curves.clone()),
+                object_tree: ObjectTree::default(),
+                screen_effects: Vec::new(),
+                fonts: worker_fonts,
+                font_style: siglus::vm::VmFontStyle::default(),
+                mwnd_config: worker_mwnd_config,
+                button_config: Arc::new(args.button_config.clone()),
+                mwnd_states: BTreeMap::new(),
+                mwnd_image_files: BTreeMap::new(),
+                message_speed: siglus::vm::VmMessageSpeed::default(),
+                message_read: false,
+                applied_effects: Vec::new(),
+                effects_resolved_at: 0,
                 input_state: worker_input_state,
                 cancel_se_map: cancel_se_map.clone(),
                 vm_scene: String::new(),
@@ -545,7 +833,14 @@ fn run_gui(args: RunConfig) -> Result<()> {
                 vm_element: Vec::new(),
                 capture_buffer: None,
                 pending_selbtn_request: None,
+                selection_shown: false,
+                vm_clock: worker_clock,
+                frame_delta: siglus::vm::ClockFrameDelta::default(),
+                koe: worker_koe,
             };
+            for mwnd_no in 0..host.mwnd_config.mwnds.len() {
+                host.sync_mwnd_images(mwnd_no as i32);
+            }
 
             let state_in = match load_persistent_state(&args.persistent_state_path) {
                 Ok(v) => v,
@@ -603,6 +898,26 @@ fn run_gui(args: RunConfig) -> Result<()> {
                     preloaded_counter_count: args.preload_counter_count,
                     preloaded_frame_action_ch_count: args.preload_frame_action_ch_count,
                     flick_scene_routes: args.flick_scene_routes.clone(),
+                    shake_patterns: args.shake_patterns.clone(),
+                    default_message_speed: args.default_message_speed,
+                    default_mwnd_no: args.mwnd_config.default_mwnd_no,
+                    // The player gets a fresh sequence per launch unless a seed is pinned.
+                    rng_seed: Some(
+                        std::env::var("SIGLUS_RNG_SEED")
+                            .ok()
+                            .and_then(|v| v.trim().parse::<u64>().ok())
+                            .unwrap_or_else(siglus::vm::VmRng::time_seed),
+                    ),
+                    watchpoints: std::env::var("SIGLUS_WATCH")
+                        .ok()
+                        .and_then(|spec| match siglus::vm::VmWatchpoint::parse_list(&spec) {
+                            Ok(points) => Some(points),
+                            Err(e) => {
+                                error!("Ignoring SIGLUS_WATCH: {:#}", e);
+                                None
+                            }
+                        })
+                        .unwrap_or_default(),
                     ..siglus::vm::VmOptions::default()
                 },
                 state_in.as_ref(),
@@ -630,6 +945,7 @@ fn run_gui(args: RunConfig) -> Result<()> {
         event_rx,
         selection_tx.clone(),
         return_to_menu_warning_tx.clone(),
+        stage_scene,
         advance_tx.clone(),
         movie_event_tx.clone(),
         skip_mode.clone(),
@@ -643,6 +959,11 @@ fn run_gui(args: RunConfig) -> Result<()> {
         args.movie_backends.clone(),
         args.quake_ref_csv.clone(),
         args.quake_ref_report.clone(),
+        hot_reload,
+        fonts.clone(),
+        mwnd_config,
+        vm_clock,
+        koe,
     );
 
     let mut native_options = eframe::NativeOptions::default();
@@ -655,7 +976,7 @@ fn run_gui(args: RunConfig) -> Result<()> {
         &format!("{} - Siglus", args.title),
         native_options,
         Box::new(|cc| {
-            setup_multilingual_fonts(&cc.egui_ctx);
+            setup_multilingual_fonts(&cc.egui_ctx, &fonts);
             Ok(Box::new(app))
         }),
     )
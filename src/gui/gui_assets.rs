@@ -198,6 +198,53 @@ pub(super) fn load_stage_like_cpp(
     )
 }
 
+/// Every cut of one stage image file, decoded together.
+pub(super) struct StageCuts {
+    cuts: Vec<image::DynamicImage>,
+    /// A g00, whose patterns past its own cuts continue in numbered files.
+    g00: bool,
+}
+
+impl StageCuts {
+    /// Cut `pat_no`; `None` when it lives in a later sequence file.
+    pub(super) fn cut(&self, pat_no: usize) -> Option<&image::DynamicImage> {
+        match self.cuts.get(pat_no) {
+            Some(cut) => Some(cut),
+            None if self.g00 => None,
+            None => self.cuts.first(),
+        }
+    }
+}
+
+/// `load_stage_like_cpp` for all cuts of the resolved file at once.
+pub(super) fn load_stage_cuts_like_cpp(
+    base_dir: &Path,
+    append_dirs: &[PathBuf],
+    file_name: &str,
+) -> Result<StageCuts> {
+    let (resolved, attempts) = resolve_image_path_like_cpp(base_dir, append_dirs, file_name);
+    let Some(path) = resolved else {
+        let attempts_desc = format_attempts_for_log(&attempts);
+        anyhow::bail!(
+            "image not found: requested={file_name}, base_dir={}, attempts={attempts_desc}",
+            base_dir.display()
+        )
+    };
+    let g00 = path
+        .extension()
+        .and_then(OsStr::to_str)
+        .is_some_and(|e| e.eq_ignore_ascii_case("g00"));
+    let cuts = if g00 {
+        siglus::resource::load_g00_images(&path)?.cuts
+    } else {
+        vec![image::open(&path).with_context(|| format!("decode image {}", path.display()))?]
+    };
+    if cuts.is_empty() {
+        anyhow::bail!("g00 has no cuts: {}", path.display());
+    }
+    Ok(StageCuts { cuts, g00 })
+}
+
 fn resolve_image_path_like_cpp(
     base_dir: &Path,
     append_dirs: &[PathBuf],
@@ -347,6 +394,27 @@ fn load_image_by_path(path: &Path, pat_no: usize) -> Result<image::DynamicImage>
     Ok(img)
 }
 
+/// `.gan` files live in `gan/` next to `g00/`; the extension is optional.
+pub(super) fn resolve_gan_path_like_cpp(
+    base_dir: &Path,
+    append_dirs: &[PathBuf],
+    file_name: &str,
+) -> Option<PathBuf> {
+    let name = if Path::new(file_name).extension().is_some() {
+        file_name.to_string()
+    } else {
+        format!("{file_name}.gan")
+    };
+    [format!("gan/{name}"), name].iter().find_map(|rel| {
+        resolve_resource_path_like_cpp(
+            base_dir,
+            append_dirs,
+            rel,
+            siglus::vm::VmResourceKind::Generic,
+        )
+    })
+}
+
 fn resolve_resource_path_like_cpp(
     base_dir: &Path,
     append_dirs: &[PathBuf],
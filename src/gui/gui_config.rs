@@ -47,9 +47,16 @@ pub(super) struct RunConfig {
     pub(super) preload_counter_count: usize,
     pub(super) preload_frame_action_ch_count: usize,
     pub(super) flick_scene_routes: Vec<siglus::vm::FlickSceneRoute>,
+    pub(super) shake_patterns: Vec<Vec<siglus::render::ShakeStep>>,
     pub(super) movie_backends: Vec<String>,
     pub(super) quake_ref_csv: Option<PathBuf>,
     pub(super) quake_ref_report: PathBuf,
+    pub(super) scene_override_dirs: Vec<PathBuf>,
+    pub(super) font_paths: Vec<PathBuf>,
+    pub(super) mwnd_config: siglus::gameexe::GameexeMwndConfig,
+    pub(super) button_config: siglus::gameexe::GameexeButtonConfig,
+    pub(super) default_message_speed: i32,
+    pub(super) tone_curves: siglus::resource::ToneCurveTable,
 }
 
 fn parse_movie_backends(cfg: &siglus::gameexe::GameexeConfig) -> Vec<String> {
@@ -107,6 +114,46 @@ fn parse_quake_reference_paths(base_dir: &Path) -> (Option<PathBuf>, PathBuf) {
     (ref_csv, report)
 }
 
+/// Loose `.dat` override directories, highest priority first.
+///
+/// `SIGLUS_SCENE_OVERRIDE_DIRS` uses the platform path-list separator; without it,
+/// a `scene_override` directory next to Gameexe.dat is used when present.
+fn parse_scene_override_dirs(base_dir: &Path) -> Vec<PathBuf> {
+    if let Some(list) = std::env::var_os("SIGLUS_SCENE_OVERRIDE_DIRS") {
+        return std::env::split_paths(&list)
+            .filter(|p| !p.as_os_str().is_empty())
+            .collect();
+    }
+    let default_dir = base_dir.join("scene_override");
+    if default_dir.is_dir() {
+        vec![default_dir]
+    } else {
+        Vec::new()
+    }
+}
+
+/// Font files or directories for string objects and message text, in priority order.
+///
+/// `SIGLUS_FONT_PATH` (platform path-list separator) comes first, then `font`/`fonts`
+/// directories next to Gameexe.dat, then Gameexe.dat's own directory.
+fn parse_font_paths(base_dir: &Path) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = std::env::var_os("SIGLUS_FONT_PATH")
+        .map(|list| {
+            std::env::split_paths(&list)
+                .filter(|p| !p.as_os_str().is_empty())
+                .collect()
+        })
+        .unwrap_or_default();
+    for sub in ["font", "fonts"] {
+        let dir = base_dir.join(sub);
+        if dir.is_dir() {
+            paths.push(dir);
+        }
+    }
+    paths.push(base_dir.to_path_buf());
+    paths
+}
+
 fn parse_config_count(cfg: &siglus::gameexe::GameexeConfig, cnt_keys: &[&str]) -> usize {
     cnt_keys
         .iter()
@@ -219,6 +266,48 @@ fn parse_flick_scene_routes(
     routes
 }
 
+/// `#TONECURVE_FILE` is not loaded: the tone curve file format is unknown
+/// (see `siglus::resource::ToneCurveTable`), so the table stays empty and
+/// objects draw without a curve.
+fn parse_tone_curves(cfg: &siglus::gameexe::GameexeConfig) -> siglus::resource::ToneCurveTable {
+    if let Some(name) = cfg
+        .first_values("TONECURVE_FILE")
+        .and_then(|v| v.first())
+        .map(|s| s.trim_matches('"'))
+        .filter(|s| !s.is_empty())
+    {
+        warn!("tonecurve file {name} ignored: its format is not supported");
+    }
+    siglus::resource::ToneCurveTable::default()
+}
+
+/// `#SHAKE.NNN` numbers are three digits, so no pattern lies past 999.
+const MAX_SHAKE_PATTERNS: usize = 1000;
+
+fn parse_shake_patterns(
+    cfg: &siglus::gameexe::GameexeConfig,
+) -> Vec<Vec<siglus::render::ShakeStep>> {
+    let mut patterns: Vec<Vec<siglus::render::ShakeStep>> = Vec::new();
+    for entry in &cfg.entries {
+        let Some(no) = entry
+            .key
+            .strip_prefix("SHAKE.")
+            .and_then(|v| v.parse::<usize>().ok())
+        else {
+            continue;
+        };
+        if no >= MAX_SHAKE_PATTERNS {
+            warn!("Gameexe {} is out of range; ignored", entry.key);
+            continue;
+        }
+        if patterns.len() <= no {
+            patterns.resize(no + 1, Vec::new());
+        }
+        patterns[no] = siglus::render::parse_shake_pattern(&entry.raw_value);
+    }
+    patterns
+}
+
 fn parse_load_wipe(cfg: &siglus::gameexe::GameexeConfig) -> (i32, u64) {
     // C++ reference: tnm_ini.cpp::LOAD_WIPE(num[0]=type, num[1]=time), defaults 0/1000.
     let wipe_type = cfg
@@ -445,8 +534,19 @@ pub(super) fn load_run_config() -> Result<RunConfig> {
         preload_cg_code_exist_cnt,
     ) = parse_vm_resource_bootstrap(&cfg, &base_dir);
     let flick_scene_routes = parse_flick_scene_routes(&cfg);
+    let shake_patterns = parse_shake_patterns(&cfg);
     let movie_backends = parse_movie_backends(&cfg);
     let (quake_ref_csv, quake_ref_report) = parse_quake_reference_paths(&base_dir);
+    let scene_override_dirs = parse_scene_override_dirs(&base_dir);
+    let font_paths = parse_font_paths(&base_dir);
+    let tone_curves = parse_tone_curves(&cfg);
+    let default_message_speed = if cfg.user_config.message_speed_nowait == Some(true) {
+        0
+    } else {
+        cfg.user_config
+            .message_speed
+            .unwrap_or(siglus::vm::DEFAULT_MESSAGE_SPEED_MS)
+    };
 
     Ok(RunConfig {
         gameexe,
@@ -495,8 +595,15 @@ pub(super) fn load_run_config() -> Result<RunConfig> {
         preload_counter_count,
         preload_frame_action_ch_count,
         flick_scene_routes,
+        shake_patterns,
         movie_backends,
         quake_ref_csv,
         quake_ref_report,
+        scene_override_dirs,
+        font_paths,
+        mwnd_config: cfg.mwnd,
+        button_config: cfg.button,
+        default_message_speed,
+        tone_curves,
     })
 }
@@ -44,16 +44,76 @@ struct SelectionOption {
     color: i32,
 }
 
+/// One `#BUTTON.ACTION` state of a `#SELBTN` item background.
+#[derive(Debug, Clone)]
+struct SelBtnLook {
+    image: Arc<image::DynamicImage>,
+    /// Stage-pixel offset of the state.
+    offset: (f32, f32),
+    alpha: f32,
+}
+
+/// A `#SELBTN` template resolved for one button selection.
+#[derive(Debug, Clone)]
+struct SelBtnTemplate {
+    layout: siglus::gameexe::GameexeSelBtn,
+    /// Item backgrounds in `GameexeButtonState` order; `None` draws text only.
+    looks: [Option<SelBtnLook>; 5],
+    hit_se: Option<String>,
+    decide_se: Option<String>,
+}
+
 #[derive(Debug, Clone, Default)]
 struct SelectionRequest {
     options: Vec<SelectionOption>,
     selbtn: Option<SelBtnNamedArgs>,
+    template_no: i32,
+    template: Option<SelBtnTemplate>,
+}
+
+/// Script-side overrides of one `mwnd_list` window; `None` keeps the Gameexe value.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct MwndState {
+    open: bool,
+    waku_no: Option<i32>,
+    waku_file: Option<String>,
+    filter_file: Option<String>,
+    window_pos: Option<(i32, i32)>,
+    window_size: Option<(i32, i32)>,
+    moji_cnt: Option<(i32, i32)>,
+    /// `set_face` file and face number.
+    face: Option<(String, i32)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum MwndImageSlot {
+    Waku,
+    Filter,
+    NameWaku,
+    Face,
+}
+
+struct ObjectWeatherEvent {
+    params: siglus::render::WeatherParams,
+    seed: u64,
+    /// RGBA cut per pattern number the particles may show.
+    patterns: Vec<(i32, Arc<image::RgbaImage>)>,
+}
+
+/// A playing gan set with its patterns already processed; the GUI picks
+/// the pattern from the VM clock each frame.
+struct ObjectGanEvent {
+    set: siglus::resource::GanSet,
+    playback: siglus::resource::GanPlayback,
+    patterns: Vec<(i32, Arc<image::RgbaImage>)>,
 }
 
 enum HostEvent {
     Name(String),
     Text {
         text: String,
+        /// Whether the VM waits for an advance; false when skipping.
+        wait: bool,
     },
     Selection(SelectionRequest),
     SelBtnSyncCheckpoint {
@@ -84,6 +144,19 @@ enum HostEvent {
         index: i32,
         image: Arc<image::DynamicImage>,
     },
+    /// A weather object's particle setup; `None` when it stops being one.
+    ObjectWeather {
+        stage: StagePlane,
+        index: i32,
+        weather: Option<ObjectWeatherEvent>,
+    },
+    /// Animate the object through a gan set; the next `UpsertObjectImage`
+    /// for it stops the animation.
+    ObjectGan {
+        stage: StagePlane,
+        index: i32,
+        gan: ObjectGanEvent,
+    },
     MissingObjectImage {
         stage: StagePlane,
         index: i32,
@@ -111,15 +184,36 @@ enum HostEvent {
         layer: i32,
         seq: u64,
     },
+    /// `index` became (or stopped being) a child slot of object `parent`.
+    SetObjectParent {
+        stage: StagePlane,
+        index: i32,
+        parent: Option<i32>,
+    },
+    /// Child sort type of object `index`: `test` keeps child-number order.
+    SetObjectChildSort {
+        stage: StagePlane,
+        index: i32,
+        test: bool,
+    },
     SetObjectRenderState {
         stage: StagePlane,
         index: i32,
         center_x: f32,
         center_y: f32,
+        center_rep_x: f32,
+        center_rep_y: f32,
         scale_x: f32,
         scale_y: f32,
         rotate_z_deg: f32,
+        z: f32,
+        rotate_x_deg: f32,
+        rotate_y_deg: f32,
+        world: i32,
+        billboard: bool,
         alpha: f32,
+        /// `#BUTTON.ACTION` position offset of the current button look.
+        button_offset: (f32, f32),
         dst_clip_use: bool,
         dst_clip_left: f32,
         dst_clip_top: f32,
@@ -130,10 +224,17 @@ enum HostEvent {
         src_clip_top: f32,
         src_clip_right: f32,
         src_clip_bottom: f32,
+        blend: siglus::render::RenderBlend,
+        reps: Vec<siglus::render::RenderRep>,
     },
     ClearPlaneObjects {
         stage: StagePlane,
     },
+    /// A stage's `world` list, by world number.
+    SetWorlds {
+        stage: StagePlane,
+        worlds: Vec<siglus::vm::VmWorld>,
+    },
     Location {
         scene_title: String,
         scene: String,
@@ -148,8 +249,15 @@ enum HostEvent {
     StartWipe {
         duration_ms: u64,
         wipe_type: i32,
+        speed_mode: i32,
+        option: Vec<i32>,
+        /// Mask image of `mask_wipe`, stretched over the stage.
+        mask: Option<Arc<siglus::render::RenderMask>>,
         wipe_direction: WipeDirection,
+        /// Script wipe whose order/layer range is transitioned; `None` covers all.
+        range: Option<siglus::vm::VmWipeRequest>,
     },
+    EndWipe,
     SetCursorPos {
         x: i32,
         y: i32,
@@ -166,6 +274,12 @@ enum HostEvent {
         name: String,
     },
     StopSe,
+    /// Voice `koe_no`; report `serial` finished to `KoePlayback` when it ends.
+    PlayKoe {
+        koe_no: i32,
+        serial: u32,
+    },
+    StopKoe,
     PlayPcm {
         ch: i32,
         name: String,
@@ -187,10 +301,41 @@ enum HostEvent {
         generation: u64,
     },
     StartQuake {
+        quake_no: usize,
         req: siglus::vm::VmQuakeRequest,
-        started_at: Instant,
+        /// VM clock ms.
+        started_at: u64,
+    },
+    EndQuake {
+        quake_no: usize,
+    },
+    StartShake {
+        steps: Vec<siglus::render::ShakeStep>,
+        /// VM clock ms.
+        started_at: u64,
+    },
+    EndShake,
+    FontStyle(siglus::vm::VmFontStyle),
+    MessageSpeed(siglus::vm::VmMessageSpeed),
+    /// Structured page of a message window (Gameexe-resolved number).
+    /// `message.parts` holds only the parts from `from_part` on; earlier ones
+    /// are those of the page already shown.
+    Message {
+        mwnd_no: i32,
+        message: siglus::vm::VmMessage,
+        from_part: usize,
+    },
+    CurMwnd(i32),
+    MwndState {
+        mwnd_no: i32,
+        state: MwndState,
+    },
+    MwndClear(i32),
+    MwndImage {
+        mwnd_no: i32,
+        slot: MwndImageSlot,
+        image: Option<Arc<image::DynamicImage>>,
     },
-    EndQuake,
     Done,
 }
 
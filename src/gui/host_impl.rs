@@ -1,5 +1,6 @@
 include!("host_impl_input.rs");
 include!("host_stage_hit_test.rs");
+include!("host_stage_button.rs");
 include!("host_impl_stage_object.rs");
 include!("host_impl_syscom_capture.rs");
 
@@ -25,7 +26,10 @@ impl GuiHost {
             positional.first().map(|p| &p.value),
             Some(siglus::vm::PropValue::Int(_))
         ) {
-            idx = 1; // template_no
+            if let Some(siglus::vm::PropValue::Int(no)) = positional.first().map(|p| &p.value) {
+                req.template_no = *no;
+            }
+            idx = 1;
         }
 
         let mut pending: Option<SelectionOption> = None;
@@ -128,6 +132,7 @@ impl GuiHost {
         if req.options.is_empty() {
             if let Some(prev) = &self.pending_selbtn_request {
                 req.options = prev.options.clone();
+                req.template_no = prev.template_no;
                 if req.selbtn.is_none() {
                     req.selbtn = prev.selbtn.clone();
                 } else if let (Some(cur), Some(old)) = (req.selbtn.as_mut(), prev.selbtn.as_ref()) {
@@ -175,14 +180,25 @@ impl GuiHost {
         });
     }
 
+    /// Put a selection on screen; `run_selection_wait` then waits for it.
+    fn show_selection(&mut self, mut req: SelectionRequest) {
+        if req.selbtn.is_some() && req.template.is_none() {
+            req.template = self.resolve_selbtn_template(req.template_no);
+        }
+        let _ = self.event_tx.send(HostEvent::Selection(req));
+    }
+
     fn run_selection_wait(&mut self, req: SelectionRequest) -> i32 {
+        let selbtn = req.selbtn.clone();
         let sync_type = req.selbtn.as_ref().map(|v| v.sync_type).unwrap_or(-1);
         let cancel_enable = req.selbtn.as_ref().map(|v| v.cancel_enable).unwrap_or(false);
         let option_count = req.options.len();
         if sync_type >= 0 {
             self.emit_selbtn_sync_checkpoint(sync_type, cancel_enable, "wait_enter", option_count, None);
         }
-        let _ = self.event_tx.send(HostEvent::Selection(req));
+        if !std::mem::take(&mut self.selection_shown) {
+            self.show_selection(req);
+        }
         let selected = self.selection_rx.recv().unwrap_or(0);
         if sync_type >= 0 {
             self.emit_selbtn_sync_checkpoint(sync_type, cancel_enable, "choice_received", option_count, Some(selected));
@@ -193,7 +209,7 @@ impl GuiHost {
                 _ => self.emit_selbtn_sync_checkpoint(sync_type, cancel_enable, "sync_unknown", option_count, Some(selected)),
             }
         }
-        if let Some(selbtn) = req.selbtn.as_ref() {
+        if let Some(selbtn) = selbtn.as_ref() {
             if selected < 0 && selbtn.cancel_enable {
                 self.emit_selbtn_sync_checkpoint(sync_type.max(0), true, "cancel_input", option_count, Some(selected));
                 self.emit_selbtn_sync_checkpoint(sync_type.max(0), true, "cancel_complete", option_count, Some(selected));
@@ -223,16 +239,24 @@ impl GuiHost {
 }
 
 impl siglus::vm::Host for GuiHost {
+    fn clock(&self) -> siglus::vm::SharedVmClock {
+        self.vm_clock.clone()
+    }
     fn on_name(&mut self, name: &str) {
         let _ = self.event_tx.send(HostEvent::Name(name.to_string()));
     }
     fn on_text(&mut self, text: &str, _read_flag_no: i32) {
+        // Ctrl skip passes everything; read skip only pages read before
+        // unless unread skip is on too.
+        let wait = !(self.skip_mode.load(Ordering::Relaxed)
+            || self.message_speed.skips(self.message_read));
         let _ = self.event_tx.send(HostEvent::Text {
             text: text.to_string(),
+            wait,
         });
 
         // If skip mode is off, wait for user click to advance
-        if !self.skip_mode.load(Ordering::Relaxed) {
+        if wait {
             loop {
                 if self.shutdown.load(Ordering::Relaxed) {
                     return;
@@ -248,6 +272,8 @@ impl siglus::vm::Host for GuiHost {
                         if self.skip_mode.load(Ordering::Relaxed) {
                             break;
                         }
+                        // Keep gan sprites (blinking eyes) moving during the wait.
+                        self.tick_gan_anims();
                         continue;
                     }
                     Err(mpsc::RecvTimeoutError::Disconnected) => return,
@@ -427,7 +453,7 @@ impl siglus::vm::Host for GuiHost {
                             color: -1,
                         })
                         .collect(),
-                    selbtn: None,
+                    ..Default::default()
                 };
                 let selected = self.run_selection_wait(req);
                 return siglus::vm::HostReturn {
@@ -435,18 +461,6 @@ impl siglus::vm::Host for GuiHost {
                     ..siglus::vm::HostReturn::default()
                 };
             }
-
-            if siglus::elm::global::is_wipe_start_command(elm) {
-                // C++ source of truth: cmd_wipe.cpp::tnm_command_proc_wipe
-                let duration_ms = parse_wipe_duration_from_cpp(elm, args);
-                let wipe_type = parse_wipe_type_from_cpp(elm, args);
-                let _ = self.event_tx.send(HostEvent::StartWipe {
-                    duration_ms,
-                    wipe_type,
-                    wipe_direction: WipeDirection::Normal,
-                });
-                return siglus::vm::HostReturn::default();
-            }
         }
 
         siglus::vm::HostReturn::default()
@@ -461,6 +475,33 @@ impl siglus::vm::Host for GuiHost {
     }
     include!("host_impl_trace.rs");
 
+    fn on_scene_reload(&mut self, changed_scenes: &[String], restarted: bool) {
+        info!(
+            "Hot reload: {} scene(s) changed {:?}{}",
+            changed_scenes.len(),
+            changed_scenes,
+            if restarted { ", restarted current scene" } else { "" }
+        );
+    }
+
+    fn on_watchpoint(&mut self, hit: &siglus::vm::VmWatchHit) {
+        let fmt = |v: &Option<siglus::vm::VmWatchValue>| {
+            v.as_ref()
+                .map(|v| v.to_string())
+                .unwrap_or_else(|| "-".to_string())
+        };
+        info!(
+            "watch#{} {}[{}]: {} -> {} at {}:{}",
+            hit.id,
+            hit.list.name(),
+            hit.index,
+            fmt(&hit.old),
+            fmt(&hit.new),
+            hit.scene,
+            hit.line_no
+        );
+    }
+
     fn on_error(&mut self, msg: &str) {
         error!("VM Error: {}", msg);
         let _ = self.event_tx.send(HostEvent::VmError {
@@ -512,10 +553,20 @@ impl siglus::vm::Host for GuiHost {
     }
     fn on_wait_frame(&mut self) {
         self.refresh_movie_lifecycle();
+        self.tick_screen_effects(false);
+        self.tick_mask_events();
+        self.tick_object_int_events();
+        self.tick_gan_anims();
+        self.tick_group_button_looks();
         if self.shutdown.load(Ordering::Relaxed) {
             return;
         }
-        std::thread::sleep(std::time::Duration::from_millis(8));
+        self.clock().sleep_ms(8);
+    }
+
+    fn on_frame_counter_elapsed(&mut self) -> (i32, i32) {
+        let clock = self.clock();
+        self.frame_delta.next(&*clock)
     }
 
     fn on_movie_is_playing(&mut self) -> bool {
@@ -527,16 +578,14 @@ impl siglus::vm::Host for GuiHost {
         self.mwnd_list_size
     }
 
-    fn on_world_list_get_size(&mut self) -> i32 {
-        self.world_list_size
-    }
-
-    fn on_world_create(&mut self) {
-        self.world_list_size = self.world_list_size.saturating_add(1);
-    }
-
-    fn on_world_destroy(&mut self) {
-        self.world_list_size = self.world_list_size.saturating_sub(1);
+    fn on_worlds(&mut self, stage_idx: i32, worlds: &[siglus::vm::VmWorld]) {
+        let Some(stage) = crate::gui::stage::stage_idx_to_plane(stage_idx) else {
+            return;
+        };
+        let _ = self.event_tx.send(HostEvent::SetWorlds {
+            stage,
+            worlds: worlds.to_vec(),
+        });
     }
 
     fn on_effect_list_get_size(&mut self) -> i32 {
@@ -567,7 +616,6 @@ impl siglus::vm::Host for GuiHost {
     fn on_int_event_list_resize(&mut self, owner_id: i32, size: i32) {
         self.int_event_list_sizes.insert(owner_id, size.max(0));
     }
-    include!("host_impl_int_event.rs");
     impl_host_input_methods!();
     fn on_msg_back_state(&mut self, open: bool) {
         let _ = self.event_tx.send(HostEvent::MsgBackState(open));
@@ -617,6 +665,65 @@ impl siglus::vm::Host for GuiHost {
         // C++ reference: eng_syscom.cpp fade-out branches push TNM_PROC_TYPE_DISP.
         info!("syscom DISP proc");
     }
+    fn on_wipe_start(&mut self, req: &siglus::vm::VmWipeRequest) {
+        // A mask that fails to load degrades to the plain wipe of the same type.
+        let mask = match req.mask_file.as_deref() {
+            Some(name) if req.is_mask() && !name.is_empty() => self
+                .load_mask_image(name)
+                .map(|image| Arc::new(siglus::render::RenderMask::new(image, 0.0, 0.0))),
+            _ => None,
+        };
+        let _ = self.event_tx.send(HostEvent::StartWipe {
+            duration_ms: req.time_ms,
+            wipe_type: req.wipe_type,
+            speed_mode: req.speed_mode,
+            option: req.option.clone(),
+            mask,
+            wipe_direction: WipeDirection::Normal,
+            range: Some(req.clone()),
+        });
+    }
+
+    fn on_mask_slot(&mut self, slot_no: usize, slot: &siglus::vm::MaskSlotState) {
+        self.apply_mask_slot(slot_no, slot);
+    }
+
+    fn on_int_event(
+        &mut self,
+        scope: siglus::vm::IntEventScope,
+        owner_id: i32,
+        event: &siglus::vm::IntEvent,
+    ) {
+        let siglus::vm::IntEventScope::Object {
+            list_id,
+            stage_idx,
+            obj_idx,
+        } = scope
+        else {
+            return;
+        };
+        if list_id != siglus::elm::objectlist::ELM_STAGE_OBJECT {
+            return;
+        }
+        let Some(plane) = crate::gui::stage::stage_idx_to_plane(stage_idx) else {
+            return;
+        };
+        self.apply_object_int_event(plane, obj_idx, owner_id, event);
+    }
+
+    fn on_screen_effect(&mut self, effect_no: usize, effect: &siglus::vm::VmScreenEffect) {
+        if self.screen_effects.len() <= effect_no {
+            self.screen_effects
+                .resize(effect_no + 1, siglus::vm::VmScreenEffect::default());
+        }
+        self.screen_effects[effect_no] = effect.clone();
+        self.tick_screen_effects(true);
+    }
+
+    fn on_wipe_end(&mut self) {
+        let _ = self.event_tx.send(HostEvent::EndWipe);
+    }
+
     fn on_syscom_proc_game_end_wipe(&mut self, wipe_type: i32, wipe_time_ms: u64) {
         // C++ reference: flow_proc.cpp::tnm_game_end_wipe_proc.
         let wipe_time_ms = wipe_time_ms.max(1);
@@ -627,14 +734,18 @@ impl siglus::vm::Host for GuiHost {
         let _ = self.event_tx.send(HostEvent::StartWipe {
             duration_ms: wipe_time_ms,
             wipe_type,
+            speed_mode: 0,
+            option: Vec::new(),
+            mask: None,
             wipe_direction: WipeDirection::SystemOut,
+            range: None,
         });
-        let started = std::time::Instant::now();
-        while started.elapsed() < std::time::Duration::from_millis(wipe_time_ms) {
+        let started_ms = self.vm_clock.now_ms();
+        while self.vm_clock.now_ms().saturating_sub(started_ms) < wipe_time_ms {
             if self.shutdown.load(Ordering::Relaxed) {
                 break;
             }
-            std::thread::sleep(std::time::Duration::from_millis(10));
+            self.vm_clock.sleep_ms(10);
         }
     }
     fn on_syscom_proc_game_start_wipe(&mut self, wipe_type: i32, wipe_time_ms: u64) {
@@ -649,14 +760,18 @@ impl siglus::vm::Host for GuiHost {
         let _ = self.event_tx.send(HostEvent::StartWipe {
             duration_ms: wipe_time_ms,
             wipe_type,
+            speed_mode: 0,
+            option: Vec::new(),
+            mask: None,
             wipe_direction: WipeDirection::SystemIn,
+            range: None,
         });
-        let started = std::time::Instant::now();
-        while started.elapsed() < std::time::Duration::from_millis(wipe_time_ms) {
+        let started_ms = self.vm_clock.now_ms();
+        while self.vm_clock.now_ms().saturating_sub(started_ms) < wipe_time_ms {
             if self.shutdown.load(Ordering::Relaxed) {
                 break;
             }
-            std::thread::sleep(std::time::Duration::from_millis(10));
+            self.vm_clock.sleep_ms(10);
         }
     }
     fn on_syscom_proc_return_to_sel(&mut self) {
@@ -757,6 +872,21 @@ impl siglus::vm::Host for GuiHost {
             scene, z_no
         );
     }
+    fn on_selbtn_start_call(
+        &mut self,
+        elm: i32,
+        args: &[siglus::vm::Prop],
+        scene: &str,
+        z_no: i32,
+    ) {
+        info!("selbtn: sel_start_call farcall scene={} z={}", scene, z_no);
+        // Same request the command resolves once the call returns.
+        let req = self.resolve_selbtn_start_request(elm, args);
+        if !req.options.is_empty() {
+            self.show_selection(req);
+            self.selection_shown = true;
+        }
+    }
     fn on_open_tweet_dialog(&mut self) {
         // C++ reference: cmd_syscom.cpp::ELM_SYSCOM_OPEN_TWEET_DIALOG -> tnm_twitter_start().
         // Rust currently opens a minimal placeholder dialog (no real tweet/upload pipeline yet).
@@ -813,6 +943,17 @@ impl siglus::vm::Host for GuiHost {
     fn on_se_stop(&mut self, _fade: i32) {
         let _ = self.event_tx.send(HostEvent::StopSe);
     }
+    fn on_koe_play(&mut self, koe_no: i32, _chara_no: i32) {
+        let serial = self.koe.request();
+        let _ = self.event_tx.send(HostEvent::PlayKoe { koe_no, serial });
+    }
+    fn on_koe_stop(&mut self) {
+        self.koe.stop();
+        let _ = self.event_tx.send(HostEvent::StopKoe);
+    }
+    fn koe_playing(&self) -> bool {
+        self.koe.is_playing()
+    }
     fn on_mov_play(&mut self, _name: &str) {
         self.global_mov_playing = true;
     }
@@ -844,61 +985,4 @@ impl siglus::vm::Host for GuiHost {
 
 }
 
-fn parse_wipe_type_from_cpp(elm: i32, args: &[siglus::vm::Prop]) -> i32 {
-    // C++ source of truth: cmd_wipe.cpp::tnm_command_proc_wipe
-    // positional arg: WIPE(...): arg0, MASK_WIPE(...): arg1
-    let default_pos = if elm == siglus::elm::global::ELM_GLOBAL_MASK_WIPE
-        || elm == siglus::elm::global::ELM_GLOBAL_MASK_WIPE_ALL
-    {
-        1
-    } else {
-        0
-    };
-    let mut wipe_type = args.get(default_pos).and_then(|p| p.as_int()).unwrap_or(0);
-    // named override: id=0
-    for arg in args {
-        if arg.id == 0 {
-            if let Some(v) = arg.as_int() {
-                wipe_type = v;
-            }
-            break;
-        }
-    }
-    wipe_type
-}
-
-fn parse_wipe_duration_from_cpp(elm: i32, args: &[siglus::vm::Prop]) -> u64 {
-    let mut wipe_time = 500i32;
-    let mut start_time = 0i32;
-
-    let time_pos = if elm == siglus::elm::global::ELM_GLOBAL_MASK_WIPE
-        || elm == siglus::elm::global::ELM_GLOBAL_MASK_WIPE_ALL
-    {
-        2
-    } else {
-        1
-    };
-    if let Some(v) = args.get(time_pos).and_then(|p| p.as_int()) {
-        wipe_time = v;
-    }
-
-    for arg in args {
-        match arg.id {
-            1 => {
-                if let Some(v) = arg.as_int() {
-                    wipe_time = v;
-                }
-            }
-            11 => {
-                if let Some(v) = arg.as_int() {
-                    start_time = v;
-                }
-            }
-            _ => {}
-        }
-    }
-
-    (wipe_time as i64 - start_time as i64).max(0) as u64
-}
-
 // ── GUI Application ─────────────────────────────────────────────────────
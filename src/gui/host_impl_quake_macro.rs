@@ -1,31 +1,62 @@
 macro_rules! impl_host_quake_methods {
     () => {
-        fn on_quake_start(&mut self, req: siglus::vm::VmQuakeRequest) {
-            let loops = req.cnt.max(0).saturating_add(1) as u64;
-            let ms = (req.time_ms.max(1) as u64).saturating_mul(loops);
-            self.quake_active_until = Some(Instant::now() + std::time::Duration::from_millis(ms));
-            self.last_quake_request = Some(req);
+        fn on_quake_start(&mut self, quake_no: usize, req: siglus::vm::VmQuakeRequest) {
             let _ = self.event_tx.send(HostEvent::StartQuake {
+                quake_no,
                 req,
-                started_at: Instant::now(),
+                started_at: self.vm_clock.now_ms(),
             });
         }
 
-        fn on_quake_end(&mut self) {
-            self.quake_active_until = None;
-            let _ = self.event_tx.send(HostEvent::EndQuake);
+        fn on_quake_end(&mut self, quake_no: usize) {
+            let _ = self.event_tx.send(HostEvent::EndQuake { quake_no });
         }
 
-        fn on_quake_is_active(&mut self) -> bool {
-            match self.quake_active_until {
-                Some(deadline) if deadline > Instant::now() => true,
-                Some(_) => {
-                    self.quake_active_until = None;
-                    let _ = self.event_tx.send(HostEvent::EndQuake);
-                    false
-                }
-                None => false,
-            }
+        fn on_shake_start(&mut self, _shake_no: i32, steps: &[siglus::render::ShakeStep]) {
+            let _ = self.event_tx.send(HostEvent::StartShake {
+                steps: steps.to_vec(),
+                started_at: self.vm_clock.now_ms(),
+            });
+        }
+
+        fn on_shake_end(&mut self) {
+            let _ = self.event_tx.send(HostEvent::EndShake);
+        }
+
+        fn on_mwnd_action(&mut self, mwnd_no: i32, sub_id: i32, args: &[siglus::vm::Prop]) {
+            self.apply_mwnd_action(mwnd_no, sub_id, args);
+        }
+
+        fn on_mwnd_get(&mut self, mwnd_no: i32, sub_id: i32) -> i32 {
+            self.mwnd_get(mwnd_no, sub_id)
+        }
+
+        fn on_cur_mwnd(&mut self, mwnd_no: i32) {
+            let _ = self.event_tx.send(HostEvent::CurMwnd(mwnd_no));
+        }
+
+        fn on_message(&mut self, mwnd_no: i32, msg: &siglus::vm::VmMessage, from_part: usize) {
+            self.message_read = msg.read;
+            let _ = self.event_tx.send(HostEvent::Message {
+                mwnd_no: self.resolve_mwnd_no(mwnd_no),
+                message: siglus::vm::VmMessage {
+                    page_serial: msg.page_serial,
+                    name: msg.name.clone(),
+                    parts: msg.parts[from_part..].to_vec(),
+                    read: msg.read,
+                },
+                from_part,
+            });
+        }
+
+        fn on_message_speed(&mut self, speed: &siglus::vm::VmMessageSpeed) {
+            self.message_speed = *speed;
+            let _ = self.event_tx.send(HostEvent::MessageSpeed(*speed));
+        }
+
+        fn on_font_style(&mut self, style: &siglus::vm::VmFontStyle) {
+            self.font_style = style.clone();
+            let _ = self.event_tx.send(HostEvent::FontStyle(style.clone()));
         }
     };
 }
@@ -118,6 +118,7 @@ macro_rules! impl_host_stage_object_methods {
                 if let Some(st) = self.groups.get_mut(&(plane, group_idx)) {
                     st.active = false;
                 }
+                self.update_group_button_looks(plane, group_idx, None, false);
             }
         }
 
@@ -146,6 +147,17 @@ macro_rules! impl_host_stage_object_methods {
             let trace_group_wait = std::env::var("SIGLUS_GROUP_WAIT_TRACE")
                 .map(|v| v != "0")
                 .unwrap_or(false);
+            // Hit-test before the group is borrowed.
+            let cursor = self
+                .input_state
+                .lock()
+                .ok()
+                .map(|state| (state.mouse_x as f32, state.mouse_y as f32));
+            let candidate =
+                cursor.and_then(|c| self.group_hit_candidate_button(plane, group_idx, c));
+            let candidate_keep = candidate
+                .and_then(|hit| self.group_button(plane, group_idx, hit))
+                .is_some_and(|btn| btn.push_keep != 0);
 
             let st = self.groups.get_mut(&(plane, group_idx))?;
             if self.shutdown.load(Ordering::Relaxed) {
@@ -169,14 +181,15 @@ macro_rules! impl_host_stage_object_methods {
                 st.result_button_no = -1;
                 st.active = false;
                 let _ = st;
+                self.update_group_button_looks(plane, group_idx, None, false);
                 self.play_cancel_se(se_no);
                 return Some(-1);
             }
 
             let mut direct_decided = None;
+            let mut look = (None, false);
             if let Ok(mut state) = self.input_state.lock() {
-                let cursor = (state.mouse_x as f32, state.mouse_y as f32);
-                let mut object_hit_button = self.group_hit_candidate_button(plane, group_idx, cursor);
+                let mut object_hit_button = candidate;
 
                 // push_keep: when press started on one button, keep it during hold.
                 if state.mouse_left.is_down {
@@ -192,21 +205,7 @@ macro_rules! impl_host_stage_object_methods {
                     st.hit_button_no = hit;
                     if state.mouse_left.is_down {
                         st.pushed_button_no = hit;
-                        let keep = self
-                            .objects
-                            .iter()
-                            .find_map(|((p, idx), _)| {
-                                if *p != plane {
-                                    return None;
-                                }
-                                let btn = self.get_object_button_state(*p, *idx);
-                                if btn.group_no == group_idx && btn.button_no == hit {
-                                    Some(btn.push_keep != 0)
-                                } else {
-                                    None
-                                }
-                            })
-                            .unwrap_or(false);
+                        let keep = st.press_keep_button_no == hit || candidate_keep;
                         st.press_keep_button_no = if keep { hit } else { -1 };
                     }
                 } else {
@@ -218,6 +217,7 @@ macro_rules! impl_host_stage_object_methods {
                         st.pushed_button_no = st.on_pushed_no;
                     }
                 }
+                look = (object_hit_button, state.mouse_left.is_down);
                 let decide_stock_before = state.decide.has_down_up_stock();
                 let mouse_stock_before = state.mouse_left.has_down_up_stock();
                 if let Some(hit) = object_hit_button.filter(|_| state.decide.use_down_up_stock()) {
@@ -246,8 +246,18 @@ macro_rules! impl_host_stage_object_methods {
                 st.result = decided;
                 st.result_button_no = decided;
                 st.active = false;
+                let _ = st;
+                self.update_group_button_looks(plane, group_idx, None, false);
+                self.play_group_button_se(plane, group_idx, decided, |se| se.decide);
                 return Some(decided);
             }
+            let _ = st;
+            self.update_group_button_looks(plane, group_idx, look.0, look.1);
+            if self.selection_shown {
+                // The choice belongs to the selection whose start call is running.
+                return None;
+            }
+            let st = self.groups.get_mut(&(plane, group_idx))?;
 
             match self.selection_rx.try_recv() {
                 Ok(selected) => {
@@ -288,6 +298,7 @@ macro_rules! impl_host_stage_object_methods {
                         );
                     }
                     let _ = st;
+                    self.update_group_button_looks(plane, group_idx, None, false);
                     if cancel_se_no >= 0 {
                         self.play_cancel_se(cancel_se_no);
                     }
@@ -309,6 +320,7 @@ macro_rules! impl_host_stage_object_methods {
             let Some(plane) = crate::gui::stage::stage_idx_to_plane(stage_idx) else {
                 return -1;
             };
+            self.tick_group_button_looks();
             if let Some(st) = self.groups.get_mut(&(plane, group_idx)) {
                 return match query_id {
                     x if x == siglus::elm::group::ELM_GROUP_GET_HIT_NO => st.hit_button_no,
@@ -360,13 +372,148 @@ macro_rules! impl_host_stage_object_methods {
                 .unwrap_or_else(|| {
                     self.objects
                         .keys()
-                        .filter(|(p, _)| *p == plane)
+                        .filter(|(p, idx)| *p == plane && *idx < CHILD_OBJECT_INDEX_BASE)
                         .map(|(_, idx)| *idx + 1)
                         .max()
                         .unwrap_or(0)
                 })
         }
 
+        fn on_object_child_list_get_size(
+            &mut self,
+            list_id: i32,
+            obj_index: i32,
+            stage_idx: Option<i32>,
+        ) -> i32 {
+            if list_id != siglus::elm::objectlist::ELM_STAGE_OBJECT {
+                return -1;
+            }
+            let Some(plane) = stage_idx.and_then(crate::gui::stage::stage_idx_to_plane) else {
+                return -1;
+            };
+            self.object_child_count(plane, obj_index) as i32
+        }
+
+        fn on_object_child_list_resize(
+            &mut self,
+            list_id: i32,
+            obj_index: i32,
+            size: i32,
+            stage_idx: Option<i32>,
+        ) {
+            if list_id != siglus::elm::objectlist::ELM_STAGE_OBJECT {
+                return;
+            }
+            let Some(plane) = stage_idx.and_then(crate::gui::stage::stage_idx_to_plane) else {
+                return;
+            };
+            self.resize_object_children(plane, obj_index, size.max(0) as usize);
+        }
+
+        fn on_object_child_is_use(
+            &mut self,
+            list_id: i32,
+            obj_index: i32,
+            child_index: i32,
+            stage_idx: Option<i32>,
+        ) -> bool {
+            if list_id != siglus::elm::objectlist::ELM_STAGE_OBJECT {
+                return true;
+            }
+            stage_idx
+                .and_then(crate::gui::stage::stage_idx_to_plane)
+                .and_then(|plane| self.child_object_index(plane, obj_index, child_index))
+                .is_some()
+        }
+
+        // Child slots are objects of their own (see `ObjectTree`), so the
+        // child lanes forward to the top-level object hooks.
+        fn on_object_child_get(
+            &mut self,
+            list_id: i32,
+            obj_index: i32,
+            child_index: i32,
+            sub_id: i32,
+            stage_idx: Option<i32>,
+        ) -> i32 {
+            let Some(plane) = stage_idx.and_then(crate::gui::stage::stage_idx_to_plane) else {
+                return 0;
+            };
+            match self.child_object_index(plane, obj_index, child_index) {
+                Some(child) => self.on_object_get(list_id, child, sub_id, stage_idx),
+                None => 0,
+            }
+        }
+
+        fn on_object_child_get_str(
+            &mut self,
+            list_id: i32,
+            obj_index: i32,
+            child_index: i32,
+            sub_id: i32,
+            stage_idx: Option<i32>,
+        ) -> String {
+            let Some(plane) = stage_idx.and_then(crate::gui::stage::stage_idx_to_plane) else {
+                return String::new();
+            };
+            match self.child_object_index(plane, obj_index, child_index) {
+                Some(child) => self.on_object_get_str(list_id, child, sub_id, stage_idx),
+                None => String::new(),
+            }
+        }
+
+        fn on_object_child_query(
+            &mut self,
+            list_id: i32,
+            obj_index: i32,
+            child_index: i32,
+            sub_id: i32,
+            args: &[siglus::vm::Prop],
+            stage_idx: Option<i32>,
+        ) -> i32 {
+            let Some(plane) = stage_idx.and_then(crate::gui::stage::stage_idx_to_plane) else {
+                return 0;
+            };
+            match self.child_object_index(plane, obj_index, child_index) {
+                Some(child) => self.on_object_query(list_id, child, sub_id, args, stage_idx),
+                None => 0,
+            }
+        }
+
+        fn on_object_child_property(
+            &mut self,
+            list_id: i32,
+            obj_index: i32,
+            child_index: i32,
+            sub_id: i32,
+            value: i32,
+            stage_idx: Option<i32>,
+        ) {
+            let Some(plane) = stage_idx.and_then(crate::gui::stage::stage_idx_to_plane) else {
+                return;
+            };
+            if let Some(child) = self.child_object_index(plane, obj_index, child_index) {
+                self.on_object_property(list_id, child, sub_id, value, stage_idx);
+            }
+        }
+
+        fn on_object_child_action(
+            &mut self,
+            list_id: i32,
+            obj_index: i32,
+            child_index: i32,
+            sub_id: i32,
+            args: &[siglus::vm::Prop],
+            stage_idx: Option<i32>,
+        ) {
+            let Some(plane) = stage_idx.and_then(crate::gui::stage::stage_idx_to_plane) else {
+                return;
+            };
+            if let Some(child) = self.child_object_index(plane, obj_index, child_index) {
+                self.on_object_action(list_id, child, sub_id, args, stage_idx);
+            }
+        }
+
         fn on_object_is_use(
             &mut self,
             list_id: i32,
@@ -410,6 +557,74 @@ macro_rules! impl_host_stage_object_methods {
             self.apply_object_command(plane, obj_index, sub_id, args);
         }
 
+        fn on_object_create_from_capture(
+            &mut self,
+            list_id: i32,
+            obj_index: i32,
+            sub_id: i32,
+            args: &[siglus::vm::Prop],
+            image: Option<Arc<image::RgbaImage>>,
+            stage_idx: Option<i32>,
+        ) {
+            if list_id != siglus::elm::objectlist::ELM_STAGE_OBJECT || obj_index < 0 {
+                return;
+            }
+            let Some(plane) = stage_idx.and_then(crate::gui::stage::stage_idx_to_plane) else {
+                return;
+            };
+            self.refresh_movie_lifecycle();
+            self.create_object_from_capture(plane, obj_index, sub_id, args, image);
+        }
+
+        fn on_object_gan_load(
+            &mut self,
+            list_id: i32,
+            obj_index: i32,
+            stage_idx: Option<i32>,
+            gan_path: &str,
+        ) {
+            if list_id != siglus::elm::objectlist::ELM_STAGE_OBJECT {
+                return;
+            }
+            let Some(plane) = stage_idx.and_then(crate::gui::stage::stage_idx_to_plane) else {
+                return;
+            };
+            self.load_object_gan(plane, obj_index, gan_path);
+        }
+
+        fn on_object_gan_start(
+            &mut self,
+            list_id: i32,
+            obj_index: i32,
+            stage_idx: Option<i32>,
+            playback: &siglus::resource::GanPlayback,
+        ) {
+            if list_id != siglus::elm::objectlist::ELM_STAGE_OBJECT {
+                return;
+            }
+            let Some(plane) = stage_idx.and_then(crate::gui::stage::stage_idx_to_plane) else {
+                return;
+            };
+            self.start_object_gan(plane, obj_index, *playback);
+        }
+
+        fn on_object_int_list_set(
+            &mut self,
+            list_id: i32,
+            obj_index: i32,
+            sub_id: i32,
+            values: &[i32],
+            stage_idx: Option<i32>,
+        ) {
+            if list_id != siglus::elm::objectlist::ELM_STAGE_OBJECT {
+                return;
+            }
+            let Some(plane) = stage_idx.and_then(crate::gui::stage::stage_idx_to_plane) else {
+                return;
+            };
+            self.set_object_rep_list(plane, obj_index, sub_id, values);
+        }
+
         fn on_object_property(
             &mut self,
             list_id: i32,
@@ -526,6 +741,37 @@ macro_rules! impl_host_stage_object_methods {
                 x if x == siglus::elm::objectlist::ELM_OBJECT_DISP => i32::from(st.visible),
                 x if x == siglus::elm::objectlist::ELM_OBJECT_X => st.x as i32,
                 x if x == siglus::elm::objectlist::ELM_OBJECT_Y => st.y as i32,
+                x if x == siglus::elm::objectlist::ELM_OBJECT_Z => st.z as i32,
+                x if x == siglus::elm::objectlist::ELM_OBJECT_ROTATE_X => (st.rotate_x_deg * 10.0) as i32,
+                x if x == siglus::elm::objectlist::ELM_OBJECT_ROTATE_Y => (st.rotate_y_deg * 10.0) as i32,
+                x if x == siglus::elm::objectlist::ELM_OBJECT_WORLD => st.world,
+                x if x == siglus::elm::objectlist::ELM_OBJECT_CENTER_REP_X => st.center_rep_x as i32,
+                x if x == siglus::elm::objectlist::ELM_OBJECT_CENTER_REP_Y => st.center_rep_y as i32,
+                x if x == siglus::elm::objectlist::ELM_OBJECT_CENTER_X => st.center_x as i32,
+                x if x == siglus::elm::objectlist::ELM_OBJECT_CENTER_Y => st.center_y as i32,
+                x if x == siglus::elm::objectlist::ELM_OBJECT_SCALE_X => (st.scale_x * 1000.0).round() as i32,
+                x if x == siglus::elm::objectlist::ELM_OBJECT_SCALE_Y => (st.scale_y * 1000.0).round() as i32,
+                x if x == siglus::elm::objectlist::ELM_OBJECT_ROTATE_Z => (st.rotate_z_deg * 10.0).round() as i32,
+                x if x == siglus::elm::objectlist::ELM_OBJECT_TR => (st.alpha * 255.0).round() as i32,
+                x if x == siglus::elm::objectlist::ELM_OBJECT_CLIP_LEFT => st.dst_clip_left as i32,
+                x if x == siglus::elm::objectlist::ELM_OBJECT_CLIP_TOP => st.dst_clip_top as i32,
+                x if x == siglus::elm::objectlist::ELM_OBJECT_CLIP_RIGHT => st.dst_clip_right as i32,
+                x if x == siglus::elm::objectlist::ELM_OBJECT_CLIP_BOTTOM => st.dst_clip_bottom as i32,
+                x if x == siglus::elm::objectlist::ELM_OBJECT_SRC_CLIP_LEFT => st.src_clip_left as i32,
+                x if x == siglus::elm::objectlist::ELM_OBJECT_SRC_CLIP_TOP => st.src_clip_top as i32,
+                x if x == siglus::elm::objectlist::ELM_OBJECT_SRC_CLIP_RIGHT => st.src_clip_right as i32,
+                x if x == siglus::elm::objectlist::ELM_OBJECT_SRC_CLIP_BOTTOM => st.src_clip_bottom as i32,
+                x if x == siglus::elm::objectlist::ELM_OBJECT_COLOR_RATE => (st.color_rate * 1000.0).round() as i32,
+                x if x == siglus::elm::objectlist::ELM_OBJECT_COLOR_R => (st.color_r * 255.0).round() as i32,
+                x if x == siglus::elm::objectlist::ELM_OBJECT_COLOR_G => (st.color_g * 255.0).round() as i32,
+                x if x == siglus::elm::objectlist::ELM_OBJECT_COLOR_B => (st.color_b * 255.0).round() as i32,
+                x if x == siglus::elm::objectlist::ELM_OBJECT_COLOR_ADD_R => st.color_add_r as i32,
+                x if x == siglus::elm::objectlist::ELM_OBJECT_COLOR_ADD_G => st.color_add_g as i32,
+                x if x == siglus::elm::objectlist::ELM_OBJECT_COLOR_ADD_B => st.color_add_b as i32,
+                x if x == siglus::elm::objectlist::ELM_OBJECT_BRIGHT => st.bright as i32,
+                x if x == siglus::elm::objectlist::ELM_OBJECT_DARK => st.dark as i32,
+                x if x == siglus::elm::objectlist::ELM_OBJECT_MONO => (st.mono * 255.0).round() as i32,
+                x if x == siglus::elm::objectlist::ELM_OBJECT_REVERSE => i32::from(st.reverse),
                 x if x == siglus::elm::objectlist::ELM_OBJECT_PATNO => st.pat_no as i32,
                 x if x == siglus::elm::objectlist::ELM_OBJECT_ORDER => st.order,
                 x if x == siglus::elm::objectlist::ELM_OBJECT_LAYER => st.layer,
@@ -48,6 +48,20 @@ impl CaptureOptionHeader {
 
 macro_rules! impl_host_syscom_capture_methods {
     () => {
+        fn on_stage_scene(&mut self) -> Option<siglus::render::RenderScene> {
+            self.stage_scene.lock().ok()?.clone()
+        }
+
+        fn on_capture_load_file(&mut self, file_name: &str) -> Option<Arc<image::RgbaImage>> {
+            let path = resolve_capture_image_path(&self.base_dir, file_name)?;
+            let content = std::fs::read(&path).ok()?;
+            let image = load_capture_image(&self.base_dir, &content);
+            if image.is_none() {
+                error!("capture image load failed: {}", path.display());
+            }
+            image
+        }
+
         fn on_syscom_create_capture_buffer(&mut self, width: i32, height: i32) {
             self.capture_buffer = Some(HostCaptureBuffer {
                 width: width.max(0),
@@ -61,10 +75,22 @@ macro_rules! impl_host_syscom_capture_methods {
         }
 
         fn on_syscom_capture_to_buffer(&mut self, x: i32, y: i32, save_png_path: &str) {
+            let frame = siglus::vm::Host::on_capture_stage(self, false);
             let st = self.capture_buffer.get_or_insert_with(HostCaptureBuffer::default);
             st.origin_x = x;
             st.origin_y = y;
             st.png_path = save_png_path.to_string();
+            st.image = frame.map(|frame| Arc::new(crop_capture_frame(&frame, x, y, st.width, st.height)));
+            if save_png_path.trim().is_empty() {
+                return;
+            }
+            if let Some(image) = &st.image {
+                let png = std::path::PathBuf::from(save_png_path);
+                let png_full = if png.is_absolute() { png } else { self.base_dir.join(png) };
+                if let Err(e) = image.save_with_format(&png_full, image::ImageFormat::Png) {
+                    error!("capture png save failed ({}): {}", png_full.display(), e);
+                }
+            }
         }
 
         fn on_syscom_save_capture_buffer_to_file(&mut self, req: &siglus::vm::VmCaptureFileOp) -> bool {
@@ -98,8 +124,14 @@ macro_rules! impl_host_syscom_capture_methods {
             body.extend_from_slice(&(png_bytes.len() as u32).to_le_bytes());
             body.extend_from_slice(png_bytes);
             if ext == "bmp" {
-                let Some(bmp) = make_bmp_from_capture_buffer(&self.base_dir, &snapshot.png_path) else {
-                    return false;
+                let bmp = match &snapshot.image {
+                    Some(image) => siglus::resource::encode_capture_bmp(image),
+                    None => {
+                        let Some(bmp) = make_bmp_from_capture_buffer(&self.base_dir, &snapshot.png_path) else {
+                            return false;
+                        };
+                        bmp
+                    }
                 };
                 body.extend_from_slice(&bmp);
             }
@@ -238,6 +270,7 @@ fn parse_capture_payload_v2(content: &[u8]) -> Option<(HostCaptureBuffer, siglus
 
     // C++ eng_syscom_capture.cpp: option area starts at bmp_size.
     let bmp_size = parse_bmp_size_and_validate(content, off)?;
+    let image = siglus::resource::decode_capture_bmp(content.get(off..off + bmp_size)?).map(Arc::new);
     off = off.checked_add(bmp_size)?;
     let header_slice = content.get(off..off + CaptureOptionHeader::byte_len())?;
     let header = CaptureOptionHeader::decode(header_slice)?;
@@ -286,52 +319,67 @@ fn parse_capture_payload_v2(content: &[u8]) -> Option<(HostCaptureBuffer, siglus
             origin_x,
             origin_y,
             png_path,
+            image,
         },
         payload,
     ))
 }
 
-fn make_bmp_stub(width: i32, height: i32) -> Vec<u8> {
-    // Minimal BMP header (14 + 40) with BGRA32 payload layout.
-    let w = width.max(1) as u32;
-    let h = height.max(1) as u32;
-    let pixel_bytes = w.saturating_mul(h).saturating_mul(4);
-    let file_size = 54u32.saturating_add(pixel_bytes);
-    let mut out = vec![0u8; file_size as usize];
-    out[0] = b'B';
-    out[1] = b'M';
-    out[2..6].copy_from_slice(&file_size.to_le_bytes());
-    out[10..14].copy_from_slice(&54u32.to_le_bytes());
-    out[14..18].copy_from_slice(&40u32.to_le_bytes());
-    out[18..22].copy_from_slice(&(w as i32).to_le_bytes());
-    out[22..26].copy_from_slice(&(h as i32).to_le_bytes());
-    out[26..28].copy_from_slice(&1u16.to_le_bytes());
-    out[28..30].copy_from_slice(&32u16.to_le_bytes());
-    out[30..34].copy_from_slice(&0u32.to_le_bytes());
-    out[34..38].copy_from_slice(&pixel_bytes.to_le_bytes());
+/// The `width` x `height` region of a stage frame at (`x`, `y`); the whole
+/// frame when the capture buffer has no size.
+fn crop_capture_frame(frame: &image::RgbaImage, x: i32, y: i32, width: i32, height: i32) -> image::RgbaImage {
+    if width <= 0 || height <= 0 {
+        return frame.clone();
+    }
+    let mut out = image::RgbaImage::new(width as u32, height as u32);
+    image::imageops::overlay(&mut out, frame, -(x as i64), -(y as i64));
     out
 }
 
+/// `capture_from_file` / `create_from_capture_file`: the file name as given,
+/// else with the capture extensions tried in turn.
+fn resolve_capture_image_path(base_dir: &std::path::Path, file_name: &str) -> Option<std::path::PathBuf> {
+    let name = file_name.trim();
+    if name.is_empty() {
+        return None;
+    }
+    let path = std::path::PathBuf::from(name);
+    let path = if path.is_absolute() { path } else { base_dir.join(path) };
+    if path.is_file() {
+        return Some(path);
+    }
+    ["bmp", "png"]
+        .iter()
+        .map(|ext| path.with_extension(ext))
+        .find(|p| p.is_file())
+}
+
+/// The image of a capture file (`SSSUCAP2` BMP body, `SSSUCAP1` PNG path) or
+/// of a plain image file.
+fn load_capture_image(base_dir: &std::path::Path, content: &[u8]) -> Option<Arc<image::RgbaImage>> {
+    if content.starts_with(b"SSSUCAP") {
+        let (buf, _) = parse_capture_payload(content)?;
+        if buf.image.is_some() {
+            return buf.image;
+        }
+        let png = std::path::PathBuf::from(&buf.png_path);
+        let png_full = if png.is_absolute() { png } else { base_dir.join(png) };
+        return image::open(png_full).ok().map(|img| Arc::new(img.to_rgba8()));
+    }
+    image::load_from_memory(content)
+        .ok()
+        .map(|img| Arc::new(img.to_rgba8()))
+}
+
 fn make_bmp_from_capture_buffer(base_dir: &std::path::Path, png_path: &str) -> Option<Vec<u8>> {
     if png_path.trim().is_empty() {
         return None;
     }
     let png = std::path::PathBuf::from(png_path);
     let png_full = if png.is_absolute() { png } else { base_dir.join(png) };
-    if let Ok(img) = image::open(&png_full) {
-        use std::io::Cursor;
-        let rgba = img.to_rgba8();
-        let dyn_img = image::DynamicImage::ImageRgba8(rgba);
-        let mut out = Vec::new();
-        if dyn_img
-            .write_to(&mut Cursor::new(&mut out), image::ImageFormat::Bmp)
-            .is_ok()
-            && out.len() >= 54
-        {
-            return Some(out);
-        }
-    }
-    None
+    image::open(&png_full)
+        .ok()
+        .map(|img| siglus::resource::encode_capture_bmp(&img.to_rgba8()))
 }
 
 
@@ -402,6 +450,7 @@ fn parse_capture_payload_v1(content: &[u8]) -> Option<(HostCaptureBuffer, siglus
             origin_x,
             origin_y,
             png_path,
+            image: None,
         },
         payload,
     ))
@@ -0,0 +1,196 @@
+impl GuiHost {
+    /// Gameexe window number for a script `mwnd_no` (-1 = default window).
+    fn resolve_mwnd_no(&self, mwnd_no: i32) -> i32 {
+        self.mwnd_config.resolve_mwnd_no(mwnd_no)
+    }
+
+    fn mwnd_def(&self, mwnd_no: i32) -> siglus::gameexe::GameexeMwnd {
+        self.mwnd_config.mwnd(mwnd_no).cloned().unwrap_or_default()
+    }
+
+    /// C++ cmd_mwnd.cpp: window state changes; everything else (text flow,
+    /// koe, selection) is driven by the message opcodes.
+    fn apply_mwnd_action(&mut self, mwnd_no: i32, sub: i32, args: &[siglus::vm::Prop]) {
+        use siglus::elm::mwnd::*;
+
+        if mwnd_no < 0 {
+            if matches!(
+                sub,
+                ELM_MWNDLIST_CLOSE | ELM_MWNDLIST_CLOSE_WAIT | ELM_MWNDLIST_CLOSE_NOWAIT
+            ) {
+                let nos: Vec<i32> = self.mwnd_states.keys().copied().collect();
+                for no in nos {
+                    self.update_mwnd_state(no, |st| st.open = false);
+                }
+            }
+            return;
+        }
+
+        let int = |i: usize| args.get(i).and_then(|p| p.as_int());
+        let pair = || match (int(0), int(1)) {
+            (Some(x), Some(y)) => Some((x, y)),
+            _ => None,
+        };
+        let text = || {
+            args.iter().find_map(|p| match &p.value {
+                siglus::vm::PropValue::Str(s) => Some(s.clone()),
+                _ => None,
+            })
+        };
+        match sub {
+            ELM_MWND_OPEN | ELM_MWND_OPEN_WAIT | ELM_MWND_OPEN_NOWAIT => {
+                self.update_mwnd_state(mwnd_no, |st| st.open = true);
+            }
+            ELM_MWND_CLOSE | ELM_MWND_CLOSE_WAIT | ELM_MWND_CLOSE_NOWAIT | ELM_MWND_END_CLOSE => {
+                self.update_mwnd_state(mwnd_no, |st| st.open = false);
+            }
+            ELM_MWND_CLEAR | ELM_MWND__NOVEL_CLEAR => {
+                let _ = self.event_tx.send(HostEvent::MwndClear(mwnd_no));
+            }
+            ELM_MWND_SET_WAKU => {
+                let waku_no = int(0);
+                self.update_mwnd_state(mwnd_no, |st| st.waku_no = waku_no);
+            }
+            ELM_MWND_SET_WAKU_FILE => {
+                let file = text();
+                self.update_mwnd_state(mwnd_no, |st| st.waku_file = file);
+            }
+            ELM_MWND_INIT_WAKU_FILE => self.update_mwnd_state(mwnd_no, |st| st.waku_file = None),
+            ELM_MWND_SET_FILTER_FILE => {
+                let file = text();
+                self.update_mwnd_state(mwnd_no, |st| st.filter_file = file);
+            }
+            ELM_MWND_INIT_FILTER_FILE => {
+                self.update_mwnd_state(mwnd_no, |st| st.filter_file = None)
+            }
+            ELM_MWND_SET_FACE => {
+                // set_face(file[, face_no]); the face number may come first.
+                let face = text().map(|file| (file, int(1).or(int(0)).unwrap_or(0)));
+                self.update_mwnd_state(mwnd_no, |st| st.face = face);
+            }
+            ELM_MWND_CLEAR_FACE => self.update_mwnd_state(mwnd_no, |st| st.face = None),
+            ELM_MWND_SET_WINDOW_POS => {
+                let v = pair();
+                self.update_mwnd_state(mwnd_no, |st| st.window_pos = v);
+            }
+            ELM_MWND_SET_WINDOW_SIZE => {
+                let v = pair();
+                self.update_mwnd_state(mwnd_no, |st| st.window_size = v);
+            }
+            ELM_MWND_SET_WINDOW_MOJI_CNT => {
+                let v = pair();
+                self.update_mwnd_state(mwnd_no, |st| st.moji_cnt = v);
+            }
+            ELM_MWND_INIT_WINDOW_POS => self.update_mwnd_state(mwnd_no, |st| st.window_pos = None),
+            ELM_MWND_INIT_WINDOW_SIZE => {
+                self.update_mwnd_state(mwnd_no, |st| st.window_size = None)
+            }
+            ELM_MWND_INIT_WINDOW_MOJI_CNT => {
+                self.update_mwnd_state(mwnd_no, |st| st.moji_cnt = None)
+            }
+            _ => {}
+        }
+    }
+
+    fn mwnd_get(&self, mwnd_no: i32, sub: i32) -> i32 {
+        use siglus::elm::mwnd::*;
+
+        let st = self.mwnd_states.get(&mwnd_no).cloned().unwrap_or_default();
+        let def = self.mwnd_def(mwnd_no);
+        match sub {
+            ELM_MWND_CHECK_OPEN => i32::from(st.open),
+            ELM_MWND_GET_WINDOW_POS_X => st.window_pos.unwrap_or(def.window_pos).0,
+            ELM_MWND_GET_WINDOW_POS_Y => st.window_pos.unwrap_or(def.window_pos).1,
+            ELM_MWND_GET_WINDOW_SIZE_X => st.window_size.unwrap_or(def.window_size).0,
+            ELM_MWND_GET_WINDOW_SIZE_Y => st.window_size.unwrap_or(def.window_size).1,
+            ELM_MWND_GET_WINDOW_MOJI_CNT_X => st.moji_cnt.unwrap_or(def.moji_cnt).0,
+            ELM_MWND_GET_WINDOW_MOJI_CNT_Y => st.moji_cnt.unwrap_or(def.moji_cnt).1,
+            ELM_MWND_GET_OPEN_ANIME_TYPE | ELM_MWND_GET_DEFAULT_OPEN_ANIME_TYPE => {
+                def.open_anime_type
+            }
+            ELM_MWND_GET_OPEN_ANIME_TIME | ELM_MWND_GET_DEFAULT_OPEN_ANIME_TIME => {
+                def.open_anime_time
+            }
+            ELM_MWND_GET_CLOSE_ANIME_TYPE | ELM_MWND_GET_DEFAULT_CLOSE_ANIME_TYPE => {
+                def.close_anime_type
+            }
+            ELM_MWND_GET_CLOSE_ANIME_TIME | ELM_MWND_GET_DEFAULT_CLOSE_ANIME_TIME => {
+                def.close_anime_time
+            }
+            _ => 0,
+        }
+    }
+
+    fn update_mwnd_state(&mut self, mwnd_no: i32, f: impl FnOnce(&mut MwndState)) {
+        let st = self.mwnd_states.entry(mwnd_no).or_default();
+        let before = st.clone();
+        f(st);
+        if *st == before {
+            return;
+        }
+        let state = st.clone();
+        let _ = self.event_tx.send(HostEvent::MwndState { mwnd_no, state });
+        self.sync_mwnd_images(mwnd_no);
+    }
+
+    /// Load the waku/filter/name-waku/face images the window needs now and send
+    /// the ones that changed.
+    fn sync_mwnd_images(&mut self, mwnd_no: i32) {
+        let st = self.mwnd_states.get(&mwnd_no).cloned().unwrap_or_default();
+        let def = self.mwnd_def(mwnd_no);
+        let waku = self
+            .mwnd_config
+            .waku(st.waku_no.unwrap_or(def.waku_no))
+            .cloned()
+            .unwrap_or_default();
+        let name_waku = self
+            .mwnd_config
+            .waku(def.name_waku_no)
+            .map(|w| w.waku_file.clone())
+            .unwrap_or_default();
+        let wanted = [
+            (
+                MwndImageSlot::Waku,
+                st.waku_file.clone().unwrap_or(waku.waku_file),
+            ),
+            (
+                MwndImageSlot::Filter,
+                st.filter_file.clone().unwrap_or(waku.filter_file),
+            ),
+            (MwndImageSlot::NameWaku, name_waku),
+            (
+                MwndImageSlot::Face,
+                st.face.map(|(file, _)| file).unwrap_or_default(),
+            ),
+        ];
+        for (slot, file) in wanted {
+            let key = (mwnd_no, slot);
+            if self
+                .mwnd_image_files
+                .get(&key)
+                .map(String::as_str)
+                .unwrap_or("")
+                == file
+            {
+                continue;
+            }
+            let image = if file.is_empty() {
+                None
+            } else {
+                match load_stage_like_cpp(&self.base_dir, &self.append_dirs, &file, 0) {
+                    Ok(img) => Some(Arc::new(img)),
+                    Err(err) => {
+                        warn!("mwnd[{mwnd_no}] {slot:?} image: {err:#}");
+                        None
+                    }
+                }
+            };
+            self.mwnd_image_files.insert(key, file);
+            let _ = self.event_tx.send(HostEvent::MwndImage {
+                mwnd_no,
+                slot,
+                image,
+            });
+        }
+    }
+}
@@ -0,0 +1,192 @@
+/// Shortest VM clock gap between two group button look updates.
+const GROUP_LOOKS_FRAME_MS: u64 = 16;
+
+impl GuiHost {
+    /// Button `button_no` of `group_idx`, if an object on `plane` is one.
+    fn group_button(
+        &self,
+        plane: StagePlane,
+        group_idx: i32,
+        button_no: i32,
+    ) -> Option<ObjectButtonState> {
+        self.objects.keys().find_map(|&(p, idx)| {
+            if p != plane {
+                return None;
+            }
+            let btn = self.get_object_button_state(p, idx);
+            (btn.group_no == group_idx && btn.button_no == button_no).then_some(btn)
+        })
+    }
+
+    /// Show `hit` as hit (or pushed) and the group's other buttons as normal,
+    /// playing the `#BUTTON.SE` of buttons the cursor enters or presses.
+    ///
+    /// C++ reference: elm_object.cpp button frame update (hit/push SE on state change).
+    fn update_group_button_looks(
+        &mut self,
+        plane: StagePlane,
+        group_idx: i32,
+        hit: Option<i32>,
+        pushed: bool,
+    ) {
+        let buttons: Vec<(i32, ObjectButtonState)> = self
+            .objects
+            .keys()
+            .filter(|(p, _)| *p == plane)
+            .map(|&(p, idx)| (idx, self.get_object_button_state(p, idx)))
+            .filter(|(_, btn)| btn.group_no == group_idx && btn.button_no >= 0)
+            .collect();
+        let mut sounds: Vec<(i32, fn(&siglus::gameexe::GameexeButtonSe) -> i32)> = Vec::new();
+        for (idx, btn) in buttons {
+            let hit_state = match hit {
+                Some(no) if no == btn.button_no && pushed => BUTTON_HIT_STATE_PUSH,
+                Some(no) if no == btn.button_no => BUTTON_HIT_STATE_HIT,
+                _ => BUTTON_HIT_STATE_NONE,
+            };
+            if hit_state == btn.hit_state {
+                continue;
+            }
+            // Buttons sharing a number sound once.
+            if !sounds.iter().any(|(se_no, _)| *se_no == btn.se_no) {
+                match hit_state {
+                    BUTTON_HIT_STATE_PUSH => sounds.push((btn.se_no, |se| se.push)),
+                    BUTTON_HIT_STATE_HIT if btn.hit_state == BUTTON_HIT_STATE_NONE => {
+                        sounds.push((btn.se_no, |se| se.hit))
+                    }
+                    _ => {}
+                }
+            }
+            self.update_object_button_state(plane, idx, |b| b.hit_state = hit_state);
+        }
+        for (se_no, pick) in sounds {
+            self.play_button_se(se_no, pick);
+        }
+    }
+
+    /// Show the cursor's button in every active group, once per frame, so
+    /// groups a script polls (instead of waiting on) still react to it.
+    fn tick_group_button_looks(&mut self) {
+        let now = siglus::vm::Host::clock(self).now_ms();
+        if now.saturating_sub(self.group_looks_ms) < GROUP_LOOKS_FRAME_MS {
+            return;
+        }
+        self.group_looks_ms = now;
+        let Some((cursor, down)) = self.input_state.lock().ok().map(|state| {
+            (
+                (state.mouse_x as f32, state.mouse_y as f32),
+                state.mouse_left.is_down,
+            )
+        }) else {
+            return;
+        };
+        let active: Vec<((StagePlane, i32), i32)> = self
+            .groups
+            .iter()
+            .filter(|(_, st)| st.active && st.result < 0)
+            .map(|(key, st)| (*key, st.press_keep_button_no))
+            .collect();
+        for ((plane, group_idx), keep) in active {
+            let hit = if down && keep >= 0 {
+                Some(keep)
+            } else {
+                self.group_hit_candidate_button(plane, group_idx, cursor)
+            };
+            self.update_group_button_looks(plane, group_idx, hit, down && hit.is_some());
+        }
+    }
+
+    /// Play one sound of the `#BUTTON.SE` entry of a group's button.
+    fn play_group_button_se(
+        &mut self,
+        plane: StagePlane,
+        group_idx: i32,
+        button_no: i32,
+        pick: fn(&siglus::gameexe::GameexeButtonSe) -> i32,
+    ) {
+        if let Some(btn) = self.group_button(plane, group_idx, button_no) {
+            self.play_button_se(btn.se_no, pick);
+        }
+    }
+
+    fn play_button_se(&mut self, se_no: i32, pick: fn(&siglus::gameexe::GameexeButtonSe) -> i32) {
+        let Some(sys_se_no) = self.button_config.se(se_no).map(pick) else {
+            return;
+        };
+        self.play_sys_se(sys_se_no);
+    }
+
+    /// Play `#SE.NNN`, falling back to the cancel SE name lookup.
+    fn play_sys_se(&mut self, sys_se_no: i32) {
+        if sys_se_no < 0 {
+            return;
+        }
+        match self.button_config.se_file(sys_se_no) {
+            Some(name) => {
+                let _ = self.event_tx.send(HostEvent::PlaySe {
+                    name: name.to_string(),
+                });
+            }
+            None => self.play_cancel_se(sys_se_no),
+        }
+    }
+
+    /// Resolve `#SELBTN` template `template_no` (template 0 past the defined
+    /// ones) with the item looks of its `#BTNSELITEM` button action.
+    ///
+    /// C++ reference: elm_sel_btn.cpp `C_elm_sel_btn::create_sel_btn`.
+    fn resolve_selbtn_template(&self, template_no: i32) -> Option<SelBtnTemplate> {
+        let template_no = if self.button_config.selbtn(template_no).is_some() {
+            template_no
+        } else {
+            0
+        };
+        let layout = self.button_config.selbtn(template_no)?.clone();
+        let item = self
+            .button_config
+            .sel_item(template_no)
+            .copied()
+            .unwrap_or_default();
+        let action = self
+            .button_config
+            .action(item.action_no)
+            .copied()
+            .unwrap_or_default();
+
+        let mut images: BTreeMap<i32, Option<Arc<image::DynamicImage>>> = BTreeMap::new();
+        let mut looks: [Option<SelBtnLook>; 5] = Default::default();
+        if !layout.base_file.is_empty() {
+            for (look, pat) in looks.iter_mut().zip(action.pats.iter()) {
+                let image = images
+                    .entry(pat.pat_no)
+                    .or_insert_with(|| {
+                        match self.load_stage_cut(&layout.base_file, pat.pat_no.max(0) as usize) {
+                            Ok(img) => Some(Arc::new(img)),
+                            Err(err) => {
+                                warn!("selbtn[{template_no}] pat {}: {err:#}", pat.pat_no);
+                                None
+                            }
+                        }
+                    })
+                    .clone();
+                *look = image.map(|image| SelBtnLook {
+                    image,
+                    offset: (pat.pos.0 as f32, pat.pos.1 as f32),
+                    alpha: pat.tr.clamp(0, 255) as f32 / 255.0,
+                });
+            }
+        }
+
+        let se = self
+            .button_config
+            .se(item.se_no)
+            .copied()
+            .unwrap_or_default();
+        let se_file = |no: i32| self.button_config.se_file(no).map(str::to_string);
+        Some(SelBtnTemplate {
+            layout,
+            looks,
+            hit_se: se_file(se.hit),
+            decide_se: se_file(se.decide),
+        })
+    }
+}
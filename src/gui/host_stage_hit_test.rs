@@ -6,35 +6,46 @@ impl GuiHost {
         cursor: (f32, f32),
     ) -> Option<i32> {
         self.objects
-            .iter()
-            .filter_map(|((p, idx), obj)| {
-                if *p != plane || !obj.visible {
+            .keys()
+            .filter_map(|&(p, idx)| {
+                if p != plane {
                     return None;
                 }
-                let btn = self.get_object_button_state(*p, *idx);
+                // Children are tested in stage space through their parents.
+                let obj = self.world_object_state(p, idx)?;
+                if !obj.visible {
+                    return None;
+                }
+                let btn = self.get_object_button_state(p, idx);
                 if btn.group_no != group_idx || btn.button_no < 0 {
                     return None;
                 }
                 if btn.state == 2 || btn.real_state == 2 {
                     return None;
                 }
-                let local = self.screen_to_object_local(obj, cursor)?;
-                if !self.local_in_object_clip(obj, local) {
+                if let Some((left, top, right, bottom)) = self.ancestor_clip(p, idx)
+                    && (cursor.0 < left || cursor.0 >= right || cursor.1 < top || cursor.1 >= bottom)
+                {
+                    return None;
+                }
+                let local = self.screen_to_object_local(&obj, cursor)?;
+                if !self.local_in_object_clip(&obj, local) {
                     return None;
                 }
-                if btn.alpha_test != 0 && !self.object_alpha_test_hit(obj, local) {
+                if btn.alpha_test != 0 && !self.object_alpha_test_hit(&obj, local) {
                     return None;
                 }
                 let dx = obj.x - cursor.0;
                 let dy = obj.y - cursor.1;
                 let dist2 = dx * dx + dy * dy;
-                Some((btn.button_no, obj.layer, obj.order, obj.seq, dist2))
+                Some((btn.button_no, self.object_sort_path(p, idx), dist2))
             })
             .max_by(|a, b| {
-                // C++ draw-order parity: higher layer/order/seq on top; tie uses nearer cursor.
-                (a.1, a.2, a.3, -(a.4 as i64)).cmp(&(b.1, b.2, b.3, -(b.4 as i64)))
+                // C++ draw-order parity: higher layer/order/seq on top (children above
+                // their parent); tie uses nearer cursor.
+                (&a.1, -(a.2 as i64)).cmp(&(&b.1, -(b.2 as i64)))
             })
-            .map(|(button_no, _, _, _, _)| button_no)
+            .map(|(button_no, _, _)| button_no)
     }
 
     fn screen_to_object_local(&self, obj: &HostObjectState, cursor: (f32, f32)) -> Option<(f32, f32)> {
@@ -79,16 +90,10 @@ impl GuiHost {
             return false;
         }
 
-        let Ok(img) = load_stage_like_cpp(
-            &self.base_dir,
-            &self.append_dirs,
-            &obj.file_name,
-            obj.pat_no,
-        ) else {
+        let Ok(img) = self.load_stage_cut(&obj.file_name, obj.pat_no) else {
             return false;
         };
-        let rgba = img.to_rgba8();
-        let (w, h) = rgba.dimensions();
+        let (w, h) = (img.width(), img.height());
         if w == 0 || h == 0 {
             return false;
         }
@@ -96,7 +101,7 @@ impl GuiHost {
         let (sample_x, sample_y) = self.local_to_source_sample(obj, local, w as f32, h as f32);
         let px = sample_to_pixel_index(sample_x, w);
         let py = sample_to_pixel_index(sample_y, h);
-        rgba.get_pixel(px, py)[3] > 0
+        image::GenericImageView::get_pixel(&img, px, py)[3] > 0
     }
 
     fn local_to_source_sample(
@@ -4,3 +4,4 @@ include!("host_stage_object_movie_reset.rs");
 include!("host_stage_object_cmd.rs");
 include!("host_stage_object_cmd_post_render.rs");
 include!("host_stage_object_assign.rs");
+include!("host_stage_object_tree.rs");
@@ -68,6 +68,7 @@ impl GuiHost {
                     x: v as f32,
                     y,
                 });
+                self.refresh_masked_object_image(plane, object_index);
             }
             x if x == siglus::elm::objectlist::ELM_OBJECT_Y => {
                 let x = {
@@ -81,35 +82,66 @@ impl GuiHost {
                     x,
                     y: v as f32,
                 });
+                self.refresh_masked_object_image(plane, object_index);
             }
             x if x == siglus::elm::objectlist::ELM_OBJECT_CENTER_X => {
                 let state = self.get_or_create_object_state(plane, object_index);
                 state.center_x = v as f32;
                 self.emit_object_render_state(plane, object_index);
+                self.refresh_masked_object_image(plane, object_index);
             }
             x if x == siglus::elm::objectlist::ELM_OBJECT_CENTER_Y => {
                 let state = self.get_or_create_object_state(plane, object_index);
                 state.center_y = v as f32;
                 self.emit_object_render_state(plane, object_index);
+                self.refresh_masked_object_image(plane, object_index);
+            }
+            x if x == siglus::elm::objectlist::ELM_OBJECT_CENTER_REP_X => {
+                let state = self.get_or_create_object_state(plane, object_index);
+                state.center_rep_x = v as f32;
+                self.emit_object_render_state(plane, object_index);
+            }
+            x if x == siglus::elm::objectlist::ELM_OBJECT_CENTER_REP_Y => {
+                let state = self.get_or_create_object_state(plane, object_index);
+                state.center_rep_y = v as f32;
+                self.emit_object_render_state(plane, object_index);
             }
             x if x == siglus::elm::objectlist::ELM_OBJECT_SCALE_X => {
                 let state = self.get_or_create_object_state(plane, object_index);
                 state.scale_x = v as f32 / 1000.0;
                 self.emit_object_render_state(plane, object_index);
+                self.refresh_masked_object_image(plane, object_index);
             }
             x if x == siglus::elm::objectlist::ELM_OBJECT_SCALE_Y => {
                 let state = self.get_or_create_object_state(plane, object_index);
                 state.scale_y = v as f32 / 1000.0;
                 self.emit_object_render_state(plane, object_index);
+                self.refresh_masked_object_image(plane, object_index);
             }
             x if x == siglus::elm::objectlist::ELM_OBJECT_ROTATE_Z => {
                 let state = self.get_or_create_object_state(plane, object_index);
                 state.rotate_z_deg = v as f32 / 10.0;
                 self.emit_object_render_state(plane, object_index);
+                self.refresh_masked_object_image(plane, object_index);
             }
-            x if x == siglus::elm::objectlist::ELM_OBJECT_ALPHA_BLEND => {
+            x if x == siglus::elm::objectlist::ELM_OBJECT_ROTATE_X => {
                 let state = self.get_or_create_object_state(plane, object_index);
-                state.alpha_blend = v != 0;
+                state.rotate_x_deg = v as f32 / 10.0;
+                self.emit_object_render_state(plane, object_index);
+            }
+            x if x == siglus::elm::objectlist::ELM_OBJECT_ROTATE_Y => {
+                let state = self.get_or_create_object_state(plane, object_index);
+                state.rotate_y_deg = v as f32 / 10.0;
+                self.emit_object_render_state(plane, object_index);
+            }
+            x if x == siglus::elm::objectlist::ELM_OBJECT_Z => {
+                let state = self.get_or_create_object_state(plane, object_index);
+                state.z = v as f32;
+                self.emit_object_render_state(plane, object_index);
+            }
+            x if x == siglus::elm::objectlist::ELM_OBJECT_WORLD => {
+                let state = self.get_or_create_object_state(plane, object_index);
+                state.world = v;
                 self.emit_object_render_state(plane, object_index);
             }
             x if x == siglus::elm::objectlist::ELM_OBJECT_TR => {
@@ -222,6 +254,22 @@ impl GuiHost {
                 state.reverse = v != 0;
                 self.refresh_object_image(plane, object_index);
             }
+            x if x == siglus::elm::objectlist::ELM_OBJECT_MASK_NO => {
+                let state = self.get_or_create_object_state(plane, object_index);
+                state.mask_no = v;
+                self.refresh_object_image(plane, object_index);
+            }
+            x if x == siglus::elm::objectlist::ELM_OBJECT_TONECURVE_NO => {
+                let state = self.get_or_create_object_state(plane, object_index);
+                state.tonecurve_no = v;
+                self.refresh_object_image(plane, object_index);
+            }
+            x if x == siglus::elm::objectlist::ELM_OBJECT_BLEND => {
+                let state = self.get_or_create_object_state(plane, object_index);
+                state.blend = siglus::render::RenderBlend::from_script(v);
+                self.refresh_object_image(plane, object_index);
+                self.emit_object_render_state(plane, object_index);
+            }
             _ => {}
         }
     }
@@ -205,10 +205,25 @@ impl GuiHost {
         };
 
         match cmd {
+            // Mesh models are out of scope: there is no loader for the mesh
+            // format, so the object stays empty rather than showing the file
+            // as a sprite.
+            x if x == siglus::elm::objectlist::ELM_OBJECT_CREATE_MESH => {
+                self.reset_object_runtime_state_for_create(plane, object_index);
+                if let Some(file_name) = arg_str(0) {
+                    log::warn!(
+                        "create_mesh {:?} stage={:?} index={}: mesh objects are not supported",
+                        file_name,
+                        plane,
+                        object_index
+                    );
+                    let state = self.get_or_create_object_state(plane, object_index);
+                    state.file_name = file_name.to_owned();
+                }
+            }
             x if x == siglus::elm::objectlist::ELM_OBJECT_CREATE
                 || x == siglus::elm::objectlist::ELM_OBJECT_CREATE_NUMBER
                 || x == siglus::elm::objectlist::ELM_OBJECT_CREATE_WEATHER
-                || x == siglus::elm::objectlist::ELM_OBJECT_CREATE_MESH
                 || x == siglus::elm::objectlist::ELM_OBJECT_CREATE_BILLBOARD =>
             {
                 self.reset_object_runtime_state_for_create(plane, object_index);
@@ -228,13 +243,24 @@ impl GuiHost {
                     state.x = pos_x;
                     state.y = pos_y;
                     state.pat_no = pat_no;
+                    state.billboard = x == siglus::elm::objectlist::ELM_OBJECT_CREATE_BILLBOARD;
+                    if state.billboard {
+                        self.emit_object_render_state(plane, object_index);
+                    }
+                    if x == siglus::elm::objectlist::ELM_OBJECT_CREATE_NUMBER {
+                        // Shows 0 from the digit cuts until set_number.
+                        self.set_object_number_state(plane, object_index, 0);
+                    }
                     self.emit_object_sort_and_visibility(plane, object_index);
                     self.emit_object_image(plane, object_index);
+                    if x == siglus::elm::objectlist::ELM_OBJECT_CREATE_WEATHER {
+                        // Snow with default parameters until set_weather_param_type_a/b.
+                        self.set_object_weather_state(plane, object_index, ObjectWeatherState::default());
+                        self.emit_object_weather(plane, object_index);
+                    }
                 }
             }
             x if x == siglus::elm::objectlist::ELM_OBJECT_CREATE_STRING
-                || x == siglus::elm::objectlist::ELM_OBJECT_CREATE_SAVE_THUMB
-                || x == siglus::elm::objectlist::ELM_OBJECT_CREATE_CAPTURE_THUMB
                 || x == siglus::elm::objectlist::ELM_OBJECT_CREATE_RECT =>
             {
                 self.reset_object_runtime_state_for_create(plane, object_index);
@@ -249,7 +275,7 @@ impl GuiHost {
                     self.emit_generated_object_image(
                         plane,
                         object_index,
-                        Self::build_string_raster_image(
+                        self.build_string_raster_image(
                             &text,
                             &self.get_object_string_style_state(plane, object_index),
                         ),
@@ -264,16 +290,15 @@ impl GuiHost {
                         object_index,
                         Self::build_rect_image(args),
                     );
-                    return;
                 }
-                self.clear_object_string_state(plane, object_index);
-                self.apply_create_tail_disp_xy_pat(plane, object_index, 1, 2, 3, None, args);
-                self.refresh_object_image(plane, object_index);
             }
-            x if x == siglus::elm::objectlist::ELM_OBJECT_CREATE_CAPTURE => {
-                self.reset_object_runtime_state_for_create(plane, object_index);
-                self.apply_create_tail_disp_xy_pat(plane, object_index, 0, 1, 2, None, args);
-                self.refresh_object_image(plane, object_index);
+            x if x == siglus::elm::objectlist::ELM_OBJECT_CREATE_SAVE_THUMB
+                || x == siglus::elm::objectlist::ELM_OBJECT_CREATE_CAPTURE_THUMB
+                || x == siglus::elm::objectlist::ELM_OBJECT_CREATE_CAPTURE
+                || x == siglus::elm::objectlist::ELM_OBJECT_CREATE_FROM_CAPTURE_FILE =>
+            {
+                // The VM resolves the image (`on_object_create_from_capture`).
+                self.create_object_from_capture(plane, object_index, x, args, None);
             }
             x if x == siglus::elm::objectlist::ELM_OBJECT_CREATE_EMOTE => {
                 self.reset_object_runtime_state_for_create(plane, object_index);
@@ -359,15 +384,26 @@ impl GuiHost {
                     args.first().and_then(|p| p.as_int()).unwrap_or(0) as f32,
                     args.get(1).and_then(|p| p.as_int()).unwrap_or(0) as f32,
                 );
+                let z = args.get(2).and_then(|p| p.as_int());
                 let state = self.get_or_create_object_state(plane, object_index);
                 state.x = x;
                 state.y = y;
+                if let Some(z) = z {
+                    state.z = z as f32;
+                    self.emit_object_render_state(plane, object_index);
+                }
                 let _ = self.event_tx.send(HostEvent::SetObjectPos {
                     stage: plane,
                     index: object_index,
                     x,
                     y,
                 });
+                self.refresh_masked_object_image(plane, object_index);
+            }
+            x if x == siglus::elm::objectlist::ELM_OBJECT_Z => {
+                let state = self.get_or_create_object_state(plane, object_index);
+                state.z = args.first().and_then(|p| p.as_int()).unwrap_or(0) as f32;
+                self.emit_object_render_state(plane, object_index);
             }
             x if x == siglus::elm::objectlist::ELM_OBJECT_X => {
                 let x = args.first().and_then(|p| p.as_int()).unwrap_or(0) as f32;
@@ -382,6 +418,7 @@ impl GuiHost {
                     x,
                     y,
                 });
+                self.refresh_masked_object_image(plane, object_index);
             }
             x if x == siglus::elm::objectlist::ELM_OBJECT_Y => {
                 let y = args.first().and_then(|p| p.as_int()).unwrap_or(0) as f32;
@@ -396,6 +433,7 @@ impl GuiHost {
                     x,
                     y,
                 });
+                self.refresh_masked_object_image(plane, object_index);
             }
             x if x == siglus::elm::objectlist::ELM_OBJECT_DISP => {
                 let visible = args.first().and_then(|p| p.as_int()).unwrap_or(1) != 0;
@@ -425,6 +463,7 @@ impl GuiHost {
                 state.scale_x = sx;
                 state.scale_y = sy;
                 self.emit_object_render_state(plane, object_index);
+                self.refresh_masked_object_image(plane, object_index);
             }
             x if x == siglus::elm::objectlist::ELM_OBJECT_INIT_PARAM => {
                 let st = self.get_or_create_object_state(plane, object_index);
@@ -451,15 +490,6 @@ impl GuiHost {
                     }
                 }
             }
-            x if x == siglus::elm::objectlist::ELM_OBJECT_CREATE_FROM_CAPTURE_FILE => {
-                // Treat these creation commands as lifecycle reset points.
-                let st = self.get_or_create_object_state(plane, object_index);
-                reset_object_state_preserve_seq(st);
-                let _ = self.event_tx.send(HostEvent::RemoveObject {
-                    stage: plane,
-                    index: object_index,
-                });
-            }
             x if x == siglus::elm::objectlist::ELM_OBJECT_SET_STRING => {
                 let text = args
                     .first()
@@ -471,7 +501,7 @@ impl GuiHost {
                 self.emit_generated_object_image(
                     plane,
                     object_index,
-                    Self::build_string_raster_image(&text, &style),
+                    self.build_string_raster_image(&text, &style),
                 );
             }
             x if x == siglus::elm::objectlist::ELM_OBJECT_SET_STRING_PARAM => {
@@ -490,18 +520,17 @@ impl GuiHost {
                 self.emit_generated_object_image(
                     plane,
                     object_index,
-                    Self::build_string_raster_image(&text, &style),
+                    self.build_string_raster_image(&text, &style),
                 );
             }
             x if x == siglus::elm::objectlist::ELM_OBJECT_SET_NUMBER => {
+                // Only `create_number` objects take a value.
+                if self.get_object_number_value(plane, object_index).is_none() {
+                    return;
+                }
                 let num = args.first().and_then(|p| p.as_int()).unwrap_or(0);
                 self.set_object_number_state(plane, object_index, num);
-                let nstyle = self.get_object_number_style_state(plane, object_index);
-                self.emit_generated_object_image(
-                    plane,
-                    object_index,
-                    Self::build_number_raster_image(num, &nstyle),
-                );
+                self.emit_number_object_image(plane, object_index);
             }
             x if x == siglus::elm::objectlist::ELM_OBJECT_SET_NUMBER_PARAM => {
                 let nstyle = ObjectNumberStyleState {
@@ -512,13 +541,10 @@ impl GuiHost {
                     space_mod: args.get(4).and_then(|p| p.as_int()).unwrap_or(0),
                     space: args.get(5).and_then(|p| p.as_int()).unwrap_or(0),
                 };
-                self.set_object_number_style_state(plane, object_index, nstyle.clone());
-                let num = self.get_object_number_state(plane, object_index);
-                self.emit_generated_object_image(
-                    plane,
-                    object_index,
-                    Self::build_number_raster_image(num, &nstyle),
-                );
+                self.set_object_number_style_state(plane, object_index, nstyle);
+                if self.get_object_number_value(plane, object_index).is_some() {
+                    self.emit_number_object_image(plane, object_index);
+                }
             }
             x if x == siglus::elm::objectlist::ELM_OBJECT_SET_CENTER => {
                 let cx = args.first().and_then(|p| p.as_int()).unwrap_or(0) as f32;
@@ -527,12 +553,51 @@ impl GuiHost {
                 state.center_x = cx;
                 state.center_y = cy;
                 self.emit_object_render_state(plane, object_index);
+                self.refresh_masked_object_image(plane, object_index);
+            }
+            x if x == siglus::elm::objectlist::ELM_OBJECT_SET_CENTER_REP => {
+                let cx = args.first().and_then(|p| p.as_int()).unwrap_or(0) as f32;
+                let cy = args.get(1).and_then(|p| p.as_int()).unwrap_or(0) as f32;
+                let state = self.get_or_create_object_state(plane, object_index);
+                state.center_rep_x = cx;
+                state.center_rep_y = cy;
+                self.emit_object_render_state(plane, object_index);
+            }
+            x if x == siglus::elm::objectlist::ELM_OBJECT_CENTER_REP_X => {
+                let state = self.get_or_create_object_state(plane, object_index);
+                state.center_rep_x = args.first().and_then(|p| p.as_int()).unwrap_or(0) as f32;
+                self.emit_object_render_state(plane, object_index);
+            }
+            x if x == siglus::elm::objectlist::ELM_OBJECT_CENTER_REP_Y => {
+                let state = self.get_or_create_object_state(plane, object_index);
+                state.center_rep_y = args.first().and_then(|p| p.as_int()).unwrap_or(0) as f32;
+                self.emit_object_render_state(plane, object_index);
             }
             x if x == siglus::elm::objectlist::ELM_OBJECT_SET_ROTATE => {
+                let rx = args.first().and_then(|p| p.as_int()).unwrap_or(0) as f32 / 10.0;
+                let ry = args.get(1).and_then(|p| p.as_int()).unwrap_or(0) as f32 / 10.0;
                 let rz = args.get(2).and_then(|p| p.as_int()).unwrap_or(0) as f32 / 10.0;
                 let state = self.get_or_create_object_state(plane, object_index);
+                state.rotate_x_deg = rx;
+                state.rotate_y_deg = ry;
                 state.rotate_z_deg = rz;
                 self.emit_object_render_state(plane, object_index);
+                self.refresh_masked_object_image(plane, object_index);
+            }
+            x if x == siglus::elm::objectlist::ELM_OBJECT_ROTATE_X => {
+                let state = self.get_or_create_object_state(plane, object_index);
+                state.rotate_x_deg = args.first().and_then(|p| p.as_int()).unwrap_or(0) as f32 / 10.0;
+                self.emit_object_render_state(plane, object_index);
+            }
+            x if x == siglus::elm::objectlist::ELM_OBJECT_ROTATE_Y => {
+                let state = self.get_or_create_object_state(plane, object_index);
+                state.rotate_y_deg = args.first().and_then(|p| p.as_int()).unwrap_or(0) as f32 / 10.0;
+                self.emit_object_render_state(plane, object_index);
+            }
+            x if x == siglus::elm::objectlist::ELM_OBJECT_WORLD => {
+                let state = self.get_or_create_object_state(plane, object_index);
+                state.world = args.first().and_then(|p| p.as_int()).unwrap_or(-1);
+                self.emit_object_render_state(plane, object_index);
             }
             x if x == siglus::elm::objectlist::ELM_OBJECT_SET_CLIP => {
                 let state = self.get_or_create_object_state(plane, object_index);
@@ -562,11 +627,6 @@ impl GuiHost {
                 state.src_clip_use = args.first().and_then(|p| p.as_int()).unwrap_or(1) != 0;
                 self.emit_object_render_state(plane, object_index);
             }
-            x if x == siglus::elm::objectlist::ELM_OBJECT_ALPHA_BLEND => {
-                let state = self.get_or_create_object_state(plane, object_index);
-                state.alpha_blend = args.first().and_then(|p| p.as_int()).unwrap_or(1) != 0;
-                self.emit_object_render_state(plane, object_index);
-            }
             x if x == siglus::elm::objectlist::ELM_OBJECT_TR => {
                 let state = self.get_or_create_object_state(plane, object_index);
                 let v = args.first().and_then(|p| p.as_int()).unwrap_or(255) as f32;
@@ -633,6 +693,29 @@ impl GuiHost {
                 state.reverse = args.first().and_then(|p| p.as_int()).unwrap_or(0) != 0;
                 self.refresh_object_image(plane, object_index);
             }
+            x if x == siglus::elm::objectlist::ELM_OBJECT_MASK_NO => {
+                let state = self.get_or_create_object_state(plane, object_index);
+                state.mask_no = args.first().and_then(|p| p.as_int()).unwrap_or(-1);
+                self.refresh_object_image(plane, object_index);
+            }
+            x if x == siglus::elm::objectlist::ELM_OBJECT_SET_CHILD_SORT_TYPE_DEFAULT => {
+                self.set_object_child_sort_test(plane, object_index, false);
+            }
+            x if x == siglus::elm::objectlist::ELM_OBJECT_SET_CHILD_SORT_TYPE_TEST => {
+                self.set_object_child_sort_test(plane, object_index, true);
+            }
+            x if x == siglus::elm::objectlist::ELM_OBJECT_TONECURVE_NO => {
+                let state = self.get_or_create_object_state(plane, object_index);
+                state.tonecurve_no = args.first().and_then(|p| p.as_int()).unwrap_or(-1);
+                self.refresh_object_image(plane, object_index);
+            }
+            x if x == siglus::elm::objectlist::ELM_OBJECT_BLEND => {
+                let state = self.get_or_create_object_state(plane, object_index);
+                let v = args.first().and_then(|p| p.as_int()).unwrap_or(0);
+                state.blend = siglus::render::RenderBlend::from_script(v);
+                self.refresh_object_image(plane, object_index);
+                self.emit_object_render_state(plane, object_index);
+            }
             x if x == siglus::elm::objectlist::ELM_OBJECT_ORDER => {
                 if let Some(order) = args.first().and_then(|p| p.as_int()) {
                     let (order_v, layer_v, seq_v) = {
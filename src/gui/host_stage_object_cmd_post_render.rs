@@ -80,6 +80,7 @@ impl GuiHost {
                 self.clear_object_button_state(plane, object_index);
                 self.clear_object_weather_state(plane, object_index);
                 self.clear_object_movie_seek_state(plane, object_index);
+                self.free_object_children(plane, object_index);
                 let _ = self.event_tx.send(HostEvent::SetObjectVisible {
                     stage: plane,
                     index: object_index,
@@ -200,62 +201,63 @@ impl GuiHost {
             x if x == siglus::elm::objectlist::ELM_OBJECT_SET_WEATHER_PARAM_TYPE_A
                 || x == siglus::elm::objectlist::ELM_OBJECT_SET_WEATHER_PARAM_TYPE_B =>
             {
-                let mut raw = Vec::with_capacity(args.len());
+                let mut params = if x == siglus::elm::objectlist::ELM_OBJECT_SET_WEATHER_PARAM_TYPE_A {
+                    siglus::render::WeatherParams::type_a()
+                } else {
+                    siglus::render::WeatherParams::type_b()
+                };
                 for a in args {
-                    raw.push(a.as_int().unwrap_or(0));
+                    params.set_named(a.id, a.as_int().unwrap_or(0));
                 }
-                self.set_object_weather_state(
-                    plane,
-                    object_index,
-                    ObjectWeatherState {
-                        last_type: cmd,
-                        params: raw,
-                    },
-                );
+                self.set_object_weather_state(plane, object_index, ObjectWeatherState { params });
+                self.emit_object_weather(plane, object_index);
             }
             x if x == siglus::elm::objectlist::ELM_OBJECT_CLEAR_BUTTON => {
+                let before = self.object_button_look(plane, object_index);
                 self.clear_object_button_state(plane, object_index);
+                self.refresh_object_button_look(plane, object_index, before);
             }
             x if x == siglus::elm::objectlist::ELM_OBJECT_SET_BUTTON => {
-                let mut st = self.get_object_button_state(plane, object_index);
-                st.button_no = args.first().and_then(|p| p.as_int()).unwrap_or(0);
-                st.group_no = args.get(1).and_then(|p| p.as_int()).unwrap_or(0);
-                st.action_no = args.get(2).and_then(|p| p.as_int()).unwrap_or(0);
-                st.se_no = args.get(3).and_then(|p| p.as_int()).unwrap_or(0);
-                self.set_object_button_state(plane, object_index, st);
+                let arg = |i: usize| args.get(i).and_then(|p| p.as_int()).unwrap_or(0);
+                self.update_object_button_state(plane, object_index, |st| {
+                    st.button_no = arg(0);
+                    st.group_no = arg(1);
+                    st.action_no = arg(2);
+                    st.se_no = arg(3);
+                });
             }
             x if x == siglus::elm::objectlist::ELM_OBJECT_SET_BUTTON_GROUP => {
-                let mut st = self.get_object_button_state(plane, object_index);
-                st.group_no = args.first().and_then(|p| p.as_int()).unwrap_or(st.group_no);
-                self.set_object_button_state(plane, object_index, st);
+                self.update_object_button_state(plane, object_index, |st| {
+                    st.group_no = args.first().and_then(|p| p.as_int()).unwrap_or(st.group_no);
+                });
             }
             x if x == siglus::elm::objectlist::ELM_OBJECT_SET_BUTTON_PUSHKEEP => {
-                let mut st = self.get_object_button_state(plane, object_index);
-                st.push_keep = args.first().and_then(|p| p.as_int()).unwrap_or(0);
-                self.set_object_button_state(plane, object_index, st);
+                self.update_object_button_state(plane, object_index, |st| {
+                    st.push_keep = args.first().and_then(|p| p.as_int()).unwrap_or(0);
+                });
             }
             x if x == siglus::elm::objectlist::ELM_OBJECT_SET_BUTTON_ALPHA_TEST => {
-                let mut st = self.get_object_button_state(plane, object_index);
-                st.alpha_test = args.first().and_then(|p| p.as_int()).unwrap_or(0);
-                self.set_object_button_state(plane, object_index, st);
+                self.update_object_button_state(plane, object_index, |st| {
+                    st.alpha_test = args.first().and_then(|p| p.as_int()).unwrap_or(0);
+                });
             }
             x if x == siglus::elm::objectlist::ELM_OBJECT_SET_BUTTON_STATE_NORMAL => {
-                let mut st = self.get_object_button_state(plane, object_index);
-                st.state = 0;
-                st.real_state = 0;
-                self.set_object_button_state(plane, object_index, st);
+                self.update_object_button_state(plane, object_index, |st| {
+                    st.state = 0;
+                    st.real_state = 0;
+                });
             }
             x if x == siglus::elm::objectlist::ELM_OBJECT_SET_BUTTON_STATE_SELECT => {
-                let mut st = self.get_object_button_state(plane, object_index);
-                st.state = 1;
-                st.real_state = 1;
-                self.set_object_button_state(plane, object_index, st);
+                self.update_object_button_state(plane, object_index, |st| {
+                    st.state = 1;
+                    st.real_state = 1;
+                });
             }
             x if x == siglus::elm::objectlist::ELM_OBJECT_SET_BUTTON_STATE_DISABLE => {
-                let mut st = self.get_object_button_state(plane, object_index);
-                st.state = 2;
-                st.real_state = 2;
-                self.set_object_button_state(plane, object_index, st);
+                self.update_object_button_state(plane, object_index, |st| {
+                    st.state = 2;
+                    st.real_state = 2;
+                });
             }
             x if x == siglus::elm::objectlist::ELM_OBJECT_CLEAR_BUTTON_CALL
                 || x == siglus::elm::objectlist::ELM_OBJECT_SET_BUTTON_CALL => {}
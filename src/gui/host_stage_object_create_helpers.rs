@@ -35,24 +35,62 @@ struct ObjectNumberStyleState {
     space: i32,
 }
 
-#[derive(Debug, Clone, Default)]
+/// `hit_state` values: the cursor is off, over, or pressing the button.
+pub(super) const BUTTON_HIT_STATE_NONE: i32 = 0;
+pub(super) const BUTTON_HIT_STATE_HIT: i32 = 1;
+pub(super) const BUTTON_HIT_STATE_PUSH: i32 = 2;
+
+#[derive(Debug, Clone)]
 pub(super) struct ObjectButtonState {
+    /// -1 = not a button.
     pub(super) button_no: i32,
     pub(super) group_no: i32,
+    /// `#BUTTON.ACTION` entry drawn for each state.
     pub(super) action_no: i32,
+    /// `#BUTTON.SE` entry played on hit/push/decide.
     pub(super) se_no: i32,
     pub(super) push_keep: i32,
     pub(super) alpha_test: i32,
+    /// 0 = normal, 1 = select, 2 = disable.
     pub(super) state: i32,
     pub(super) hit_state: i32,
     pub(super) real_state: i32,
 }
 
-#[allow(dead_code)]
+impl Default for ObjectButtonState {
+    fn default() -> Self {
+        Self {
+            button_no: -1,
+            group_no: -1,
+            action_no: 0,
+            se_no: 0,
+            push_keep: 0,
+            alpha_test: 0,
+            state: 0,
+            hit_state: BUTTON_HIT_STATE_NONE,
+            real_state: 0,
+        }
+    }
+}
+
+impl ObjectButtonState {
+    /// Which `#BUTTON.ACTION` state is drawn; disable wins over the cursor,
+    /// the cursor over select.
+    fn look(&self) -> siglus::gameexe::GameexeButtonState {
+        use siglus::gameexe::GameexeButtonState as S;
+        match (self.state, self.hit_state) {
+            (2, _) => S::Disable,
+            (_, BUTTON_HIT_STATE_PUSH) => S::Push,
+            (_, BUTTON_HIT_STATE_HIT) => S::Hit,
+            (1, _) => S::Select,
+            _ => S::Normal,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub(super) struct ObjectWeatherState {
-    last_type: i32,
-    params: Vec<i32>,
+    params: siglus::render::WeatherParams,
 }
 
 static OBJECT_STRING_STATE: std::sync::OnceLock<
@@ -184,6 +222,14 @@ impl GuiHost {
         }
     }
 
+    /// Value of a number object; `None` for objects not made by `create_number`.
+    fn get_object_number_value(&self, plane: StagePlane, object_index: i32) -> Option<i32> {
+        if let Ok(guard) = self.object_number_state_map().lock() {
+            return guard.get(&(plane, object_index)).copied();
+        }
+        None
+    }
+
     pub(super) fn get_object_number_state(&self, plane: StagePlane, object_index: i32) -> i32 {
         if let Ok(guard) = self.object_number_state_map().lock() {
             return guard.get(&(plane, object_index)).copied().unwrap_or(0);
@@ -249,6 +295,69 @@ impl GuiHost {
         }
     }
 
+    /// The `#BUTTON.ACTION` pattern a button object is drawn with.
+    fn object_button_look(
+        &self,
+        plane: StagePlane,
+        object_index: i32,
+    ) -> Option<siglus::gameexe::GameexeButtonActionPat> {
+        let guard = self.object_button_state_map().lock().ok()?;
+        let btn = guard.get(&(plane, object_index))?;
+        if btn.button_no < 0 {
+            return None;
+        }
+        let action = self.button_config.action(btn.action_no)?;
+        Some(*action.pat(btn.look()))
+    }
+
+    /// Change a button's state and redraw the object when its look changes.
+    pub(super) fn update_object_button_state(
+        &mut self,
+        plane: StagePlane,
+        object_index: i32,
+        update: impl FnOnce(&mut ObjectButtonState),
+    ) {
+        let before = self.object_button_look(plane, object_index);
+        let mut btn = self.get_object_button_state(plane, object_index);
+        update(&mut btn);
+        self.set_object_button_state(plane, object_index, btn);
+        self.refresh_object_button_look(plane, object_index, before);
+    }
+
+    fn refresh_object_button_look(
+        &mut self,
+        plane: StagePlane,
+        object_index: i32,
+        before: Option<siglus::gameexe::GameexeButtonActionPat>,
+    ) {
+        let after = self.object_button_look(plane, object_index);
+        if before == after {
+            return;
+        }
+        let pixels = |pat: Option<siglus::gameexe::GameexeButtonActionPat>| {
+            pat.map(|p| (p.pat_no, p.bright, p.dark))
+        };
+        if pixels(before) != pixels(after) {
+            self.refresh_object_image(plane, object_index);
+        }
+        self.emit_object_render_state(plane, object_index);
+    }
+
+    /// Apply the button look's pattern and color to an object about to be drawn.
+    fn apply_button_look_pixels(
+        &self,
+        plane: StagePlane,
+        object_index: i32,
+        state: &mut HostObjectState,
+    ) {
+        let Some(look) = self.object_button_look(plane, object_index) else {
+            return;
+        };
+        state.pat_no = (state.pat_no as i64 + i64::from(look.pat_no)).max(0) as usize;
+        state.bright += look.bright as f32;
+        state.dark += look.dark as f32;
+    }
+
     pub(super) fn set_object_weather_state(
         &self,
         plane: StagePlane,
@@ -260,12 +369,61 @@ impl GuiHost {
         }
     }
 
+    pub(super) fn get_object_weather_state(
+        &self,
+        plane: StagePlane,
+        object_index: i32,
+    ) -> Option<ObjectWeatherState> {
+        if let Ok(guard) = self.object_weather_state_map().lock() {
+            return guard.get(&(plane, object_index)).cloned();
+        }
+        None
+    }
+
     pub(super) fn clear_object_weather_state(&self, plane: StagePlane, object_index: i32) {
-        if let Ok(mut guard) = self.object_weather_state_map().lock() {
-            guard.remove(&(plane, object_index));
+        let removed = match self.object_weather_state_map().lock() {
+            Ok(mut guard) => guard.remove(&(plane, object_index)).is_some(),
+            Err(_) => false,
+        };
+        if removed {
+            let _ = self.event_tx.send(HostEvent::ObjectWeather {
+                stage: plane,
+                index: object_index,
+                weather: None,
+            });
         }
     }
 
+    /// Send the weather's parameters with the g00 cuts its particles use.
+    fn emit_object_weather(&self, plane: StagePlane, object_index: i32) {
+        let Some(weather) = self.get_object_weather_state(plane, object_index) else {
+            return;
+        };
+        let Some(st) = self.objects.get(&(plane, object_index)) else {
+            return;
+        };
+        let mut patterns = Vec::new();
+        for pat_no in weather.params.pat_range().take(64) {
+            match self.load_stage_cut(&st.file_name, pat_no as usize) {
+                Ok(img) => {
+                    let tone_curve = self.tone_curves.get(st.tonecurve_no);
+                    let img = apply_color_semantics(&img, st, tone_curve);
+                    patterns.push((pat_no, Arc::new(img.to_rgba8())))
+                }
+                Err(e) => warn!("weather pattern {pat_no} of {}: {e:#}", st.file_name),
+            }
+        }
+        let _ = self.event_tx.send(HostEvent::ObjectWeather {
+            stage: plane,
+            index: object_index,
+            weather: Some(ObjectWeatherEvent {
+                params: weather.params,
+                seed: st.seq ^ ((object_index as u64) << 32),
+                patterns,
+            }),
+        });
+    }
+
     pub(super) fn set_object_movie_seek_state(&self, plane: StagePlane, object_index: i32, seek: i32) {
         if let Ok(mut guard) = self.object_movie_seek_map().lock() {
             guard.insert((plane, object_index), seek);
@@ -335,6 +493,36 @@ impl GuiHost {
         }
     }
 
+    /// create_save_thumb / create_capture_thumb / create_capture /
+    /// create_from_capture_file: show `image` as the object's picture. Without
+    /// an image the object is only reset, like a create of a missing file.
+    pub(super) fn create_object_from_capture(
+        &mut self,
+        plane: StagePlane,
+        object_index: i32,
+        cmd: i32,
+        args: &[siglus::vm::Prop],
+        image: Option<Arc<image::RgbaImage>>,
+    ) {
+        self.reset_object_runtime_state_for_create(plane, object_index);
+        self.clear_object_string_state(plane, object_index);
+        // create_capture has no leading save number / file name.
+        let tail = usize::from(cmd != siglus::elm::objectlist::ELM_OBJECT_CREATE_CAPTURE);
+        self.apply_create_tail_disp_xy_pat(plane, object_index, tail, tail + 1, tail + 2, None, args);
+        match image {
+            Some(image) => {
+                let image = image::DynamicImage::ImageRgba8(image.as_ref().clone());
+                self.emit_generated_object_image(plane, object_index, image);
+            }
+            None => {
+                let _ = self.event_tx.send(HostEvent::RemoveObject {
+                    stage: plane,
+                    index: object_index,
+                });
+            }
+        }
+    }
+
     fn build_rect_image(args: &[siglus::vm::Prop]) -> image::DynamicImage {
         let l = args.first().and_then(|p| p.as_int()).unwrap_or(0);
         let t = args.get(1).and_then(|p| p.as_int()).unwrap_or(0);
@@ -500,7 +688,42 @@ impl GuiHost {
         }
     }
 
+    /// Rasterize a string object with the loaded TTF faces and the VM font style;
+    /// without any usable font the 5x7 bitmap glyphs are used instead.
+    ///
+    /// `shadow_mode` >= 0 overrides the VM decoration for this object.
     fn build_string_raster_image(
+        &self,
+        text: &str,
+        style: &ObjectStringStyleState,
+    ) -> image::DynamicImage {
+        let mut text = text.to_string();
+        if style.moji_cnt > 0 {
+            let max_chars = (style.moji_cnt as usize).saturating_mul(2);
+            if let Some((cut, _)) = text.char_indices().nth(max_chars) {
+                text.truncate(cut);
+            }
+        }
+        let text_style = siglus::render::TextStyle {
+            size: style.moji_size.max(1) as f32,
+            color: Self::color_from_script_i32(style.moji_color, [255, 255, 255]),
+            bold: self.font_style.bold,
+            decoration: siglus::vm::FontDecoration::from_config(
+                style.shadow_mode,
+                self.font_style.decoration,
+            ),
+            shadow_color: Self::color_from_script_i32(style.shadow_color, [0, 0, 0]),
+            outline_color: Self::color_from_script_i32(style.fuchi_color, [0, 0, 0]),
+            space_x: style.moji_space_x as f32,
+            space_y: style.moji_space_y as f32,
+        };
+        match siglus::render::render_text(&self.fonts, &self.font_style.name, &text, &text_style) {
+            Some(img) => image::DynamicImage::ImageRgba8(img),
+            None => Self::build_bitmap_string_raster_image(&text, style),
+        }
+    }
+
+    fn build_bitmap_string_raster_image(
         text: &str,
         style: &ObjectStringStyleState,
     ) -> image::DynamicImage {
@@ -644,7 +867,84 @@ impl GuiHost {
         slots[..end].iter().collect()
     }
 
+    /// Draw a number from the digit cuts of its g00 (C++ elm_object.cpp number
+    /// objects): cuts 0-9 are the digits. Columns are `space` apart with
+    /// `space_mod`, otherwise the digit width plus `space`. The g00 is decoded
+    /// once through `load_stage_cut`.
+    ///
+    /// Limitation: which cuts hold `+` and `-` is not known here (the C++ number
+    /// object is not in this tree), so sign columns keep their place but stay
+    /// blank.
+    fn build_number_g00_image(
+        &self,
+        file_name: &str,
+        value: i32,
+        style: &ObjectNumberStyleState,
+    ) -> anyhow::Result<image::DynamicImage> {
+        let text = Self::build_number_display_text(value, style);
+        let columns: Vec<Option<usize>> = text
+            .chars()
+            .map(|ch| match ch {
+                '0'..='9' => Some(ch as usize - '0' as usize),
+                _ => None,
+            })
+            .collect();
+        let mut cuts: std::collections::BTreeMap<usize, image::RgbaImage> =
+            std::collections::BTreeMap::new();
+        for pat_no in std::iter::once(0).chain(columns.iter().flatten().copied()) {
+            if !cuts.contains_key(&pat_no) {
+                cuts.insert(pat_no, self.load_stage_cut(file_name, pat_no)?.to_rgba8());
+            }
+        }
+
+        let digit_w = cuts.get(&0).map(|c| c.width() as i64).unwrap_or(0);
+        let pitch = if style.space_mod != 0 {
+            style.space as i64
+        } else {
+            digit_w + style.space as i64
+        }
+        .max(0);
+        let placed: Vec<(i64, &image::RgbaImage)> = columns
+            .iter()
+            .enumerate()
+            .filter_map(|(i, pat)| Some((i as i64 * pitch, cuts.get(&(*pat)?)?)))
+            .collect();
+        let width = placed
+            .iter()
+            .map(|(x, c)| x + c.width() as i64)
+            .max()
+            .unwrap_or(digit_w)
+            .max(1);
+        let height = cuts.values().map(|c| c.height()).max().unwrap_or(1).max(1);
+        let mut out = image::RgbaImage::new(width as u32, height);
+        for (x, cut) in placed {
+            image::imageops::overlay(&mut out, cut, x, 0);
+        }
+        Ok(image::DynamicImage::ImageRgba8(out))
+    }
+
+    /// Re-render a number object: from its g00 when it has one, otherwise
+    /// with the bitmap font.
+    fn emit_number_object_image(&mut self, plane: StagePlane, object_index: i32) {
+        let has_file = self
+            .objects
+            .get(&(plane, object_index))
+            .is_some_and(|s| !s.file_name.is_empty());
+        if has_file {
+            self.emit_object_image(plane, object_index);
+            return;
+        }
+        let num = self.get_object_number_state(plane, object_index);
+        let style = self.get_object_number_style_state(plane, object_index);
+        self.emit_generated_object_image(
+            plane,
+            object_index,
+            self.build_number_raster_image(num, &style),
+        );
+    }
+
     fn build_number_raster_image(
+        &self,
         value: i32,
         style: &ObjectNumberStyleState,
     ) -> image::DynamicImage {
@@ -654,7 +954,7 @@ impl GuiHost {
         sstyle.moji_space_x = style.space.max(0);
         sstyle.moji_color = 0xFFFFFF;
         sstyle.shadow_mode = if style.space_mod == 0 { 0 } else { -1 };
-        Self::build_string_raster_image(&text, &sstyle)
+        self.build_string_raster_image(&text, &sstyle)
     }
 
     fn apply_create_tail_disp_xy_pat(
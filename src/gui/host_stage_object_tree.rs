@@ -0,0 +1,131 @@
+impl GuiHost {
+    /// Object index behind `object.child[child_no]`, if that slot exists.
+    pub(super) fn child_object_index(
+        &self,
+        plane: StagePlane,
+        parent: i32,
+        child_no: i32,
+    ) -> Option<i32> {
+        self.object_tree.child_index(plane, parent, child_no)
+    }
+
+    pub(super) fn object_child_count(&self, plane: StagePlane, parent: i32) -> usize {
+        self.object_tree.child_count(plane, parent)
+    }
+
+    /// `object.child.resize`: new slots start empty, dropped ones are freed
+    /// along with everything below them.
+    pub(super) fn resize_object_children(&mut self, plane: StagePlane, parent: i32, size: usize) {
+        let (added, removed) = self.object_tree.resize(plane, parent, size);
+        for index in added {
+            let _ = self.event_tx.send(HostEvent::SetObjectParent {
+                stage: plane,
+                index,
+                parent: Some(parent),
+            });
+        }
+        for index in removed {
+            self.resize_object_children(plane, index, 0);
+            self.object_tree.children.remove(&(plane, index));
+            self.set_object_child_sort_test(plane, index, false);
+            self.apply_object_command(plane, index, siglus::elm::objectlist::ELM_OBJECT_FREE, &[]);
+            self.objects.remove(&(plane, index));
+            let _ = self.event_tx.send(HostEvent::SetObjectParent {
+                stage: plane,
+                index,
+                parent: None,
+            });
+        }
+    }
+
+    /// FREE/INIT of a parent frees its children too; the child list keeps
+    /// its size so scripts can create into the slots again.
+    fn free_object_children(&mut self, plane: StagePlane, parent: i32) {
+        let children = self
+            .object_tree
+            .children
+            .get(&(plane, parent))
+            .cloned()
+            .unwrap_or_default();
+        for child in children {
+            self.apply_object_command(plane, child, siglus::elm::objectlist::ELM_OBJECT_FREE, &[]);
+        }
+        self.set_object_child_sort_test(plane, parent, false);
+    }
+
+    /// `set_child_sort_type_default` / `set_child_sort_type_test`.
+    fn set_object_child_sort_test(&mut self, plane: StagePlane, index: i32, test: bool) {
+        let changed = if test {
+            self.object_tree.sort_test.insert((plane, index))
+        } else {
+            self.object_tree.sort_test.remove(&(plane, index))
+        };
+        if changed {
+            let _ = self.event_tx.send(HostEvent::SetObjectChildSort {
+                stage: plane,
+                index,
+                test,
+            });
+        }
+    }
+
+    /// The object's state with position, scale, rotation, alpha and
+    /// visibility composed through its parents.
+    pub(super) fn world_object_state(
+        &self,
+        plane: StagePlane,
+        index: i32,
+    ) -> Option<HostObjectState> {
+        let st = self.objects.get(&(plane, index))?;
+        let Some(parent) = self.object_tree.parent(plane, index) else {
+            return Some(st.clone());
+        };
+        let parent_st = self
+            .world_object_state(plane, parent)
+            .unwrap_or_else(default_host_object_state);
+        let world = ObjectTransform::from(&parent_st).then(&ObjectTransform::from(st));
+        let mut out = st.clone();
+        out.x = world.x;
+        out.y = world.y;
+        out.scale_x = world.scale_x;
+        out.scale_y = world.scale_y;
+        out.rotate_z_deg = world.rotate_z_deg;
+        out.alpha = world.alpha;
+        out.visible = st.visible && parent_st.visible;
+        Some(out)
+    }
+
+    /// Stage-space clip inherited from the object's parents.
+    pub(super) fn ancestor_clip(
+        &self,
+        plane: StagePlane,
+        index: i32,
+    ) -> Option<(f32, f32, f32, f32)> {
+        let mut clip = None;
+        let mut cur = index;
+        while let Some(parent) = self.object_tree.parent(plane, cur) {
+            if let Some(st) = self.objects.get(&(plane, parent))
+                && st.dst_clip_use
+            {
+                let own = (
+                    st.dst_clip_left,
+                    st.dst_clip_top,
+                    st.dst_clip_right,
+                    st.dst_clip_bottom,
+                );
+                clip = intersect_clip(clip, Some(own));
+            }
+            cur = parent;
+        }
+        clip
+    }
+
+    /// Draw-order key of an object: parents before (below) their children.
+    pub(super) fn object_sort_path(&self, plane: StagePlane, index: i32) -> Vec<(i32, i32, u64)> {
+        self.object_tree.sort_path(plane, index, |i| {
+            self.objects
+                .get(&(plane, i))
+                .map_or((0, 0, 0), |st| (st.layer, st.order, st.seq))
+        })
+    }
+}
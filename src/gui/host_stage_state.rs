@@ -1,3 +1,6 @@
+/// Files `GuiHost::stage_cuts` holds before starting over.
+const STAGE_CUTS_CACHE_MAX: usize = 64;
+
 impl GuiHost {
     fn get_or_create_object_state(
         &mut self,
@@ -18,16 +21,27 @@ impl GuiHost {
     }
 
     fn emit_object_render_state(&mut self, plane: StagePlane, object_index: i32) {
+        let look = self
+            .object_button_look(plane, object_index)
+            .unwrap_or_default();
         if let Some(state) = self.objects.get(&(plane, object_index)) {
             let _ = self.event_tx.send(HostEvent::SetObjectRenderState {
                 stage: plane,
                 index: object_index,
                 center_x: state.center_x,
                 center_y: state.center_y,
+                center_rep_x: state.center_rep_x,
+                center_rep_y: state.center_rep_y,
                 scale_x: state.scale_x,
                 scale_y: state.scale_y,
                 rotate_z_deg: state.rotate_z_deg,
-                alpha: state.alpha,
+                z: state.z,
+                rotate_x_deg: state.rotate_x_deg,
+                rotate_y_deg: state.rotate_y_deg,
+                world: state.world,
+                billboard: state.billboard,
+                alpha: state.alpha * (look.tr.clamp(0, 255) as f32 / 255.0),
+                button_offset: (look.pos.0 as f32, look.pos.1 as f32),
                 dst_clip_use: state.dst_clip_use,
                 dst_clip_left: state.dst_clip_left,
                 dst_clip_top: state.dst_clip_top,
@@ -38,10 +52,33 @@ impl GuiHost {
                 src_clip_top: state.src_clip_top,
                 src_clip_right: state.src_clip_right,
                 src_clip_bottom: state.src_clip_bottom,
+                blend: state.blend,
+                reps: siglus::render::RenderRep::from_lists(
+                    &state.x_rep,
+                    &state.y_rep,
+                    &state.z_rep,
+                    &state.tr_rep,
+                ),
             });
         }
     }
 
+    /// Replace one of the object's rep lists and redraw its repeats.
+    pub(super) fn set_object_rep_list(
+        &mut self,
+        plane: StagePlane,
+        object_index: i32,
+        sub: i32,
+        values: &[i32],
+    ) {
+        let state = self.get_or_create_object_state(plane, object_index);
+        if let Some(list) = state.rep_list_mut(sub) {
+            list.clear();
+            list.extend_from_slice(values);
+            self.emit_object_render_state(plane, object_index);
+        }
+    }
+
     fn refresh_object_image(&mut self, plane: StagePlane, object_index: i32) {
         if self
             .objects
@@ -53,6 +90,272 @@ impl GuiHost {
         }
     }
 
+    /// Masks are baked into the uploaded image, so moving a masked object
+    /// re-applies the mask to its cached `mask_base`.
+    fn refresh_masked_object_image(&mut self, plane: StagePlane, object_index: i32) {
+        let Some(state) = self.objects.get(&(plane, object_index)) else {
+            return;
+        };
+        if state.mask_no < 0 {
+            return;
+        }
+        let Some(base) = state.mask_base.clone() else {
+            self.refresh_object_image(plane, object_index);
+            return;
+        };
+        let state = state.clone();
+        let img = match self.object_mask(state.mask_no) {
+            Some(mask) => apply_object_mask(&base, &state, &mask),
+            None => (*base).clone(),
+        };
+        let _ = self.event_tx.send(HostEvent::UpsertObjectImage {
+            stage: plane,
+            index: object_index,
+            image: Arc::new(bake_blend_for_egui(img, state.blend)),
+        });
+    }
+
+    fn refresh_objects_masked_by(&mut self, slot_no: usize) {
+        let masked: Vec<(StagePlane, i32)> = self
+            .objects
+            .iter()
+            .filter(|(_, st)| st.mask_no == slot_no as i32)
+            .map(|(k, _)| *k)
+            .collect();
+        for (plane, idx) in masked {
+            self.refresh_masked_object_image(plane, idx);
+        }
+    }
+
+    /// Cut `pat_no` of an object image, decoding each file once. The cache
+    /// starts over once `STAGE_CUTS_CACHE_MAX` files are held.
+    pub(super) fn load_stage_cut(
+        &self,
+        file_name: &str,
+        pat_no: usize,
+    ) -> anyhow::Result<image::DynamicImage> {
+        let cached = self.stage_cuts.borrow().get(file_name).cloned();
+        let cuts = match cached {
+            Some(cuts) => cuts,
+            None => {
+                let cuts = Arc::new(load_stage_cuts_like_cpp(
+                    &self.base_dir,
+                    &self.append_dirs,
+                    file_name,
+                )?);
+                let mut cache = self.stage_cuts.borrow_mut();
+                if cache.len() >= STAGE_CUTS_CACHE_MAX {
+                    cache.clear();
+                }
+                cache.insert(file_name.to_string(), cuts.clone());
+                cuts
+            }
+        };
+        match cuts.cut(pat_no) {
+            Some(cut) => Ok(cut.clone()),
+            None => load_stage_like_cpp(&self.base_dir, &self.append_dirs, file_name, pat_no),
+        }
+    }
+
+    pub(super) fn load_mask_image(&mut self, file_name: &str) -> Option<Arc<image::GrayImage>> {
+        if let Some(cached) = self.mask_images.get(file_name) {
+            return cached.clone();
+        }
+        let loaded = match load_stage_like_cpp(&self.base_dir, &self.append_dirs, file_name, 0) {
+            Ok(img) => Some(Arc::new(siglus::render::mask_luminance(&img.to_rgba8()))),
+            Err(e) => {
+                error!("Failed to load mask image {}: {:#}", file_name, e);
+                None
+            }
+        };
+        self.mask_images
+            .insert(file_name.to_string(), loaded.clone());
+        loaded
+    }
+
+    fn object_mask(&mut self, mask_no: i32) -> Option<siglus::render::RenderMask> {
+        let slot = self
+            .mask_slots
+            .get(&usize::try_from(mask_no).ok()?)?
+            .clone();
+        if slot.name.is_empty() {
+            return None;
+        }
+        let image = self.load_mask_image(&slot.name)?;
+        let (x, y) = slot.pos_at(siglus::vm::Host::clock(self).now_ms());
+        Some(siglus::render::RenderMask::new(image, x as f32, y as f32))
+    }
+
+    pub(super) fn apply_mask_slot(&mut self, slot_no: usize, slot: &siglus::vm::MaskSlotState) {
+        let now = siglus::vm::Host::clock(self).now_ms();
+        self.mask_slots.insert(slot_no, slot.clone());
+        self.mask_baked_pos.insert(slot_no, slot.pos_at(now));
+        self.refresh_objects_masked_by(slot_no);
+    }
+
+    /// Track an object `*_eve` event from the VM and apply its current value.
+    pub(super) fn apply_object_int_event(
+        &mut self,
+        plane: StagePlane,
+        object_index: i32,
+        owner_id: i32,
+        event: &siglus::vm::IntEvent,
+    ) {
+        let key = (plane, object_index, owner_id);
+        self.object_int_events.remove(&key);
+        let Some(prop) = siglus::vm::object_event_property(owner_id) else {
+            return;
+        };
+        let now = siglus::vm::Host::clock(self).now_ms();
+        let value = event.value_at(now);
+        self.apply_object_int_prop(plane, object_index, prop, value);
+        if event.is_active(now) {
+            self.object_int_events.insert(key, (*event, value));
+        }
+    }
+
+    /// Step object `*_eve` events on the VM clock, applying values that changed
+    /// and dropping events once they settle.
+    pub(super) fn tick_object_int_events(&mut self) {
+        let now = siglus::vm::Host::clock(self).now_ms();
+        let changed: Vec<((StagePlane, i32, i32), i32, bool)> = self
+            .object_int_events
+            .iter()
+            .map(|(&key, (ev, applied))| (key, ev.value_at(now), ev.is_active(now), *applied))
+            .filter(|&(_, value, active, applied)| value != applied || !active)
+            .map(|(key, value, active, _)| (key, value, active))
+            .collect();
+        for (key, value, active) in changed {
+            if !active {
+                self.object_int_events.remove(&key);
+            } else if let Some(entry) = self.object_int_events.get_mut(&key) {
+                entry.1 = value;
+            }
+            let (plane, object_index, owner_id) = key;
+            if let Some(prop) = siglus::vm::object_event_property(owner_id) {
+                self.apply_object_int_prop(plane, object_index, prop, value);
+            }
+        }
+    }
+
+    fn apply_object_int_prop(&mut self, plane: StagePlane, object_index: i32, prop: i32, v: i32) {
+        let arg = siglus::vm::Prop {
+            id: 0,
+            form: siglus::elm::form::INT,
+            value: siglus::vm::PropValue::Int(v),
+        };
+        self.apply_object_command(plane, object_index, prop, &[arg]);
+    }
+
+    /// Re-bake objects whose mask moved on the VM clock (`mask[n].x_eve`/`y_eve`).
+    pub(super) fn tick_mask_events(&mut self) {
+        let now = siglus::vm::Host::clock(self).now_ms();
+        let moved: Vec<(usize, (i32, i32))> = self
+            .mask_slots
+            .iter()
+            .map(|(&no, slot)| (no, slot.pos_at(now)))
+            .filter(|(no, pos)| self.mask_baked_pos.get(no) != Some(pos))
+            .collect();
+        for (no, pos) in moved {
+            self.mask_baked_pos.insert(no, pos);
+            self.refresh_objects_masked_by(no);
+        }
+    }
+
+    /// Re-resolve screen effects on the VM clock and re-render the objects whose
+    /// effect colors changed. Colors are baked into the uploaded images, so
+    /// animations are refreshed at most every `EFFECT_REFRESH_MS` unless `force`d;
+    /// effect x/y shifts are not shown here.
+    pub(super) fn tick_screen_effects(&mut self, force: bool) {
+        const EFFECT_REFRESH_MS: u64 = 50;
+        let now = siglus::vm::Host::clock(self).now_ms();
+        if !force && now.saturating_sub(self.effects_resolved_at) < EFFECT_REFRESH_MS {
+            return;
+        }
+        self.effects_resolved_at = now;
+        let resolved: Vec<siglus::render::RenderEffect> =
+            self.screen_effects.iter().map(|e| e.resolve(now)).collect();
+        if resolved == self.applied_effects {
+            return;
+        }
+        let colors_for = |effects: &[siglus::render::RenderEffect], st: &HostObjectState| {
+            effects
+                .iter()
+                .filter(|e| e.covers(st.order, st.layer))
+                .map(|e| e.color)
+                .collect::<Vec<_>>()
+        };
+        let stale: Vec<(StagePlane, i32)> = self
+            .objects
+            .iter()
+            .filter(|(_, st)| colors_for(&resolved, st) != colors_for(&self.applied_effects, st))
+            .map(|(k, _)| *k)
+            .collect();
+        self.applied_effects = resolved;
+        for (plane, idx) in stale {
+            self.refresh_object_image(plane, idx);
+        }
+    }
+
+    /// Read `gan_path` for an object; a failed load leaves it without gan.
+    pub(super) fn load_object_gan(&mut self, plane: StagePlane, object_index: i32, gan_path: &str) {
+        let gan = match resolve_gan_path_like_cpp(&self.base_dir, &self.append_dirs, gan_path) {
+            Some(path) => match siglus::resource::read_gan(&path) {
+                Ok(gan) => Some(Arc::new(gan)),
+                Err(e) => {
+                    warn!("load_gan failed: {e:#}");
+                    None
+                }
+            },
+            None => {
+                warn!("load_gan: gan not found: {gan_path}");
+                None
+            }
+        };
+        let state = self.get_or_create_object_state(plane, object_index);
+        state.gan = gan;
+        let was_playing = state.gan_play.take().is_some();
+        if was_playing {
+            self.emit_object_image(plane, object_index);
+        }
+    }
+
+    /// Start stepping a loaded gan; ignored when no gan was loaded.
+    pub(super) fn start_object_gan(
+        &mut self,
+        plane: StagePlane,
+        object_index: i32,
+        playback: siglus::resource::GanPlayback,
+    ) {
+        let state = self.get_or_create_object_state(plane, object_index);
+        if state.gan.is_none() {
+            return;
+        }
+        state.gan_play = Some(playback);
+        self.tick_gan_anims();
+        self.emit_object_image(plane, object_index);
+    }
+
+    /// Keep every started gan's `pat_no` in step with the VM clock (C++
+    /// `C_elm_object::frame` → `m_gan.frame`). The GUI animates from the
+    /// patterns sent by `emit_object_image`, so nothing is redrawn here.
+    pub(super) fn tick_gan_anims(&mut self) {
+        let now = siglus::vm::Host::clock(self).now_ms();
+        for st in self.objects.values_mut() {
+            let (Some(gan), Some(play)) = (st.gan.as_ref(), st.gan_play) else {
+                continue;
+            };
+            let Some((pat, running)) = play.pat_at(gan, now) else {
+                st.gan_play = None;
+                continue;
+            };
+            if !running {
+                st.gan_play = None;
+            }
+            st.pat_no = pat.pat_no.max(0) as usize;
+        }
+    }
+
     pub(super) fn apply_stage_plane_command(
         &mut self,
         plane: StagePlane,
@@ -86,27 +389,99 @@ impl GuiHost {
         }
     }
 
+    /// The object's own pixels: its g00 cut, or for number objects the
+    /// digits composed from the g00's digit cuts.
+    fn load_object_source_image(
+        &self,
+        plane: StagePlane,
+        object_index: i32,
+        state: &HostObjectState,
+    ) -> anyhow::Result<image::DynamicImage> {
+        if let Some(value) = self.get_object_number_value(plane, object_index) {
+            let style = self.get_object_number_style_state(plane, object_index);
+            return self.build_number_g00_image(&state.file_name, value, &style);
+        }
+        self.load_stage_cut(&state.file_name, state.pat_no)
+    }
+
+    /// Run an object's source image through its colors, the covering
+    /// effects, its mask and the blend bake. Also returns the pre-mask
+    /// pixels `mask_no` keeps for re-masking.
+    fn process_object_image(
+        &mut self,
+        plane: StagePlane,
+        object_index: i32,
+        state: &HostObjectState,
+    ) -> anyhow::Result<(image::DynamicImage, Option<Arc<image::DynamicImage>>)> {
+        let img = self.load_object_source_image(plane, object_index, state)?;
+        let mut img = apply_color_semantics(&img, state, self.tone_curves.get(state.tonecurve_no));
+        let effects: Vec<&siglus::render::RenderEffect> = self
+            .applied_effects
+            .iter()
+            .filter(|e| e.covers(state.order, state.layer))
+            .collect();
+        if effects.iter().any(|e| !e.color.is_identity()) {
+            img = apply_effect_colors(&img, &effects);
+        }
+        let mask_base = (state.mask_no >= 0).then(|| Arc::new(img.clone()));
+        if let Some(mask) = self.object_mask(state.mask_no) {
+            img = apply_object_mask(&img, state, &mask);
+        }
+        Ok((bake_blend_for_egui(img, state.blend), mask_base))
+    }
+
+    /// Every pattern a playing gan set shows, processed once so the GUI
+    /// can step through them without the VM thread decoding per frame.
+    fn emit_object_gan(&mut self, plane: StagePlane, object_index: i32, state: &HostObjectState) {
+        let (Some(gan), Some(playback)) = (state.gan.as_ref(), state.gan_play) else {
+            return;
+        };
+        let Some(set) = gan.sets.get(playback.set_no) else {
+            return;
+        };
+        let mut patterns: Vec<(i32, Arc<image::RgbaImage>)> = Vec::new();
+        for pat in &set.pats {
+            if patterns.iter().any(|(pat_no, _)| *pat_no == pat.pat_no) {
+                continue;
+            }
+            let mut pat_state = state.clone();
+            pat_state.pat_no = pat.pat_no.max(0) as usize;
+            match self.process_object_image(plane, object_index, &pat_state) {
+                Ok((img, _)) => patterns.push((pat.pat_no, Arc::new(img.to_rgba8()))),
+                Err(e) => warn!("gan pattern {} of {}: {e:#}", pat.pat_no, state.file_name),
+            }
+        }
+        let _ = self.event_tx.send(HostEvent::ObjectGan {
+            stage: plane,
+            index: object_index,
+            gan: ObjectGanEvent {
+                set: set.clone(),
+                playback,
+                patterns,
+            },
+        });
+    }
+
     fn emit_object_image(&mut self, plane: StagePlane, object_index: i32) {
-        let Some(state) = self.objects.get(&(plane, object_index)).cloned() else {
+        let Some(mut state) = self.objects.get(&(plane, object_index)).cloned() else {
             return;
         };
         if state.file_name.is_empty() {
             return;
         }
+        self.apply_button_look_pixels(plane, object_index, &mut state);
 
-        match load_stage_like_cpp(
-            &self.base_dir,
-            &self.append_dirs,
-            &state.file_name,
-            state.pat_no,
-        ) {
-            Ok(img) => {
-                let img = apply_color_semantics(&img, &state);
+        match self.process_object_image(plane, object_index, &state) {
+            Ok((img, mask_base)) => {
+                if let Some(st) = self.objects.get_mut(&(plane, object_index)) {
+                    st.mask_base = mask_base;
+                }
                 let _ = self.event_tx.send(HostEvent::UpsertObjectImage {
                     stage: plane,
                     index: object_index,
                     image: Arc::new(img),
                 });
+                self.emit_object_gan(plane, object_index, &state);
                 let _ = self.event_tx.send(HostEvent::SetObjectPos {
                     stage: plane,
                     index: object_index,
@@ -0,0 +1,141 @@
+/// First object index handed to child objects, far above any script object list.
+const CHILD_OBJECT_INDEX_BASE: i32 = 0x4000_0000;
+
+/// Child lists of stage objects (C++ `C_elm_object::m_child`).
+///
+/// Children are ordinary `GuiHost::objects` entries under indices handed out
+/// from `CHILD_OBJECT_INDEX_BASE`, so every object command, image and event
+/// path serves them unchanged; the tree only records who hangs off whom.
+#[derive(Debug, Default)]
+struct ObjectTree {
+    /// `object.child` slots of a parent, by child number.
+    children: BTreeMap<(StagePlane, i32), Vec<i32>>,
+    parents: BTreeMap<(StagePlane, i32), i32>,
+    /// Parents whose children use `set_child_sort_type_test`.
+    sort_test: BTreeSet<(StagePlane, i32)>,
+    next_index: i32,
+}
+
+impl ObjectTree {
+    fn child_count(&self, plane: StagePlane, parent: i32) -> usize {
+        self.children.get(&(plane, parent)).map_or(0, Vec::len)
+    }
+
+    fn child_index(&self, plane: StagePlane, parent: i32, child_no: i32) -> Option<i32> {
+        let list = self.children.get(&(plane, parent))?;
+        list.get(usize::try_from(child_no).ok()?).copied()
+    }
+
+    fn parent(&self, plane: StagePlane, index: i32) -> Option<i32> {
+        self.parents.get(&(plane, index)).copied()
+    }
+
+    /// Grow or shrink a child list. Returns the slots added and the slots dropped.
+    fn resize(&mut self, plane: StagePlane, parent: i32, size: usize) -> (Vec<i32>, Vec<i32>) {
+        let list = self.children.entry((plane, parent)).or_default();
+        let removed = if size < list.len() {
+            list.split_off(size)
+        } else {
+            Vec::new()
+        };
+        let mut added = Vec::new();
+        while list.len() < size {
+            let index = CHILD_OBJECT_INDEX_BASE + self.next_index;
+            self.next_index += 1;
+            list.push(index);
+            self.parents.insert((plane, index), parent);
+            added.push(index);
+        }
+        for index in &removed {
+            self.parents.remove(&(plane, *index));
+        }
+        (added, removed)
+    }
+
+    /// Sort key of `index` among its siblings, root first: the stage sort
+    /// key for top-level objects, and for children either their own sort
+    /// key or (with the test sort type) their child number.
+    fn sort_path(
+        &self,
+        plane: StagePlane,
+        index: i32,
+        sort_key: impl Fn(i32) -> (i32, i32, u64),
+    ) -> Vec<(i32, i32, u64)> {
+        let mut path = Vec::new();
+        let mut cur = index;
+        loop {
+            let Some(parent) = self.parent(plane, cur) else {
+                path.push(sort_key(cur));
+                break;
+            };
+            if self.sort_test.contains(&(plane, parent)) {
+                let no = self.children[&(plane, parent)]
+                    .iter()
+                    .position(|c| *c == cur)
+                    .unwrap_or(0);
+                path.push((0, 0, no as u64));
+            } else {
+                path.push(sort_key(cur));
+            }
+            cur = parent;
+        }
+        path.reverse();
+        path
+    }
+}
+
+/// Placement an object hands down to its children: stage position of its
+/// anchor, scale, rotation and opacity. Children are laid out in their
+/// parent's space, so a child at (10, 0) under a parent scaled 2x sits 20
+/// stage pixels right of the parent's anchor.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ObjectTransform {
+    x: f32,
+    y: f32,
+    scale_x: f32,
+    scale_y: f32,
+    rotate_z_deg: f32,
+    alpha: f32,
+}
+
+impl ObjectTransform {
+    /// Place `child`, given in this object's space, on the stage.
+    fn then(&self, child: &ObjectTransform) -> ObjectTransform {
+        let (sin, cos) = self.rotate_z_deg.to_radians().sin_cos();
+        let lx = child.x * self.scale_x;
+        let ly = child.y * self.scale_y;
+        ObjectTransform {
+            x: self.x + lx * cos - ly * sin,
+            y: self.y + lx * sin + ly * cos,
+            scale_x: self.scale_x * child.scale_x,
+            scale_y: self.scale_y * child.scale_y,
+            rotate_z_deg: self.rotate_z_deg + child.rotate_z_deg,
+            alpha: self.alpha * child.alpha,
+        }
+    }
+}
+
+/// Intersection of two `(left, top, right, bottom)` clips; `None` is unclipped.
+fn intersect_clip(
+    a: Option<(f32, f32, f32, f32)>,
+    b: Option<(f32, f32, f32, f32)>,
+) -> Option<(f32, f32, f32, f32)> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some((a.0.max(b.0), a.1.max(b.1), a.2.min(b.2), a.3.min(b.3))),
+        (a, None) => a,
+        (None, b) => b,
+    }
+}
+
+impl From<&HostObjectState> for ObjectTransform {
+    fn from(st: &HostObjectState) -> Self {
+        Self {
+            x: st.x,
+            y: st.y,
+            scale_x: st.scale_x,
+            scale_y: st.scale_y,
+            rotate_z_deg: st.rotate_z_deg,
+            alpha: st.alpha,
+        }
+    }
+}
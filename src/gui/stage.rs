@@ -119,6 +119,20 @@ pub(super) fn parse_stage_plane_command(element: &[i32]) -> Option<(StagePlane,
     None
 }
 
+impl HostObjectState {
+    /// The `x_rep` / `y_rep` / `z_rep` / `tr_rep` list named by `sub`.
+    fn rep_list_mut(&mut self, sub: i32) -> Option<&mut Vec<i32>> {
+        use siglus::elm::objectlist::*;
+        match sub {
+            ELM_OBJECT_X_REP => Some(&mut self.x_rep),
+            ELM_OBJECT_Y_REP => Some(&mut self.y_rep),
+            ELM_OBJECT_Z_REP => Some(&mut self.z_rep),
+            ELM_OBJECT_TR_REP => Some(&mut self.tr_rep),
+            _ => None,
+        }
+    }
+}
+
 fn default_host_object_state() -> HostObjectState {
     HostObjectState {
         file_name: String::new(),
@@ -127,14 +141,22 @@ fn default_host_object_state() -> HostObjectState {
         y: 0.0,
         center_x: 0.0,
         center_y: 0.0,
+        center_rep_x: 0.0,
+        center_rep_y: 0.0,
         visible: true,
         order: 0,
         layer: 0,
         scale_x: 1.0,
         scale_y: 1.0,
         rotate_z_deg: 0.0,
+        z: 0.0,
+        rotate_x_deg: 0.0,
+        rotate_y_deg: 0.0,
+        world: -1,
+        billboard: false,
         alpha: 1.0,
-        alpha_blend: true,
+        blend: siglus::render::RenderBlend::Normal,
+        tonecurve_no: -1,
         dst_clip_use: false,
         dst_clip_left: 0.0,
         dst_clip_top: 0.0,
@@ -156,11 +178,19 @@ fn default_host_object_state() -> HostObjectState {
         dark: 0.0,
         mono: 0.0,
         reverse: false,
+        mask_no: -1,
         movie_auto_init: true,
         movie_real_time: true,
         movie_ready_only: false,
         emote_rep_x: 0,
         emote_rep_y: 0,
+        x_rep: Vec::new(),
+        y_rep: Vec::new(),
+        z_rep: Vec::new(),
+        tr_rep: Vec::new(),
+        gan: None,
+        gan_play: None,
+        mask_base: None,
         seq: 0,
     }
 }
@@ -227,38 +257,116 @@ pub(super) fn looks_like_stage_object_path(element: &[i32]) -> bool {
             || (element.len() >= 5 && element[4] == siglus::elm::objectlist::ELM_STAGE_OBJECT))
 }
 
-fn apply_color_semantics(image: &image::DynamicImage, st: &HostObjectState) -> image::DynamicImage {
-    let mut rgba = image.to_rgba8();
-    for p in rgba.pixels_mut() {
-        let mut r = p[0] as f32;
-        let mut g = p[1] as f32;
-        let mut b = p[2] as f32;
-
-        if st.mono > 0.0 {
-            let y = 0.299 * r + 0.587 * g + 0.114 * b;
-            let t = st.mono;
-            r = r + (y - r) * t;
-            g = g + (y - g) * t;
-            b = b + (y - b) * t;
-        }
+fn object_render_color(st: &HostObjectState) -> siglus::render::RenderColor {
+    siglus::render::RenderColor {
+        color_rate: st.color_rate,
+        color_r: st.color_r,
+        color_g: st.color_g,
+        color_b: st.color_b,
+        color_add_r: st.color_add_r,
+        color_add_g: st.color_add_g,
+        color_add_b: st.color_add_b,
+        bright: st.bright,
+        dark: st.dark,
+        mono: st.mono,
+        reverse: st.reverse,
+        ..Default::default()
+    }
+}
 
-        if st.reverse {
-            r = 255.0 - r;
-            g = 255.0 - g;
-            b = 255.0 - b;
-        }
+fn apply_color_semantics(
+    image: &image::DynamicImage,
+    st: &HostObjectState,
+    tone_curve: Option<&siglus::resource::ToneCurve>,
+) -> image::DynamicImage {
+    let mut rgba = image.to_rgba8();
+    if let Some(curve) = tone_curve {
+        curve.apply_to_image(&mut rgba);
+    }
+    object_render_color(st).apply_to_image(&mut rgba);
+    image::DynamicImage::ImageRgba8(rgba)
+}
 
-        r = (r + st.bright - st.dark).clamp(0.0, 255.0);
-        g = (g + st.bright - st.dark).clamp(0.0, 255.0);
-        b = (b + st.bright - st.dark).clamp(0.0, 255.0);
+/// Multiply alpha by `mask` at each texel's stage position.
+///
+/// The mask is baked into the unrotated image, so each texel is carried
+/// through the object's anchor, scale and `rotate_z` (the same forward
+/// transform the compositor draws with) before sampling.
+fn apply_object_mask(
+    image: &image::DynamicImage,
+    st: &HostObjectState,
+    mask: &siglus::render::RenderMask,
+) -> image::DynamicImage {
+    let mut rgba = image.to_rgba8();
+    let (clip_x, clip_y) = if st.src_clip_use {
+        (st.src_clip_left, st.src_clip_top)
+    } else {
+        (0.0, 0.0)
+    };
+    let (sin, cos) = st.rotate_z_deg.to_radians().sin_cos();
+    for (u, v, px) in rgba.enumerate_pixels_mut() {
+        let lx = u as f32 + 0.5 - clip_x;
+        let ly = v as f32 + 0.5 - clip_y;
+        let dx = (lx - st.center_x) * st.scale_x;
+        let dy = (ly - st.center_y) * st.scale_y;
+        let m = mask.value_at(st.x + dx * cos - dy * sin, st.y + dx * sin + dy * cos);
+        px[3] = (px[3] as f32 * m).round() as u8;
+    }
+    image::DynamicImage::ImageRgba8(rgba)
+}
 
-        r = (r * st.color_rate * st.color_r + st.color_add_r).clamp(0.0, 255.0);
-        g = (g * st.color_rate * st.color_g + st.color_add_g).clamp(0.0, 255.0);
-        b = (b * st.color_rate * st.color_b + st.color_add_b).clamp(0.0, 255.0);
+/// Stack screen effect colors (already range-filtered) over an object image.
+fn apply_effect_colors(
+    image: &image::DynamicImage,
+    effects: &[&siglus::render::RenderEffect],
+) -> image::DynamicImage {
+    let mut rgba = image.to_rgba8();
+    for effect in effects {
+        effect.color.apply_to_image(&mut rgba);
+    }
+    image::DynamicImage::ImageRgba8(rgba)
+}
 
-        p[0] = r as u8;
-        p[1] = g as u8;
-        p[2] = b as u8;
+/// Shape an image so egui's premultiplied source-over (drawn with a
+/// premultiplied `alpha` tint) gives the object's blend mode.
+///
+/// `Add` needs no shaping: it is drawn with a zero-alpha tint, and `Sub`
+/// is drawn by `SubBlendPainter`. `Screen` (`S + D·(1-S)`) is exact for
+/// grey sources and `Mul` (`D·S`) uses the source luminance.
+fn bake_blend_for_egui(
+    image: image::DynamicImage,
+    blend: siglus::render::RenderBlend,
+) -> image::DynamicImage {
+    use siglus::render::RenderBlend;
+    if matches!(
+        blend,
+        RenderBlend::Normal | RenderBlend::Add | RenderBlend::Sub
+    ) {
+        return image;
+    }
+    let mut rgba = image.into_rgba8();
+    for p in rgba.pixels_mut() {
+        let a = p[3] as f32 / 255.0;
+        let [r, g, b] = [p[0] as f32, p[1] as f32, p[2] as f32];
+        match blend {
+            RenderBlend::Screen => {
+                let m = r.max(g).max(b);
+                if m > 0.0 {
+                    for c in 0..3 {
+                        p[c] = (p[c] as f32 * 255.0 / m).round() as u8;
+                    }
+                }
+                p[3] = (m * a).round() as u8;
+            }
+            RenderBlend::Mul => {
+                let y = (0.299 * r + 0.587 * g + 0.114 * b) / 255.0;
+                p[0] = 0;
+                p[1] = 0;
+                p[2] = 0;
+                p[3] = ((1.0 - y) * a * 255.0).round() as u8;
+            }
+            RenderBlend::Normal | RenderBlend::Add | RenderBlend::Sub => {}
+        }
     }
     image::DynamicImage::ImageRgba8(rgba)
 }
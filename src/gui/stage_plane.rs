@@ -5,3 +5,12 @@ enum StagePlane {
     Next,
 }
 
+impl StagePlane {
+    fn render_plane(self) -> siglus::render::RenderPlane {
+        match self {
+            Self::Back => siglus::render::RenderPlane::Back,
+            Self::Front => siglus::render::RenderPlane::Front,
+            Self::Next => siglus::render::RenderPlane::Next,
+        }
+    }
+}
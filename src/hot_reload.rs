@@ -0,0 +1,253 @@
+//! Watch mode for script development: notice rebuilt Scene.pck / override `.dat`
+//! files while a `Runtime` is executing and drop the stale decoded scenes.
+//!
+//! Polling is driven by the VM run loop through `SceneProvider::poll_scene_reload`,
+//! so no watcher thread is needed. Hosts toggle and trigger it through a cloneable
+//! `HotReloadHandle` (e.g. from a GUI thread).
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::SystemTime,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HotReloadOptions {
+    /// Restart the running scene at its nearest label / z-label when it changes.
+    pub restart_scene: bool,
+    /// Minimum time between two file system scans.
+    pub poll_interval_ms: u64,
+}
+
+impl Default for HotReloadOptions {
+    fn default() -> Self {
+        Self {
+            restart_scene: true,
+            poll_interval_ms: 500,
+        }
+    }
+}
+
+/// Thread-safe switches shared between the runtime and its host.
+#[derive(Debug, Clone)]
+pub struct HotReloadHandle {
+    enabled: Arc<AtomicBool>,
+    restart_scene: Arc<AtomicBool>,
+    reload_requested: Arc<AtomicBool>,
+}
+
+impl HotReloadHandle {
+    pub fn new(options: HotReloadOptions) -> Self {
+        Self {
+            enabled: Arc::new(AtomicBool::new(true)),
+            restart_scene: Arc::new(AtomicBool::new(options.restart_scene)),
+            reload_requested: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    /// Turn automatic change detection on or off (manual requests still work).
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    pub fn restart_scene(&self) -> bool {
+        self.restart_scene.load(Ordering::Relaxed)
+    }
+
+    pub fn set_restart_scene(&self, restart: bool) {
+        self.restart_scene.store(restart, Ordering::Relaxed);
+    }
+
+    /// Ask for a reload on the next VM step, ignoring file stamps and the poll interval.
+    pub fn request_reload(&self) {
+        self.reload_requested.store(true, Ordering::Relaxed);
+    }
+
+    pub(crate) fn take_reload_request(&self) -> bool {
+        self.reload_requested.swap(false, Ordering::Relaxed)
+    }
+}
+
+impl Default for HotReloadHandle {
+    fn default() -> Self {
+        Self::new(HotReloadOptions::default())
+    }
+}
+
+/// Modification stamp of one watched file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct FileStamp {
+    modified: Option<SystemTime>,
+    len: u64,
+}
+
+impl FileStamp {
+    pub(crate) fn of(path: &Path) -> Option<Self> {
+        let meta = std::fs::metadata(path).ok()?;
+        Some(Self {
+            modified: meta.modified().ok(),
+            len: meta.len(),
+        })
+    }
+}
+
+/// Runtime-side watcher state: the handle plus the last seen file stamps.
+#[derive(Debug)]
+pub(crate) struct HotReloadWatcher {
+    pub(crate) handle: HotReloadHandle,
+    pub(crate) pack_path: PathBuf,
+    pub(crate) pack_stamp: Option<FileStamp>,
+    /// `<scene>.dat` stamps per override directory, keyed by scene name.
+    pub(crate) dir_stamps: HashMap<PathBuf, HashMap<String, FileStamp>>,
+    poll_interval_ms: u64,
+    /// VM clock time of the last scan.
+    last_poll_ms: Option<u64>,
+}
+
+impl HotReloadWatcher {
+    pub(crate) fn new(
+        pack_path: PathBuf,
+        options: HotReloadOptions,
+        handle: HotReloadHandle,
+    ) -> Self {
+        let pack_stamp = FileStamp::of(&pack_path);
+        Self {
+            handle,
+            pack_path,
+            pack_stamp,
+            dir_stamps: HashMap::new(),
+            poll_interval_ms: options.poll_interval_ms,
+            last_poll_ms: None,
+        }
+    }
+
+    /// Whether a scan should run at `now_ms` on the VM clock; returns `(scan, forced)`.
+    pub(crate) fn due(&mut self, now_ms: u64) -> (bool, bool) {
+        let forced = self.handle.take_reload_request();
+        if !forced && !self.handle.is_enabled() {
+            return (false, false);
+        }
+        if !forced
+            && let Some(last) = self.last_poll_ms
+            && now_ms.saturating_sub(last) < self.poll_interval_ms
+        {
+            return (false, false);
+        }
+        self.last_poll_ms = Some(now_ms);
+        (true, forced)
+    }
+
+    /// Current `<scene>.dat` stamps of an override directory.
+    pub(crate) fn scan_dir(dir: &Path) -> HashMap<String, FileStamp> {
+        let Ok(rd) = std::fs::read_dir(dir) else {
+            return HashMap::new();
+        };
+        rd.flatten()
+            .map(|e| e.path())
+            .filter(|p| {
+                p.extension()
+                    .map(|e| e.eq_ignore_ascii_case("dat"))
+                    .unwrap_or(false)
+            })
+            .filter_map(|p| {
+                let name = p.file_stem()?.to_string_lossy().into_owned();
+                Some((name, FileStamp::of(&p)?))
+            })
+            .collect()
+    }
+
+    /// Record `dir` and return scene names whose `.dat` was added, removed or touched.
+    pub(crate) fn diff_dir(&mut self, dir: &Path) -> Vec<String> {
+        let now = Self::scan_dir(dir);
+        let before = self.dir_stamps.insert(dir.to_path_buf(), now.clone());
+        let Some(before) = before else {
+            return Vec::new();
+        };
+        let mut changed: Vec<String> = now
+            .iter()
+            .filter(|(name, stamp)| before.get(*name) != Some(*stamp))
+            .map(|(name, _)| name.clone())
+            .collect();
+        changed.extend(before.keys().filter(|n| !now.contains_key(*n)).cloned());
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(tag: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("siglus_{tag}_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn watcher(poll_interval_ms: u64) -> HotReloadWatcher {
+        let options = HotReloadOptions {
+            restart_scene: true,
+            poll_interval_ms,
+        };
+        HotReloadWatcher::new(
+            PathBuf::from("missing.pck"),
+            options,
+            HotReloadHandle::new(options),
+        )
+    }
+
+    #[test]
+    fn default_handle_matches_default_options() {
+        let handle = HotReloadHandle::default();
+        assert!(handle.is_enabled());
+        assert_eq!(
+            handle.restart_scene(),
+            HotReloadOptions::default().restart_scene
+        );
+    }
+
+    #[test]
+    fn scans_are_spaced_on_the_vm_clock() {
+        let mut w = watcher(500);
+        assert_eq!(w.due(1000), (true, false));
+        assert_eq!(w.due(1499), (false, false));
+        assert_eq!(w.due(1500), (true, false));
+
+        w.handle.request_reload();
+        assert_eq!(w.due(1501), (true, true));
+
+        w.handle.set_enabled(false);
+        assert_eq!(w.due(5000), (false, false));
+        w.handle.request_reload();
+        assert_eq!(w.due(5000), (true, true));
+    }
+
+    #[test]
+    fn diff_dir_reports_added_touched_and_removed_scenes() {
+        let dir = temp_dir("hot_reload_diff");
+        std::fs::write(dir.join("a.dat"), b"1").unwrap();
+        std::fs::write(dir.join("b.dat"), b"1").unwrap();
+        std::fs::write(dir.join("notes.txt"), b"1").unwrap();
+        let mut w = watcher(0);
+        assert!(w.diff_dir(&dir).is_empty());
+        assert!(w.diff_dir(&dir).is_empty());
+
+        std::fs::write(dir.join("a.dat"), b"22").unwrap();
+        std::fs::remove_file(dir.join("b.dat")).unwrap();
+        std::fs::write(dir.join("C.DAT"), b"1").unwrap();
+        std::fs::write(dir.join("notes.txt"), b"22").unwrap();
+        let mut changed = w.diff_dir(&dir);
+        changed.sort();
+        assert_eq!(changed, vec!["C", "a", "b"]);
+        assert!(w.diff_dir(&dir).is_empty());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -7,6 +7,7 @@ pub mod elm;
 pub mod gameexe;
 pub mod lzss;
 pub mod pck;
+pub mod render;
 pub mod resource;
 
 // VM / interpreter (work in progress)
@@ -15,4 +16,6 @@ pub mod stack;
 pub mod vm;
 
 // Higher-level runtime that can load .pck and run a scene.
+pub mod hot_reload;
 pub mod runtime;
+pub mod scene_provider;
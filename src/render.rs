@@ -0,0 +1,22 @@
+//! CPU-side stage rendering, usable without a GPU or window.
+
+pub mod compositor;
+pub use compositor::*;
+
+pub mod wipe;
+pub use wipe::*;
+
+pub mod mask;
+pub use mask::*;
+
+pub mod quake;
+pub use quake::*;
+
+pub mod font;
+pub use font::*;
+
+pub mod weather;
+pub use weather::*;
+
+pub mod camera;
+pub use camera::*;
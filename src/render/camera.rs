@@ -0,0 +1,413 @@
+//! World cameras (C++ `C_elm_world`) and the CPU perspective projection of
+//! objects placed in 3D.
+//!
+//! World space is left-handed like the engine's Direct3D scene: x right,
+//! y up, z away from the default camera. Objects outside any world that
+//! still use `z`, `rotate_x` or `rotate_y` go through `WorldCamera::stage`,
+//! which looks at the stage so that z = 0 lands 1:1 on stage pixels.
+
+pub type Vec3 = [f32; 3];
+
+fn sub(a: Vec3, b: Vec3) -> Vec3 {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn add(a: Vec3, b: Vec3) -> Vec3 {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+fn scale(a: Vec3, s: f32) -> Vec3 {
+    [a[0] * s, a[1] * s, a[2] * s]
+}
+
+fn dot(a: Vec3, b: Vec3) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: Vec3, b: Vec3) -> Vec3 {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn normalize(a: Vec3) -> Option<Vec3> {
+    let len = dot(a, a).sqrt();
+    (len > f32::EPSILON).then(|| scale(a, 1.0 / len))
+}
+
+/// Points closer to the eye than this are not projected.
+const NEAR_Z: f32 = 1.0;
+
+/// One world's camera (`camera_eye`, `camera_pint`, `camera_up`,
+/// `camera_view_angle`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WorldCamera {
+    pub eye: Vec3,
+    /// Look-at point (the engine's "pint").
+    pub pint: Vec3,
+    pub up: Vec3,
+    /// Vertical field of view in degrees (`camera_view_angle` is in tenths).
+    pub view_angle_deg: f32,
+}
+
+impl Default for WorldCamera {
+    /// C++ `C_elm_world::reinit`: 1000 units in front of the origin, 45°.
+    fn default() -> Self {
+        Self {
+            eye: [0.0, 0.0, -1000.0],
+            pint: [0.0, 0.0, 0.0],
+            up: [0.0, 1.0, 0.0],
+            view_angle_deg: 45.0,
+        }
+    }
+}
+
+/// Camera-space basis: unit right, up and forward vectors.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CameraBasis {
+    pub right: Vec3,
+    pub up: Vec3,
+    pub forward: Vec3,
+}
+
+/// A point on the stage after projection; `depth` is the camera-space
+/// distance along the view direction.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProjectedPoint {
+    pub x: f32,
+    pub y: f32,
+    pub depth: f32,
+}
+
+fn direction(rotate_h: i32, rotate_v: i32) -> Vec3 {
+    let (sh, ch) = (rotate_h as f32 / 10.0).to_radians().sin_cos();
+    let (sv, cv) = (rotate_v as f32 / 10.0).to_radians().sin_cos();
+    [sh * cv, sv, -ch * cv]
+}
+
+impl WorldCamera {
+    /// The camera 2D objects are seen through: the default view angle,
+    /// backed off until z = 0 fills a stage `height` pixels tall exactly.
+    pub fn stage(height: f32) -> Self {
+        let camera = Self::default();
+        let dist = camera.focal_length(height);
+        Self {
+            eye: [0.0, 0.0, -dist],
+            ..camera
+        }
+    }
+
+    /// `calc_camera_eye(distance, rotate_h, rotate_v)`: put the eye
+    /// `distance` away from the look-at point, turned `rotate_h` around the
+    /// vertical axis and raised `rotate_v` (both in tenths of a degree).
+    pub fn calc_eye(&mut self, distance: i32, rotate_h: i32, rotate_v: i32) {
+        self.eye = add(
+            self.pint,
+            scale(direction(rotate_h, rotate_v), distance as f32),
+        );
+    }
+
+    /// `calc_camera_pint(distance, rotate_h, rotate_v)`: the inverse of
+    /// `calc_eye`, moving the look-at point around the eye.
+    pub fn calc_pint(&mut self, distance: i32, rotate_h: i32, rotate_v: i32) {
+        self.pint = sub(
+            self.eye,
+            scale(direction(rotate_h, rotate_v), distance as f32),
+        );
+    }
+
+    /// Distance from the eye at which one world unit covers one stage pixel.
+    pub fn focal_length(&self, height: f32) -> f32 {
+        let half = (self.view_angle_deg.clamp(1.0, 179.0) / 2.0).to_radians();
+        height / 2.0 / half.tan()
+    }
+
+    /// Right/up/forward of the view (Direct3D `LookAtLH`); `None` when the
+    /// eye sits on the look-at point or `up` is parallel to the view.
+    pub fn basis(&self) -> Option<CameraBasis> {
+        let forward = normalize(sub(self.pint, self.eye))?;
+        let right = normalize(cross(self.up, forward))?;
+        let up = cross(forward, right);
+        Some(CameraBasis { right, up, forward })
+    }
+
+    /// Camera-space depth of a world point.
+    pub fn depth_of(&self, p: Vec3) -> f32 {
+        match self.basis() {
+            Some(basis) => dot(sub(p, self.eye), basis.forward),
+            None => 0.0,
+        }
+    }
+
+    /// Project a world point onto a `width` x `height` stage.
+    pub fn project(&self, p: Vec3, width: f32, height: f32) -> Option<ProjectedPoint> {
+        let basis = self.basis()?;
+        let rel = sub(p, self.eye);
+        let depth = dot(rel, basis.forward);
+        if depth < NEAR_Z {
+            return None;
+        }
+        let f = self.focal_length(height) / depth;
+        Some(ProjectedPoint {
+            x: width / 2.0 + dot(rel, basis.right) * f,
+            y: height / 2.0 - dot(rel, basis.up) * f,
+            depth,
+        })
+    }
+}
+
+/// An image placed in 3D: the object's position, anchor, size and
+/// rotations. `width`/`height` is the drawn (source-clipped) image size.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sprite3d {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub center_x: f32,
+    pub center_y: f32,
+    pub width: f32,
+    pub height: f32,
+    pub scale_x: f32,
+    pub scale_y: f32,
+    pub rotate_x_deg: f32,
+    pub rotate_y_deg: f32,
+    pub rotate_z_deg: f32,
+    /// Always faces the camera; `rotate_x`/`rotate_y` are ignored.
+    pub billboard: bool,
+    /// `x`/`y`/`z` are world coordinates; otherwise they are stage pixels
+    /// (y down) seen through `WorldCamera::stage`.
+    pub in_world: bool,
+}
+
+/// A sprite's image corners on the stage (top-left, top-right,
+/// bottom-right, bottom-left) and the depth of its anchor.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProjectedQuad {
+    pub corners: [[f32; 2]; 4],
+    pub depth: f32,
+}
+
+impl Sprite3d {
+    /// Anchor in world space.
+    pub fn anchor(&self, width: f32, height: f32) -> Vec3 {
+        if self.in_world {
+            [self.x, self.y, self.z]
+        } else {
+            [self.x - width / 2.0, height / 2.0 - self.y, self.z]
+        }
+    }
+
+    /// World-space offset of an image-local point (y down) from the anchor.
+    fn corner_offset(&self, lx: f32, ly: f32, camera: &CameraBasis) -> Vec3 {
+        // Scale and rotate_z in stage orientation, like the 2D path.
+        let dx = (lx - self.center_x) * self.scale_x;
+        let dy = (ly - self.center_y) * self.scale_y;
+        let (sz, cz) = self.rotate_z_deg.to_radians().sin_cos();
+        let (x, y) = (dx * cz - dy * sz, dx * sz + dy * cz);
+        if self.billboard {
+            return add(scale(camera.right, x), scale(camera.up, -y));
+        }
+        // Flip to y up, then pitch around x and yaw around y.
+        let (x, y, z) = (x, -y, 0.0f32);
+        let (sx, cx) = self.rotate_x_deg.to_radians().sin_cos();
+        let (y, z) = (y * cx - z * sx, y * sx + z * cx);
+        let (sy, cy) = self.rotate_y_deg.to_radians().sin_cos();
+        let (x, z) = (x * cy + z * sy, -x * sy + z * cy);
+        [x, y, z]
+    }
+
+    /// Project the image corners through `camera` onto a `width` x `height`
+    /// stage. `None` if any corner is behind the eye.
+    pub fn project(&self, camera: &WorldCamera, width: f32, height: f32) -> Option<ProjectedQuad> {
+        let basis = camera.basis()?;
+        let anchor = self.anchor(width, height);
+        let local = [
+            (0.0, 0.0),
+            (self.width, 0.0),
+            (self.width, self.height),
+            (0.0, self.height),
+        ];
+        let mut corners = [[0.0; 2]; 4];
+        for (out, (lx, ly)) in corners.iter_mut().zip(local) {
+            let p = camera.project(
+                add(anchor, self.corner_offset(lx, ly, &basis)),
+                width,
+                height,
+            )?;
+            *out = [p.x, p.y];
+        }
+        Some(ProjectedQuad {
+            corners,
+            depth: camera.depth_of(anchor),
+        })
+    }
+}
+
+/// Plane-to-plane perspective map, applied with the homogeneous divide.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Homography {
+    m: [[f32; 3]; 3],
+}
+
+impl Homography {
+    /// Map the unit square ((0,0), (1,0), (1,1), (0,1)) onto `quad`.
+    pub fn square_to_quad(quad: &[[f32; 2]; 4]) -> Option<Self> {
+        let [[x0, y0], [x1, y1], [x2, y2], [x3, y3]] = *quad;
+        let sx = x0 - x1 + x2 - x3;
+        let sy = y0 - y1 + y2 - y3;
+        let (g, h) = if sx.abs() < 1e-6 && sy.abs() < 1e-6 {
+            (0.0, 0.0)
+        } else {
+            let (dx1, dx2) = (x1 - x2, x3 - x2);
+            let (dy1, dy2) = (y1 - y2, y3 - y2);
+            let den = dx1 * dy2 - dx2 * dy1;
+            if den.abs() < 1e-9 {
+                return None;
+            }
+            ((sx * dy2 - dx2 * sy) / den, (dx1 * sy - sx * dy1) / den)
+        };
+        Some(Self {
+            m: [
+                [x1 - x0 + g * x1, x3 - x0 + h * x3, x0],
+                [y1 - y0 + g * y1, y3 - y0 + h * y3, y0],
+                [g, h, 1.0],
+            ],
+        })
+    }
+
+    pub fn apply(&self, x: f32, y: f32) -> Option<(f32, f32)> {
+        let m = &self.m;
+        let w = m[2][0] * x + m[2][1] * y + m[2][2];
+        if w.abs() < 1e-9 {
+            return None;
+        }
+        Some((
+            (m[0][0] * x + m[0][1] * y + m[0][2]) / w,
+            (m[1][0] * x + m[1][1] * y + m[1][2]) / w,
+        ))
+    }
+
+    pub fn inverse(&self) -> Option<Self> {
+        let m = &self.m;
+        let c = |r0: usize, r1: usize, c0: usize, c1: usize| {
+            m[r0][c0] * m[r1][c1] - m[r0][c1] * m[r1][c0]
+        };
+        let adj = [
+            [c(1, 2, 1, 2), -c(0, 2, 1, 2), c(0, 1, 1, 2)],
+            [-c(1, 2, 0, 2), c(0, 2, 0, 2), -c(0, 1, 0, 2)],
+            [c(1, 2, 0, 1), -c(0, 2, 0, 1), c(0, 1, 0, 1)],
+        ];
+        let det = m[0][0] * adj[0][0] + m[0][1] * adj[1][0] + m[0][2] * adj[2][0];
+        if det.abs() < 1e-12 {
+            return None;
+        }
+        let k = 1.0 / det;
+        Some(Self {
+            m: adj.map(|row| row.map(|v| v * k)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: [f32; 2], b: [f32; 2]) -> bool {
+        (a[0] - b[0]).abs() < 1e-3 && (a[1] - b[1]).abs() < 1e-3
+    }
+
+    fn flat_sprite(x: f32, y: f32, z: f32) -> Sprite3d {
+        Sprite3d {
+            x,
+            y,
+            z,
+            center_x: 0.0,
+            center_y: 0.0,
+            width: 100.0,
+            height: 50.0,
+            scale_x: 1.0,
+            scale_y: 1.0,
+            rotate_x_deg: 0.0,
+            rotate_y_deg: 0.0,
+            rotate_z_deg: 0.0,
+            billboard: false,
+            in_world: false,
+        }
+    }
+
+    #[test]
+    fn stage_camera_maps_z0_to_stage_pixels() {
+        let camera = WorldCamera::stage(600.0);
+        let quad = flat_sprite(10.0, 20.0, 0.0)
+            .project(&camera, 800.0, 600.0)
+            .expect("in front of the eye");
+        let expected = [[10.0, 20.0], [110.0, 20.0], [110.0, 70.0], [10.0, 70.0]];
+        for (got, want) in quad.corners.iter().zip(expected) {
+            assert!(close(*got, want), "{got:?} != {want:?}");
+        }
+
+        // Farther away is smaller.
+        let far = flat_sprite(10.0, 20.0, 500.0)
+            .project(&camera, 800.0, 600.0)
+            .unwrap();
+        assert!(far.corners[1][0] - far.corners[0][0] < 100.0);
+        assert!(far.depth > quad.depth);
+    }
+
+    #[test]
+    fn points_behind_the_eye_are_not_projected() {
+        let camera = WorldCamera::default();
+        let center = camera.project([0.0, 0.0, 0.0], 800.0, 600.0).unwrap();
+        assert!(close([center.x, center.y], [400.0, 300.0]));
+        assert_eq!(camera.project([0.0, 0.0, -2000.0], 800.0, 600.0), None);
+        assert_eq!(
+            flat_sprite(0.0, 0.0, -5000.0).project(&camera, 800.0, 600.0),
+            None
+        );
+
+        // Eye on the look-at point has no view direction.
+        let degenerate = WorldCamera {
+            eye: [0.0; 3],
+            ..WorldCamera::default()
+        };
+        assert_eq!(degenerate.basis(), None);
+    }
+
+    #[test]
+    fn calc_eye_and_pint_orbit_in_tenths_of_a_degree() {
+        let mut camera = WorldCamera::default();
+        camera.calc_eye(500, 900, 0);
+        assert!((camera.eye[0] - 500.0).abs() < 1e-3);
+        assert!(camera.eye[1].abs() < 1e-3 && camera.eye[2].abs() < 1e-3);
+
+        camera.calc_pint(500, 900, 0);
+        assert!(camera.pint.iter().all(|v| v.abs() < 1e-3));
+
+        camera.calc_eye(100, 0, 900);
+        assert!((camera.eye[1] - 100.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn homography_maps_square_corners_and_inverts() {
+        let quad = [[10.0, 10.0], [90.0, 20.0], [80.0, 70.0], [20.0, 60.0]];
+        let h = Homography::square_to_quad(&quad).unwrap();
+        let square = [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]];
+        for (s, q) in square.iter().zip(quad) {
+            let (x, y) = h.apply(s[0], s[1]).unwrap();
+            assert!(close([x, y], q), "{:?} -> ({x}, {y})", s);
+        }
+
+        let inv = h.inverse().unwrap();
+        let (x, y) = h.apply(0.25, 0.75).unwrap();
+        let (u, v) = inv.apply(x, y).unwrap();
+        assert!(close([u, v], [0.25, 0.75]));
+    }
+
+    #[test]
+    fn degenerate_quads_have_no_homography() {
+        let line = [[0.0, 0.0], [1.0, 0.0], [2.0, 0.0], [3.0, 0.0]];
+        assert_eq!(Homography::square_to_quad(&line), None);
+    }
+}
@@ -0,0 +1,1111 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use image::RgbaImage;
+
+use super::{
+    Homography, ProjectedQuad, QuakeTransform, RenderMask, RenderQuake, Sprite3d, WorldCamera,
+    quake_transform_for_order,
+};
+use crate::resource::ToneCurve;
+
+/// Stage plane an object or background belongs to (C++ `TNM_STAGE_BACK/FRONT/NEXT`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum RenderPlane {
+    Back,
+    #[default]
+    Front,
+    Next,
+}
+
+/// Axis-aligned rectangle in stage pixels (`right`/`bottom` exclusive).
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct RenderRect {
+    pub left: f32,
+    pub top: f32,
+    pub right: f32,
+    pub bottom: f32,
+}
+
+impl RenderRect {
+    pub fn new(left: f32, top: f32, right: f32, bottom: f32) -> Self {
+        Self {
+            left,
+            top,
+            right,
+            bottom,
+        }
+    }
+
+    pub fn width(&self) -> f32 {
+        self.right - self.left
+    }
+
+    pub fn height(&self) -> f32 {
+        self.bottom - self.top
+    }
+
+    pub fn intersect(&self, other: &RenderRect) -> RenderRect {
+        RenderRect {
+            left: self.left.max(other.left),
+            top: self.top.max(other.top),
+            right: self.right.min(other.right),
+            bottom: self.bottom.min(other.bottom),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.width() <= 0.0 || self.height() <= 0.0
+    }
+}
+
+/// Per-object color filter (C++ elm_object.cpp color properties).
+///
+/// Normalized like the GUI host stores them: `color_rate` 1.0 = 1000, `color_r/g/b`
+/// 1.0 = 255, `mono` 1.0 = 255; `color_add_*`, `bright` and `dark` stay in 0..255.
+/// `tint_*` is the screen effect's blend toward a color (C++ `color_r/g/b` at
+/// `color_rate`/255), in 0..255 with `tint_rate` 1.0 = 255.
+/// Applied in the order mono → reverse → bright/dark → tint → rate/add.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RenderColor {
+    pub color_rate: f32,
+    pub color_r: f32,
+    pub color_g: f32,
+    pub color_b: f32,
+    pub color_add_r: f32,
+    pub color_add_g: f32,
+    pub color_add_b: f32,
+    pub bright: f32,
+    pub dark: f32,
+    pub mono: f32,
+    pub reverse: bool,
+    pub tint_r: f32,
+    pub tint_g: f32,
+    pub tint_b: f32,
+    pub tint_rate: f32,
+}
+
+impl Default for RenderColor {
+    fn default() -> Self {
+        Self {
+            color_rate: 1.0,
+            color_r: 1.0,
+            color_g: 1.0,
+            color_b: 1.0,
+            color_add_r: 0.0,
+            color_add_g: 0.0,
+            color_add_b: 0.0,
+            bright: 0.0,
+            dark: 0.0,
+            mono: 0.0,
+            reverse: false,
+            tint_r: 0.0,
+            tint_g: 0.0,
+            tint_b: 0.0,
+            tint_rate: 0.0,
+        }
+    }
+}
+
+impl RenderColor {
+    pub fn is_identity(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Filter one RGB triple (0..255 floats).
+    pub fn apply_rgb(&self, rgb: [f32; 3]) -> [f32; 3] {
+        let [mut r, mut g, mut b] = rgb;
+        if self.mono > 0.0 {
+            let y = 0.299 * r + 0.587 * g + 0.114 * b;
+            let t = self.mono;
+            r += (y - r) * t;
+            g += (y - g) * t;
+            b += (y - b) * t;
+        }
+        if self.reverse {
+            r = 255.0 - r;
+            g = 255.0 - g;
+            b = 255.0 - b;
+        }
+        let shift = self.bright - self.dark;
+        r = (r + shift).clamp(0.0, 255.0);
+        g = (g + shift).clamp(0.0, 255.0);
+        b = (b + shift).clamp(0.0, 255.0);
+        if self.tint_rate > 0.0 {
+            let t = self.tint_rate.min(1.0);
+            r += (self.tint_r - r) * t;
+            g += (self.tint_g - g) * t;
+            b += (self.tint_b - b) * t;
+        }
+        r = (r * self.color_rate * self.color_r + self.color_add_r).clamp(0.0, 255.0);
+        g = (g * self.color_rate * self.color_g + self.color_add_g).clamp(0.0, 255.0);
+        b = (b * self.color_rate * self.color_b + self.color_add_b).clamp(0.0, 255.0);
+        [r, g, b]
+    }
+
+    /// Filter every pixel of `img` in place (alpha untouched).
+    pub fn apply_to_image(&self, img: &mut RgbaImage) {
+        if self.is_identity() {
+            return;
+        }
+        for p in img.pixels_mut() {
+            let [r, g, b] = self.apply_rgb([p[0] as f32, p[1] as f32, p[2] as f32]);
+            p[0] = r as u8;
+            p[1] = g as u8;
+            p[2] = b as u8;
+        }
+    }
+}
+
+/// How an object's pixels combine with what is under them (C++ `TNM_BLEND_TYPE_*`,
+/// the `blend` property).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RenderBlend {
+    #[default]
+    Normal,
+    Add,
+    Sub,
+    Mul,
+    Screen,
+}
+
+impl RenderBlend {
+    /// Map the script's `blend` value; unknown values draw normally.
+    pub fn from_script(v: i32) -> Self {
+        match v {
+            1 => Self::Add,
+            2 => Self::Sub,
+            3 => Self::Mul,
+            4 => Self::Screen,
+            _ => Self::Normal,
+        }
+    }
+
+    /// Combined color of source `s` over destination `d` (0..255 floats),
+    /// before coverage is applied.
+    pub fn combine(&self, d: [f32; 3], s: [f32; 3]) -> [f32; 3] {
+        let f = |d: f32, s: f32| match self {
+            Self::Normal => s,
+            Self::Add => d + s,
+            Self::Sub => d - s,
+            Self::Mul => d * s / 255.0,
+            Self::Screen => d + s - d * s / 255.0,
+        };
+        [f(d[0], s[0]), f(d[1], s[1]), f(d[2], s[2])].map(|v| v.clamp(0.0, 255.0))
+    }
+}
+
+/// One repeat of an object from its rep lists (`x_rep[i]`, `y_rep[i]`,
+/// `z_rep[i]`, `tr_rep[i]`): the object is drawn again shifted by
+/// `x`/`y`/`z` with its alpha scaled by `alpha`. A `z` shift takes the
+/// repeat through the camera like any other object off the stage plane.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RenderRep {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub alpha: f32,
+}
+
+impl RenderRep {
+    /// The object drawn once, where it is.
+    pub const IDENTITY: RenderRep = RenderRep {
+        x: 0.0,
+        y: 0.0,
+        z: 0.0,
+        alpha: 1.0,
+    };
+
+    /// Zip the script rep lists into repeats, one per index of the longest
+    /// list. Missing offsets are 0 and missing `tr_rep` entries 255. All
+    /// lists empty gives no repeats.
+    pub fn from_lists(x: &[i32], y: &[i32], z: &[i32], tr: &[i32]) -> Vec<RenderRep> {
+        let n = x.len().max(y.len()).max(z.len()).max(tr.len());
+        (0..n)
+            .map(|i| RenderRep {
+                x: x.get(i).copied().unwrap_or(0) as f32,
+                y: y.get(i).copied().unwrap_or(0) as f32,
+                z: z.get(i).copied().unwrap_or(0) as f32,
+                alpha: (tr.get(i).copied().unwrap_or(255) as f32 / 255.0).clamp(0.0, 1.0),
+            })
+            .collect()
+    }
+}
+
+/// One drawable stage object, mirroring the host-side object state.
+///
+/// `x`/`y` is where the anchor (`center_x`/`center_y`, measured from the
+/// source-clip origin) lands on the stage; scale and rotation pivot on it.
+#[derive(Debug, Clone)]
+pub struct RenderObject {
+    pub plane: RenderPlane,
+    pub index: i32,
+    /// Creation sequence; breaks order/layer ties like the engine's object list order.
+    pub seq: u64,
+    pub image: Option<Arc<RgbaImage>>,
+    pub visible: bool,
+    pub x: f32,
+    pub y: f32,
+    /// Depth; non-zero moves the object off the stage plane (see `is_3d`).
+    pub z: f32,
+    pub center_x: f32,
+    pub center_y: f32,
+    pub scale_x: f32,
+    pub scale_y: f32,
+    pub rotate_x_deg: f32,
+    pub rotate_y_deg: f32,
+    pub rotate_z_deg: f32,
+    /// `world` number the object is placed in (x/y/z in world units), -1 for the stage.
+    pub world: i32,
+    /// `create_billboard`: faces the camera.
+    pub billboard: bool,
+    /// 0.0..=1.0 multiplier on the image alpha.
+    pub alpha: f32,
+    pub order: i32,
+    pub layer: i32,
+    pub src_clip: Option<RenderRect>,
+    pub dst_clip: Option<RenderRect>,
+    pub color: RenderColor,
+    /// `mask_no` mask (slot image at its x/y), in stage coordinates; scales alpha.
+    pub mask: Option<RenderMask>,
+    /// `tonecurve_no` curve, applied to the source before `color`.
+    pub tone_curve: Option<Arc<ToneCurve>>,
+    pub blend: RenderBlend,
+    /// Rep-list repeats; empty draws the object once where it stands.
+    pub reps: Vec<RenderRep>,
+}
+
+impl Default for RenderObject {
+    fn default() -> Self {
+        Self {
+            plane: RenderPlane::Front,
+            index: 0,
+            seq: 0,
+            image: None,
+            visible: true,
+            x: 0.0,
+            y: 0.0,
+            z: 0.0,
+            center_x: 0.0,
+            center_y: 0.0,
+            scale_x: 1.0,
+            scale_y: 1.0,
+            rotate_x_deg: 0.0,
+            rotate_y_deg: 0.0,
+            rotate_z_deg: 0.0,
+            world: -1,
+            billboard: false,
+            alpha: 1.0,
+            order: 0,
+            layer: 0,
+            src_clip: None,
+            dst_clip: None,
+            color: RenderColor::default(),
+            mask: None,
+            tone_curve: None,
+            blend: RenderBlend::Normal,
+            reps: Vec::new(),
+        }
+    }
+}
+
+impl RenderObject {
+    pub fn new(plane: RenderPlane, index: i32, image: Arc<RgbaImage>) -> Self {
+        Self {
+            plane,
+            index,
+            image: Some(image),
+            ..Self::default()
+        }
+    }
+
+    /// Sort key used by the compositor (and the GUI's `draw_objects`).
+    pub fn sort_key(&self) -> (i32, i32, RenderPlane, i32, u64) {
+        (self.order, self.layer, self.plane, self.index, self.seq)
+    }
+
+    /// Drawn through a camera rather than the flat 2D transform: placed in a
+    /// world, a billboard, or moved off / tilted out of the stage plane.
+    pub fn is_3d(&self) -> bool {
+        self.world >= 0
+            || self.billboard
+            || self.z != 0.0
+            || self.rotate_x_deg != 0.0
+            || self.rotate_y_deg != 0.0
+    }
+
+    /// Repeats to draw: the rep lists, or the object once when it has none.
+    pub fn repeats(&self) -> &[RenderRep] {
+        if self.reps.is_empty() {
+            std::slice::from_ref(&RenderRep::IDENTITY)
+        } else {
+            &self.reps
+        }
+    }
+
+    /// The object as a 3D sprite; `width`/`height` is its source-clipped size.
+    pub fn sprite3d(&self, width: f32, height: f32) -> Sprite3d {
+        Sprite3d {
+            x: self.x,
+            y: self.y,
+            z: self.z,
+            center_x: self.center_x,
+            center_y: self.center_y,
+            width,
+            height,
+            scale_x: self.scale_x,
+            scale_y: self.scale_y,
+            rotate_x_deg: self.rotate_x_deg,
+            rotate_y_deg: self.rotate_y_deg,
+            rotate_z_deg: self.rotate_z_deg,
+            billboard: self.billboard,
+            in_world: self.world >= 0,
+        }
+    }
+}
+
+/// Screen effect layer (`screen.effect[n]`) resolved for one frame.
+///
+/// Shifts and filters every object whose order/layer falls in the inclusive
+/// ranges; effects stack in list order on top of the object's own color.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RenderEffect {
+    pub x: f32,
+    pub y: f32,
+    pub color: RenderColor,
+    pub begin_order: i32,
+    pub end_order: i32,
+    pub begin_layer: i32,
+    pub end_layer: i32,
+}
+
+impl Default for RenderEffect {
+    fn default() -> Self {
+        Self {
+            x: 0.0,
+            y: 0.0,
+            color: RenderColor::default(),
+            begin_order: i32::MIN,
+            end_order: i32::MAX,
+            begin_layer: i32::MIN,
+            end_layer: i32::MAX,
+        }
+    }
+}
+
+impl RenderEffect {
+    pub fn covers(&self, order: i32, layer: i32) -> bool {
+        (self.begin_order..=self.end_order).contains(&order)
+            && (self.begin_layer..=self.end_layer).contains(&layer)
+    }
+
+    /// No shift and no color change.
+    pub fn is_identity(&self) -> bool {
+        self.x == 0.0 && self.y == 0.0 && self.color.is_identity()
+    }
+}
+
+/// Everything needed to produce one stage frame.
+#[derive(Debug, Clone)]
+pub struct RenderScene {
+    pub width: u32,
+    pub height: u32,
+    pub clear_color: [u8; 4],
+    /// Full-stage plane images, drawn stretched in `RenderPlane` order before objects.
+    pub backgrounds: Vec<(RenderPlane, Arc<RgbaImage>)>,
+    pub objects: Vec<RenderObject>,
+    /// Screen effects applied to the objects in their ranges.
+    pub effects: Vec<RenderEffect>,
+    /// Running quakes, applied per object order.
+    pub quakes: Vec<RenderQuake>,
+    /// `screen.shake` offset, applied to every object.
+    pub shake: (f32, f32),
+    /// Each plane's world cameras by world number, for objects with `world >= 0`.
+    pub worlds: BTreeMap<RenderPlane, Vec<WorldCamera>>,
+}
+
+impl RenderScene {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            clear_color: [8, 8, 16, 255],
+            backgrounds: Vec::new(),
+            objects: Vec::new(),
+            effects: Vec::new(),
+            quakes: Vec::new(),
+            shake: (0.0, 0.0),
+            worlds: BTreeMap::new(),
+        }
+    }
+
+    /// Quake and shake transform for objects at `order`.
+    pub fn quake_transform(&self, order: i32) -> QuakeTransform {
+        quake_transform_for_order(&self.quakes, self.shake, order)
+    }
+
+    /// Camera an object is seen through: its world's, or the stage camera.
+    pub fn camera_for(&self, obj: &RenderObject) -> WorldCamera {
+        match usize::try_from(obj.world) {
+            Ok(no) => self
+                .worlds
+                .get(&obj.plane)
+                .and_then(|worlds| worlds.get(no))
+                .copied()
+                .unwrap_or_default(),
+            Err(_) => WorldCamera::stage(self.height as f32),
+        }
+    }
+
+    /// Camera-space depth of the object's anchor; flat objects all share the
+    /// stage plane's depth.
+    pub fn object_depth(&self, obj: &RenderObject) -> f32 {
+        let camera = self.camera_for(obj);
+        let anchor = obj
+            .sprite3d(0.0, 0.0)
+            .anchor(self.width as f32, self.height as f32);
+        camera.depth_of(if obj.is_3d() { anchor } else { [0.0; 3] })
+    }
+
+    /// Image corners of one repeat of a 3D object on the stage.
+    pub fn project_object(&self, obj: &RenderObject, rep: &RenderRep) -> Option<ProjectedQuad> {
+        let (_, src) = object_source(obj)?;
+        let mut sprite = obj.sprite3d(src.width(), src.height());
+        sprite.x += rep.x;
+        sprite.y += rep.y;
+        sprite.z += rep.z;
+        sprite.project(&self.camera_for(obj), self.width as f32, self.height as f32)
+    }
+
+    /// Objects in draw order: `(order, layer, plane, index, seq)`, hidden ones
+    /// dropped. Within one order/layer, farther objects draw first.
+    pub fn sorted_objects(&self) -> Vec<&RenderObject> {
+        let mut objs: Vec<(f32, &RenderObject)> = self
+            .objects
+            .iter()
+            .filter(|o| o.visible)
+            .map(|o| (self.object_depth(o), o))
+            .collect();
+        objs.sort_by(|(da, a), (db, b)| {
+            (a.order, a.layer)
+                .cmp(&(b.order, b.layer))
+                .then(db.total_cmp(da))
+                .then(a.sort_key().cmp(&b.sort_key()))
+        });
+        objs.into_iter().map(|(_, o)| o).collect()
+    }
+
+    /// Render into a fresh RGBA framebuffer.
+    pub fn compose(&self) -> RgbaImage {
+        let mut fb = RgbaImage::from_pixel(
+            self.width.max(1),
+            self.height.max(1),
+            image::Rgba(self.clear_color),
+        );
+        self.compose_into(&mut fb);
+        fb
+    }
+
+    /// Render over an existing framebuffer (not cleared).
+    pub fn compose_into(&self, fb: &mut RgbaImage) {
+        let mut backgrounds: Vec<&(RenderPlane, Arc<RgbaImage>)> =
+            self.backgrounds.iter().collect();
+        backgrounds.sort_by_key(|(plane, _)| *plane);
+        for (_, img) in backgrounds {
+            draw_stretched(fb, img);
+        }
+        for obj in self.sorted_objects() {
+            let effects: Vec<&RenderEffect> = self
+                .effects
+                .iter()
+                .filter(|e| !e.is_identity() && e.covers(obj.order, obj.layer))
+                .collect();
+            let quake = self.quake_transform(obj.order);
+            let (w, h) = (self.width as f32, self.height as f32);
+            for rep in obj.repeats() {
+                if obj.is_3d() || rep.z != 0.0 {
+                    let Some(mut quad) = self.project_object(obj, rep) else {
+                        continue;
+                    };
+                    if !quake.is_identity() {
+                        quad.corners = quad.corners.map(|[x, y]| {
+                            let (x, y) = quake.apply_point(x, y, w, h);
+                            [x, y]
+                        });
+                    }
+                    draw_object_projected(fb, obj, &quad, rep.alpha, &effects);
+                    continue;
+                }
+                let (x, y) = quake.apply_point(obj.x + rep.x, obj.y + rep.y, w, h);
+                let at = Placement {
+                    x,
+                    y,
+                    scale: quake.scale,
+                    alpha: obj.alpha * rep.alpha,
+                };
+                draw_object_placed(fb, obj, at, &effects);
+            }
+        }
+    }
+}
+
+/// Source-over blend of a non-premultiplied color into `dst`.
+pub fn blend_pixel(dst: &mut image::Rgba<u8>, rgb: [f32; 3], alpha: f32) {
+    if alpha <= 0.0 {
+        return;
+    }
+    let a = alpha.min(1.0);
+    let da = dst[3] as f32 / 255.0;
+    let out_a = a + da * (1.0 - a);
+    if out_a <= 0.0 {
+        return;
+    }
+    for (c, s) in rgb.iter().enumerate() {
+        let d = dst[c] as f32;
+        let v = (s * a + d * da * (1.0 - a)) / out_a;
+        dst[c] = v.round().clamp(0.0, 255.0) as u8;
+    }
+    dst[3] = (out_a * 255.0).round() as u8;
+}
+
+/// `blend_pixel` with the source first combined with `dst` by `mode`.
+pub fn blend_pixel_mode(dst: &mut image::Rgba<u8>, rgb: [f32; 3], alpha: f32, mode: RenderBlend) {
+    if mode == RenderBlend::Normal {
+        blend_pixel(dst, rgb, alpha);
+        return;
+    }
+    let d = [dst[0] as f32, dst[1] as f32, dst[2] as f32];
+    blend_pixel(dst, mode.combine(d, rgb), alpha);
+}
+
+/// Bilinear sample at continuous texel coordinates (pixel centers at `.5`),
+/// clamped to `bounds` so clipped edges do not bleed neighbouring texels.
+pub fn sample_bilinear(img: &RgbaImage, u: f32, v: f32, bounds: RenderRect) -> [f32; 4] {
+    let max_x = (bounds.right - 1.0).max(bounds.left);
+    let max_y = (bounds.bottom - 1.0).max(bounds.top);
+    let fx = (u - 0.5).clamp(bounds.left, max_x);
+    let fy = (v - 0.5).clamp(bounds.top, max_y);
+    let x0 = fx.floor();
+    let y0 = fy.floor();
+    let tx = fx - x0;
+    let ty = fy - y0;
+    let x1 = (x0 + 1.0).min(max_x);
+    let y1 = (y0 + 1.0).min(max_y);
+    let px = |x: f32, y: f32| img.get_pixel(x as u32, y as u32).0;
+    let (p00, p10, p01, p11) = (px(x0, y0), px(x1, y0), px(x0, y1), px(x1, y1));
+    let mut out = [0.0f32; 4];
+    for c in 0..4 {
+        let top = p00[c] as f32 * (1.0 - tx) + p10[c] as f32 * tx;
+        let bot = p01[c] as f32 * (1.0 - tx) + p11[c] as f32 * tx;
+        out[c] = top * (1.0 - ty) + bot * ty;
+    }
+    out
+}
+
+fn draw_stretched(fb: &mut RgbaImage, img: &RgbaImage) {
+    if img.width() == 0 || img.height() == 0 {
+        return;
+    }
+    let sx = img.width() as f32 / fb.width() as f32;
+    let sy = img.height() as f32 / fb.height() as f32;
+    let bounds = RenderRect::new(0.0, 0.0, img.width() as f32, img.height() as f32);
+    for (x, y, dst) in fb.enumerate_pixels_mut() {
+        let s = sample_bilinear(img, (x as f32 + 0.5) * sx, (y as f32 + 0.5) * sy, bounds);
+        blend_pixel(dst, [s[0], s[1], s[2]], s[3] / 255.0);
+    }
+}
+
+/// Draw one object onto `fb` with its transform, clips, alpha, tone curve,
+/// color filter and blend mode.
+pub fn draw_object(fb: &mut RgbaImage, obj: &RenderObject) {
+    draw_object_with_effects(fb, obj, &[]);
+}
+
+/// `draw_object` with screen effects (already range-filtered) stacked on top.
+/// Repeats are drawn flat; `z_rep` needs the scene camera (`compose_into`).
+pub fn draw_object_with_effects(fb: &mut RgbaImage, obj: &RenderObject, effects: &[&RenderEffect]) {
+    for rep in obj.repeats() {
+        let at = Placement {
+            x: obj.x + rep.x,
+            y: obj.y + rep.y,
+            scale: 1.0,
+            alpha: obj.alpha * rep.alpha,
+        };
+        draw_object_placed(fb, obj, at, effects);
+    }
+}
+
+/// Where one flat repeat of an object lands: its anchor on the stage, a
+/// zoom on top of its own scale, and its final alpha.
+#[derive(Debug, Clone, Copy)]
+struct Placement {
+    x: f32,
+    y: f32,
+    scale: f32,
+    alpha: f32,
+}
+
+fn draw_object_placed(
+    fb: &mut RgbaImage,
+    obj: &RenderObject,
+    at: Placement,
+    effects: &[&RenderEffect],
+) {
+    let Some((_, src)) = object_source(obj) else {
+        return;
+    };
+    let pos_x = at.x + effects.iter().map(|e| e.x).sum::<f32>();
+    let pos_y = at.y + effects.iter().map(|e| e.y).sum::<f32>();
+
+    // Matches the GUI's zero-scale guard so degenerate objects stay invertible.
+    let guard = |s: f32| if s.abs() < f32::EPSILON { 0.001 } else { s };
+    let (scale_x, scale_y) = (guard(obj.scale_x * at.scale), guard(obj.scale_y * at.scale));
+    let (sin, cos) = obj.rotate_z_deg.to_radians().sin_cos();
+
+    // Forward transform of a source-local point (relative to the src clip origin).
+    let to_stage = |lx: f32, ly: f32| {
+        let dx = (lx - obj.center_x) * scale_x;
+        let dy = (ly - obj.center_y) * scale_y;
+        [pos_x + dx * cos - dy * sin, pos_y + dx * sin + dy * cos]
+    };
+    let corners = [
+        to_stage(0.0, 0.0),
+        to_stage(src.width(), 0.0),
+        to_stage(src.width(), src.height()),
+        to_stage(0.0, src.height()),
+    ];
+    // Inverse transform back into source-local space.
+    let to_local = |sx: f32, sy: f32| {
+        let dx = sx - pos_x;
+        let dy = sy - pos_y;
+        Some((
+            (dx * cos + dy * sin) / scale_x + obj.center_x,
+            (-dx * sin + dy * cos) / scale_y + obj.center_y,
+        ))
+    };
+    draw_mapped(fb, obj, corners, at.alpha, &to_local, effects);
+}
+
+/// Draw one repeat of an object whose image corners were projected onto the
+/// stage (`RenderScene::project_object`). Effect shifts move the projected
+/// quad; the image is mapped onto it with perspective and its alpha scaled
+/// by `rep_alpha`.
+pub fn draw_object_projected(
+    fb: &mut RgbaImage,
+    obj: &RenderObject,
+    quad: &ProjectedQuad,
+    rep_alpha: f32,
+    effects: &[&RenderEffect],
+) {
+    let Some((_, src)) = object_source(obj) else {
+        return;
+    };
+    let shift_x = effects.iter().map(|e| e.x).sum::<f32>();
+    let shift_y = effects.iter().map(|e| e.y).sum::<f32>();
+    let corners = quad.corners.map(|[x, y]| [x + shift_x, y + shift_y]);
+    let Some(inv) = Homography::square_to_quad(&corners).and_then(|h| h.inverse()) else {
+        return;
+    };
+    let to_local = |sx: f32, sy: f32| {
+        let (u, v) = inv.apply(sx, sy)?;
+        Some((u * src.width(), v * src.height()))
+    };
+    draw_mapped(fb, obj, corners, obj.alpha * rep_alpha, &to_local, effects);
+}
+
+/// The object's image and source rect, if it draws anything at all.
+fn object_source(obj: &RenderObject) -> Option<(&RgbaImage, RenderRect)> {
+    let img = obj.image.as_deref()?;
+    if !obj.visible || obj.alpha <= 0.0 || img.width() == 0 || img.height() == 0 {
+        return None;
+    }
+    let full = RenderRect::new(0.0, 0.0, img.width() as f32, img.height() as f32);
+    let src = match obj.src_clip {
+        Some(clip) => clip.intersect(&full),
+        None => full,
+    };
+    (!src.is_empty()).then_some((img, src))
+}
+
+/// Fill the stage area covered by `corners`, sampling the object's image
+/// at the source-local point `to_local` gives for each pixel centre.
+fn draw_mapped(
+    fb: &mut RgbaImage,
+    obj: &RenderObject,
+    corners: [[f32; 2]; 4],
+    alpha: f32,
+    to_local: &dyn Fn(f32, f32) -> Option<(f32, f32)>,
+    effects: &[&RenderEffect],
+) {
+    let Some((img, src)) = object_source(obj) else {
+        return;
+    };
+    let mut area = RenderRect::new(
+        corners.iter().map(|c| c[0]).fold(f32::MAX, f32::min),
+        corners.iter().map(|c| c[1]).fold(f32::MAX, f32::min),
+        corners.iter().map(|c| c[0]).fold(f32::MIN, f32::max),
+        corners.iter().map(|c| c[1]).fold(f32::MIN, f32::max),
+    )
+    .intersect(&RenderRect::new(
+        0.0,
+        0.0,
+        fb.width() as f32,
+        fb.height() as f32,
+    ));
+    if let Some(clip) = obj.dst_clip {
+        area = area.intersect(&clip);
+    }
+    if area.is_empty() {
+        return;
+    }
+
+    let x0 = area.left.floor().max(0.0) as u32;
+    let y0 = area.top.floor().max(0.0) as u32;
+    let x1 = (area.right.ceil() as u32).min(fb.width());
+    let y1 = (area.bottom.ceil() as u32).min(fb.height());
+    let alpha = alpha.clamp(0.0, 1.0);
+    for py in y0..y1 {
+        for px in x0..x1 {
+            let sx = px as f32 + 0.5;
+            let sy = py as f32 + 0.5;
+            if sx < area.left || sx >= area.right || sy < area.top || sy >= area.bottom {
+                continue;
+            }
+            let Some((lx, ly)) = to_local(sx, sy) else {
+                continue;
+            };
+            if lx < 0.0 || ly < 0.0 || lx >= src.width() || ly >= src.height() {
+                continue;
+            }
+            let s = sample_bilinear(img, src.left + lx, src.top + ly, src);
+            let mut rgb = [s[0], s[1], s[2]];
+            if let Some(curve) = &obj.tone_curve {
+                rgb = curve.apply_rgb(rgb);
+            }
+            let rgb = effects
+                .iter()
+                .fold(obj.color.apply_rgb(rgb), |rgb, e| e.color.apply_rgb(rgb));
+            let mask = obj.mask.as_ref().map_or(1.0, |m| m.value_at(sx, sy));
+            blend_pixel_mode(
+                fb.get_pixel_mut(px, py),
+                rgb,
+                s[3] / 255.0 * alpha * mask,
+                obj.blend,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RED: [u8; 4] = [255, 0, 0, 255];
+    const GREEN: [u8; 4] = [0, 255, 0, 255];
+    const BLUE: [u8; 4] = [0, 0, 255, 255];
+    const BLACK: [u8; 4] = [0, 0, 0, 255];
+
+    fn solid(w: u32, h: u32, rgba: [u8; 4]) -> Arc<RgbaImage> {
+        Arc::new(RgbaImage::from_pixel(w, h, image::Rgba(rgba)))
+    }
+
+    /// One-row image with the given pixels left to right.
+    fn row(pixels: &[[u8; 4]]) -> Arc<RgbaImage> {
+        Arc::new(RgbaImage::from_fn(pixels.len() as u32, 1, |x, _| {
+            image::Rgba(pixels[x as usize])
+        }))
+    }
+
+    fn object(index: i32, image: Arc<RgbaImage>, x: f32, y: f32) -> RenderObject {
+        RenderObject {
+            x,
+            y,
+            ..RenderObject::new(RenderPlane::Front, index, image)
+        }
+    }
+
+    fn scene(size: u32, clear: [u8; 4], objects: Vec<RenderObject>) -> RenderScene {
+        RenderScene {
+            clear_color: clear,
+            objects,
+            ..RenderScene::new(size, size)
+        }
+    }
+
+    fn px(fb: &RgbaImage, x: u32, y: u32) -> [u8; 4] {
+        fb.get_pixel(x, y).0
+    }
+
+    /// Color on top where a red and a blue full-frame object overlap.
+    fn top_of(red: RenderObject, blue: RenderObject) -> [u8; 4] {
+        px(&scene(4, BLACK, vec![red, blue]).compose(), 1, 1)
+    }
+
+    #[test]
+    fn objects_stack_by_order_layer_plane_index_and_seq() {
+        let red = || object(0, solid(4, 4, RED), 0.0, 0.0);
+        let blue = || object(0, solid(4, 4, BLUE), 0.0, 0.0);
+
+        // Order beats layer, layer beats plane, plane beats index, index beats seq.
+        let r = RenderObject { order: 1, ..red() };
+        let b = RenderObject { layer: 5, ..blue() };
+        assert_eq!(top_of(r, b), RED);
+        let r = RenderObject { layer: 1, ..red() };
+        let b = RenderObject {
+            plane: RenderPlane::Next,
+            ..blue()
+        };
+        assert_eq!(top_of(r, b), RED);
+        let r = RenderObject {
+            plane: RenderPlane::Next,
+            ..red()
+        };
+        let b = RenderObject { index: 3, ..blue() };
+        assert_eq!(top_of(r, b), RED);
+        let r = RenderObject { index: 1, ..red() };
+        let b = RenderObject { seq: 9, ..blue() };
+        assert_eq!(top_of(r, b), RED);
+        let r = RenderObject { seq: 2, ..red() };
+        let b = RenderObject { seq: 1, ..blue() };
+        assert_eq!(top_of(r, b), RED);
+
+        // Hidden objects draw nothing.
+        let r = RenderObject {
+            order: 1,
+            visible: false,
+            ..red()
+        };
+        assert_eq!(top_of(r, blue()), BLUE);
+    }
+
+    #[test]
+    fn farther_objects_draw_first_within_an_order_and_layer() {
+        let near = object(0, solid(64, 64, RED), 0.0, 0.0);
+        let far = RenderObject {
+            z: 10.0,
+            ..object(1, solid(64, 64, BLUE), 0.0, 0.0)
+        };
+        let s = scene(64, BLACK, vec![far.clone(), near.clone()]);
+        assert!(s.object_depth(&far) > s.object_depth(&near));
+        assert_eq!(px(&s.compose(), 32, 32), RED);
+
+        // A higher layer still wins over depth.
+        let far = RenderObject { layer: 1, ..far };
+        assert_eq!(
+            px(&scene(64, BLACK, vec![far, near]).compose(), 32, 32),
+            BLUE
+        );
+    }
+
+    #[test]
+    fn objects_are_moved_scaled_and_rotated_about_their_center() {
+        let fb = scene(8, BLACK, vec![object(0, solid(2, 2, RED), 3.0, 4.0)]).compose();
+        assert_eq!(px(&fb, 3, 4), RED);
+        assert_eq!(px(&fb, 4, 5), RED);
+        assert_eq!(px(&fb, 2, 4), BLACK);
+        assert_eq!(px(&fb, 5, 4), BLACK);
+
+        let scaled = RenderObject {
+            scale_x: 2.0,
+            scale_y: 2.0,
+            center_x: 1.0,
+            center_y: 1.0,
+            ..object(0, solid(2, 2, RED), 4.0, 4.0)
+        };
+        let fb = scene(8, BLACK, vec![scaled]).compose();
+        assert_eq!(px(&fb, 2, 2), RED);
+        assert_eq!(px(&fb, 5, 5), RED);
+        assert_eq!(px(&fb, 1, 2), BLACK);
+        assert_eq!(px(&fb, 6, 5), BLACK);
+
+        // 90 degrees turns the row into a column growing downwards.
+        let rotated = RenderObject {
+            rotate_z_deg: 90.0,
+            ..object(0, row(&[RED, BLUE]), 4.0, 4.0)
+        };
+        let fb = scene(8, BLACK, vec![rotated]).compose();
+        assert_eq!(px(&fb, 3, 4), RED);
+        assert_eq!(px(&fb, 3, 5), BLUE);
+        assert_eq!(px(&fb, 4, 4), BLACK);
+    }
+
+    #[test]
+    fn source_and_destination_clips_cut_the_object() {
+        let clipped = RenderObject {
+            src_clip: Some(RenderRect::new(1.0, 0.0, 3.0, 1.0)),
+            ..object(0, row(&[RED, GREEN, BLUE, RED]), 2.0, 0.0)
+        };
+        let fb = scene(8, BLACK, vec![clipped]).compose();
+        assert_eq!(px(&fb, 1, 0), BLACK);
+        assert_eq!(px(&fb, 2, 0), GREEN);
+        assert_eq!(px(&fb, 3, 0), BLUE);
+        assert_eq!(px(&fb, 4, 0), BLACK);
+
+        let clipped = RenderObject {
+            dst_clip: Some(RenderRect::new(1.0, 1.0, 3.0, 3.0)),
+            ..object(0, solid(4, 4, RED), 0.0, 0.0)
+        };
+        let fb = scene(4, BLACK, vec![clipped]).compose();
+        assert_eq!(px(&fb, 0, 0), BLACK);
+        assert_eq!(px(&fb, 1, 1), RED);
+        assert_eq!(px(&fb, 2, 2), RED);
+        assert_eq!(px(&fb, 3, 3), BLACK);
+    }
+
+    #[test]
+    fn alpha_scales_coverage() {
+        let half = RenderObject {
+            alpha: 0.5,
+            ..object(0, solid(1, 1, RED), 0.0, 0.0)
+        };
+        let fb = scene(1, BLACK, vec![half]).compose();
+        assert_eq!(px(&fb, 0, 0), [128, 0, 0, 255]);
+
+        let quarter = RenderObject {
+            alpha: 0.5,
+            ..object(0, solid(1, 1, [255, 0, 0, 128]), 0.0, 0.0)
+        };
+        let fb = scene(1, BLACK, vec![quarter]).compose();
+        assert_eq!(px(&fb, 0, 0), [64, 0, 0, 255]);
+
+        let gone = RenderObject {
+            alpha: 0.0,
+            ..object(0, solid(1, 1, RED), 0.0, 0.0)
+        };
+        assert_eq!(px(&scene(1, BLACK, vec![gone]).compose(), 0, 0), BLACK);
+    }
+
+    #[test]
+    fn blend_modes_combine_with_the_pixels_below() {
+        let gray = [100, 100, 100, 255];
+        let draw = |blend: i32, rgba: [u8; 4]| {
+            let obj = RenderObject {
+                blend: RenderBlend::from_script(blend),
+                ..object(0, solid(1, 1, rgba), 0.0, 0.0)
+            };
+            px(&scene(1, gray, vec![obj]).compose(), 0, 0)
+        };
+        assert_eq!(draw(0, [100, 50, 200, 255]), [100, 50, 200, 255]);
+        assert_eq!(draw(1, [100, 50, 200, 255]), [200, 150, 255, 255]);
+        assert_eq!(draw(2, [100, 50, 200, 255]), [0, 50, 0, 255]);
+        assert_eq!(draw(3, [255, 0, 100, 255]), [100, 0, 39, 255]);
+        assert_eq!(draw(4, [100, 0, 255, 255]), [161, 100, 255, 255]);
+        assert_eq!(draw(99, [1, 2, 3, 255]), [1, 2, 3, 255]);
+        // Half coverage mixes the combined color with the destination.
+        let obj = RenderObject {
+            alpha: 0.5,
+            blend: RenderBlend::Add,
+            ..object(0, solid(1, 1, [100, 100, 100, 255]), 0.0, 0.0)
+        };
+        assert_eq!(
+            px(&scene(1, gray, vec![obj]).compose(), 0, 0),
+            [150, 150, 150, 255]
+        );
+    }
+
+    #[test]
+    fn color_filters_apply_in_order() {
+        let filter = |color: RenderColor, rgb: [f32; 3]| color.apply_rgb(rgb).map(|v| v.round());
+        let base = RenderColor::default();
+        assert!(base.is_identity());
+        assert_eq!(filter(base, [10.0, 20.0, 30.0]), [10.0, 20.0, 30.0]);
+
+        let mono = RenderColor { mono: 1.0, ..base };
+        assert_eq!(filter(mono, [255.0, 0.0, 0.0]), [76.0, 76.0, 76.0]);
+        let reverse = RenderColor {
+            reverse: true,
+            ..base
+        };
+        assert_eq!(filter(reverse, [255.0, 0.0, 100.0]), [0.0, 255.0, 155.0]);
+        // Mono comes before reverse.
+        let both = RenderColor {
+            reverse: true,
+            ..mono
+        };
+        assert_eq!(filter(both, [255.0, 0.0, 0.0]), [179.0, 179.0, 179.0]);
+
+        let bright = RenderColor {
+            bright: 50.0,
+            dark: 20.0,
+            ..base
+        };
+        assert_eq!(filter(bright, [0.0, 240.0, 100.0]), [30.0, 255.0, 130.0]);
+        let tint = RenderColor {
+            tint_r: 255.0,
+            tint_rate: 0.5,
+            ..base
+        };
+        assert_eq!(filter(tint, [0.0, 100.0, 200.0]), [128.0, 50.0, 100.0]);
+        let rate = RenderColor {
+            color_rate: 0.5,
+            color_g: 0.0,
+            color_add_b: 10.0,
+            ..base
+        };
+        assert_eq!(filter(rate, [200.0, 200.0, 200.0]), [100.0, 0.0, 110.0]);
+
+        let mut img = RgbaImage::from_pixel(1, 1, image::Rgba([255, 0, 100, 7]));
+        reverse.apply_to_image(&mut img);
+        assert_eq!(px(&img, 0, 0), [0, 255, 155, 7]);
+    }
+
+    #[test]
+    fn object_colors_and_covering_effects_filter_pixels() {
+        let reversed = RenderObject {
+            color: RenderColor {
+                reverse: true,
+                ..RenderColor::default()
+            },
+            ..object(0, solid(1, 1, [255, 0, 100, 255]), 0.0, 0.0)
+        };
+        let fb = scene(2, BLACK, vec![reversed]).compose();
+        assert_eq!(px(&fb, 0, 0), [0, 255, 155, 255]);
+
+        let low = object(0, solid(1, 1, RED), 0.0, 0.0);
+        let high = RenderObject {
+            order: 2,
+            ..object(1, solid(1, 1, RED), 0.0, 1.0)
+        };
+        let mut s = scene(4, BLACK, vec![low, high]);
+        s.effects.push(RenderEffect {
+            x: 2.0,
+            color: RenderColor {
+                mono: 1.0,
+                ..RenderColor::default()
+            },
+            begin_order: 1,
+            end_order: 3,
+            ..RenderEffect::default()
+        });
+        let fb = s.compose();
+        assert_eq!(px(&fb, 0, 0), RED);
+        assert_eq!(px(&fb, 0, 1), BLACK);
+        assert_eq!(px(&fb, 2, 1), [76, 76, 76, 255]);
+    }
+
+    #[test]
+    fn rep_lists_repeat_the_object() {
+        let reps = RenderRep::from_lists(&[0, 3], &[1], &[], &[255, 128, 0]);
+        assert_eq!(reps.len(), 3);
+        assert_eq!(
+            reps[1],
+            RenderRep {
+                x: 3.0,
+                y: 0.0,
+                z: 0.0,
+                alpha: 128.0 / 255.0,
+            }
+        );
+        assert_eq!(reps[2].alpha, 0.0);
+        assert!(RenderRep::from_lists(&[], &[], &[], &[]).is_empty());
+
+        let obj = object(0, solid(1, 1, RED), 1.0, 1.0);
+        assert_eq!(obj.repeats(), &[RenderRep::IDENTITY]);
+        let repeated = RenderObject { reps, ..obj };
+        let fb = scene(6, BLACK, vec![repeated]).compose();
+        assert_eq!(px(&fb, 1, 1), BLACK);
+        assert_eq!(px(&fb, 1, 2), RED);
+        assert_eq!(px(&fb, 4, 1), [128, 0, 0, 255]);
+        assert_eq!(px(&fb, 4, 2), BLACK);
+    }
+}
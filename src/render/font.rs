@@ -0,0 +1,488 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use ab_glyph::{Font, FontArc, FontVec, GlyphId, PxScale, ScaleFont, point};
+use anyhow::{Context, Result};
+use image::RgbaImage;
+
+use super::blend_pixel;
+use crate::vm::FontDecoration;
+
+/// One face of a TTF/OTF file (or of a TTC collection).
+#[derive(Clone)]
+pub struct FontFace {
+    /// Every family / full name found in the `name` table, all languages.
+    pub names: Vec<String>,
+    pub bold: bool,
+    pub italic: bool,
+    /// `OS/2` weight class (400 regular, 700 bold).
+    pub weight: u16,
+    /// `OS/2` width is normal (not condensed/expanded).
+    pub normal_width: bool,
+    pub path: PathBuf,
+    pub index: u32,
+    /// Bytes of the whole file, shared by every face of a collection.
+    pub data: Arc<[u8]>,
+    font: FontArc,
+}
+
+impl std::fmt::Debug for FontFace {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FontFace")
+            .field("names", &self.names)
+            .field("bold", &self.bold)
+            .field("italic", &self.italic)
+            .field("weight", &self.weight)
+            .field("path", &self.path)
+            .field("index", &self.index)
+            .finish()
+    }
+}
+
+impl FontFace {
+    /// Primary display name (first family name in the `name` table).
+    pub fn name(&self) -> &str {
+        self.names.first().map(String::as_str).unwrap_or("")
+    }
+
+    fn matches(&self, key: &str) -> bool {
+        self.names.iter().any(|n| normalize_font_name(n) == key)
+    }
+
+    fn has_glyph(&self, ch: char) -> bool {
+        self.font.glyph_id(ch) != GlyphId(0)
+    }
+
+    /// How far this face is from an upright, normal-width face of the wanted
+    /// weight; lower is better.
+    fn style_penalty(&self, bold: bool) -> u32 {
+        let want = if bold { 700 } else { 400 };
+        u32::from(self.italic) * 2000
+            + u32::from(!self.normal_width) * 1000
+            + u32::from(self.weight.abs_diff(want))
+    }
+}
+
+/// Fonts available to the text rasterizer, in load order.
+///
+/// The first face is the default when a script font name is empty or unknown;
+/// later faces are per-glyph fallbacks (e.g. a Latin face backed by a CJK one).
+#[derive(Debug, Clone, Default)]
+pub struct FontSet {
+    faces: Vec<FontFace>,
+}
+
+impl FontSet {
+    /// Load every font file in `paths`; directories are scanned (not recursively)
+    /// for `.ttf`/`.otf`/`.ttc`/`.otc`. Unreadable files are logged and skipped.
+    pub fn load_paths(paths: &[PathBuf]) -> Self {
+        let mut set = Self::default();
+        for path in paths {
+            if path.is_dir() {
+                let Ok(rd) = std::fs::read_dir(path) else {
+                    continue;
+                };
+                let mut files: Vec<PathBuf> = rd
+                    .filter_map(|e| e.ok().map(|e| e.path()))
+                    .filter(|p| is_font_file(p))
+                    .collect();
+                files.sort();
+                for file in files {
+                    if let Err(err) = set.load_file(&file) {
+                        log::warn!("font: {err:#}");
+                    }
+                }
+            } else if path.is_file()
+                && let Err(err) = set.load_file(path)
+            {
+                log::warn!("font: {err:#}");
+            }
+        }
+        set
+    }
+
+    /// Load all faces of one font file; returns how many were added.
+    ///
+    /// The faces of a collection share one copy of the file in `data`; each
+    /// face's rasterizer owns its own, since ab_glyph's owned fonts take a `Vec`.
+    pub fn load_file(&mut self, path: &Path) -> Result<usize> {
+        let data: Arc<[u8]> = std::fs::read(path)
+            .with_context(|| format!("failed to read {}", path.display()))?
+            .into();
+        let count = ttf_parser::fonts_in_collection(&data).unwrap_or(1);
+        let mut added = 0;
+        for index in 0..count {
+            let Ok(face) = ttf_parser::Face::parse(&data, index) else {
+                continue;
+            };
+            let mut names = Vec::new();
+            for id in [
+                ttf_parser::name_id::TYPOGRAPHIC_FAMILY,
+                ttf_parser::name_id::FAMILY,
+                ttf_parser::name_id::FULL_NAME,
+            ] {
+                for name in face.names().into_iter().filter(|n| n.name_id == id) {
+                    if let Some(s) = name.to_string()
+                        && !s.is_empty()
+                        && !names.contains(&s)
+                    {
+                        names.push(s);
+                    }
+                }
+            }
+            let bold = face.is_bold();
+            let italic = face.is_italic() || face.is_oblique();
+            let weight = face.weight().to_number();
+            let normal_width = face.width() == ttf_parser::Width::Normal;
+            let Ok(font) = FontVec::try_from_vec_and_index(data.to_vec(), index) else {
+                continue;
+            };
+            self.faces.push(FontFace {
+                names,
+                bold,
+                italic,
+                weight,
+                normal_width,
+                path: path.to_path_buf(),
+                index,
+                data: data.clone(),
+                font: FontArc::new(font),
+            });
+            added += 1;
+        }
+        anyhow::ensure!(added > 0, "no usable faces in {}", path.display());
+        Ok(added)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.faces.is_empty()
+    }
+
+    pub fn faces(&self) -> &[FontFace] {
+        &self.faces
+    }
+
+    /// Face for a script font name, preferring an upright face of the wanted
+    /// weight among those sharing the name.
+    ///
+    /// Names compare case-, width- and whitespace-insensitively, so
+    /// `ＭＳ ゴシック` and `MS Gothic` both find msgothic.ttc.
+    pub fn find(&self, name: &str, bold: bool) -> Option<usize> {
+        let key = normalize_font_name(name);
+        if key.is_empty() {
+            return None;
+        }
+        self.best_face(bold, |face| face.matches(&key))
+    }
+
+    /// Face used for `name`: the named face, else the best-matching default
+    /// (earliest loaded upright face).
+    pub fn resolve(&self, name: &str, bold: bool) -> Option<usize> {
+        self.find(name, bold)
+            .or_else(|| self.best_face(bold, |_| true))
+    }
+
+    fn best_face(&self, bold: bool, filter: impl Fn(&FontFace) -> bool) -> Option<usize> {
+        self.faces
+            .iter()
+            .enumerate()
+            .filter(|(_, face)| filter(face))
+            .min_by_key(|(_, face)| face.style_penalty(bold))
+            .map(|(i, _)| i)
+    }
+
+    fn face_for_char(&self, primary: usize, ch: char) -> usize {
+        if self.faces[primary].has_glyph(ch) {
+            return primary;
+        }
+        self.faces
+            .iter()
+            .position(|f| f.has_glyph(ch))
+            .unwrap_or(primary)
+    }
+}
+
+fn is_font_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .map(|e| {
+            ["ttf", "otf", "ttc", "otc"]
+                .iter()
+                .any(|x| e.eq_ignore_ascii_case(x))
+        })
+        .unwrap_or(false)
+}
+
+/// Lowercase, fold full-width ASCII to half-width and drop whitespace.
+pub fn normalize_font_name(name: &str) -> String {
+    name.chars()
+        .map(|c| match c {
+            '\u{FF01}'..='\u{FF5E}' => char::from_u32(c as u32 - 0xFEE0).unwrap_or(c),
+            _ => c,
+        })
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Parameters for `render_text`; sizes are in stage pixels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextStyle {
+    /// Em size (`moji_size`).
+    pub size: f32,
+    pub color: [u8; 3],
+    pub bold: bool,
+    pub decoration: FontDecoration,
+    pub shadow_color: [u8; 3],
+    pub outline_color: [u8; 3],
+    /// Extra advance after each character (`moji_space_x`).
+    pub space_x: f32,
+    /// Extra gap between lines (`moji_space_y`).
+    pub space_y: f32,
+}
+
+impl Default for TextStyle {
+    fn default() -> Self {
+        Self {
+            size: 18.0,
+            color: [255, 255, 255],
+            bold: false,
+            decoration: FontDecoration::None,
+            shadow_color: [0, 0, 0],
+            outline_color: [0, 0, 0],
+            space_x: 0.0,
+            space_y: 0.0,
+        }
+    }
+}
+
+/// Rasterize `text` (lines split on `\n`) with the face resolved from
+/// `font_name`; glyphs missing from it come from the other loaded faces.
+///
+/// Shadow and outline grow with the size (1px at 18px). Bold uses a bold face
+/// when one matches, otherwise the glyph coverage is widened by one pixel.
+/// Returns `None` when no font is loaded.
+pub fn render_text(
+    fonts: &FontSet,
+    font_name: &str,
+    text: &str,
+    style: &TextStyle,
+) -> Option<RgbaImage> {
+    let primary = fonts.resolve(font_name, style.bold)?;
+    let size = style.size.max(1.0);
+    let px_scale = |face: &FontFace| {
+        face.font
+            .pt_to_px_scale(size)
+            .unwrap_or(PxScale::from(size))
+    };
+
+    let primary_font = fonts.faces[primary]
+        .font
+        .as_scaled(px_scale(&fonts.faces[primary]));
+    let ascent = primary_font.ascent();
+    let line_h = (primary_font.ascent() - primary_font.descent()).max(size) + style.space_y;
+
+    let deco = (size / 18.0).round().max(1.0) as i32;
+    let outline = if style.decoration.has_outline() {
+        deco
+    } else {
+        0
+    };
+    let shadow = if style.decoration.has_shadow() {
+        deco
+    } else {
+        0
+    };
+    let faux_bold = style.bold && fonts.faces[primary].weight < 600;
+    let pad = outline + 1;
+
+    // Layout: (face, glyph, pen x, baseline y).
+    let mut placed = Vec::new();
+    let mut width = 0.0f32;
+    let mut lines = 0usize;
+    for (line_idx, line) in text.split('\n').enumerate() {
+        lines = line_idx + 1;
+        let baseline = line_idx as f32 * line_h + ascent;
+        let mut x = 0.0f32;
+        let mut prev: Option<(usize, GlyphId)> = None;
+        for ch in line.chars().filter(|c| *c != '\r') {
+            let face_idx = fonts.face_for_char(primary, ch);
+            let face = &fonts.faces[face_idx];
+            let sf = face.font.as_scaled(px_scale(face));
+            let id = face.font.glyph_id(ch);
+            if let Some((pf, pid)) = prev
+                && pf == face_idx
+            {
+                x += sf.kern(pid, id);
+            }
+            placed.push((face_idx, id, x, baseline));
+            x += sf.h_advance(id) + style.space_x;
+            prev = Some((face_idx, id));
+        }
+        width = width.max(x - style.space_x);
+    }
+
+    let text_w = width.ceil().max(1.0) as i32 + i32::from(faux_bold);
+    let text_h = (lines as f32 * line_h - style.space_y).ceil().max(1.0) as i32;
+    let w = (text_w + pad * 2 + shadow) as u32;
+    let h = (text_h + pad * 2 + shadow) as u32;
+
+    let mut fill = vec![0.0f32; (w * h) as usize];
+    for (face_idx, id, x, baseline) in placed {
+        let face = &fonts.faces[face_idx];
+        let glyph = id
+            .with_scale_and_position(px_scale(face), point(x + pad as f32, baseline + pad as f32));
+        let Some(outlined) = face.font.outline_glyph(glyph) else {
+            continue;
+        };
+        let bounds = outlined.px_bounds();
+        outlined.draw(|gx, gy, c| {
+            let px = bounds.min.x as i32 + gx as i32;
+            let py = bounds.min.y as i32 + gy as i32;
+            if px >= 0 && py >= 0 && (px as u32) < w && (py as u32) < h {
+                let cell = &mut fill[(py as u32 * w + px as u32) as usize];
+                *cell = cell.max(c.min(1.0));
+            }
+        });
+    }
+    if faux_bold {
+        fill = dilate(&fill, w, h, 1, 0);
+    }
+
+    let body = if outline > 0 {
+        dilate(&fill, w, h, outline, outline)
+    } else {
+        fill.clone()
+    };
+
+    let mut img = RgbaImage::new(w, h);
+    let layers: [(&[f32], [u8; 3], i32); 3] = [
+        (&body, style.shadow_color, shadow),
+        (&body, style.outline_color, 0),
+        (&fill, style.color, 0),
+    ];
+    for (i, (mask, rgb, offset)) in layers.into_iter().enumerate() {
+        if (i == 0 && shadow == 0) || (i == 1 && outline == 0) {
+            continue;
+        }
+        let rgb = rgb.map(f32::from);
+        for y in 0..h as i32 {
+            for x in 0..w as i32 {
+                let (sx, sy) = (x - offset, y - offset);
+                if sx < 0 || sy < 0 {
+                    continue;
+                }
+                let a = mask[(sy as u32 * w + sx as u32) as usize];
+                if a > 0.0 {
+                    blend_pixel(img.get_pixel_mut(x as u32, y as u32), rgb, a);
+                }
+            }
+        }
+    }
+    Some(img)
+}
+
+/// Grow coverage by `rx`/`ry` pixels (elliptical max filter).
+fn dilate(src: &[f32], w: u32, h: u32, rx: i32, ry: i32) -> Vec<f32> {
+    let mut out = src.to_vec();
+    let (w, h) = (w as i32, h as i32);
+    for y in 0..h {
+        for x in 0..w {
+            let mut m = 0.0f32;
+            for dy in -ry..=ry {
+                for dx in -rx..=rx {
+                    if rx > 0 && ry > 0 && dx * dx * ry * ry + dy * dy * rx * rx > rx * rx * ry * ry
+                    {
+                        continue;
+                    }
+                    let (sx, sy) = (x + dx, y + dy);
+                    if sx >= 0 && sy >= 0 && sx < w && sy < h {
+                        m = m.max(src[(sy * w + sx) as usize]);
+                    }
+                }
+            }
+            out[(y * w + x) as usize] = m;
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Hack (regular) and Ubuntu Light, loaded from a scratch directory in that order.
+    fn test_fonts(tag: &str) -> FontSet {
+        let dir = std::env::temp_dir().join(format!("siglus_{tag}_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("a.ttf"), epaint_default_fonts::HACK_REGULAR).unwrap();
+        std::fs::write(dir.join("b.ttf"), epaint_default_fonts::UBUNTU_LIGHT).unwrap();
+        std::fs::write(dir.join("notes.txt"), b"not a font").unwrap();
+        let fonts = FontSet::load_paths(std::slice::from_ref(&dir));
+        let _ = std::fs::remove_dir_all(&dir);
+        fonts
+    }
+
+    #[test]
+    fn names_fold_case_width_and_whitespace() {
+        assert_eq!(normalize_font_name("ＭＳ　ゴシック"), "msゴシック");
+        assert_eq!(normalize_font_name(" MS Gothic "), "msgothic");
+        assert_eq!(normalize_font_name("ＭＳ Ｇｏｔｈｉｃ"), "msgothic");
+    }
+
+    #[test]
+    fn find_matches_folded_names_and_resolve_falls_back() {
+        let fonts = test_fonts("font_resolve");
+        assert_eq!(fonts.faces().len(), 2);
+        assert_eq!(fonts.faces()[0].name(), "Hack");
+        assert_eq!(&*fonts.faces()[0].data, epaint_default_fonts::HACK_REGULAR);
+
+        assert_eq!(fonts.find("ｈａｃｋ", false), Some(0));
+        assert_eq!(fonts.find("Ubuntu", false), Some(1));
+        assert_eq!(fonts.find("Missing", false), None);
+        assert_eq!(fonts.find("", false), None);
+        // Unknown names take the upright face closest to the wanted weight.
+        assert_eq!(fonts.resolve("Missing", false), Some(0));
+        assert_eq!(fonts.resolve("Ubuntu", true), Some(1));
+        assert_eq!(FontSet::default().resolve("Hack", false), None);
+    }
+
+    #[test]
+    fn rendered_text_grows_with_size_lines_and_decoration() {
+        let fonts = test_fonts("font_render");
+        let style = TextStyle {
+            decoration: FontDecoration::None,
+            ..TextStyle::default()
+        };
+        let render = |text: &str, style: &TextStyle| {
+            render_text(&fonts, "Hack", text, style)
+                .unwrap()
+                .dimensions()
+        };
+        let (w1, h1) = render("AB", &style);
+        let (w2, h2) = render(
+            "AB",
+            &TextStyle {
+                size: 36.0,
+                ..style
+            },
+        );
+        assert!(w2 > w1 * 3 / 2 && h2 > h1 * 3 / 2);
+
+        let (w, h) = render("AB\nAB", &style);
+        assert_eq!(w, w1);
+        assert!(h > h1 * 3 / 2);
+
+        let (w, h) = render(
+            "AB",
+            &TextStyle {
+                decoration: FontDecoration::OutlineShadow,
+                ..style
+            },
+        );
+        // 1px outline on each side plus a 1px shadow at 18px.
+        assert_eq!((w, h), (w1 + 3, h1 + 3));
+        assert!(render_text(&FontSet::default(), "Hack", "AB", &style).is_none());
+    }
+}
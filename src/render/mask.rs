@@ -0,0 +1,188 @@
+use std::{path::Path, sync::Arc};
+
+use anyhow::{Context, Result};
+use image::{GrayImage, RgbaImage};
+
+/// Grayscale mask placed on the stage (`mask[n]` slot image at `x`/`y`).
+///
+/// Values are luminance × alpha of the source image, so both opaque grayscale
+/// masks and alpha-only g00 masks work. Outside the image the mask is 0.
+#[derive(Debug, Clone)]
+pub struct RenderMask {
+    pub image: Arc<GrayImage>,
+    pub x: f32,
+    pub y: f32,
+}
+
+impl RenderMask {
+    pub fn new(image: Arc<GrayImage>, x: f32, y: f32) -> Self {
+        Self { image, x, y }
+    }
+
+    pub fn from_rgba(img: &RgbaImage, x: f32, y: f32) -> Self {
+        Self::new(Arc::new(mask_luminance(img)), x, y)
+    }
+
+    /// Load a g00 (first cut) or any image format `image` understands.
+    pub fn load(path: &Path) -> Result<Self> {
+        let is_g00 = path
+            .extension()
+            .map(|e| e.eq_ignore_ascii_case("g00"))
+            .unwrap_or(false);
+        let rgba = if is_g00 {
+            let g00 = crate::resource::load_g00_images(path)?;
+            g00.cuts
+                .into_iter()
+                .next()
+                .with_context(|| format!("g00 has no cuts: {}", path.display()))?
+                .to_rgba8()
+        } else {
+            image::open(path)
+                .with_context(|| format!("open mask image: {}", path.display()))?
+                .to_rgba8()
+        };
+        Ok(Self::from_rgba(&rgba, 0.0, 0.0))
+    }
+
+    /// Mask value (0.0..=1.0) at stage coordinates, nearest texel.
+    pub fn value_at(&self, sx: f32, sy: f32) -> f32 {
+        let lx = sx - self.x;
+        let ly = sy - self.y;
+        if lx < 0.0 || ly < 0.0 {
+            return 0.0;
+        }
+        let (ix, iy) = (lx as u32, ly as u32);
+        if ix >= self.image.width() || iy >= self.image.height() {
+            return 0.0;
+        }
+        self.image.get_pixel(ix, iy)[0] as f32 / 255.0
+    }
+
+    /// Mask value with the image stretched over a `w`×`h` frame (offset ignored).
+    pub fn stretched_value_at(&self, x: f32, y: f32, w: f32, h: f32) -> f32 {
+        let (mw, mh) = self.image.dimensions();
+        if mw == 0 || mh == 0 || w <= 0.0 || h <= 0.0 {
+            return 0.0;
+        }
+        let ix = ((x / w * mw as f32) as u32).min(mw - 1);
+        let iy = ((y / h * mh as f32) as u32).min(mh - 1);
+        self.image.get_pixel(ix, iy)[0] as f32 / 255.0
+    }
+
+    /// Average stretched mask value per cell of a `cols`×`rows` grid, row-major.
+    ///
+    /// Lets renderers without per-pixel masking (the GUI) approximate a mask wipe.
+    pub fn grid(&self, cols: u32, rows: u32) -> Vec<f32> {
+        let (mw, mh) = self.image.dimensions();
+        let (cols, rows) = (cols.max(1), rows.max(1));
+        let mut out = vec![0.0; (cols * rows) as usize];
+        if mw == 0 || mh == 0 {
+            return out;
+        }
+        let mut counts = vec![0u32; out.len()];
+        for (x, y, p) in self.image.enumerate_pixels() {
+            let cx = (x as u64 * cols as u64 / mw as u64) as u32;
+            let cy = (y as u64 * rows as u64 / mh as u64) as u32;
+            let i = (cy * cols + cx) as usize;
+            out[i] += p[0] as f32 / 255.0;
+            counts[i] += 1;
+        }
+        for (v, n) in out.iter_mut().zip(counts) {
+            if n > 0 {
+                *v /= n as f32;
+            }
+        }
+        out
+    }
+}
+
+/// Luminance × alpha of every pixel (BT.601 weights, as `RenderColor::mono`).
+pub fn mask_luminance(img: &RgbaImage) -> GrayImage {
+    GrayImage::from_fn(img.width(), img.height(), |x, y| {
+        let p = img.get_pixel(x, y).0;
+        let l = p[0] as f32 * 0.299 + p[1] as f32 * 0.587 + p[2] as f32 * 0.114;
+        image::Luma([(l * p[3] as f32 / 255.0).round().clamp(0.0, 255.0) as u8])
+    })
+}
+
+/// Share of the new frame shown where the mask is `m` at progress `t`.
+///
+/// Dark mask areas switch first (`reverse` flips that). `soft` is the width of
+/// the fade band as a fraction of the mask range; 0 gives a hard threshold.
+pub fn mask_wipe_coverage(m: f32, t: f32, soft: f32, reverse: bool) -> f32 {
+    let m = if reverse { 1.0 - m } else { m };
+    let t = t.clamp(0.0, 1.0);
+    if soft <= 0.0 {
+        return if m < t || t >= 1.0 { 1.0 } else { 0.0 };
+    }
+    // Stretch so the band fully enters at t=0 and fully leaves at t=1.
+    ((t * (1.0 + soft) - m) / soft).clamp(0.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gradient() -> RenderMask {
+        // 4×2: columns 0, 85, 170, 255 on both rows.
+        let image = GrayImage::from_fn(4, 2, |x, _| image::Luma([(x * 85) as u8]));
+        RenderMask::new(Arc::new(image), 10.0, 20.0)
+    }
+
+    #[test]
+    fn values_follow_the_offset_and_vanish_outside() {
+        let mask = gradient();
+        assert_eq!(mask.value_at(13.5, 21.0), 1.0);
+        assert_eq!(mask.value_at(11.0, 20.0), 85.0 / 255.0);
+        assert_eq!(mask.value_at(9.5, 20.0), 0.0);
+        assert_eq!(mask.value_at(14.0, 20.0), 0.0);
+        assert_eq!(mask.value_at(10.0, 22.0), 0.0);
+    }
+
+    #[test]
+    fn stretched_values_ignore_the_offset() {
+        let mask = gradient();
+        assert_eq!(mask.stretched_value_at(0.0, 0.0, 400.0, 100.0), 0.0);
+        assert_eq!(mask.stretched_value_at(399.0, 99.0, 400.0, 100.0), 1.0);
+        assert_eq!(
+            mask.stretched_value_at(250.0, 10.0, 400.0, 100.0),
+            170.0 / 255.0
+        );
+        assert_eq!(mask.stretched_value_at(1.0, 1.0, 0.0, 100.0), 0.0);
+    }
+
+    #[test]
+    fn grid_averages_each_cell() {
+        let grid = gradient().grid(2, 1);
+        assert_eq!(grid.len(), 2);
+        assert!((grid[0] - 42.5 / 255.0).abs() < 1e-6);
+        assert!((grid[1] - 212.5 / 255.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn luminance_is_weighted_by_alpha() {
+        let img = RgbaImage::from_fn(3, 1, |x, _| match x {
+            0 => image::Rgba([255, 255, 255, 255]),
+            1 => image::Rgba([255, 255, 255, 0]),
+            _ => image::Rgba([0, 255, 0, 255]),
+        });
+        let gray = mask_luminance(&img);
+        assert_eq!(gray.get_pixel(0, 0)[0], 255);
+        assert_eq!(gray.get_pixel(1, 0)[0], 0);
+        assert_eq!(gray.get_pixel(2, 0)[0], 150);
+    }
+
+    #[test]
+    fn wipe_coverage_switches_dark_areas_first() {
+        assert_eq!(mask_wipe_coverage(0.2, 0.5, 0.0, false), 1.0);
+        assert_eq!(mask_wipe_coverage(0.8, 0.5, 0.0, false), 0.0);
+        assert_eq!(mask_wipe_coverage(0.8, 0.5, 0.0, true), 1.0);
+        assert_eq!(mask_wipe_coverage(1.0, 1.0, 0.0, false), 1.0);
+        for m in [0.0, 0.5, 1.0] {
+            assert_eq!(mask_wipe_coverage(m, 0.0, 0.25, false), 0.0);
+            assert_eq!(mask_wipe_coverage(m, 1.0, 0.25, false), 1.0);
+        }
+        let mid = mask_wipe_coverage(0.5, 0.5, 0.25, false);
+        assert!(mid > 0.0 && mid < 1.0);
+    }
+}
@@ -0,0 +1,436 @@
+use crate::vm::{VmQuakeKind, VmQuakeRequest};
+
+/// Per-order stage transform produced by a running quake.
+///
+/// Objects are moved by `x`/`y` and scaled by `scale` around the stage centre
+/// offset by `center_x`/`center_y` (zoom quakes only).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QuakeTransform {
+    pub x: f32,
+    pub y: f32,
+    pub scale: f32,
+    pub center_x: f32,
+    pub center_y: f32,
+}
+
+impl Default for QuakeTransform {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl QuakeTransform {
+    pub const IDENTITY: Self = Self {
+        x: 0.0,
+        y: 0.0,
+        scale: 1.0,
+        center_x: 0.0,
+        center_y: 0.0,
+    };
+
+    pub fn is_identity(&self) -> bool {
+        *self == Self::IDENTITY
+    }
+
+    /// Map a stage point: the stage is scaled by `scale` around its centre,
+    /// shifted by `x`/`y` and by the zoom centre.
+    pub fn apply_point(&self, px: f32, py: f32, stage_w: f32, stage_h: f32) -> (f32, f32) {
+        let (cx, cy) = (stage_w / 2.0, stage_h / 2.0);
+        let ox = cx + self.x + (1.0 - self.scale) * self.center_x;
+        let oy = cy + self.y + (1.0 - self.scale) * self.center_y;
+        (ox + (px - cx) * self.scale, oy + (py - cy) * self.scale)
+    }
+
+    /// Stack another quake on top: offsets add, scales multiply, the last zoom
+    /// centre wins.
+    pub fn combine(self, other: Self) -> Self {
+        let zoomed = other.scale != 1.0;
+        Self {
+            x: self.x + other.x,
+            y: self.y + other.y,
+            scale: self.scale * other.scale,
+            center_x: if zoomed {
+                other.center_x
+            } else {
+                self.center_x
+            },
+            center_y: if zoomed {
+                other.center_y
+            } else {
+                self.center_y
+            },
+        }
+    }
+}
+
+/// A quake as seen by a renderer: its request and how long it has been running.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RenderQuake {
+    pub req: VmQuakeRequest,
+    pub elapsed_ms: f32,
+}
+
+impl RenderQuake {
+    pub fn transform_for_order(&self, order: i32) -> QuakeTransform {
+        quake_transform_at(&self.req, self.elapsed_ms, order)
+    }
+}
+
+/// Combined transform of every quake plus the screen shake offset at `order`.
+pub fn quake_transform_for_order(
+    quakes: &[RenderQuake],
+    shake: (f32, f32),
+    order: i32,
+) -> QuakeTransform {
+    let base = QuakeTransform {
+        x: shake.0,
+        y: shake.1,
+        ..QuakeTransform::IDENTITY
+    };
+    quakes
+        .iter()
+        .fold(base, |acc, q| acc.combine(q.transform_for_order(order)))
+}
+
+/// Total running time of a quake (`time * (cnt + end_cnt)`).
+pub fn quake_duration_ms(req: &VmQuakeRequest) -> u64 {
+    req.time_ms.max(1) as u64 * (req.cnt.max(0) as u64 + req.end_cnt.max(0) as u64)
+}
+
+/// Transform applied to objects at `order`, `elapsed_ms` after the quake started.
+///
+/// C++ reference: elm_quake.cpp `C_elm_quake::frame`. Each cycle of `time_ms`
+/// is split in quarters eased with accelerate/decelerate curves; the last
+/// `end_cnt` cycles fade linearly back to identity. Orders outside
+/// `begin_order..=end_order` are never moved.
+pub fn quake_transform_at(req: &VmQuakeRequest, elapsed_ms: f32, order: i32) -> QuakeTransform {
+    if order < req.begin_order || order > req.end_order {
+        return QuakeTransform::IDENTITY;
+    }
+
+    let total = req.time_ms.max(1) as f32;
+    let total_quake_time = quake_duration_ms(req) as f32;
+    if elapsed_ms >= total_quake_time {
+        return QuakeTransform::IDENTITY;
+    }
+
+    let quarter = (total / 4.0).max(1.0);
+    let cur = elapsed_ms % total;
+    let mut out = QuakeTransform::IDENTITY;
+
+    match req.kind {
+        VmQuakeKind::Vec => {
+            // Accelerate out, decelerate to the peak, then the same back home.
+            let rad = (req.vec as f32).to_radians();
+            let power = req.power as f32;
+            let d = if cur < quarter {
+                speed_up_limit(cur, 0.0, 0.0, quarter, power / 2.0)
+            } else if cur < quarter * 2.0 {
+                speed_down_limit(cur - quarter, 0.0, power / 2.0, quarter, power)
+            } else if cur < quarter * 3.0 {
+                speed_up_limit(cur - quarter * 2.0, 0.0, power, quarter, power / 2.0)
+            } else {
+                speed_down_limit(cur - quarter * 3.0, 0.0, power / 2.0, quarter, 0.0)
+            };
+            out.x = d * rad.cos();
+            out.y = d * rad.sin();
+        }
+        VmQuakeKind::Dir => {
+            // Eight discrete directions, swinging to both sides of the origin.
+            let (sx, sy) = match req.vec {
+                0 => (0.0, -1.0),
+                1 => (1.0, -1.0),
+                2 => (1.0, 0.0),
+                3 => (1.0, 1.0),
+                4 => (0.0, 1.0),
+                5 => (-1.0, 1.0),
+                6 => (-1.0, 0.0),
+                7 => (-1.0, -1.0),
+                _ => (0.0, 0.0),
+            };
+            let power = req.power as f32;
+            let d = if cur < quarter {
+                speed_down_limit(cur, 0.0, 0.0, quarter, power / 2.0)
+            } else if cur < quarter * 2.0 {
+                speed_up_limit(cur - quarter, 0.0, power / 2.0, quarter, 0.0)
+            } else if cur < quarter * 3.0 {
+                speed_down_limit(cur - quarter * 2.0, 0.0, 0.0, quarter, -power / 2.0)
+            } else {
+                speed_up_limit(cur - quarter * 3.0, 0.0, -power / 2.0, quarter, 0.0)
+            };
+            out.x = d * sx;
+            out.y = d * sy;
+        }
+        VmQuakeKind::Zoom => {
+            // Only scale and centre change; `power` 0..255 maps to 1.0..256x.
+            let power = req.power.clamp(0, 255) as f32;
+            let peak = 256.0 / (256.0 - power);
+            let half = (peak - 1.0) / 2.0 + 1.0;
+            out.center_x = req.center_x as f32;
+            out.center_y = req.center_y as f32;
+            out.scale = if cur < quarter {
+                speed_up_limit(cur, 0.0, 1.0, quarter, half)
+            } else if cur < quarter * 2.0 {
+                speed_down_limit(cur - quarter, 0.0, half, quarter, peak)
+            } else if cur < quarter * 3.0 {
+                speed_up_limit(cur - quarter * 2.0, 0.0, peak, quarter, half)
+            } else {
+                speed_down_limit(cur - quarter * 3.0, 0.0, half, quarter, 1.0)
+            };
+        }
+    }
+
+    let fade_start = total * req.cnt.max(0) as f32;
+    if elapsed_ms >= fade_start {
+        let fade = linear_limit(elapsed_ms, fade_start, 1.0, total_quake_time, 0.0);
+        out.x *= fade;
+        out.y *= fade;
+        out.scale = (out.scale - 1.0) * fade + 1.0;
+    }
+    out
+}
+
+/// One step of a `#SHAKE.NNN` pattern: hold the offset for `time_ms`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShakeStep {
+    pub x: i32,
+    pub y: i32,
+    pub time_ms: i32,
+}
+
+/// Pattern used when Gameexe defines no `#SHAKE.NNN` for the requested number.
+pub const DEFAULT_SHAKE_PATTERN: &[ShakeStep] = &[
+    ShakeStep {
+        x: 0,
+        y: -8,
+        time_ms: 33,
+    },
+    ShakeStep {
+        x: 0,
+        y: 8,
+        time_ms: 33,
+    },
+    ShakeStep {
+        x: 0,
+        y: -6,
+        time_ms: 33,
+    },
+    ShakeStep {
+        x: 0,
+        y: 6,
+        time_ms: 33,
+    },
+    ShakeStep {
+        x: 0,
+        y: -4,
+        time_ms: 33,
+    },
+    ShakeStep {
+        x: 0,
+        y: 4,
+        time_ms: 33,
+    },
+    ShakeStep {
+        x: 0,
+        y: -2,
+        time_ms: 33,
+    },
+    ShakeStep {
+        x: 0,
+        y: 2,
+        time_ms: 33,
+    },
+];
+
+/// Parse a Gameexe `#SHAKE.NNN` value: `(x, y, time), (x, y, time), ...`.
+pub fn parse_shake_pattern(raw: &str) -> Vec<ShakeStep> {
+    let nums: Vec<i32> = raw
+        .split(|c: char| c == ',' || c == '(' || c == ')' || c.is_whitespace())
+        .filter_map(|s| s.trim().parse::<i32>().ok())
+        .collect();
+    nums.chunks_exact(3)
+        .map(|c| ShakeStep {
+            x: c[0],
+            y: c[1],
+            time_ms: c[2].max(0),
+        })
+        .collect()
+}
+
+/// Total running time of a shake pattern.
+pub fn shake_duration_ms(steps: &[ShakeStep]) -> u64 {
+    steps.iter().map(|s| s.time_ms.max(0) as u64).sum()
+}
+
+/// Stage offset of a shake `elapsed_ms` after it started; `None` once finished.
+///
+/// C++ reference: elm_screen.cpp `C_elm_screen_shake::frame`.
+pub fn shake_offset_at(steps: &[ShakeStep], elapsed_ms: u64) -> Option<(f32, f32)> {
+    let mut t = 0u64;
+    for s in steps {
+        t += s.time_ms.max(0) as u64;
+        if elapsed_ms < t {
+            return Some((s.x as f32, s.y as f32));
+        }
+    }
+    None
+}
+
+/// Ease-in from `start_value` to `end_value` over `start_time..end_time`.
+pub fn speed_up_limit(
+    cur_time: f32,
+    start_time: f32,
+    start_value: f32,
+    end_time: f32,
+    end_value: f32,
+) -> f32 {
+    if (start_time - end_time).abs() < f32::EPSILON {
+        return end_value;
+    }
+    let ct = cur_time.clamp(start_time.min(end_time), start_time.max(end_time));
+    let t = (ct - start_time) / (end_time - start_time);
+    t * t * (end_value - start_value) + start_value
+}
+
+/// Ease-out from `start_value` to `end_value` over `start_time..end_time`.
+pub fn speed_down_limit(
+    cur_time: f32,
+    start_time: f32,
+    start_value: f32,
+    end_time: f32,
+    end_value: f32,
+) -> f32 {
+    if (start_time - end_time).abs() < f32::EPSILON {
+        return end_value;
+    }
+    let ct = cur_time.clamp(start_time.min(end_time), start_time.max(end_time));
+    let t = (ct - end_time) / (end_time - start_time);
+    -(t * t) * (end_value - start_value) + end_value
+}
+
+/// Linear ramp from `start_value` to `end_value`, clamped at both ends.
+pub fn linear_limit(
+    cur_time: f32,
+    start_time: f32,
+    start_value: f32,
+    end_time: f32,
+    end_value: f32,
+) -> f32 {
+    if (start_time - end_time).abs() < f32::EPSILON {
+        return end_value;
+    }
+    if cur_time <= start_time {
+        return start_value;
+    }
+    if cur_time >= end_time {
+        return end_value;
+    }
+    (end_value - start_value) * (cur_time - start_time) / (end_time - start_time) + start_value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quake(kind: VmQuakeKind, power: i32) -> VmQuakeRequest {
+        VmQuakeRequest {
+            sub: 0,
+            kind,
+            time_ms: 400,
+            cnt: 1,
+            end_cnt: 1,
+            begin_order: 0,
+            end_order: 10,
+            wait_flag: false,
+            key_flag: false,
+            power,
+            vec: 0,
+            center_x: 0,
+            center_y: 0,
+        }
+    }
+
+    #[test]
+    fn vec_quake_peaks_mid_cycle_and_fades_out() {
+        let req = quake(VmQuakeKind::Vec, 20);
+        assert_eq!(quake_duration_ms(&req), 800);
+
+        let peak = quake_transform_at(&req, 200.0, 5);
+        assert!((peak.x - 20.0).abs() < 1e-3 && peak.y.abs() < 1e-3);
+        // Halfway through the end cycle the swing is halved.
+        let fading = quake_transform_at(&req, 600.0, 5);
+        assert!((fading.x - 10.0).abs() < 1e-3);
+
+        assert!(quake_transform_at(&req, 800.0, 5).is_identity());
+        assert!(quake_transform_at(&req, 200.0, 11).is_identity());
+    }
+
+    #[test]
+    fn zoom_quake_scales_around_the_stage_centre() {
+        let req = quake(VmQuakeKind::Zoom, 128);
+        let peak = quake_transform_at(&req, 200.0, 0);
+        assert!((peak.scale - 2.0).abs() < 1e-3);
+        assert_eq!(peak.apply_point(400.0, 300.0, 800.0, 600.0), (400.0, 300.0));
+        assert_eq!(peak.apply_point(500.0, 300.0, 800.0, 600.0), (600.0, 300.0));
+    }
+
+    #[test]
+    fn stacked_quakes_add_offsets_and_multiply_scales() {
+        let shift = QuakeTransform {
+            x: 3.0,
+            y: -2.0,
+            ..QuakeTransform::IDENTITY
+        };
+        let zoom = QuakeTransform {
+            scale: 2.0,
+            center_x: 5.0,
+            ..QuakeTransform::IDENTITY
+        };
+        let both = shift.combine(zoom).combine(shift);
+        assert_eq!((both.x, both.y, both.scale), (6.0, -4.0, 2.0));
+        assert_eq!(both.center_x, 5.0);
+
+        let quakes = [RenderQuake {
+            req: quake(VmQuakeKind::Vec, 20),
+            elapsed_ms: 200.0,
+        }];
+        let t = quake_transform_for_order(&quakes, (1.0, 1.0), 0);
+        assert!((t.x - 21.0).abs() < 1e-3 && (t.y - 1.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn shake_patterns_parse_and_step() {
+        let steps = parse_shake_pattern("(1, 2, 30), (3, 4, 10), (5, 6)");
+        assert_eq!(
+            steps,
+            [
+                ShakeStep {
+                    x: 1,
+                    y: 2,
+                    time_ms: 30
+                },
+                ShakeStep {
+                    x: 3,
+                    y: 4,
+                    time_ms: 10
+                },
+            ]
+        );
+        assert_eq!(shake_duration_ms(&steps), 40);
+        assert_eq!(shake_offset_at(&steps, 0), Some((1.0, 2.0)));
+        assert_eq!(shake_offset_at(&steps, 35), Some((3.0, 4.0)));
+        assert_eq!(shake_offset_at(&steps, 40), None);
+    }
+
+    #[test]
+    fn easing_curves_hit_their_end_values() {
+        for ease in [speed_up_limit, speed_down_limit, linear_limit] {
+            assert_eq!(ease(0.0, 0.0, 1.0, 10.0, 5.0), 1.0);
+            assert_eq!(ease(10.0, 0.0, 1.0, 10.0, 5.0), 5.0);
+            assert_eq!(ease(99.0, 0.0, 1.0, 10.0, 5.0), 5.0);
+        }
+        // Ease-in lags and ease-out leads the straight line.
+        assert!(speed_up_limit(5.0, 0.0, 0.0, 10.0, 1.0) < 0.5);
+        assert!(speed_down_limit(5.0, 0.0, 0.0, 10.0, 1.0) > 0.5);
+    }
+}
@@ -0,0 +1,507 @@
+use std::sync::Arc;
+
+use image::RgbaImage;
+
+use super::RenderObject;
+use crate::vm::VmRng;
+
+/// Fixed simulation step; every host stepping a `WeatherSim` to the same time
+/// sees the same particles regardless of its frame rate.
+pub const WEATHER_STEP_MS: u64 = 16;
+
+/// Named-arg ids of `set_weather_param_type_a` (falling particles).
+pub const WEATHER_A_CNT: i32 = 0;
+pub const WEATHER_A_PAT_MODE: i32 = 1;
+pub const WEATHER_A_PAT_NO_00: i32 = 2;
+pub const WEATHER_A_PAT_NO_01: i32 = 3;
+pub const WEATHER_A_PAT_TIME: i32 = 4;
+pub const WEATHER_A_MOVE_TIME_X: i32 = 5;
+pub const WEATHER_A_MOVE_TIME_Y: i32 = 6;
+pub const WEATHER_A_SIN_TIME_X: i32 = 7;
+pub const WEATHER_A_SIN_POWER_X: i32 = 8;
+pub const WEATHER_A_SIN_TIME_Y: i32 = 9;
+pub const WEATHER_A_SIN_POWER_Y: i32 = 10;
+pub const WEATHER_A_ZOOM_MIN: i32 = 11;
+pub const WEATHER_A_ZOOM_MAX: i32 = 12;
+pub const WEATHER_A_TR_MIN: i32 = 13;
+pub const WEATHER_A_TR_MAX: i32 = 14;
+pub const WEATHER_A_ACTIVE_TIME: i32 = 15;
+pub const WEATHER_A_ARG_CNT: i32 = 16;
+
+/// Named-arg ids of `set_weather_param_type_b` (particles spreading from a
+/// center point).
+pub const WEATHER_B_CNT: i32 = 0;
+pub const WEATHER_B_PAT_MODE: i32 = 1;
+pub const WEATHER_B_PAT_NO_00: i32 = 2;
+pub const WEATHER_B_PAT_NO_01: i32 = 3;
+pub const WEATHER_B_PAT_TIME: i32 = 4;
+pub const WEATHER_B_CENTER_X: i32 = 5;
+pub const WEATHER_B_CENTER_Y: i32 = 6;
+pub const WEATHER_B_APPEAR_RANGE: i32 = 7;
+pub const WEATHER_B_MOVE_TIME: i32 = 8;
+pub const WEATHER_B_MOVE_RANGE: i32 = 9;
+pub const WEATHER_B_ZOOM_MIN: i32 = 10;
+pub const WEATHER_B_ZOOM_MAX: i32 = 11;
+pub const WEATHER_B_TR_MIN: i32 = 12;
+pub const WEATHER_B_TR_MAX: i32 = 13;
+pub const WEATHER_B_ACTIVE_TIME: i32 = 14;
+pub const WEATHER_B_ARG_CNT: i32 = 15;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WeatherKind {
+    /// Snow/rain/petals crossing the stage with wind and sway.
+    #[default]
+    A,
+    /// Sparkles appearing around a point and drifting outwards.
+    B,
+}
+
+/// How a particle picks its g00 pattern.
+pub const WEATHER_PAT_MODE_FIXED: i32 = 0;
+pub const WEATHER_PAT_MODE_RANDOM: i32 = 1;
+pub const WEATHER_PAT_MODE_ANIME: i32 = 2;
+
+/// Parameters of a weather object (C++ `S_tnm_weather_param`). Times are
+/// milliseconds, zooms per mille and transparency 0..=255.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WeatherParams {
+    pub kind: WeatherKind,
+    pub cnt: i32,
+    pub pat_mode: i32,
+    pub pat_no_00: i32,
+    pub pat_no_01: i32,
+    pub pat_time: i32,
+    /// Type A: time to cross the stage sideways (sign is the wind
+    /// direction, 0 for no wind) and to fall its height.
+    pub move_time_x: i32,
+    pub move_time_y: i32,
+    pub sin_time_x: i32,
+    pub sin_power_x: i32,
+    pub sin_time_y: i32,
+    pub sin_power_y: i32,
+    /// Type B: spawn point and radius, and how far particles travel in
+    /// `move_time`.
+    pub center_x: i32,
+    pub center_y: i32,
+    pub appear_range: i32,
+    pub move_time: i32,
+    pub move_range: i32,
+    pub zoom_min: i32,
+    pub zoom_max: i32,
+    pub tr_min: i32,
+    pub tr_max: i32,
+    /// Particle lifetime; 0 keeps type A particles until they leave the
+    /// stage and type B ones for `move_time`.
+    pub active_time: i32,
+}
+
+impl WeatherParams {
+    pub fn type_a() -> Self {
+        Self {
+            kind: WeatherKind::A,
+            cnt: 64,
+            pat_mode: WEATHER_PAT_MODE_FIXED,
+            pat_no_00: 0,
+            pat_no_01: 0,
+            pat_time: 100,
+            move_time_x: 0,
+            move_time_y: 4000,
+            sin_time_x: 2000,
+            sin_power_x: 20,
+            sin_time_y: 0,
+            sin_power_y: 0,
+            center_x: 0,
+            center_y: 0,
+            appear_range: 0,
+            move_time: 0,
+            move_range: 0,
+            zoom_min: 1000,
+            zoom_max: 1000,
+            tr_min: 255,
+            tr_max: 255,
+            active_time: 0,
+        }
+    }
+
+    pub fn type_b() -> Self {
+        Self {
+            kind: WeatherKind::B,
+            cnt: 32,
+            move_time_y: 0,
+            sin_time_x: 0,
+            sin_power_x: 0,
+            appear_range: 100,
+            move_time: 2000,
+            move_range: 200,
+            ..Self::type_a()
+        }
+    }
+
+    /// Apply one named argument of `set_weather_param_type_a/b`; returns
+    /// false for an id the type does not have.
+    pub fn set_named(&mut self, id: i32, value: i32) -> bool {
+        let slot = match (self.kind, id) {
+            (_, WEATHER_A_CNT) => &mut self.cnt,
+            (_, WEATHER_A_PAT_MODE) => &mut self.pat_mode,
+            (_, WEATHER_A_PAT_NO_00) => &mut self.pat_no_00,
+            (_, WEATHER_A_PAT_NO_01) => &mut self.pat_no_01,
+            (_, WEATHER_A_PAT_TIME) => &mut self.pat_time,
+            (WeatherKind::A, WEATHER_A_MOVE_TIME_X) => &mut self.move_time_x,
+            (WeatherKind::A, WEATHER_A_MOVE_TIME_Y) => &mut self.move_time_y,
+            (WeatherKind::A, WEATHER_A_SIN_TIME_X) => &mut self.sin_time_x,
+            (WeatherKind::A, WEATHER_A_SIN_POWER_X) => &mut self.sin_power_x,
+            (WeatherKind::A, WEATHER_A_SIN_TIME_Y) => &mut self.sin_time_y,
+            (WeatherKind::A, WEATHER_A_SIN_POWER_Y) => &mut self.sin_power_y,
+            (WeatherKind::A, WEATHER_A_ZOOM_MIN) => &mut self.zoom_min,
+            (WeatherKind::A, WEATHER_A_ZOOM_MAX) => &mut self.zoom_max,
+            (WeatherKind::A, WEATHER_A_TR_MIN) => &mut self.tr_min,
+            (WeatherKind::A, WEATHER_A_TR_MAX) => &mut self.tr_max,
+            (WeatherKind::A, WEATHER_A_ACTIVE_TIME) => &mut self.active_time,
+            (WeatherKind::B, WEATHER_B_CENTER_X) => &mut self.center_x,
+            (WeatherKind::B, WEATHER_B_CENTER_Y) => &mut self.center_y,
+            (WeatherKind::B, WEATHER_B_APPEAR_RANGE) => &mut self.appear_range,
+            (WeatherKind::B, WEATHER_B_MOVE_TIME) => &mut self.move_time,
+            (WeatherKind::B, WEATHER_B_MOVE_RANGE) => &mut self.move_range,
+            (WeatherKind::B, WEATHER_B_ZOOM_MIN) => &mut self.zoom_min,
+            (WeatherKind::B, WEATHER_B_ZOOM_MAX) => &mut self.zoom_max,
+            (WeatherKind::B, WEATHER_B_TR_MIN) => &mut self.tr_min,
+            (WeatherKind::B, WEATHER_B_TR_MAX) => &mut self.tr_max,
+            (WeatherKind::B, WEATHER_B_ACTIVE_TIME) => &mut self.active_time,
+            _ => return false,
+        };
+        *slot = value;
+        true
+    }
+
+    /// Every pattern a particle may show.
+    pub fn pat_range(&self) -> std::ops::RangeInclusive<i32> {
+        let lo = self.pat_no_00.min(self.pat_no_01).max(0);
+        let hi = self.pat_no_00.max(self.pat_no_01).max(0);
+        if self.pat_mode == WEATHER_PAT_MODE_FIXED {
+            self.pat_no_00.max(0)..=self.pat_no_00.max(0)
+        } else {
+            lo..=hi
+        }
+    }
+}
+
+impl Default for WeatherParams {
+    fn default() -> Self {
+        Self::type_a()
+    }
+}
+
+/// One live particle, in stage pixels relative to the weather object.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WeatherParticle {
+    pub x: f32,
+    pub y: f32,
+    pub scale: f32,
+    /// 0.0..=1.0, including the fade in/out of timed particles.
+    pub alpha: f32,
+    pub pat_no: i32,
+    base_x: f32,
+    base_y: f32,
+    vx: f32,
+    vy: f32,
+    tr: f32,
+    age_ms: u64,
+    life_ms: u64,
+    sin_phase: f32,
+    pat_offset: i32,
+}
+
+/// Fixed-step particle simulation of one weather object.
+#[derive(Debug, Clone)]
+pub struct WeatherSim {
+    params: WeatherParams,
+    width: f32,
+    height: f32,
+    rng: VmRng,
+    time_ms: u64,
+    particles: Vec<WeatherParticle>,
+}
+
+impl WeatherSim {
+    /// Start a weather over a `width`x`height` stage. Type A starts with
+    /// particles already spread over the stage.
+    pub fn new(params: WeatherParams, width: u32, height: u32, seed: u64) -> Self {
+        let mut sim = Self {
+            params,
+            width: width.max(1) as f32,
+            height: height.max(1) as f32,
+            rng: VmRng::new(seed),
+            time_ms: 0,
+            particles: Vec::new(),
+        };
+        for _ in 0..params.cnt.clamp(0, 4096) {
+            let p = sim.spawn(true);
+            sim.particles.push(p);
+        }
+        sim
+    }
+
+    pub fn params(&self) -> &WeatherParams {
+        &self.params
+    }
+
+    pub fn time_ms(&self) -> u64 {
+        self.time_ms
+    }
+
+    pub fn particles(&self) -> &[WeatherParticle] {
+        &self.particles
+    }
+
+    /// Step until `time_ms` (since the weather started); never steps back.
+    pub fn advance_to(&mut self, time_ms: u64) {
+        while self.time_ms + WEATHER_STEP_MS <= time_ms {
+            self.step();
+        }
+    }
+
+    fn unit(&mut self) -> f32 {
+        self.rng.next_u32() as f32 / u32::MAX as f32
+    }
+
+    fn range(&mut self, lo: i32, hi: i32) -> f32 {
+        let (lo, hi) = if lo <= hi { (lo, hi) } else { (hi, lo) };
+        (lo + self.rng.next_below(hi.saturating_sub(lo).saturating_add(1))) as f32
+    }
+
+    /// Per-particle speed spread around the configured time.
+    fn speed(&mut self, distance: f32, time_ms: i32) -> f32 {
+        if time_ms == 0 {
+            return 0.0;
+        }
+        let jitter = 0.75 + self.unit() * 0.5;
+        distance / time_ms as f32 * jitter
+    }
+
+    fn spawn(&mut self, initial: bool) -> WeatherParticle {
+        let p = self.params;
+        let scale = self.range(p.zoom_min, p.zoom_max).max(0.0) / 1000.0;
+        let tr = self.range(p.tr_min, p.tr_max).clamp(0.0, 255.0) / 255.0;
+        let pats = p.pat_range();
+        let pat_span = pats.end() - pats.start() + 1;
+        let pat_offset = self.rng.next_below(pat_span);
+        let sin_phase = self.unit();
+        let (base_x, base_y, vx, vy, life_ms) = match p.kind {
+            WeatherKind::A => {
+                // Spawn above the stage, upwind so wind carries it across.
+                let vx = self.speed(self.width, p.move_time_x);
+                let vy = self.speed(self.height, p.move_time_y);
+                let margin = self.width * 0.25;
+                let x = -margin + self.unit() * (self.width + margin * 2.0);
+                let y = if initial {
+                    self.unit() * self.height
+                } else {
+                    -self.height * 0.05 - self.unit() * self.height * 0.1
+                };
+                (x, y, vx, vy, p.active_time.max(0) as u64)
+            }
+            WeatherKind::B => {
+                let angle = self.unit() * std::f32::consts::TAU;
+                let r = self.unit() * p.appear_range.max(0) as f32;
+                let (s, c) = angle.sin_cos();
+                let speed = self.speed(p.move_range as f32, p.move_time);
+                let life = if p.active_time > 0 {
+                    p.active_time
+                } else {
+                    p.move_time
+                };
+                (
+                    p.center_x as f32 + c * r,
+                    p.center_y as f32 + s * r,
+                    c * speed,
+                    s * speed,
+                    life.max(1) as u64,
+                )
+            }
+        };
+        let mut particle = WeatherParticle {
+            x: base_x,
+            y: base_y,
+            scale,
+            alpha: tr,
+            pat_no: *pats.start() + pat_offset,
+            base_x,
+            base_y,
+            vx,
+            vy,
+            tr,
+            age_ms: 0,
+            life_ms,
+            sin_phase,
+            pat_offset,
+        };
+        if initial && life_ms > 0 {
+            // Stagger lifetimes so particles do not all expire together.
+            particle.age_ms = (self.unit() * life_ms as f32) as u64;
+        }
+        self.place(&mut particle);
+        particle
+    }
+
+    /// Derive the drawn position, pattern and alpha from the particle's age.
+    fn place(&self, pt: &mut WeatherParticle) {
+        let p = &self.params;
+        let age = pt.age_ms as f32;
+        let sway = |time: i32, power: i32| {
+            if time == 0 || power == 0 {
+                0.0
+            } else {
+                (std::f32::consts::TAU * (age / time as f32 + pt.sin_phase)).sin() * power as f32
+            }
+        };
+        pt.x = pt.base_x + sway(p.sin_time_x, p.sin_power_x);
+        pt.y = pt.base_y + sway(p.sin_time_y, p.sin_power_y);
+        let pats = p.pat_range();
+        let pat_span = pats.end() - pats.start() + 1;
+        pt.pat_no = if p.pat_mode == WEATHER_PAT_MODE_ANIME && p.pat_time > 0 {
+            let step = (pt.age_ms / p.pat_time as u64) as i32;
+            pats.start() + (pt.pat_offset + step).rem_euclid(pat_span)
+        } else {
+            pats.start() + pt.pat_offset
+        };
+        // Timed particles fade in and out over a tenth of their life each.
+        let fade = if pt.life_ms > 0 {
+            let t = age / pt.life_ms as f32;
+            (t * 10.0).min((1.0 - t) * 10.0).clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+        pt.alpha = pt.tr * fade;
+    }
+
+    fn step(&mut self) {
+        self.time_ms += WEATHER_STEP_MS;
+        let dt = WEATHER_STEP_MS as f32;
+        for i in 0..self.particles.len() {
+            let mut pt = self.particles[i];
+            pt.age_ms += WEATHER_STEP_MS;
+            pt.base_x += pt.vx * dt;
+            pt.base_y += pt.vy * dt;
+            let expired = pt.life_ms > 0 && pt.age_ms >= pt.life_ms;
+            let gone = self.params.kind == WeatherKind::A && self.out_of_stage(&pt);
+            if expired || gone {
+                pt = self.spawn(false);
+            } else {
+                self.place(&mut pt);
+            }
+            self.particles[i] = pt;
+        }
+    }
+
+    /// Type A particles past the stage edge they are moving towards.
+    fn out_of_stage(&self, pt: &WeatherParticle) -> bool {
+        let margin_x = self.width * 0.3;
+        let margin_y = self.height * 0.2;
+        (pt.vy >= 0.0 && pt.base_y > self.height + margin_y)
+            || (pt.vy < 0.0 && pt.base_y < -margin_y)
+            || (pt.vx > 0.0 && pt.base_x > self.width + margin_x)
+            || (pt.vx < 0.0 && pt.base_x < -margin_x)
+    }
+
+    /// Expand into drawable objects: one copy of `base` per particle, moved
+    /// by the particle offset and scaled/faded by it. `pattern` supplies the
+    /// g00 cut for a pattern number.
+    pub fn render_objects(
+        &self,
+        base: &RenderObject,
+        pattern: &dyn Fn(i32) -> Option<Arc<RgbaImage>>,
+    ) -> Vec<RenderObject> {
+        self.particles
+            .iter()
+            .filter(|pt| pt.alpha > 0.0)
+            .filter_map(|pt| {
+                let image = pattern(pt.pat_no)?;
+                Some(RenderObject {
+                    x: base.x + pt.x,
+                    y: base.y + pt.y,
+                    center_x: image.width() as f32 / 2.0,
+                    center_y: image.height() as f32 / 2.0,
+                    scale_x: base.scale_x * pt.scale,
+                    scale_y: base.scale_y * pt.scale,
+                    alpha: base.alpha * pt.alpha,
+                    image: Some(image),
+                    ..base.clone()
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn named_params_follow_the_weather_type() {
+        let mut a = WeatherParams::type_a();
+        assert!(a.set_named(WEATHER_A_MOVE_TIME_X, -3000));
+        assert_eq!(a.move_time_x, -3000);
+        assert!(!a.set_named(WEATHER_A_ARG_CNT, 1));
+
+        let mut b = WeatherParams::type_b();
+        // Id 5 is move_time_x for type A but center_x for type B.
+        assert!(b.set_named(WEATHER_B_CENTER_X, 640));
+        assert_eq!((b.center_x, b.move_time_x), (640, 0));
+
+        b.pat_no_00 = 4;
+        b.pat_no_01 = 2;
+        assert_eq!(b.pat_range(), 4..=4);
+        b.pat_mode = WEATHER_PAT_MODE_RANDOM;
+        assert_eq!(b.pat_range(), 2..=4);
+    }
+
+    #[test]
+    fn simulation_depends_only_on_seed_and_time() {
+        let params = WeatherParams {
+            cnt: 12,
+            ..WeatherParams::type_a()
+        };
+        let mut fine = WeatherSim::new(params, 640, 480, 7);
+        let mut coarse = WeatherSim::new(params, 640, 480, 7);
+        for t in (0..=1000).step_by(5) {
+            fine.advance_to(t);
+        }
+        coarse.advance_to(1000);
+        assert_eq!(fine.particles().len(), 12);
+        assert_eq!(fine.particles(), coarse.particles());
+        assert_eq!(fine.time_ms(), 1000 / WEATHER_STEP_MS * WEATHER_STEP_MS);
+
+        // Time never runs backwards.
+        let before = coarse.particles().to_vec();
+        coarse.advance_to(0);
+        assert_eq!(coarse.particles(), &before[..]);
+    }
+
+    #[test]
+    fn render_objects_place_one_copy_per_particle() {
+        let params = WeatherParams {
+            cnt: 5,
+            ..WeatherParams::type_a()
+        };
+        let sim = WeatherSim::new(params, 320, 240, 1);
+        let base = RenderObject {
+            x: 10.0,
+            y: 20.0,
+            alpha: 0.5,
+            ..RenderObject::default()
+        };
+        let flake = Arc::new(RgbaImage::new(4, 6));
+        let objects = sim.render_objects(&base, &|pat_no| (pat_no == 0).then(|| flake.clone()));
+        let visible: Vec<_> = sim.particles().iter().filter(|pt| pt.alpha > 0.0).collect();
+        assert!(!objects.is_empty());
+        assert_eq!(objects.len(), visible.len());
+        for (obj, pt) in objects.iter().zip(visible) {
+            assert_eq!((obj.x, obj.y), (10.0 + pt.x, 20.0 + pt.y));
+            assert_eq!((obj.center_x, obj.center_y), (2.0, 3.0));
+            assert_eq!(obj.alpha, 0.5 * pt.alpha);
+        }
+
+        // Particles whose pattern has no image are dropped.
+        assert!(sim.render_objects(&base, &|_| None).is_empty());
+    }
+}
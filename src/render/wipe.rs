@@ -0,0 +1,176 @@
+use image::RgbaImage;
+
+use super::{RenderMask, RenderRect, blend_pixel, mask_wipe_coverage, sample_bilinear};
+
+/// Wipe catalog.
+///
+/// Only type 0 (crossfade, the `LOAD_WIPE` default in tnm_ini.cpp) is
+/// supported: the engine's wipe table is not part of this tree, so every other
+/// `wipe_type` falls back to a crossfade until the real catalog is ported.
+///
+/// `mask_wipe` / `mask_wipe_all` always produce `Mask`, a mask image thresholded
+/// over time with a `soft` fade band (0.0..=1.0 of the mask range); see
+/// `WipeKind::mask_from_type`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WipeKind {
+    Crossfade,
+    Mask { soft: f32, reverse: bool },
+}
+
+impl WipeKind {
+    /// Kind for a plain wipe; see the catalog note on `WipeKind`.
+    pub fn from_type(_wipe_type: i32) -> Self {
+        Self::Crossfade
+    }
+
+    /// Kind for a mask wipe: odd `wipe_type` switches bright mask areas first,
+    /// `option[0]` is the soft band width in mask levels (default 32, of 255).
+    pub fn mask_from_type(wipe_type: i32, option: &[i32]) -> Self {
+        let soft = option.first().copied().unwrap_or(32).clamp(0, 255);
+        Self::Mask {
+            soft: soft as f32 / 255.0,
+            reverse: wipe_type.rem_euclid(2) == 1,
+        }
+    }
+
+    pub fn from_request(req: &crate::vm::VmWipeRequest) -> Self {
+        if req.is_mask() {
+            Self::mask_from_type(req.wipe_type, &req.option)
+        } else {
+            Self::from_type(req.wipe_type)
+        }
+    }
+}
+
+/// Eased progress for `speed_mode` (C++ cmd_wipe.cpp: 0 linear, 1 accelerate, 2 decelerate).
+pub fn wipe_speed_curve(speed_mode: i32, t: f32) -> f32 {
+    let t = t.clamp(0.0, 1.0);
+    match speed_mode {
+        1 => t * t,
+        2 => 1.0 - (1.0 - t) * (1.0 - t),
+        _ => t,
+    }
+}
+
+/// Progress of a wipe `elapsed_ms` into `time_ms`, after the speed curve.
+pub fn wipe_progress(elapsed_ms: u64, time_ms: u64, speed_mode: i32) -> f32 {
+    if time_ms == 0 {
+        return 1.0;
+    }
+    wipe_speed_curve(speed_mode, elapsed_ms as f32 / time_ms as f32)
+}
+
+/// Blend `old` → `new` (same size as the output) at progress `t`.
+///
+/// `WipeKind::Mask` needs the mask image; use `compose_mask_wipe` for it.
+/// Without one it degrades to a crossfade.
+pub fn compose_wipe(old: &RgbaImage, new: &RgbaImage, _kind: WipeKind, t: f32) -> RgbaImage {
+    let (w, h) = new.dimensions();
+    let t = t.clamp(0.0, 1.0);
+    let old_bounds = RenderRect::new(0.0, 0.0, old.width() as f32, old.height() as f32);
+    let mut out = RgbaImage::new(w, h);
+    for (px, py, dst) in out.enumerate_pixels_mut() {
+        let (x, y) = (px as f32 + 0.5, py as f32 + 0.5);
+        if x < old_bounds.right && y < old_bounds.bottom {
+            let o = sample_bilinear(old, x, y, old_bounds);
+            blend_pixel(dst, [o[0], o[1], o[2]], o[3] / 255.0);
+        }
+        let n = new.get_pixel(px, py).0;
+        blend_pixel(
+            dst,
+            [n[0] as f32, n[1] as f32, n[2] as f32],
+            n[3] as f32 / 255.0 * t,
+        );
+    }
+    out
+}
+
+/// Mask wipe `old` → `new`: the mask is stretched over the frame and
+/// thresholded against `t` (see `mask_wipe_coverage`).
+///
+/// Non-mask kinds are forwarded to `compose_wipe`.
+pub fn compose_mask_wipe(
+    old: &RgbaImage,
+    new: &RgbaImage,
+    mask: &RenderMask,
+    kind: WipeKind,
+    t: f32,
+) -> RgbaImage {
+    let WipeKind::Mask { soft, reverse } = kind else {
+        return compose_wipe(old, new, kind, t);
+    };
+    let (w, h) = new.dimensions();
+    let (wf, hf) = (w as f32, h as f32);
+    let old_bounds = RenderRect::new(0.0, 0.0, old.width() as f32, old.height() as f32);
+    let mut out = RgbaImage::new(w, h);
+    for (px, py, dst) in out.enumerate_pixels_mut() {
+        let (x, y) = (px as f32 + 0.5, py as f32 + 0.5);
+        if x < old_bounds.right && y < old_bounds.bottom {
+            let o = sample_bilinear(old, x, y, old_bounds);
+            blend_pixel(dst, [o[0], o[1], o[2]], o[3] / 255.0);
+        }
+        let m = mask.stretched_value_at(x, y, wf, hf);
+        let cov = mask_wipe_coverage(m, t, soft, reverse);
+        let n = new.get_pixel(px, py).0;
+        blend_pixel(
+            dst,
+            [n[0] as f32, n[1] as f32, n[2] as f32],
+            n[3] as f32 / 255.0 * cov,
+        );
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_plain_type_is_a_crossfade() {
+        for wipe_type in [0, 1, 10, 42, 99, -1] {
+            assert_eq!(WipeKind::from_type(wipe_type), WipeKind::Crossfade);
+        }
+    }
+
+    #[test]
+    fn mask_kind_reads_parity_and_soft_band() {
+        assert_eq!(
+            WipeKind::mask_from_type(1, &[255]),
+            WipeKind::Mask {
+                soft: 1.0,
+                reverse: true
+            }
+        );
+        assert_eq!(
+            WipeKind::mask_from_type(2, &[]),
+            WipeKind::Mask {
+                soft: 32.0 / 255.0,
+                reverse: false
+            }
+        );
+    }
+
+    #[test]
+    fn crossfade_blends_old_into_new() {
+        let old = RgbaImage::from_pixel(4, 4, image::Rgba([200, 0, 0, 255]));
+        let new = RgbaImage::from_pixel(4, 4, image::Rgba([0, 0, 200, 255]));
+        let start = compose_wipe(&old, &new, WipeKind::Crossfade, 0.0);
+        let mid = compose_wipe(&old, &new, WipeKind::Crossfade, 0.5);
+        let end = compose_wipe(&old, &new, WipeKind::Crossfade, 1.0);
+        assert_eq!(start.get_pixel(1, 1).0, [200, 0, 0, 255]);
+        let m = mid.get_pixel(1, 1).0;
+        assert!((m[0] as i32 - 100).abs() <= 1 && (m[2] as i32 - 100).abs() <= 1);
+        assert_eq!(end.get_pixel(1, 1).0, [0, 0, 200, 255]);
+    }
+
+    #[test]
+    fn speed_curves_keep_their_endpoints() {
+        for mode in 0..3 {
+            assert_eq!(wipe_speed_curve(mode, 0.0), 0.0);
+            assert_eq!(wipe_speed_curve(mode, 1.0), 1.0);
+        }
+        assert!(wipe_speed_curve(1, 0.5) < 0.5);
+        assert!(wipe_speed_curve(2, 0.5) > 0.5);
+        assert_eq!(wipe_progress(10, 0, 0), 1.0);
+    }
+}
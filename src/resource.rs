@@ -3,3 +3,12 @@ pub use g00::*;
 
 pub mod media;
 pub use media::*;
+
+pub mod gan;
+pub use gan::*;
+
+pub mod tonecurve;
+pub use tonecurve::*;
+
+pub mod capture_bmp;
+pub use capture_bmp::*;
@@ -0,0 +1,124 @@
+use image::RgbaImage;
+
+/// Byte size of the file and info headers (`BITMAPFILEHEADER` + `BITMAPINFOHEADER`).
+const BMP_HEADER_SIZE: usize = 54;
+
+/// Capture file body: BMP header (14 + 40, BI_RGB) and bottom-up BGRA32 rows.
+pub fn encode_capture_bmp(image: &RgbaImage) -> Vec<u8> {
+    let w = image.width().max(1);
+    let h = image.height().max(1);
+    let pixel_bytes = w.saturating_mul(h).saturating_mul(4);
+    let file_size = (BMP_HEADER_SIZE as u32).saturating_add(pixel_bytes);
+    let mut out = vec![0u8; file_size as usize];
+    out[0] = b'B';
+    out[1] = b'M';
+    out[2..6].copy_from_slice(&file_size.to_le_bytes());
+    out[10..14].copy_from_slice(&(BMP_HEADER_SIZE as u32).to_le_bytes());
+    out[14..18].copy_from_slice(&40u32.to_le_bytes());
+    out[18..22].copy_from_slice(&(w as i32).to_le_bytes());
+    out[22..26].copy_from_slice(&(h as i32).to_le_bytes());
+    out[26..28].copy_from_slice(&1u16.to_le_bytes());
+    out[28..30].copy_from_slice(&32u16.to_le_bytes());
+    out[30..34].copy_from_slice(&0u32.to_le_bytes());
+    out[34..38].copy_from_slice(&pixel_bytes.to_le_bytes());
+    let rows = out[BMP_HEADER_SIZE..].chunks_exact_mut(w as usize * 4);
+    for (y, row) in rows.rev().enumerate().take(image.height() as usize) {
+        for (x, px) in row
+            .chunks_exact_mut(4)
+            .enumerate()
+            .take(image.width() as usize)
+        {
+            let [r, g, b, a] = image.get_pixel(x as u32, y as u32).0;
+            px.copy_from_slice(&[b, g, r, a]);
+        }
+    }
+    out
+}
+
+/// Inverse of `encode_capture_bmp` (24- or 32-bit BI_RGB, either row order).
+///
+/// The rows the header describes must all be in `bmp`; the image is only
+/// allocated after that check, so a corrupt size cannot blow up memory.
+pub fn decode_capture_bmp(bmp: &[u8]) -> Option<RgbaImage> {
+    let i32_at = |off: usize| Some(i32::from_le_bytes(bmp.get(off..off + 4)?.try_into().ok()?));
+    let pixel_off = usize::try_from(i32_at(10)?).ok()?;
+    let width = i32_at(18)?;
+    let height = i32_at(22)?;
+    let bpp = usize::from(u16::from_le_bytes(bmp.get(28..30)?.try_into().ok()?)) / 8;
+    if width <= 0 || height == 0 || !(bpp == 3 || bpp == 4) {
+        return None;
+    }
+    let (w, h) = (width as usize, height.unsigned_abs() as usize);
+    let row_bytes = w.checked_mul(bpp)?;
+    let stride = row_bytes.div_ceil(4).checked_mul(4)?;
+    let end = stride
+        .checked_mul(h - 1)?
+        .checked_add(row_bytes)?
+        .checked_add(pixel_off)?;
+    if end > bmp.len() {
+        return None;
+    }
+    let mut out = RgbaImage::new(w as u32, h as u32);
+    for y in 0..h {
+        let src_y = if height > 0 { h - 1 - y } else { y };
+        let start = pixel_off + src_y * stride;
+        let row = &bmp[start..start + row_bytes];
+        for (x, px) in row.chunks_exact(bpp).enumerate() {
+            let a = if bpp == 4 { px[3] } else { 255 };
+            out.put_pixel(x as u32, y as u32, image::Rgba([px[2], px[1], px[0], a]));
+        }
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn capture_bmp_round_trips() {
+        let mut image = RgbaImage::new(3, 2);
+        for (x, y, px) in image.enumerate_pixels_mut() {
+            *px = image::Rgba([x as u8 * 80, y as u8 * 100, 7, 200 + x as u8]);
+        }
+        let bmp = encode_capture_bmp(&image);
+        assert_eq!(&bmp[0..2], b"BM");
+        assert_eq!(bmp.len(), BMP_HEADER_SIZE + 3 * 2 * 4);
+        // Bottom-up BGRA: the first stored pixel is (0, 1).
+        assert_eq!(
+            &bmp[BMP_HEADER_SIZE..BMP_HEADER_SIZE + 4],
+            &[7, 100, 0, 200]
+        );
+        assert_eq!(decode_capture_bmp(&bmp), Some(image));
+    }
+
+    #[test]
+    fn truncated_or_oversized_bmps_are_rejected() {
+        let bmp = encode_capture_bmp(&RgbaImage::new(4, 4));
+        assert_eq!(decode_capture_bmp(&bmp[..bmp.len() - 1]), None);
+
+        // A header claiming a huge image over a few bytes of pixels.
+        let mut huge = bmp.clone();
+        huge[18..22].copy_from_slice(&0x4000_0000i32.to_le_bytes());
+        huge[22..26].copy_from_slice(&0x4000_0000i32.to_le_bytes());
+        assert_eq!(decode_capture_bmp(&huge), None);
+
+        let mut bad_depth = bmp;
+        bad_depth[28..30].copy_from_slice(&8u16.to_le_bytes());
+        assert_eq!(decode_capture_bmp(&bad_depth), None);
+    }
+
+    #[test]
+    fn top_down_24_bit_rows_decode() {
+        // 2x1, 24-bit, top-down (negative height), rows padded to 8 bytes.
+        let mut bmp = vec![0u8; BMP_HEADER_SIZE];
+        bmp[10..14].copy_from_slice(&(BMP_HEADER_SIZE as u32).to_le_bytes());
+        bmp[18..22].copy_from_slice(&2i32.to_le_bytes());
+        bmp[22..26].copy_from_slice(&(-1i32).to_le_bytes());
+        bmp[28..30].copy_from_slice(&24u16.to_le_bytes());
+        bmp.extend_from_slice(&[1, 2, 3, 4, 5, 6, 0, 0]);
+        let image = decode_capture_bmp(&bmp).expect("decodes");
+        assert_eq!(image.get_pixel(0, 0).0, [3, 2, 1, 255]);
+        assert_eq!(image.get_pixel(1, 0).0, [6, 5, 4, 255]);
+    }
+}
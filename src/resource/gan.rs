@@ -0,0 +1,291 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result, bail};
+
+const GAN_CODE_HEADER: i32 = 10000;
+const GAN_CODE_SET_LIST: i32 = 20000;
+const GAN_CODE_SET: i32 = 30000;
+const GAN_CODE_PAT_NO: i32 = 30100;
+const GAN_CODE_X: i32 = 30101;
+const GAN_CODE_Y: i32 = 30102;
+const GAN_CODE_TIME: i32 = 30103;
+const GAN_CODE_TR: i32 = 30104;
+const GAN_CODE_Z: i32 = 30105;
+const GAN_CODE_PAT_END: i32 = 999999;
+
+/// One frame of a gan set: which g00 pattern to show and for how long.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GanPat {
+    pub pat_no: i32,
+    pub x: i32,
+    pub y: i32,
+    /// Milliseconds this pattern stays up.
+    pub time_ms: i32,
+    /// Transparency, 0..=255.
+    pub tr: i32,
+    pub z: i32,
+}
+
+impl Default for GanPat {
+    fn default() -> Self {
+        Self {
+            pat_no: 0,
+            x: 0,
+            y: 0,
+            time_ms: 0,
+            tr: 255,
+            z: 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GanSet {
+    pub pats: Vec<GanPat>,
+}
+
+impl GanSet {
+    /// Length of one pass through every pattern.
+    pub fn total_ms(&self) -> u64 {
+        self.pats.iter().map(|p| p.time_ms.max(0) as u64).sum()
+    }
+
+    /// Index of the pattern shown `elapsed_ms` after the set started, and
+    /// whether the animation is still running. A set that does not loop
+    /// stops on its last pattern.
+    pub fn pat_index_at(&self, elapsed_ms: u64, loop_flag: bool) -> Option<(usize, bool)> {
+        let last = self.pats.len().checked_sub(1)?;
+        let total = self.total_ms();
+        if total == 0 {
+            return Some((last, false));
+        }
+        let mut t = elapsed_ms;
+        if t >= total {
+            if !loop_flag {
+                return Some((last, false));
+            }
+            t %= total;
+        }
+        for (i, pat) in self.pats.iter().enumerate() {
+            let time = pat.time_ms.max(0) as u64;
+            if t < time {
+                return Some((i, true));
+            }
+            t -= time;
+        }
+        Some((last, loop_flag))
+    }
+
+    pub fn pat_at(&self, elapsed_ms: u64, loop_flag: bool) -> Option<&GanPat> {
+        self.pat_index_at(elapsed_ms, loop_flag)
+            .map(|(i, _)| &self.pats[i])
+    }
+}
+
+/// A parsed `.gan` file: the g00 it animates and its pattern sets
+/// (C++ ganlib `C_gan_data`).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GanData {
+    pub g00_name: String,
+    pub sets: Vec<GanSet>,
+}
+
+struct GanReader<'a> {
+    data: &'a [u8],
+    off: usize,
+}
+
+impl GanReader<'_> {
+    fn int(&mut self) -> Result<i32> {
+        let Some(bytes) = self.data.get(self.off..self.off + 4) else {
+            bail!("gan truncated at {:#x}", self.off);
+        };
+        self.off += 4;
+        Ok(i32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn expect(&mut self, code: i32) -> Result<()> {
+        let at = self.off;
+        let v = self.int()?;
+        if v != code {
+            bail!("gan expected code {code} at {at:#x}, got {v}");
+        }
+        Ok(())
+    }
+
+    fn bytes(&mut self, len: usize) -> Result<&[u8]> {
+        let Some(bytes) = self.data.get(self.off..self.off + len) else {
+            bail!("gan truncated at {:#x}", self.off);
+        };
+        self.off += len;
+        Ok(bytes)
+    }
+}
+
+/// Parse a `.gan` file:
+///
+/// ```text
+/// 10000 10000 name_len name[name_len]
+/// 20000 set_cnt
+///   30000 pat_cnt
+///     (code value)* 999999     code: 30100 pat_no, 30101 x, 30102 y,
+///                                    30103 time, 30104 tr, 30105 z
+/// ```
+pub fn parse_gan(data: &[u8]) -> Result<GanData> {
+    let mut r = GanReader { data, off: 0 };
+    r.expect(GAN_CODE_HEADER)?;
+    r.expect(GAN_CODE_HEADER)?;
+    let name_len = r.int()?.max(0) as usize;
+    let name_raw = r.bytes(name_len)?;
+    let name_raw = name_raw.split(|b| *b == 0).next().unwrap_or_default();
+    let g00_name = encoding_rs::SHIFT_JIS.decode(name_raw).0.into_owned();
+
+    r.expect(GAN_CODE_SET_LIST)?;
+    let set_cnt = r.int()?.max(0) as usize;
+    let mut sets = Vec::with_capacity(set_cnt.min(1024));
+    for _ in 0..set_cnt {
+        r.expect(GAN_CODE_SET)?;
+        let pat_cnt = r.int()?.max(0) as usize;
+        let mut pats = Vec::with_capacity(pat_cnt.min(1024));
+        for _ in 0..pat_cnt {
+            let mut pat = GanPat::default();
+            loop {
+                let code = r.int()?;
+                if code == GAN_CODE_PAT_END {
+                    break;
+                }
+                let value = r.int()?;
+                match code {
+                    GAN_CODE_PAT_NO => pat.pat_no = value,
+                    GAN_CODE_X => pat.x = value,
+                    GAN_CODE_Y => pat.y = value,
+                    GAN_CODE_TIME => pat.time_ms = value,
+                    GAN_CODE_TR => pat.tr = value,
+                    GAN_CODE_Z => pat.z = value,
+                    _ => bail!("gan unknown pattern code {code} at {:#x}", r.off - 8),
+                }
+            }
+            pats.push(pat);
+        }
+        sets.push(GanSet { pats });
+    }
+    Ok(GanData { g00_name, sets })
+}
+
+pub fn read_gan(path: &Path) -> Result<GanData> {
+    let data = fs::read(path).with_context(|| format!("read gan: {}", path.display()))?;
+    parse_gan(&data).with_context(|| format!("parse gan: {}", path.display()))
+}
+
+/// A started gan set (`object.start_gan`), timed on the VM clock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GanPlayback {
+    pub set_no: usize,
+    pub loop_flag: bool,
+    pub start_ms: u64,
+}
+
+impl GanPlayback {
+    /// Pattern to show at `now_ms`, and whether the animation still runs.
+    pub fn pat_at<'a>(&self, gan: &'a GanData, now_ms: u64) -> Option<(&'a GanPat, bool)> {
+        let set = gan.sets.get(self.set_no)?;
+        let (i, running) =
+            set.pat_index_at(now_ms.saturating_sub(self.start_ms), self.loop_flag)?;
+        Some((&set.pats[i], running))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pat(pat_no: i32, time_ms: i32) -> GanPat {
+        GanPat {
+            pat_no,
+            time_ms,
+            ..GanPat::default()
+        }
+    }
+
+    #[test]
+    fn parses_sets_and_pattern_fields() {
+        let name = b"anim\0\0\0\0";
+        let mut words = vec![GAN_CODE_HEADER, GAN_CODE_HEADER, name.len() as i32];
+        let mut data: Vec<u8> = words.drain(..).flat_map(i32::to_le_bytes).collect();
+        data.extend_from_slice(name);
+        for v in [
+            GAN_CODE_SET_LIST,
+            1,
+            GAN_CODE_SET,
+            2,
+            GAN_CODE_PAT_NO,
+            3,
+            GAN_CODE_X,
+            -4,
+            GAN_CODE_TIME,
+            100,
+            GAN_CODE_TR,
+            128,
+            GAN_CODE_PAT_END,
+            GAN_CODE_PAT_NO,
+            5,
+            GAN_CODE_Y,
+            6,
+            GAN_CODE_Z,
+            7,
+            GAN_CODE_PAT_END,
+        ] {
+            data.extend_from_slice(&v.to_le_bytes());
+        }
+
+        let gan = parse_gan(&data).unwrap();
+        assert_eq!(gan.g00_name, "anim");
+        assert_eq!(gan.sets.len(), 1);
+        let pats = &gan.sets[0].pats;
+        assert_eq!(
+            pats[0],
+            GanPat {
+                pat_no: 3,
+                x: -4,
+                y: 0,
+                time_ms: 100,
+                tr: 128,
+                z: 0,
+            }
+        );
+        assert_eq!(
+            (pats[1].pat_no, pats[1].y, pats[1].z, pats[1].tr),
+            (5, 6, 7, 255)
+        );
+
+        data.truncate(data.len() - 4);
+        assert!(parse_gan(&data).is_err());
+    }
+
+    #[test]
+    fn steps_patterns_and_stops_or_loops() {
+        let set = GanSet {
+            pats: vec![pat(0, 100), pat(1, 50), pat(2, 100)],
+        };
+        assert_eq!(set.pat_index_at(0, false), Some((0, true)));
+        assert_eq!(set.pat_index_at(120, false), Some((1, true)));
+        assert_eq!(set.pat_index_at(150, false), Some((2, true)));
+        assert_eq!(set.pat_index_at(250, false), Some((2, false)));
+        assert_eq!(set.pat_index_at(260, true), Some((0, true)));
+        assert_eq!(set.pat_index_at(360, true), Some((1, true)));
+        assert_eq!(GanSet::default().pat_index_at(0, true), None);
+
+        let play = GanPlayback {
+            set_no: 0,
+            loop_flag: false,
+            start_ms: 1000,
+        };
+        let gan = GanData {
+            g00_name: String::new(),
+            sets: vec![set],
+        };
+        assert_eq!(play.pat_at(&gan, 500).map(|(p, _)| p.pat_no), Some(0));
+        assert_eq!(play.pat_at(&gan, 1100).map(|(p, _)| p.pat_no), Some(1));
+    }
+}
@@ -0,0 +1,99 @@
+/// Per-channel lookup tables of one tone curve (C++ `C_tnm_tonecurve`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToneCurve {
+    pub r: [u8; 256],
+    pub g: [u8; 256],
+    pub b: [u8; 256],
+}
+
+impl Default for ToneCurve {
+    /// The identity curve.
+    fn default() -> Self {
+        let mut t = [0u8; 256];
+        for (i, v) in t.iter_mut().enumerate() {
+            *v = i as u8;
+        }
+        Self { r: t, g: t, b: t }
+    }
+}
+
+impl ToneCurve {
+    /// Remap one RGB triple (0..255 floats), interpolating between entries.
+    pub fn apply_rgb(&self, rgb: [f32; 3]) -> [f32; 3] {
+        let lookup = |table: &[u8; 256], v: f32| {
+            let v = v.clamp(0.0, 255.0);
+            let i = v.floor() as usize;
+            let j = (i + 1).min(255);
+            let t = v - i as f32;
+            table[i] as f32 * (1.0 - t) + table[j] as f32 * t
+        };
+        [
+            lookup(&self.r, rgb[0]),
+            lookup(&self.g, rgb[1]),
+            lookup(&self.b, rgb[2]),
+        ]
+    }
+
+    /// Remap every pixel of `img` in place (alpha untouched).
+    pub fn apply_to_image(&self, img: &mut image::RgbaImage) {
+        for p in img.pixels_mut() {
+            p[0] = self.r[p[0] as usize];
+            p[1] = self.g[p[1] as usize];
+            p[2] = self.b[p[2] as usize];
+        }
+    }
+}
+
+/// Tone curves indexed by `tonecurve_no`.
+///
+/// There is no loader for the game's tone curve file (`#TONECURVE_FILE`): its
+/// layout is unknown in this tree (no C++ loader or sample file to check
+/// against), so hosts start with an empty table and every curve number draws
+/// untouched.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ToneCurveTable {
+    pub curves: Vec<Option<ToneCurve>>,
+}
+
+impl ToneCurveTable {
+    /// Curve `no`; negative numbers and empty slots mean "no curve".
+    pub fn get(&self, no: i32) -> Option<&ToneCurve> {
+        usize::try_from(no)
+            .ok()
+            .and_then(|i| self.curves.get(i))
+            .and_then(Option::as_ref)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn table_lookup_skips_negative_and_empty_slots() {
+        let inverted = ToneCurve {
+            r: std::array::from_fn(|i| 255 - i as u8),
+            ..ToneCurve::default()
+        };
+        let table = ToneCurveTable {
+            curves: vec![None, Some(inverted.clone())],
+        };
+        assert!(table.get(-1).is_none());
+        assert!(table.get(0).is_none());
+        assert!(table.get(2).is_none());
+        assert_eq!(table.get(1), Some(&inverted));
+    }
+
+    #[test]
+    fn curves_remap_pixels_and_interpolate_floats() {
+        let curve = ToneCurve {
+            r: std::array::from_fn(|i| 255 - i as u8),
+            g: [7; 256],
+            ..ToneCurve::default()
+        };
+        assert_eq!(curve.apply_rgb([10.5, 0.0, 20.0]), [244.5, 7.0, 20.0]);
+        let mut img = image::RgbaImage::from_pixel(1, 1, image::Rgba([0, 100, 200, 50]));
+        curve.apply_to_image(&mut img);
+        assert_eq!(img.get_pixel(0, 0).0, [255, 7, 200, 50]);
+    }
+}
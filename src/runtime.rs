@@ -1,17 +1,23 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, path::PathBuf, sync::Arc};
 
 use anyhow::Result;
 
 use crate::{
     dat::SceneDat,
+    hot_reload::{HotReloadHandle, HotReloadOptions, HotReloadWatcher},
     pck,
-    vm::{Host, SceneProvider, Vm, VmOptions, VmPersistentState, VmStats},
+    scene_provider::{ChainSceneProvider, DirSceneProvider},
+    vm::{Host, SceneProvider, Vm, VmOptions, VmPersistentState, VmSceneReload, VmStats},
 };
 
 /// High-level runtime wrapper around the VM + Scene.pck.
 pub struct Runtime {
     pub pack: pck::Pack,
     pub scenes: HashMap<String, Arc<SceneDat>>,
+    /// Scene sources consulted before `pack` (patch directories, extra packs).
+    pub overrides: ChainSceneProvider,
+    override_dirs: Vec<PathBuf>,
+    hot_reload: Option<HotReloadWatcher>,
 }
 
 impl Runtime {
@@ -21,7 +27,119 @@ impl Runtime {
             let dat = Arc::new(SceneDat::parse(name.clone(), pack.scenes[*idx].clone())?);
             scenes.insert(name.clone(), dat);
         }
-        Ok(Self { pack, scenes })
+        Ok(Self {
+            pack,
+            scenes,
+            overrides: ChainSceneProvider::new(),
+            override_dirs: Vec::new(),
+            hot_reload: None,
+        })
+    }
+
+    /// Add a directory of loose `<scene>.dat` files that override the pack.
+    ///
+    /// Directories added later have lower priority than earlier ones.
+    pub fn add_override_dir(&mut self, dir: impl Into<PathBuf>) {
+        let provider = DirSceneProvider::new(dir);
+        for name in provider.scene_names() {
+            self.evict_scene(&name);
+        }
+        if let Some(watcher) = &mut self.hot_reload {
+            watcher.diff_dir(provider.dir());
+        }
+        self.override_dirs.push(provider.dir().to_path_buf());
+        self.overrides.push(Box::new(provider));
+    }
+
+    /// Watch `pack_path` (the file this runtime's pack was read from) and every
+    /// override directory for changes while scenes run.
+    ///
+    /// Changed scenes are dropped from `scenes` and re-decoded on next use. With
+    /// `restart_scene`, a change to the running scene restarts it at the nearest
+    /// label or z-label at or before the current position; flags and the call
+    /// stack are kept.
+    pub fn enable_hot_reload(
+        &mut self,
+        pack_path: impl Into<PathBuf>,
+        options: HotReloadOptions,
+    ) -> HotReloadHandle {
+        let handle = HotReloadHandle::new(options);
+        self.enable_hot_reload_with_handle(pack_path, options, handle.clone());
+        handle
+    }
+
+    /// Like `enable_hot_reload`, reusing a handle created before the runtime
+    /// (e.g. by a UI thread that starts the VM worker).
+    pub fn enable_hot_reload_with_handle(
+        &mut self,
+        pack_path: impl Into<PathBuf>,
+        options: HotReloadOptions,
+        handle: HotReloadHandle,
+    ) {
+        let mut watcher = HotReloadWatcher::new(pack_path.into(), options, handle);
+        for dir in &self.override_dirs {
+            watcher.diff_dir(dir);
+        }
+        self.hot_reload = Some(watcher);
+    }
+
+    pub fn disable_hot_reload(&mut self) {
+        self.hot_reload = None;
+    }
+
+    pub fn hot_reload_handle(&self) -> Option<HotReloadHandle> {
+        self.hot_reload.as_ref().map(|w| w.handle.clone())
+    }
+
+    /// Drop every cached decode of `name`; scene names match without regard to
+    /// case, like `DirSceneProvider::scene_path`.
+    fn evict_scene(&mut self, name: &str) {
+        self.scenes
+            .retain(|cached, _| !cached.eq_ignore_ascii_case(name));
+    }
+
+    /// Re-read the pack if its file changed and return the scenes whose bytes differ.
+    fn reload_pack_if_changed(&mut self, force: bool) -> Vec<String> {
+        let Some(watcher) = &mut self.hot_reload else {
+            return Vec::new();
+        };
+        let stamp = crate::hot_reload::FileStamp::of(&watcher.pack_path);
+        if !force && (stamp.is_none() || stamp == watcher.pack_stamp) {
+            return Vec::new();
+        }
+        let pack = match pck::read_file(&watcher.pack_path) {
+            Ok(pack) => pack,
+            Err(e) => {
+                // Usually a half-written pck; keep the old stamp so the next poll retries.
+                log::debug!("hot reload: {:#}", e);
+                return Vec::new();
+            }
+        };
+        watcher.pack_stamp = stamp;
+        let old = std::mem::replace(&mut self.pack, pack);
+        let scene_bytes = |p: &pck::Pack, name: &str| {
+            p.scene_name_to_index
+                .get(name)
+                .and_then(|idx| p.scenes.get(*idx))
+                .cloned()
+        };
+        let mut changed: Vec<String> = self
+            .pack
+            .scene_name_to_index
+            .keys()
+            .chain(old.scene_name_to_index.keys())
+            .filter(|name| scene_bytes(&old, name) != scene_bytes(&self.pack, name))
+            .cloned()
+            .collect();
+        changed.sort();
+        changed.dedup();
+        changed
+    }
+
+    /// Add an arbitrary override provider (e.g. a second `pck::Pack`).
+    pub fn add_override(&mut self, provider: Box<dyn SceneProvider + Send>) {
+        self.scenes.clear();
+        self.overrides.push(provider);
     }
 
     pub fn run_scene_z(
@@ -75,48 +193,132 @@ impl Runtime {
         }
         vm.lexer.jump_to_z_label(z_label)?;
         vm.run(host, self)?;
-        Ok((vm.steps, vm.stats.clone(), vm.snapshot_persistent_state()))
+        Ok((vm.steps, vm.stats.clone(), vm.snapshot_global_state()))
     }
 }
 
 impl SceneProvider for Runtime {
     fn get_scene(&mut self, scene: &str) -> Result<Arc<SceneDat>> {
-        // Return cached scene if present; else decode from pack.
+        // Return cached scene if present; else decode from overrides, then pack.
         if let Some(x) = self.scenes.get(scene) {
             return Ok(x.clone());
         }
-        if let Some(idx) = self.pack.scene_name_to_index.get(scene) {
-            let dat = Arc::new(SceneDat::parse(
-                scene.to_string(),
-                self.pack.scenes[*idx].clone(),
-            )?);
-            self.scenes.insert(scene.to_string(), dat.clone());
-            return Ok(dat);
-        }
-        anyhow::bail!("scene not found: {}", scene);
+        let dat = if self.overrides.has_scene(scene) {
+            self.overrides.get_scene(scene)?
+        } else if self.pack.has_scene(scene) {
+            self.pack.get_scene(scene)?
+        } else {
+            anyhow::bail!("scene not found: {}", scene);
+        };
+        self.scenes.insert(scene.to_string(), dat.clone());
+        Ok(dat)
+    }
+
+    fn has_scene(&self, scene: &str) -> bool {
+        self.scenes.contains_key(scene)
+            || self.overrides.has_scene(scene)
+            || self.pack.has_scene(scene)
     }
 
     fn inc_cmd_count(&self) -> i32 {
-        self.pack.header.inc_cmd_cnt
+        self.pack
+            .inc_cmd_count()
+            .max(self.overrides.inc_cmd_count())
     }
 
     fn get_inc_cmd_target(&mut self, user_cmd_id: i32) -> Result<Option<(String, i32)>> {
-        if user_cmd_id < 0 {
-            return Ok(None);
+        if let Some(target) = self.overrides.get_inc_cmd_target(user_cmd_id)? {
+            return Ok(Some(target));
         }
-        let idx = user_cmd_id as usize;
-        if idx >= self.pack.inc_cmd_list.len() {
-            return Ok(None);
+        self.pack.get_inc_cmd_target(user_cmd_id)
+    }
+
+    fn invalidate(&mut self) {
+        self.scenes.clear();
+        self.overrides.invalidate();
+    }
+
+    fn poll_scene_reload(&mut self, now_ms: u64) -> Option<VmSceneReload> {
+        let (scan, force) = self.hot_reload.as_mut()?.due(now_ms);
+        if !scan {
+            return None;
         }
-        let (scn_no, offset) = self.pack.inc_cmd_list[idx];
-        if scn_no < 0 {
-            return Ok(None);
+        let mut changed = self.reload_pack_if_changed(force);
+        let mut dirs_changed = false;
+        if let Some(watcher) = &mut self.hot_reload {
+            for dir in &self.override_dirs {
+                let names = watcher.diff_dir(dir);
+                dirs_changed |= !names.is_empty();
+                changed.extend(names);
+            }
         }
-        let scn_idx = scn_no as usize;
-        if scn_idx >= self.pack.scene_names.len() {
-            return Ok(None);
+        if dirs_changed {
+            self.overrides.invalidate();
         }
-        let scene_name = self.pack.scene_names[scn_idx].to_string_lossy();
-        Ok(Some((scene_name, offset)))
+        if force {
+            // A manual reload re-decodes everything that was cached.
+            changed.extend(self.scenes.keys().cloned());
+            self.overrides.invalidate();
+        }
+        changed.sort();
+        changed.dedup();
+        if changed.is_empty() && !force {
+            return None;
+        }
+        for name in &changed {
+            self.evict_scene(name);
+        }
+        let restart = self.hot_reload.as_ref()?.handle.restart_scene();
+        Some(VmSceneReload {
+            changed_scenes: changed,
+            restart,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vm::test_support::{pack_bytes, scene_dat_bytes};
+
+    #[test]
+    fn pack_rebuilds_drop_only_the_changed_scenes() {
+        let dir = std::env::temp_dir().join(format!("siglus_pack_reload_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("Scene.pck");
+        let write_pack = |a_size: usize, b_size: usize| {
+            let bytes = pack_bytes(&[
+                ("a", scene_dat_bytes(a_size)),
+                ("b", scene_dat_bytes(b_size)),
+            ]);
+            std::fs::write(&path, bytes).unwrap();
+        };
+        write_pack(16, 16);
+        let mut rt = Runtime::new(pck::read_file(&path).unwrap()).unwrap();
+        let options = HotReloadOptions {
+            restart_scene: false,
+            poll_interval_ms: 100,
+        };
+        let handle = rt.enable_hot_reload(&path, options);
+        assert!(rt.poll_scene_reload(0).is_none());
+
+        write_pack(32, 16);
+        assert!(rt.poll_scene_reload(50).is_none());
+        let reload = rt.poll_scene_reload(100).unwrap();
+        assert_eq!(reload.changed_scenes, vec!["a".to_string()]);
+        assert!(!reload.restart);
+        assert!(!rt.scenes.contains_key("a"));
+        assert!(rt.scenes.contains_key("b"));
+        assert_eq!(rt.get_scene("a").unwrap().scn_bytes.len(), 32);
+        assert!(rt.poll_scene_reload(200).is_none());
+
+        handle.request_reload();
+        let reload = rt.poll_scene_reload(200).unwrap();
+        assert_eq!(
+            reload.changed_scenes,
+            vec!["a".to_string(), "b".to_string()]
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
     }
 }
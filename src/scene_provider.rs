@@ -0,0 +1,332 @@
+//! Composable `SceneProvider`s: Scene.pck, loose `.dat` directories and priority chains.
+//!
+//! A chain lets patch directories override individual scenes of the shipped
+//! Scene.pck without rebuilding it. Inc commands are resolved through the same
+//! priority list so an overriding `.dat` also supplies its own command offsets.
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::{Context, Result, bail};
+
+use crate::{dat::SceneDat, pck, vm::SceneProvider};
+
+impl SceneProvider for pck::Pack {
+    fn get_scene(&mut self, scene: &str) -> Result<Arc<SceneDat>> {
+        let Some(idx) = self.scene_name_to_index.get(scene) else {
+            bail!("scene not found: {}", scene);
+        };
+        Ok(Arc::new(SceneDat::parse(
+            scene.to_string(),
+            self.scenes[*idx].clone(),
+        )?))
+    }
+
+    fn has_scene(&self, scene: &str) -> bool {
+        self.scene_name_to_index.contains_key(scene)
+    }
+
+    fn inc_cmd_count(&self) -> i32 {
+        self.header.inc_cmd_cnt
+    }
+
+    fn get_inc_cmd_target(&mut self, user_cmd_id: i32) -> Result<Option<(String, i32)>> {
+        if user_cmd_id < 0 {
+            return Ok(None);
+        }
+        let idx = user_cmd_id as usize;
+        if idx >= self.inc_cmd_list.len() {
+            return Ok(None);
+        }
+        let (scn_no, offset) = self.inc_cmd_list[idx];
+        if scn_no < 0 {
+            return Ok(None);
+        }
+        let scn_idx = scn_no as usize;
+        if scn_idx >= self.scene_names.len() {
+            return Ok(None);
+        }
+        let scene_name = self.scene_names[scn_idx].to_string_lossy();
+        Ok(Some((scene_name, offset)))
+    }
+}
+
+/// Loads individual compiled `<scene>.dat` files from a directory.
+///
+/// Files are plain compiler output (not pck-encrypted). Inc command targets come
+/// from each scene's `cmd_label_list`, indexed lazily on first lookup.
+#[derive(Debug, Clone)]
+pub struct DirSceneProvider {
+    dir: PathBuf,
+    inc_cmd_count: i32,
+    inc_cmd_index: Option<HashMap<i32, (String, i32)>>,
+}
+
+impl DirSceneProvider {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            inc_cmd_count: 0,
+            inc_cmd_index: None,
+        }
+    }
+
+    /// Inc command count to report when this provider is used without a pack.
+    pub fn with_inc_cmd_count(mut self, inc_cmd_count: i32) -> Self {
+        self.inc_cmd_count = inc_cmd_count;
+        self
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// On-disk path of `scene`; names and the `.dat` extension match without
+    /// regard to case, so `SEL01.DAT` loads on case-sensitive filesystems.
+    pub fn scene_path(&self, scene: &str) -> PathBuf {
+        let exact = self.dir.join(format!("{scene}.dat"));
+        if exact.is_file() {
+            return exact;
+        }
+        std::fs::read_dir(&self.dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|e| e.path())
+            .find(|p| {
+                p.extension().is_some_and(|e| e.eq_ignore_ascii_case("dat"))
+                    && p.file_stem()
+                        .is_some_and(|s| s.to_string_lossy().eq_ignore_ascii_case(scene))
+                    && p.is_file()
+            })
+            .unwrap_or(exact)
+    }
+
+    /// Scene names of every `*.dat` in the directory.
+    pub fn scene_names(&self) -> Vec<String> {
+        let Ok(rd) = std::fs::read_dir(&self.dir) else {
+            return Vec::new();
+        };
+        let mut names: Vec<String> = rd
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| {
+                p.extension()
+                    .map(|e| e.eq_ignore_ascii_case("dat"))
+                    .unwrap_or(false)
+            })
+            .filter_map(|p| p.file_stem().map(|s| s.to_string_lossy().into_owned()))
+            .collect();
+        names.sort();
+        names
+    }
+
+    fn build_inc_cmd_index(&self) -> HashMap<i32, (String, i32)> {
+        let mut index = HashMap::new();
+        for name in self.scene_names() {
+            let Ok(bytes) = std::fs::read(self.scene_path(&name)) else {
+                continue;
+            };
+            let Ok(dat) = SceneDat::parse(name.clone(), bytes) else {
+                continue;
+            };
+            for &(cmd_id, offset) in &dat.cmd_labels {
+                index
+                    .entry(cmd_id)
+                    .or_insert_with(|| (name.clone(), offset));
+            }
+        }
+        index
+    }
+}
+
+impl SceneProvider for DirSceneProvider {
+    fn get_scene(&mut self, scene: &str) -> Result<Arc<SceneDat>> {
+        let path = self.scene_path(scene);
+        let bytes = std::fs::read(&path).with_context(|| format!("read {}", path.display()))?;
+        let dat = SceneDat::parse(scene.to_string(), bytes)
+            .with_context(|| format!("parse {}", path.display()))?;
+        Ok(Arc::new(dat))
+    }
+
+    fn has_scene(&self, scene: &str) -> bool {
+        self.scene_path(scene).is_file()
+    }
+
+    fn inc_cmd_count(&self) -> i32 {
+        self.inc_cmd_count
+    }
+
+    fn get_inc_cmd_target(&mut self, user_cmd_id: i32) -> Result<Option<(String, i32)>> {
+        if self.inc_cmd_index.is_none() {
+            self.inc_cmd_index = Some(self.build_inc_cmd_index());
+        }
+        Ok(self
+            .inc_cmd_index
+            .as_ref()
+            .and_then(|index| index.get(&user_cmd_id).cloned()))
+    }
+
+    /// Forget the inc command index (call after `.dat` files change on disk).
+    fn invalidate(&mut self) {
+        self.inc_cmd_index = None;
+    }
+}
+
+/// Resolves scenes and inc commands through providers in priority order.
+///
+/// The first provider whose `has_scene` is true serves the scene (its parse errors
+/// are reported, not skipped). Inc commands use the first provider that knows the id;
+/// the inc command count is the largest any provider reports.
+#[derive(Default)]
+pub struct ChainSceneProvider {
+    providers: Vec<Box<dyn SceneProvider + Send>>,
+}
+
+impl ChainSceneProvider {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a provider with lower priority than every provider already present.
+    pub fn push(&mut self, provider: Box<dyn SceneProvider + Send>) {
+        self.providers.push(provider);
+    }
+
+    /// Insert a provider with higher priority than every provider already present.
+    pub fn push_front(&mut self, provider: Box<dyn SceneProvider + Send>) {
+        self.providers.insert(0, provider);
+    }
+
+    pub fn with(mut self, provider: Box<dyn SceneProvider + Send>) -> Self {
+        self.push(provider);
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.providers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.providers.is_empty()
+    }
+}
+
+impl SceneProvider for ChainSceneProvider {
+    fn get_scene(&mut self, scene: &str) -> Result<Arc<SceneDat>> {
+        for provider in &mut self.providers {
+            if provider.has_scene(scene) {
+                return provider.get_scene(scene);
+            }
+        }
+        bail!("scene not found: {}", scene);
+    }
+
+    fn has_scene(&self, scene: &str) -> bool {
+        self.providers.iter().any(|p| p.has_scene(scene))
+    }
+
+    fn inc_cmd_count(&self) -> i32 {
+        self.providers
+            .iter()
+            .map(|p| p.inc_cmd_count())
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn get_inc_cmd_target(&mut self, user_cmd_id: i32) -> Result<Option<(String, i32)>> {
+        for provider in &mut self.providers {
+            if let Some(target) = provider.get_inc_cmd_target(user_cmd_id)? {
+                return Ok(Some(target));
+            }
+        }
+        Ok(None)
+    }
+
+    fn invalidate(&mut self) {
+        for provider in &mut self.providers {
+            provider.invalidate();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vm::test_support::{pack_bytes, scene_dat_bytes};
+
+    /// Fresh directory holding `files` as `.dat` scenes of the given script sizes.
+    fn scene_dir(tag: &str, files: &[(&str, usize)]) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("siglus_{tag}_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        for &(file, size) in files {
+            std::fs::write(dir.join(file), scene_dat_bytes(size)).unwrap();
+        }
+        dir
+    }
+
+    fn scn_len(provider: &mut dyn SceneProvider, scene: &str) -> usize {
+        provider.get_scene(scene).unwrap().scn_bytes.len()
+    }
+
+    #[test]
+    fn scene_path_finds_upper_case_dat() {
+        let dir = std::env::temp_dir().join(format!("siglus_scene_path_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("SEL01.DAT"), b"").unwrap();
+        let provider = DirSceneProvider::new(&dir);
+        assert_eq!(provider.scene_names(), vec!["SEL01".to_string()]);
+        assert_eq!(provider.scene_path("SEL01"), dir.join("SEL01.DAT"));
+        assert_eq!(provider.scene_path("sel01"), dir.join("SEL01.DAT"));
+        assert!(provider.has_scene("SEL01"));
+        assert_eq!(provider.scene_path("missing"), dir.join("missing.dat"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn chain_serves_each_scene_from_the_first_provider_that_has_it() {
+        let a = scene_dir("chain_a", &[("s1.dat", 16)]);
+        let b = scene_dir("chain_b", &[("S1.DAT", 32), ("s2.dat", 48)]);
+        let c = scene_dir("chain_c", &[("s1.dat", 64)]);
+        let mut chain = ChainSceneProvider::new()
+            .with(Box::new(DirSceneProvider::new(&a)))
+            .with(Box::new(DirSceneProvider::new(&b)));
+        assert_eq!(scn_len(&mut chain, "s1"), 16);
+        assert_eq!(scn_len(&mut chain, "s2"), 48);
+        assert!(!chain.has_scene("s3"));
+        assert!(chain.get_scene("s3").is_err());
+
+        chain.push_front(Box::new(DirSceneProvider::new(&c)));
+        assert_eq!(scn_len(&mut chain, "s1"), 64);
+        assert_eq!(scn_len(&mut chain, "s2"), 48);
+        for dir in [a, b, c] {
+            std::fs::remove_dir_all(dir).unwrap();
+        }
+    }
+
+    #[test]
+    fn override_dirs_replace_cached_pack_scenes_regardless_of_case() {
+        let pack = pck::parse(&pack_bytes(&[
+            ("sel01", scene_dat_bytes(16)),
+            ("main", scene_dat_bytes(16)),
+        ]))
+        .unwrap();
+        let mut rt = crate::runtime::Runtime::new(pack).unwrap();
+        assert_eq!(scn_len(&mut rt, "sel01"), 16);
+
+        let first = scene_dir("override_first", &[("SEL01.DAT", 32)]);
+        let second = scene_dir("override_second", &[("sel01.dat", 48), ("main.dat", 64)]);
+        rt.add_override_dir(&first);
+        assert_eq!(scn_len(&mut rt, "sel01"), 32);
+        rt.add_override_dir(&second);
+        assert_eq!(scn_len(&mut rt, "sel01"), 32);
+        assert_eq!(scn_len(&mut rt, "main"), 64);
+        for dir in [first, second] {
+            std::fs::remove_dir_all(dir).unwrap();
+        }
+    }
+}
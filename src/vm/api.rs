@@ -118,6 +118,43 @@ pub enum VmQuakeKind {
     Zoom,
 }
 
+/// Parsed `wipe`/`wipe_all`/`mask_wipe`/`mask_wipe_all` arguments.
+///
+/// C++ cmd_wipe.cpp::tnm_command_proc_wipe: positional `(type, time, speed_mode, option...)`
+/// (mask wipes take the mask file first), named ids 0..=11 override them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VmWipeRequest {
+    /// `ELM_GLOBAL_WIPE`, `ELM_GLOBAL_WIPE_ALL`, `ELM_GLOBAL_MASK_WIPE` or `ELM_GLOBAL_MASK_WIPE_ALL`.
+    pub command: i32,
+    pub mask_file: Option<String>,
+    pub wipe_type: i32,
+    /// Remaining duration (`wipe_time - start_time`).
+    pub time_ms: u64,
+    pub speed_mode: i32,
+    pub option: Vec<i32>,
+    pub begin_order: i32,
+    pub end_order: i32,
+    pub begin_layer: i32,
+    pub end_layer: i32,
+    pub with_low_order: bool,
+    pub wait_flag: bool,
+    pub key_wait_mode: i32,
+}
+
+impl VmWipeRequest {
+    pub fn is_mask(&self) -> bool {
+        self.command == crate::elm::global::ELM_GLOBAL_MASK_WIPE
+            || self.command == crate::elm::global::ELM_GLOBAL_MASK_WIPE_ALL
+    }
+
+    /// Whether an object at `order`/`layer` takes part in this wipe.
+    pub fn covers(&self, order: i32, layer: i32) -> bool {
+        let order_ok =
+            (self.with_low_order || order >= self.begin_order) && order <= self.end_order;
+        order_ok && layer >= self.begin_layer && layer <= self.end_layer
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct VmQuakeRequest {
     pub sub: i32,
@@ -197,6 +234,12 @@ pub trait Host {
     /// Optional verbose trace lines from the VM.
     fn on_trace(&mut self, _msg: &str) {}
 
+    /// A registered watchpoint observed a value change (see `Vm::add_watchpoint`).
+    fn on_watchpoint(&mut self, _hit: &crate::vm::VmWatchHit) {}
+
+    /// Scenes were re-read from disk (hot reload); `restarted` if the running one restarted.
+    fn on_scene_reload(&mut self, _changed_scenes: &[String], _restarted: bool) {}
+
     fn on_error(&mut self, _msg: &str) {}
 
     /// C++ tnm_set_error(TNM_ERROR_TYPE_FATAL, ...).
@@ -275,6 +318,16 @@ pub trait Host {
     /// C++ reference: eng_syscom.cpp fade-out branches -> TNM_PROC_TYPE_DISP.
     fn on_syscom_proc_disp(&mut self) {}
 
+    /// A script wipe started (before the VM waits on it); hosts transition the
+    /// stage from the previous frame to the current one over `req.time_ms`.
+    fn on_wipe_start(&mut self, _req: &crate::vm::VmWipeRequest) {}
+
+    /// `mask[slot_no]` was created, cleared or moved (used by `mask_wipe` and object `mask_no`).
+    fn on_mask_slot(&mut self, _slot_no: usize, _slot: &crate::vm::MaskSlotState) {}
+
+    /// The running wipe was cut short (`wipe_end`, or skipped while waiting).
+    fn on_wipe_end(&mut self) {}
+
     /// C++ reference: flow_proc.cpp::tnm_game_end_wipe_proc.
     fn on_syscom_proc_game_end_wipe(&mut self, _wipe_type: i32, _wipe_time_ms: u64) {}
 
@@ -366,6 +419,16 @@ pub trait Host {
     fn on_se_play(&mut self, _id: i32, _name: &str) {}
     /// C++ cmd_sound.cpp: SE stop.
     fn on_se_stop(&mut self, _fade: i32) {}
+    /// C++ cmd_global.cpp: KOE play (`koe`, `exkoe`, `mwnd.koe` and the
+    /// `*_play_wait` forms).
+    fn on_koe_play(&mut self, _koe_no: i32, _chara_no: i32) {}
+    /// C++ cmd_global.cpp: KOE stop.
+    fn on_koe_stop(&mut self) {}
+    /// Whether the last voice started by `on_koe_play` is still playing; it
+    /// must already be true when `on_koe_play` returns.
+    fn koe_playing(&self) -> bool {
+        false
+    }
     /// C++ cmd_sound.cpp: MOV play.
     fn on_mov_play(&mut self, _name: &str) {}
     /// C++ cmd_sound.cpp: MOV stop.
@@ -376,6 +439,15 @@ pub trait Host {
     /// `load_after_call_scene` / `load_after_call_z_no` is about to execute.
     fn on_frame_action_load_after_call(&mut self, _scene: &str, _z_no: i32) {}
 
+    /// C++ elm_sel_btn.cpp sel_start_call — a button selection is about to
+    /// farcall `sel_start_call_scn` / `sel_start_call_z_no`.
+    ///
+    /// `elm` and `args` are the starting SELBTN command's. Hosts that own the
+    /// selection should show its buttons here, so the called scene runs with
+    /// them on screen; the command itself still comes to `on_command` after
+    /// the call returns.
+    fn on_selbtn_start_call(&mut self, _elm: i32, _args: &[Prop], _scene: &str, _z_no: i32) {}
+
     /// C++ flow_script.cpp fatal parse/eof path hook.
     ///
     /// Expected host-side side effect: switch process to NONE-equivalent.
@@ -388,11 +460,19 @@ pub trait Host {
         false
     }
 
+    /// Time source shared with the VM (timewait, wipe deadlines, frame ticks).
+    ///
+    /// Default is the process-wide real clock; headless hosts and tests can return
+    /// a `VirtualClock` to make timing deterministic.
+    fn clock(&self) -> crate::vm::SharedVmClock {
+        crate::vm::real_clock()
+    }
+
     /// Wait one frame-equivalent tick for proc-based waits (KEY_WAIT/int_event/etc).
     ///
     /// Default keeps legacy VM single-run behavior by sleeping a short duration.
     fn on_wait_frame(&mut self) {
-        std::thread::sleep(std::time::Duration::from_millis(8));
+        self.clock().sleep_ms(8);
     }
     /// C++ elm_counter.cpp::update_time inputs used by frame-mode counters.
     ///
@@ -494,36 +574,50 @@ pub trait Host {
     /// C++ cmd_effect.cpp: effect reinit.
     fn on_effect_init(&mut self) {}
 
-    /// C++ cmd_effect.cpp: quake start (vec/dir/zoom variants).
-    fn on_quake_start(&mut self, _req: VmQuakeRequest) {}
+    /// `screen.effect[effect_no]` changed (a value, a range or a new `_eve`).
+    ///
+    /// Running `_eve` animations are part of `effect`; evaluate it with
+    /// `VmScreenEffect::resolve` on `Host::clock()` each frame.
+    fn on_screen_effect(&mut self, _effect_no: usize, _effect: &crate::vm::VmScreenEffect) {}
 
-    /// C++ cmd_effect.cpp: quake end.
-    fn on_quake_end(&mut self) {}
+    /// Font settings for script text changed (C++ cmd_script.cpp `set_font_*`,
+    /// cmd_syscom.cpp `set_font_*`), or were restored by a load.
+    fn on_font_style(&mut self, _style: &crate::vm::VmFontStyle) {}
 
-    /// C++ cmd_effect.cpp: quake->check() for WAIT/CHECK polling branches.
-    fn on_quake_is_active(&mut self) -> bool {
-        false
-    }
+    /// The page of message window `mwnd_no` changed.
+    ///
+    /// `msg` is the whole page so far: text runs with ruby and colors, line
+    /// breaks, indents and wait points. `msg.parts[..from_part]` are unchanged
+    /// since the last call with the same `page_serial`, so hosts that keep a
+    /// copy only need the rest; `from_part` is 0 for a new or reloaded page.
+    /// Sent before `on_name`/`on_text`, which still carry the flat strings.
+    fn on_message(&mut self, _mwnd_no: i32, _msg: &crate::vm::VmMessage, _from_part: usize) {}
 
-    // World Host callbacks (cmd_world.cpp alignment)
+    /// Message speed, nowait, auto mode or skip settings changed (C++
+    /// cmd_syscom.cpp / cmd_script.cpp setters), or were restored by a load.
+    fn on_message_speed(&mut self, _speed: &crate::vm::VmMessageSpeed) {}
 
-    /// C++ cmd_world.cpp: world property set.
-    fn on_world_property(&mut self, _property_id: i32, _value: i32) {}
+    /// C++ cmd_effect.cpp: quake start (vec/dir/zoom variants).
+    ///
+    /// The VM owns the quake (`Vm::quake`); evaluate it with
+    /// `render::quake_transform_at` against `Host::clock()` to draw it.
+    fn on_quake_start(&mut self, _quake_no: usize, _req: VmQuakeRequest) {}
 
-    /// C++ cmd_world.cpp: create_world.
-    fn on_world_create(&mut self) {}
+    /// C++ cmd_effect.cpp: quake end, or a quake finished while waited on.
+    fn on_quake_end(&mut self, _quake_no: usize) {}
 
-    /// C++ cmd_world.cpp: destroy_world.
-    fn on_world_destroy(&mut self) {}
+    /// C++ elm_screen.cpp: `screen.shake` started with the resolved pattern.
+    fn on_shake_start(&mut self, _shake_no: i32, _steps: &[crate::render::ShakeStep]) {}
 
-    /// C++ cmd_world.cpp: world reinit.
-    fn on_world_init(&mut self) {}
+    /// `screen.shake` finished or was skipped.
+    fn on_shake_end(&mut self) {}
 
-    /// C++ cmd_world.cpp: set_camera_eye / set_camera_pint / set_camera_up.
-    fn on_world_set_camera(&mut self, _sub: i32, _x: i32, _y: i32, _z: i32) {}
+    // World Host callbacks (cmd_world.cpp alignment)
 
-    /// C++ cmd_world.cpp: calc_camera_eye / calc_camera_pint.
-    fn on_world_calc_camera(&mut self, _sub: i32, _distance: i32, _rotate_h: i32, _rotate_v: i32) {}
+    /// C++ cmd_world.cpp: `stage[stage_idx].world` after any change — create,
+    /// destroy, init, camera sets/calcs, `_eve` events — and after a load.
+    /// `VmWorld::camera_at` resolves each camera on the VM clock.
+    fn on_worlds(&mut self, _stage_idx: i32, _worlds: &[crate::vm::VmWorld]) {}
 
     // PCMCH Host callbacks (cmd_sound.cpp alignment)
 
@@ -611,11 +705,6 @@ pub trait Host {
         -1
     }
 
-    /// C++ cmd_world.cpp: world_list->get_sub(index, disp_out_of_range_error).
-    fn on_world_list_get_size(&mut self) -> i32 {
-        -1
-    }
-
     /// C++ cmd_effect.cpp: effect_list->get_sub(index, disp_out_of_range_error).
     fn on_effect_list_get_size(&mut self) -> i32 {
         -1
@@ -676,53 +765,46 @@ pub trait Host {
         None
     }
 
-    // int_event Host callbacks (cmd_others.cpp alignment)
+    /// The stage as last drawn, for the software compositor: objects,
+    /// backgrounds and effects only, without message windows or host overlays.
+    /// Hosts that keep one get `on_capture_stage` for free.
+    fn on_stage_scene(&mut self) -> Option<crate::render::RenderScene> {
+        None
+    }
 
-    /// C++ cmd_others.cpp: int_event SET/SET_REAL.
-    fn on_int_event_set(
-        &mut self,
-        _owner_id: i32,
-        _start: i32,
-        _end: i32,
-        _time: i32,
-        _delay: i32,
-        _realtime: i32,
-        _value_override: Option<i32>,
-    ) {
+    /// C++ cmd_global.cpp: capture / capture_for_object / capture_for_local_save.
+    /// The stage frame at stage resolution; `hide_mwnd` leaves the message
+    /// windows out (save thumbnails). By default `on_stage_scene` is composed,
+    /// which never has message windows.
+    fn on_capture_stage(&mut self, _hide_mwnd: bool) -> Option<std::sync::Arc<image::RgbaImage>> {
+        self.on_stage_scene()
+            .map(|scene| std::sync::Arc::new(scene.compose()))
     }
 
-    /// C++ cmd_others.cpp: int_event LOOP/LOOP_REAL.
-    fn on_int_event_loop(
+    /// C++ cmd_global.cpp: capture_from_file / object create_from_capture_file.
+    /// Reads the image of a file written by `save_capture_buffer_to_file`.
+    fn on_capture_load_file(
         &mut self,
-        _owner_id: i32,
-        _start: i32,
-        _end: i32,
-        _time: i32,
-        _delay: i32,
-        _speed_type: i32,
-        _realtime: i32,
-    ) {
+        _file_name: &str,
+    ) -> Option<std::sync::Arc<image::RgbaImage>> {
+        None
     }
 
-    /// C++ cmd_others.cpp: int_event TURN/TURN_REAL.
-    fn on_int_event_turn(
+    // int_event Host callbacks (cmd_others.cpp alignment)
+
+    /// An `*_eve` event held by the VM was started, ended or skipped.
+    ///
+    /// `event` is clock-driven: evaluate `IntEvent::value_at` on `Host::clock()`
+    /// to animate the property. Effect events are reported through
+    /// `on_screen_effect` instead.
+    fn on_int_event(
         &mut self,
+        _scope: crate::vm::IntEventScope,
         _owner_id: i32,
-        _start: i32,
-        _end: i32,
-        _time: i32,
-        _delay: i32,
-        _speed_type: i32,
-        _realtime: i32,
+        _event: &crate::vm::IntEvent,
     ) {
     }
 
-    /// C++ cmd_others.cpp: int_event END.
-    fn on_int_event_end(&mut self, _owner_id: i32) {}
-
-    /// C++ cmd_others.cpp: int_event WAIT/WAIT_KEY.
-    fn on_int_event_wait(&mut self, _owner_id: i32, _key_skip: bool) {}
-
     /// VM unified wait status callback for proc-level consumers.
     ///
     /// Status is one of `crate::vm::EVE_WAIT_*` constants.
@@ -760,28 +842,6 @@ pub trait Host {
         _proc_top: i32,
     ) {
     }
-    /// C++ cmd_others.cpp: int_event CHECK.
-    fn on_int_event_check(&mut self, _owner_id: i32) -> bool {
-        false
-    }
-
-    /// C++ cmd_others.cpp: int_event GET_EVENT_VALUE.
-    fn on_int_event_get_value(&mut self, _owner_id: i32) -> i32 {
-        0
-    }
-
-    /// C++ cmd_others.cpp: int_event YURE / YURE_REAL.
-    fn on_int_event_yure(
-        &mut self,
-        _owner_id: i32,
-        _center: i32,
-        _swing: i32,
-        _time: i32,
-        _delay: i32,
-        _speed_type: i32,
-        _realtime: bool,
-    ) {
-    }
 
     // Object Host callbacks (cmd_object.cpp alignment)
 
@@ -917,6 +977,57 @@ pub trait Host {
     ) {
     }
 
+    /// C++ cmd_object.cpp: create_save_thumb / create_capture_thumb /
+    /// create_capture / create_from_capture_file. `image` is the save slot's
+    /// thumbnail or the capture the VM resolved (`None` if there is none);
+    /// hosts without captures handle it as a plain create.
+    fn on_object_create_from_capture(
+        &mut self,
+        list_id: i32,
+        obj_index: i32,
+        sub_id: i32,
+        args: &[Prop],
+        _image: Option<std::sync::Arc<image::RgbaImage>>,
+        stage_idx: Option<i32>,
+    ) {
+        self.on_object_action(list_id, obj_index, sub_id, args, stage_idx);
+    }
+
+    /// C++ cmd_object.cpp: `frame_action.load_gan(path)`; the host reads the
+    /// file with `resource::read_gan`.
+    fn on_object_gan_load(
+        &mut self,
+        _list_id: i32,
+        _obj_index: i32,
+        _stage_idx: Option<i32>,
+        _gan_path: &str,
+    ) {
+    }
+
+    /// C++ cmd_object.cpp: `frame_action.start_gan(set_no, loop_flag)` on a
+    /// loaded gan. `playback.start_ms` is on `Host::clock()`; the host steps
+    /// the object's pattern with `GanPlayback::pat_at`.
+    fn on_object_gan_start(
+        &mut self,
+        _list_id: i32,
+        _obj_index: i32,
+        _stage_idx: Option<i32>,
+        _playback: &crate::resource::GanPlayback,
+    ) {
+    }
+
+    /// C++ cmd_object.cpp: an object int list (`x_rep`/`y_rep`/`z_rep`/`tr_rep`)
+    /// after a write, resize or clear, and every stored list after a load.
+    fn on_object_int_list_set(
+        &mut self,
+        _list_id: i32,
+        _obj_index: i32,
+        _sub_id: i32,
+        _values: &[i32],
+        _stage_idx: Option<i32>,
+    ) {
+    }
+
     /// C++ cmd_object.cpp: object property get.
     fn on_object_get(
         &mut self,
@@ -1000,13 +1111,19 @@ pub trait Host {
     // Mwnd Host callbacks (cmd_mwnd.cpp alignment)
 
     /// C++ cmd_mwnd.cpp: mwnd action command (sub_id identifies the command).
-    fn on_mwnd_action(&mut self, _sub_id: i32, _args: &[Prop]) {}
+    ///
+    /// `mwnd_no` is the `mwnd_list` index, or -1 for list-wide commands (close all).
+    fn on_mwnd_action(&mut self, _mwnd_no: i32, _sub_id: i32, _args: &[Prop]) {}
 
     /// C++ cmd_mwnd.cpp: mwnd property get.
-    fn on_mwnd_get(&mut self, _sub_id: i32) -> i32 {
+    fn on_mwnd_get(&mut self, _mwnd_no: i32, _sub_id: i32) -> i32 {
         0
     }
 
+    /// C++ cmd_global.cpp `set_mwnd`: window that receives message text and names
+    /// from now on (-1 = the Gameexe default window).
+    fn on_cur_mwnd(&mut self, _mwnd_no: i32) {}
+
     // Counter / Database / Others Host callbacks
 
     /// C++ cmd_others.cpp: counter action (set/reset/start/stop/resume/wait).
@@ -0,0 +1,153 @@
+use std::{
+    sync::{
+        Arc, OnceLock,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+/// Time source shared by the VM and its host.
+///
+/// C++ reference: eng_frame.cpp reads `timeGetTime()` once per frame and feeds the
+/// deltas into waits, wipes, counters and frame actions. Routing every VM-side time
+/// read through this trait lets hosts swap in a manually advanced clock.
+pub trait VmClock: Send + Sync {
+    /// Milliseconds elapsed since the clock origin.
+    fn now_ms(&self) -> u64;
+
+    /// Let `ms` milliseconds pass (real clocks block, virtual clocks advance).
+    fn sleep_ms(&self, ms: u64);
+}
+
+pub type SharedVmClock = Arc<dyn VmClock>;
+
+/// Wall-clock implementation backed by `Instant`.
+#[derive(Debug)]
+pub struct RealClock {
+    origin: Instant,
+}
+
+impl RealClock {
+    pub fn new() -> Self {
+        Self {
+            origin: Instant::now(),
+        }
+    }
+}
+
+impl Default for RealClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl VmClock for RealClock {
+    fn now_ms(&self) -> u64 {
+        self.origin.elapsed().as_millis() as u64
+    }
+
+    fn sleep_ms(&self, ms: u64) {
+        if ms > 0 {
+            std::thread::sleep(Duration::from_millis(ms));
+        }
+    }
+}
+
+/// Manually advanced clock for headless runs and deterministic tests.
+///
+/// Time only moves through `advance_ms`/`set_ms` or when the VM sleeps on it, so
+/// timewait/wipe waits complete instantly while still observing their durations.
+#[derive(Debug, Default)]
+pub struct VirtualClock {
+    now_ms: AtomicU64,
+}
+
+impl VirtualClock {
+    pub fn new(start_ms: u64) -> Self {
+        Self {
+            now_ms: AtomicU64::new(start_ms),
+        }
+    }
+
+    pub fn shared(start_ms: u64) -> Arc<Self> {
+        Arc::new(Self::new(start_ms))
+    }
+
+    pub fn advance_ms(&self, ms: u64) {
+        self.now_ms.fetch_add(ms, Ordering::Relaxed);
+    }
+
+    pub fn set_ms(&self, ms: u64) {
+        self.now_ms.store(ms, Ordering::Relaxed);
+    }
+}
+
+impl VmClock for VirtualClock {
+    fn now_ms(&self) -> u64 {
+        self.now_ms.load(Ordering::Relaxed)
+    }
+
+    fn sleep_ms(&self, ms: u64) {
+        self.advance_ms(ms);
+    }
+}
+
+/// Process-wide real clock used by hosts that do not provide their own.
+pub fn real_clock() -> SharedVmClock {
+    static CLOCK: OnceLock<SharedVmClock> = OnceLock::new();
+    CLOCK.get_or_init(|| Arc::new(RealClock::new())).clone()
+}
+
+/// Per-frame `(past_game_time, past_real_time)` delta tracker over a `VmClock`.
+///
+/// C++ elm_counter.cpp::update_time inputs. Hosts back
+/// `Host::on_frame_counter_elapsed` with this so frame-action counters advance by
+/// the shared clock instead of unit steps.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ClockFrameDelta {
+    last_ms: Option<u64>,
+}
+
+impl ClockFrameDelta {
+    pub fn next(&mut self, clock: &dyn VmClock) -> (i32, i32) {
+        let now = clock.now_ms();
+        let past = match self.last_ms {
+            Some(last) => now.saturating_sub(last).min(i32::MAX as u64) as i32,
+            None => 0,
+        };
+        self.last_ms = Some(now);
+        (past, past)
+    }
+
+    pub fn reset(&mut self) {
+        self.last_ms = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn virtual_clock_advances_only_when_told() {
+        let clock = VirtualClock::new(100);
+        assert_eq!(clock.now_ms(), 100);
+        clock.sleep_ms(16);
+        assert_eq!(clock.now_ms(), 116);
+        clock.set_ms(5);
+        assert_eq!(clock.now_ms(), 5);
+    }
+
+    #[test]
+    fn frame_delta_reports_time_since_last_call() {
+        let clock = VirtualClock::new(0);
+        let mut delta = ClockFrameDelta::default();
+        assert_eq!(delta.next(&clock), (0, 0));
+        clock.advance_ms(33);
+        assert_eq!(delta.next(&clock), (33, 33));
+        assert_eq!(delta.next(&clock), (0, 0));
+        delta.reset();
+        clock.advance_ms(10);
+        assert_eq!(delta.next(&clock), (0, 0));
+    }
+}
@@ -71,6 +71,10 @@ impl Vm {
             .retain(|(_, _, stage_idx), _| *stage_idx < 0 || *stage_idx > 2);
         self.object_gan_started_set
             .retain(|(_, _, stage_idx), _| *stage_idx < 0 || *stage_idx > 2);
+        self.object_rep_lists
+            .retain(|(stage_idx, ..), _| *stage_idx < 0 || *stage_idx > 2);
+        self.worlds
+            .retain(|stage_idx, _| *stage_idx < 0 || *stage_idx > 2);
     }
 
     fn reset_excall_scope_runtime_state(&mut self, scope: usize, ready: bool) {
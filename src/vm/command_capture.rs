@@ -0,0 +1,87 @@
+/// Stage capture commands and the images objects are created from.
+///
+/// C++ reference: cmd_global.cpp (capture family), cmd_object.cpp
+/// (create_save_thumb / create_capture_thumb / create_capture /
+/// create_from_capture_file)
+///
+/// The host composes the frame (`Host::on_capture_stage`); the VM keeps the
+/// captures and stores the local-save capture with each save slot.
+use super::*;
+
+impl Vm {
+    /// Route `capture`, `capture_free`, `capture_for_object(_free)`,
+    /// `capture_for_local_save`, `capture_from_file` and the tweet captures.
+    pub(super) fn try_command_global_capture(
+        &mut self,
+        elm: i32,
+        args: &[Prop],
+        ret_form: i32,
+        host: &mut dyn Host,
+    ) -> bool {
+        use crate::elm::global::*;
+
+        let mut ret = 0;
+        match elm {
+            ELM_GLOBAL_CAPTURE | ELM_GLOBAL_CAPTURE_FOR_OBJECT => {
+                self.capture_image = host.on_capture_stage(false);
+            }
+            ELM_GLOBAL_CAPTURE_FREE | ELM_GLOBAL_CAPTURE_FOR_OBJECT_FREE => {
+                self.capture_image = None;
+            }
+            ELM_GLOBAL_CAPTURE_FOR_LOCAL_SAVE => {
+                self.save_thumb_image = host.on_capture_stage(true);
+            }
+            ELM_GLOBAL_CAPTURE_FROM_FILE => {
+                let file_name = match args.first().map(|p| &p.value) {
+                    Some(PropValue::Str(v)) => v.as_str(),
+                    _ => "",
+                };
+                self.capture_image = host.on_capture_load_file(file_name);
+                ret = i32::from(self.capture_image.is_some());
+            }
+            // Nothing posts tweets; the capture is accepted and dropped.
+            ELM_GLOBAL_CAPTURE_FOR_TWEET | ELM_GLOBAL_CAPTURE_FREE_FOR_TWEET => {}
+            _ => return false,
+        }
+        if ret_form == crate::elm::form::INT {
+            self.stack.push_int(ret);
+        }
+        true
+    }
+
+    /// Thumbnail stored with a new save slot: the `capture_for_local_save`
+    /// capture, which the save uses up, or the current stage without message
+    /// windows.
+    pub(super) fn take_local_save_thumb(
+        &mut self,
+        host: &mut dyn Host,
+    ) -> Option<Arc<image::RgbaImage>> {
+        self.save_thumb_image
+            .take()
+            .or_else(|| host.on_capture_stage(true))
+    }
+
+    /// Image an object capture create command shows.
+    pub(super) fn object_capture_image(
+        &self,
+        sub: i32,
+        args: &[Prop],
+        host: &mut dyn Host,
+    ) -> Option<Arc<image::RgbaImage>> {
+        use crate::elm::objectlist::*;
+
+        match sub {
+            ELM_OBJECT_CREATE_SAVE_THUMB => {
+                let save_no = Self::slot_arg(args, 0)?;
+                self.local_save_slots.get(&save_no)?.thumb.clone()
+            }
+            ELM_OBJECT_CREATE_CAPTURE_THUMB => self.save_thumb_image.clone(),
+            ELM_OBJECT_CREATE_CAPTURE => self.capture_image.clone(),
+            ELM_OBJECT_CREATE_FROM_CAPTURE_FILE => match args.first().map(|p| &p.value) {
+                Some(PropValue::Str(file_name)) => host.on_capture_load_file(file_name),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
@@ -7,12 +7,21 @@
 ///   - `global.screen.effect[idx].*` — per-effect properties
 ///   - `global.screen.quake[idx].*` — quake start/end/wait/check
 ///
-/// Approach: Property get commands push default 0 values.
-/// Property set commands delegate to Host callbacks.
-/// Quake commands are accepted as no-ops (no animation backend yet).
+/// Approach: screen/effect properties live in `Vm::screen_effects` (effect 0 is
+/// `screen.<prop>`); gets read them back, sets and `_eve` events update them and
+/// report the whole effect through `Host::on_screen_effect`. The older per-value
+/// `on_screen_property` / `on_effect_property` callbacks still fire on sets.
+/// Quakes and `screen.shake` run in `Vm::quakes` / `Vm::shake` on the VM clock;
+/// hosts render them from `Host::on_quake_start` / `on_shake_start`.
 use super::*;
 
 impl Vm {
+    /// Highest `screen.quake[n]` index plus one; `quakes` grows up to it.
+    const MAX_QUAKES: usize = 1000;
+
+    /// Highest `screen.effect[n]` index plus one; `screen_effects` grows up to it.
+    const MAX_SCREEN_EFFECTS: usize = 1000;
+
     // ---------------------------------------------------------------
     // Top-level: global.screen
     // ---------------------------------------------------------------
@@ -45,8 +54,40 @@ impl Vm {
                 self.try_command_quake_list(&element[1..], arg_list_id, args, ret_form, host)
             }
             ELM_SCREEN_SHAKE => {
-                // C++ p_screen->shake().start(arg0, true)
-                // Accept as no-op.
+                // C++ p_screen->shake().start(arg0, true): the pattern plays to the end.
+                let shake_no = Self::int_arg(args, 0);
+                let steps = usize::try_from(shake_no)
+                    .ok()
+                    .and_then(|no| self.options.shake_patterns.get(no))
+                    .filter(|p| !p.is_empty())
+                    .cloned()
+                    .unwrap_or_else(|| crate::render::DEFAULT_SHAKE_PATTERN.to_vec());
+                host.on_shake_start(shake_no, &steps);
+                self.shake = Some(VmShake {
+                    shake_no,
+                    steps,
+                    start_ms: host.clock().now_ms(),
+                });
+                while self
+                    .shake
+                    .as_ref()
+                    .is_some_and(|s| s.is_active(host.clock().now_ms()))
+                {
+                    if host.should_interrupt() || host.should_skip_wait() {
+                        break;
+                    }
+                    host.on_wait_frame();
+                }
+                self.shake = None;
+                host.on_shake_end();
+                true
+            }
+
+            ELM_SCREEN_INIT => {
+                // C++ C_elm_screen::reinit: every effect back to its defaults.
+                let len = self.screen_effects.len().max(1);
+                self.screen_effects = vec![VmScreenEffect::default(); len];
+                self.notify_screen_effects(host);
                 true
             }
 
@@ -66,18 +107,14 @@ impl Vm {
             | ELM_SCREEN_COLOR_ADD_G
             | ELM_SCREEN_COLOR_ADD_B => {
                 // C++ al_id==0 → push, al_id==1 → set
+                let prop = VmScreenEffect::screen_prop(sub).unwrap_or(sub);
                 if arg_list_id == 0 {
-                    self.stack.push_int(0);
+                    let v = self.effect_prop_get(0, prop, host);
+                    self.stack.push_int(v);
                 } else {
-                    host.on_screen_property(
-                        sub,
-                        args.first()
-                            .and_then(|p| match p.value {
-                                PropValue::Int(v) => Some(v),
-                                _ => None,
-                            })
-                            .unwrap_or(0),
-                    );
+                    let v = Self::int_arg(args, 0);
+                    host.on_screen_property(sub, v);
+                    self.effect_prop_set(0, prop, v, host);
                 }
                 true
             }
@@ -97,8 +134,15 @@ impl Vm {
             | ELM_SCREEN_COLOR_ADD_R_EVE
             | ELM_SCREEN_COLOR_ADD_G_EVE
             | ELM_SCREEN_COLOR_ADD_B_EVE => {
-                // C++ dispatches to tnm_command_proc_int_event.
-                self.try_command_int_event(&element[1..], arg_list_id, args, ret_form, host, sub)
+                let prop = VmScreenEffect::screen_prop(sub).unwrap_or(sub);
+                self.try_command_int_event(
+                    &element[1..],
+                    arg_list_id,
+                    args,
+                    ret_form,
+                    host,
+                    IntEventTarget::new(IntEventScope::Effect { effect_idx: 0 }, prop),
+                )
             }
 
             _ => {
@@ -132,8 +176,10 @@ impl Vm {
             if element.len() >= 2 {
                 let idx = element[1];
                 let size = host.on_effect_list_get_size();
-                if size >= 0 && (idx < 0 || idx >= size) {
-                    if self.options.disp_out_of_range_error {
+                // Past the VM's own cap is always an error: `screen_effects` grows to the index.
+                let over_cap = !usize::try_from(idx).is_ok_and(|i| i < Self::MAX_SCREEN_EFFECTS);
+                if over_cap || (size >= 0 && idx >= size) {
+                    if over_cap || self.options.disp_out_of_range_error {
                         host.on_error("範囲外のエフェクト番号が指定されました。(effect_list)");
                     }
                     if ret_form == crate::elm::form::INT {
@@ -148,8 +194,15 @@ impl Vm {
                 } else {
                     &[]
                 };
-                host.on_effect_list_resize((idx + 1).max(0));
-                return self.try_command_effect(rest, arg_list_id, args, ret_form, host);
+                host.on_effect_list_resize(idx + 1);
+                return self.try_command_effect(
+                    idx as usize,
+                    rest,
+                    arg_list_id,
+                    args,
+                    ret_form,
+                    host,
+                );
             }
             return true;
         }
@@ -187,10 +240,11 @@ impl Vm {
     /// Route per-effect commands matching C++ `tnm_command_proc_effect`.
     fn try_command_effect(
         &mut self,
+        idx: usize,
         element: &[i32],
         arg_list_id: i32,
         args: &[Prop],
-        _ret_form: i32,
+        ret_form: i32,
         host: &mut dyn Host,
     ) -> bool {
         if element.is_empty() {
@@ -202,7 +256,9 @@ impl Vm {
         match sub {
             ELM_EFFECT_INIT => {
                 // C++ p_effect->reinit()
+                *self.screen_effect_mut(idx) = VmScreenEffect::default();
                 host.on_effect_init();
+                self.notify_screen_effect(idx, host);
                 true
             }
 
@@ -228,17 +284,12 @@ impl Vm {
             | ELM_EFFECT_BEGIN_LAYER
             | ELM_EFFECT_END_LAYER => {
                 if arg_list_id == 0 {
-                    self.stack.push_int(0);
+                    let v = self.effect_prop_get(idx, sub, host);
+                    self.stack.push_int(v);
                 } else {
-                    host.on_effect_property(
-                        sub,
-                        args.first()
-                            .and_then(|p| match p.value {
-                                PropValue::Int(v) => Some(v),
-                                _ => None,
-                            })
-                            .unwrap_or(0),
-                    );
+                    let v = Self::int_arg(args, 0);
+                    host.on_effect_property(sub, v);
+                    self.effect_prop_set(idx, sub, v, host);
                 }
                 true
             }
@@ -257,10 +308,14 @@ impl Vm {
             | ELM_EFFECT_COLOR_RATE_EVE
             | ELM_EFFECT_COLOR_ADD_R_EVE
             | ELM_EFFECT_COLOR_ADD_G_EVE
-            | ELM_EFFECT_COLOR_ADD_B_EVE => {
-                // C++ dispatches to tnm_command_proc_int_event.
-                self.try_command_int_event(&element[1..], arg_list_id, args, _ret_form, host, sub)
-            }
+            | ELM_EFFECT_COLOR_ADD_B_EVE => self.try_command_int_event(
+                &element[1..],
+                arg_list_id,
+                args,
+                ret_form,
+                host,
+                IntEventTarget::new(IntEventScope::Effect { effect_idx: idx }, sub),
+            ),
 
             _ => {
                 host.on_error("無効なコマンドが指定されました。(effect)");
@@ -269,6 +324,64 @@ impl Vm {
         }
     }
 
+    // ---------------------------------------------------------------
+    // Effect state: values, `_eve` events, host notification
+    // ---------------------------------------------------------------
+
+    /// All `screen.effect[n]` layers; effect 0 is `screen.<prop>`.
+    pub fn screen_effects(&self) -> &[VmScreenEffect] {
+        &self.screen_effects
+    }
+
+    /// Screen effects resolved for rendering at `now_ms` on the VM clock.
+    pub fn render_effects(&self, now_ms: u64) -> Vec<crate::render::RenderEffect> {
+        self.screen_effects
+            .iter()
+            .map(|e| e.resolve(now_ms))
+            .collect()
+    }
+
+    /// Effect `idx`, growing the list; routes keep `idx` below `MAX_SCREEN_EFFECTS`.
+    pub(super) fn screen_effect_mut(&mut self, idx: usize) -> &mut VmScreenEffect {
+        if self.screen_effects.len() <= idx {
+            self.screen_effects
+                .resize(idx + 1, VmScreenEffect::default());
+        }
+        &mut self.screen_effects[idx]
+    }
+
+    /// Re-announce every effect (after a load replaced them).
+    pub(super) fn notify_screen_effects(&self, host: &mut dyn Host) {
+        for (idx, effect) in self.screen_effects.iter().enumerate() {
+            host.on_screen_effect(idx, effect);
+        }
+    }
+
+    pub(super) fn notify_screen_effect(&self, idx: usize, host: &mut dyn Host) {
+        if let Some(effect) = self.screen_effects.get(idx) {
+            host.on_screen_effect(idx, effect);
+        }
+    }
+
+    fn effect_prop_get(&mut self, idx: usize, prop: i32, host: &mut dyn Host) -> i32 {
+        let now = host.clock().now_ms();
+        let effect = self.screen_effect_mut(idx);
+        if let Some(ev) = effect.int_event(prop) {
+            return ev.value_at(now);
+        }
+        effect.int_prop_mut(prop).map(|v| *v).unwrap_or(0)
+    }
+
+    fn effect_prop_set(&mut self, idx: usize, prop: i32, value: i32, host: &mut dyn Host) {
+        let effect = self.screen_effect_mut(idx);
+        if let Some(ev) = effect.int_event_mut(prop) {
+            ev.set_value(value);
+        } else if let Some(v) = effect.int_prop_mut(prop) {
+            *v = value;
+        }
+        self.notify_screen_effect(idx, host);
+    }
+
     // ---------------------------------------------------------------
     // Quake list: global.screen.quake[idx]
     // ---------------------------------------------------------------
@@ -290,8 +403,10 @@ impl Vm {
             if element.len() >= 2 {
                 let idx = element[1];
                 let size = host.on_quake_list_get_size();
-                if size >= 0 && (idx < 0 || idx >= size) {
-                    if self.options.disp_out_of_range_error {
+                // Past the VM's own cap is always an error: `quakes` grows to the index.
+                let over_cap = !usize::try_from(idx).is_ok_and(|i| i < Self::MAX_QUAKES);
+                if over_cap || (size >= 0 && idx >= size) {
+                    if over_cap || self.options.disp_out_of_range_error {
                         host.on_error("範囲外のクェイク番号が指定されました。(quake_list)");
                     }
                     if ret_form == crate::elm::form::INT {
@@ -306,8 +421,15 @@ impl Vm {
                 } else {
                     &[]
                 };
-                host.on_quake_list_resize((idx + 1).max(0));
-                return self.try_command_quake(rest, arg_list_id, args, ret_form, host);
+                host.on_quake_list_resize(idx + 1);
+                return self.try_command_quake(
+                    idx as usize,
+                    rest,
+                    arg_list_id,
+                    args,
+                    ret_form,
+                    host,
+                );
             }
             return true;
         }
@@ -321,20 +443,13 @@ impl Vm {
 
     /// Route per-quake commands matching C++ `tnm_command_proc_quake`.
     ///
-    /// C++ `cmd_effect.cpp` wait/check 时序对照表：
-    ///
-    /// | 分支 | 条件 | VM循环行为 | 返回值语义 |
-    /// | --- | --- | --- | --- |
-    /// | `quake.wait` / `quake.wait_key` | `on_quake_is_active()==true` | 每帧调用 `on_wait_frame()`，直到 inactive 或被 `interrupt/skip_wait` 打断 | 立即返回（void） |
-    /// | `quake.wait` / `quake.wait_key` | `on_quake_is_active()==false` | 不进入循环，直接继续执行后续 opcode | 立即返回（void） |
-    /// | `quake.check` | 任意时刻 | 不推进帧，不改变 quake 状态 | `1=active, 0=inactive` |
-    ///
-    /// 该表用于约束 VM 调度语义：
-    /// - WAIT 是“阻塞轮询 + 帧推进”；
-    /// - CHECK 是“无副作用快照读取”；
-    /// - 二者共享 `on_quake_is_active()` 的判定基准，避免脚本层观察到不一致时序。
+    /// The quake itself lives in `self.quakes[quake_no]` on the VM clock:
+    /// `wait` blocks (advancing frames) until it finishes, `wait_key` also ends
+    /// it on skip, and `check` reports whether it is still running. Hosts are
+    /// told about start/end only to render it.
     fn try_command_quake(
         &mut self,
+        quake_no: usize,
         element: &[i32],
         arg_list_id: i32,
         args: &[Prop],
@@ -448,7 +563,7 @@ impl Vm {
                     2 => VmQuakeKind::Zoom,
                     _ => VmQuakeKind::Vec,
                 };
-                host.on_quake_start(VmQuakeRequest {
+                let req = VmQuakeRequest {
                     sub,
                     kind,
                     time_ms: time,
@@ -462,24 +577,32 @@ impl Vm {
                     vec,
                     center_x,
                     center_y,
+                };
+                if self.quakes.len() <= quake_no {
+                    self.quakes.resize(quake_no + 1, None);
+                }
+                self.quakes[quake_no] = Some(VmQuake {
+                    req,
+                    start_ms: host.clock().now_ms(),
                 });
+                host.on_quake_start(quake_no, req);
+                if wait_flag {
+                    self.wait_quake(quake_no, key_flag, host);
+                }
                 true
             }
             ELM_QUAKE_END => {
-                host.on_quake_end();
+                self.end_quake(quake_no, host);
                 true
             }
             ELM_QUAKE_WAIT | ELM_QUAKE_WAIT_KEY => {
-                while host.on_quake_is_active() {
-                    if host.should_interrupt() || host.should_skip_wait() {
-                        break;
-                    }
-                    host.on_wait_frame();
-                }
+                self.wait_quake(quake_no, sub == ELM_QUAKE_WAIT_KEY, host);
                 true
             }
             ELM_QUAKE_CHECK => {
-                self.stack.push_int(i32::from(host.on_quake_is_active()));
+                let now = host.clock().now_ms();
+                self.stack
+                    .push_int(i32::from(self.quake_is_active(quake_no, now)));
                 true
             }
             _ => {
@@ -488,4 +611,141 @@ impl Vm {
             }
         }
     }
+
+    /// `screen.quake[quake_no]` while it is still running at `now_ms`.
+    pub fn quake(&self, quake_no: usize, now_ms: u64) -> Option<&VmQuake> {
+        self.quakes
+            .get(quake_no)?
+            .as_ref()
+            .filter(|q| q.is_active(now_ms))
+    }
+
+    pub fn quake_is_active(&self, quake_no: usize, now_ms: u64) -> bool {
+        self.quake(quake_no, now_ms).is_some()
+    }
+
+    /// Every running quake, for `RenderScene::quakes`.
+    pub fn render_quakes(&self, now_ms: u64) -> Vec<crate::render::RenderQuake> {
+        self.quakes
+            .iter()
+            .flatten()
+            .filter(|q| q.is_active(now_ms))
+            .map(|q| q.render(now_ms))
+            .collect()
+    }
+
+    /// `screen.shake` offset at `now_ms`, `(0, 0)` when not shaking.
+    pub fn shake_offset(&self, now_ms: u64) -> (f32, f32) {
+        self.shake
+            .as_ref()
+            .and_then(|s| s.offset_at(now_ms))
+            .unwrap_or((0.0, 0.0))
+    }
+
+    /// Combined quake and shake transform for objects at `order`.
+    pub fn quake_transform(&self, order: i32, now_ms: u64) -> crate::render::QuakeTransform {
+        crate::render::quake_transform_for_order(
+            &self.render_quakes(now_ms),
+            self.shake_offset(now_ms),
+            order,
+        )
+    }
+
+    fn end_quake(&mut self, quake_no: usize, host: &mut dyn Host) {
+        if let Some(slot) = self.quakes.get_mut(quake_no)
+            && slot.take().is_some()
+        {
+            host.on_quake_end(quake_no);
+        }
+    }
+
+    /// C++ `C_elm_quake::wait`: advance frames until the quake finishes.
+    /// With `key_flag` a skip ends the quake instead of leaving it running.
+    fn wait_quake(&mut self, quake_no: usize, key_flag: bool, host: &mut dyn Host) {
+        while self.quake_is_active(quake_no, host.clock().now_ms()) {
+            if host.should_interrupt() {
+                return;
+            }
+            if host.should_skip_wait() {
+                if key_flag {
+                    self.end_quake(quake_no, host);
+                }
+                return;
+            }
+            host.on_wait_frame();
+        }
+        self.end_quake(quake_no, host);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vm::test_support::test_vm;
+
+    #[derive(Default)]
+    struct ErrorHost {
+        errors: Vec<String>,
+    }
+
+    impl Host for ErrorHost {
+        fn on_error(&mut self, msg: &str) {
+            self.errors.push(msg.to_string());
+        }
+    }
+
+    fn start_quake(vm: &mut Vm, host: &mut ErrorHost, idx: i32) {
+        let args = [Prop {
+            id: -1,
+            form: crate::elm::form::INT,
+            value: PropValue::Int(0),
+        }];
+        let element = [
+            crate::elm::ELM_ARRAY,
+            idx,
+            crate::elm::quake::ELM_QUAKE_START_NOWAIT,
+        ];
+        assert!(vm.try_command_quake_list(&element, 0, &args, crate::elm::form::VOID, host));
+    }
+
+    fn set_effect_x(vm: &mut Vm, host: &mut ErrorHost, idx: i32) {
+        let args = [Prop {
+            id: -1,
+            form: crate::elm::form::INT,
+            value: PropValue::Int(5),
+        }];
+        let element = [crate::elm::ELM_ARRAY, idx, crate::elm::effect::ELM_EFFECT_X];
+        assert!(vm.try_command_effect_list(&element, 1, &args, crate::elm::form::VOID, host));
+    }
+
+    #[test]
+    fn effect_indices_past_the_cap_are_script_errors() {
+        let mut vm = test_vm();
+        let mut host = ErrorHost::default();
+        set_effect_x(&mut vm, &mut host, Vm::MAX_SCREEN_EFFECTS as i32 - 1);
+        assert!(host.errors.is_empty());
+        assert_eq!(vm.screen_effects.len(), Vm::MAX_SCREEN_EFFECTS);
+        assert_eq!(vm.screen_effects[Vm::MAX_SCREEN_EFFECTS - 1].x.value(), 5);
+
+        for idx in [Vm::MAX_SCREEN_EFFECTS as i32, i32::MAX, -1] {
+            set_effect_x(&mut vm, &mut host, idx);
+        }
+        assert_eq!(host.errors.len(), 3);
+        assert_eq!(vm.screen_effects.len(), Vm::MAX_SCREEN_EFFECTS);
+    }
+
+    #[test]
+    fn quake_indices_past_the_cap_are_script_errors() {
+        let mut vm = test_vm();
+        let mut host = ErrorHost::default();
+        start_quake(&mut vm, &mut host, Vm::MAX_QUAKES as i32 - 1);
+        assert!(host.errors.is_empty());
+        assert_eq!(vm.quakes.len(), Vm::MAX_QUAKES);
+
+        for idx in [Vm::MAX_QUAKES as i32, i32::MAX, -1] {
+            start_quake(&mut vm, &mut host, idx);
+        }
+        assert_eq!(host.errors.len(), 3);
+        assert_eq!(vm.quakes.len(), Vm::MAX_QUAKES);
+    }
 }
@@ -12,6 +12,11 @@ impl Vm {
         provider: &mut dyn SceneProvider,
         host: &mut dyn Host,
     ) -> Result<Option<bool>> {
+        if crate::elm::global::is_selbtn_family(element[0]) {
+            // The host shows the buttons and the start call runs before the
+            // command itself goes to the host to wait for the choice.
+            self.selbtn_start_call(element[0], args, provider, host)?;
+        }
         match element[0] {
             x if crate::elm::global::is_nop(x) => {
                 return Ok(Some(true));
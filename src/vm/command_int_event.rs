@@ -3,15 +3,17 @@
 /// C++ reference: cmd_others.cpp — `tnm_command_proc_int_event` / `tnm_command_proc_int_event_list`
 ///
 /// Covers:
-///   - Per-event: set/set_real/loop/loop_real/turn/turn_real/end/wait/wait_key/check
+///   - Per-event: set/set_real/loop/loop_real/turn/turn_real/yure/yure_real/end/wait/wait_key/check
 ///   - Event list: indexed access, resize
 ///
-/// All actual animation/interpolation state lives host-side.
-/// The VM routes commands, parses args, and delegates via Host callbacks.
+/// Event state lives in the VM (`Vm::int_events`, or `VmScreenEffect` for
+/// effects) as clock-driven `IntEvent`s keyed by `(IntEventScope, owner_id)`.
+/// Hosts are told about changes through `Host::on_int_event` and read values
+/// back with `IntEvent::value_at` on `Host::clock()`.
 use super::*;
 
 impl Vm {
-    fn report_intevent_invalid_fatal(host: &mut dyn Host, owner_id: i32, sub: i32) {
+    pub(super) fn report_intevent_invalid_fatal(host: &mut dyn Host, owner_id: i32, sub: i32) {
         host.on_error_fatal(&format!(
             "無効なコマンドが指定されました。(intevent owner={} sub={})",
             owner_id, sub
@@ -25,6 +27,208 @@ impl Vm {
         ));
     }
 
+    /// Owner id of `*_rep_eve[list_idx]`, or of a plain `*_eve` when `None`.
+    pub(super) fn int_event_list_owner_id(sub: i32, list_idx: Option<i32>) -> i32 {
+        match list_idx {
+            Some(idx) => ((sub & 0xFFFF) << 16) | (idx & 0xFFFF),
+            None => sub,
+        }
+    }
+
+    /// VM-held int event, if it was ever started.
+    pub fn int_event(&self, scope: IntEventScope, owner_id: i32) -> Option<&IntEvent> {
+        match scope {
+            IntEventScope::Effect { effect_idx } => {
+                self.screen_effects.get(effect_idx)?.int_event(owner_id)
+            }
+            IntEventScope::Mask { mask_idx } => self.mask_slots.get(mask_idx)?.int_event(owner_id),
+            IntEventScope::World {
+                stage_idx,
+                world_idx,
+            } => self
+                .worlds
+                .get(&stage_idx)?
+                .get(world_idx)?
+                .int_event(owner_id),
+            _ => self.int_events.get(&(scope, owner_id)),
+        }
+    }
+
+    /// Current value of an int event on the VM clock (`None` if never started).
+    pub fn int_event_value(&self, scope: IntEventScope, owner_id: i32, now_ms: u64) -> Option<i32> {
+        self.int_event(scope, owner_id).map(|e| e.value_at(now_ms))
+    }
+
+    pub fn int_event_is_active(&self, scope: IntEventScope, owner_id: i32, now_ms: u64) -> bool {
+        self.int_event(scope, owner_id)
+            .is_some_and(|e| e.is_active(now_ms))
+    }
+
+    fn int_event_mut(&mut self, scope: IntEventScope, owner_id: i32) -> Option<&mut IntEvent> {
+        match scope {
+            IntEventScope::Effect { effect_idx } => {
+                self.screen_effect_mut(effect_idx).int_event_mut(owner_id)
+            }
+            IntEventScope::Mask { mask_idx } => {
+                self.mask_slots.get_mut(mask_idx)?.int_event_mut(owner_id)
+            }
+            IntEventScope::World {
+                stage_idx,
+                world_idx,
+            } => self
+                .worlds
+                .get_mut(&stage_idx)?
+                .get_mut(world_idx)?
+                .int_event_mut(owner_id),
+            _ => Some(self.int_events.entry((scope, owner_id)).or_default()),
+        }
+    }
+
+    fn notify_int_event(&mut self, scope: IntEventScope, owner_id: i32, host: &mut dyn Host) {
+        match scope {
+            IntEventScope::Effect { effect_idx } => self.notify_screen_effect(effect_idx, host),
+            IntEventScope::Mask { mask_idx } => {
+                if let Some(slot) = self.mask_slots.get(mask_idx) {
+                    host.on_mask_slot(mask_idx, slot);
+                }
+            }
+            IntEventScope::World { stage_idx, .. } => self.notify_stage_worlds(stage_idx, host),
+            _ => {
+                if let Some(ev) = self.int_events.get(&(scope, owner_id)) {
+                    host.on_int_event(scope, owner_id, ev);
+                }
+            }
+        }
+    }
+
+    /// Object `*_eve` events start from the property's current value, so the
+    /// first `x_eve.set` tweens from where the object is rather than from 0.
+    fn seed_object_int_event(&mut self, scope: IntEventScope, owner_id: i32, host: &mut dyn Host) {
+        let IntEventScope::Object {
+            list_id,
+            stage_idx,
+            obj_idx,
+        } = scope
+        else {
+            return;
+        };
+        if self.int_events.contains_key(&(scope, owner_id)) {
+            return;
+        }
+        let Some(prop) = object_event_property(owner_id) else {
+            return;
+        };
+        let stage = (stage_idx >= 0).then_some(stage_idx);
+        let value = host.on_object_get(list_id, obj_idx, prop, stage);
+        self.int_events
+            .insert((scope, owner_id), IntEvent::new(value));
+    }
+
+    /// A plain assign (`obj.x = v`) sets the event's value and cancels it,
+    /// like C++ `C_elm_int_event::set_value`.
+    pub(super) fn object_property_assigned(
+        &mut self,
+        list_id: i32,
+        obj_idx: i32,
+        prop: i32,
+        value: i32,
+        stage_idx: Option<i32>,
+        host: &mut dyn Host,
+    ) {
+        let Some(owner_id) = object_property_event(prop) else {
+            return;
+        };
+        let scope = Self::object_int_event_scope(list_id, stage_idx, obj_idx);
+        let Some(ev) = self.int_events.get_mut(&(scope, owner_id)) else {
+            return;
+        };
+        ev.set_value(value);
+        self.notify_int_event(scope, owner_id, host);
+    }
+
+    /// Resume every saved event `delta_ms` later (in-memory local saves).
+    pub(super) fn shift_int_events(&mut self, delta_ms: u64) {
+        for ev in self.int_events.values_mut() {
+            ev.shift_ms(delta_ms);
+        }
+        for slot in &mut self.mask_slots {
+            slot.x.shift_ms(delta_ms);
+            slot.y.shift_ms(delta_ms);
+        }
+        for effect in &mut self.screen_effects {
+            effect.shift_ms(delta_ms);
+        }
+        for world in self.worlds.values_mut().flatten() {
+            world.shift_ms(delta_ms);
+        }
+    }
+
+    /// Re-send every VM-held event to the host after a load.
+    pub(super) fn notify_int_events(&mut self, host: &mut dyn Host) {
+        for (&(scope, owner_id), ev) in &self.int_events {
+            host.on_int_event(scope, owner_id, ev);
+        }
+    }
+
+    /// Drop an object's events when it is created, freed or re-initialised.
+    /// Running ones are ended first so the host stops driving them.
+    pub(super) fn object_int_events_clear(
+        &mut self,
+        list_id: i32,
+        obj_idx: i32,
+        stage_idx: Option<i32>,
+        host: &mut dyn Host,
+    ) {
+        let scope = Self::object_int_event_scope(list_id, stage_idx, obj_idx);
+        self.end_int_events(scope, host);
+        self.int_events.retain(|(s, _), _| *s != scope);
+    }
+
+    /// Owner ids of the running events of one scope.
+    pub(super) fn active_int_events(&self, scope: IntEventScope, now_ms: u64) -> Vec<i32> {
+        self.int_events
+            .iter()
+            .filter(|((s, _), ev)| *s == scope && ev.is_active(now_ms))
+            .map(|((_, owner_id), _)| *owner_id)
+            .collect()
+    }
+
+    /// End every running event of one scope (C++ `end_all_event`).
+    pub(super) fn end_int_events(&mut self, scope: IntEventScope, host: &mut dyn Host) {
+        for owner_id in self.active_int_events(scope, host.clock().now_ms()) {
+            if let Some(ev) = self.int_events.get_mut(&(scope, owner_id)) {
+                ev.end_event();
+            }
+            self.notify_int_event(scope, owner_id, host);
+        }
+    }
+
+    /// C++ `C_elm_int_event::wait`: advance frames until the event settles.
+    ///
+    /// With `key_skip` a skip ends the event. Returns an `EVE_WAIT_*` status.
+    pub(super) fn wait_int_event(
+        &mut self,
+        scope: IntEventScope,
+        owner_id: i32,
+        key_skip: bool,
+        host: &mut dyn Host,
+    ) -> i32 {
+        while self.int_event_is_active(scope, owner_id, host.clock().now_ms()) {
+            if host.should_interrupt() {
+                return crate::vm::EVE_WAIT_PENDING;
+            }
+            if key_skip && host.should_skip_wait() {
+                if let Some(ev) = self.int_event_mut(scope, owner_id) {
+                    ev.end_event();
+                }
+                self.notify_int_event(scope, owner_id, host);
+                return crate::vm::EVE_WAIT_KEY_SKIPPED;
+            }
+            host.on_wait_frame();
+        }
+        crate::vm::EVE_WAIT_DONE
+    }
+
     // ---------------------------------------------------------------
     // int_event: single event property dispatcher
     // ---------------------------------------------------------------
@@ -34,8 +238,14 @@ impl Vm {
     /// `element` starts AFTER the `*_EVE` root, i.e. element[0] is the event
     /// sub-command (SET, LOOP, END, WAIT, CHECK, etc.).
     ///
-    /// `owner_id` is the elm id of the parent property (e.g. ELM_OBJECT_X_EVE)
-    /// so the host can identify which event is being targeted.
+    /// `target.owner_id` is the elm id of the parent property (e.g.
+    /// ELM_OBJECT_X_EVE), `target.scope` says which object/world/effect it
+    /// belongs to.
+    ///
+    /// Arguments follow C++: `set(value, time, delay, speed_type)` tweens from
+    /// the current value, `loop`/`turn(start, end, time, delay, speed_type)`
+    /// repeat, `yure(center, swing, time, delay, speed_type)` shakes. `*_real`
+    /// variants run on the same VM clock.
     pub(super) fn try_command_int_event(
         &mut self,
         element: &[i32],
@@ -43,8 +253,9 @@ impl Vm {
         args: &[Prop],
         ret_form: i32,
         host: &mut dyn Host,
-        owner_id: i32,
+        target: IntEventTarget,
     ) -> bool {
+        let IntEventTarget { scope, owner_id } = target;
         if element.is_empty() {
             // bare element reference — accept (C++ sets ret element)
             return true;
@@ -52,85 +263,25 @@ impl Vm {
 
         let sub = element[0];
         use crate::elm::intevent::*;
+        let arg = |i: usize| Self::int_arg(args, i);
+        let now = host.clock().now_ms();
 
         match sub {
-            // --- set / set_real ---
-            ELM_INTEVENT_SET | ELM_INTEVENT_SET_REAL => {
-                // C++ tnm_command_proc_int_event: SET/SET_REAL
-                // positional: start(0), end(1), time(2), delay(3)
-                // named-arg id=0 → value override
-                // realtime_flag: SET=0, SET_REAL=1
-                let start = Self::int_arg(args, 0);
-                let end = Self::int_arg(args, 1);
-                let time = Self::int_arg(args, 2);
-                let delay = Self::int_arg(args, 3);
-                let realtime = if sub == ELM_INTEVENT_SET_REAL { 1 } else { 0 };
-
-                // named-arg value override
-                let mut value_override: Option<i32> = None;
-                for arg in args.iter() {
-                    if arg.id == 0 {
-                        if let PropValue::Int(v) = arg.value {
-                            value_override = Some(v);
-                        }
-                    }
-                }
-
-                host.on_int_event_set(owner_id, start, end, time, delay, realtime, value_override);
-                true
-            }
-
-            // --- loop / loop_real ---
-            ELM_INTEVENT_LOOP | ELM_INTEVENT_LOOP_REAL => {
-                // C++ p_int_event->loop_event(start, end, time, delay, count, realtime)
-                let start = Self::int_arg(args, 0);
-                let end = Self::int_arg(args, 1);
-                let time = Self::int_arg(args, 2);
-                let delay = Self::int_arg(args, 3);
-                let speed_type = Self::int_arg(args, 4);
-                let realtime = if sub == ELM_INTEVENT_LOOP_REAL { 1 } else { 0 };
-                host.on_int_event_loop(owner_id, start, end, time, delay, speed_type, realtime);
-                true
-            }
-
-            // --- turn / turn_real ---
-            ELM_INTEVENT_TURN | ELM_INTEVENT_TURN_REAL => {
-                // C++ p_int_event->turn_event(start, end, time, delay, count, realtime)
-                let start = Self::int_arg(args, 0);
-                let end = Self::int_arg(args, 1);
-                let time = Self::int_arg(args, 2);
-                let delay = Self::int_arg(args, 3);
-                let speed_type = Self::int_arg(args, 4);
-                let realtime = if sub == ELM_INTEVENT_TURN_REAL { 1 } else { 0 };
-                host.on_int_event_turn(owner_id, start, end, time, delay, speed_type, realtime);
-                true
+            ELM_INTEVENT_CHECK => {
+                // C++ tnm_stack_push_int(p_int_event->check_event() ? 1 : 0)
+                let active = self.int_event_is_active(scope, owner_id, now);
+                self.stack.push_int(i32::from(active));
+                return true;
             }
-
-            // --- end ---
-            ELM_INTEVENT_END => {
-                // C++ p_int_event->end_event()
-                host.on_int_event_end(owner_id);
-                true
+            ELM_INTEVENT_GET_EVENT_VALUE => {
+                // C++ tnm_stack_push_int(p_int_event->get_event_value())
+                let v = self.int_event_value(scope, owner_id, now).unwrap_or(0);
+                self.stack.push_int(v);
+                return true;
             }
-
-            // --- wait / wait_key ---
             ELM_INTEVENT_WAIT | ELM_INTEVENT_WAIT_KEY => {
-                // flow_proc.cpp / ifc_proc_stack alignment:
-                // expose the same observable wait protocol used in property lane.
                 let key_skip = sub == ELM_INTEVENT_WAIT_KEY;
-                let status = if !host.on_int_event_check(owner_id) {
-                    crate::vm::EVE_WAIT_DONE
-                } else if key_skip && host.should_skip_wait() {
-                    crate::vm::EVE_WAIT_KEY_SKIPPED
-                } else {
-                    host.on_int_event_wait(owner_id, key_skip);
-                    host.on_wait_frame();
-                    if host.on_int_event_check(owner_id) {
-                        crate::vm::EVE_WAIT_PENDING
-                    } else {
-                        crate::vm::EVE_WAIT_DONE
-                    }
-                };
+                let status = self.wait_int_event(scope, owner_id, key_skip, host);
                 let (proc_depth, proc_top) = self.observe_proc_stack_tuple();
                 host.on_int_event_wait_status(owner_id, key_skip, status);
                 host.on_int_event_wait_status_with_proc(
@@ -139,58 +290,58 @@ impl Vm {
                 if ret_form == crate::elm::form::INT {
                     self.stack.push_int(status);
                 }
-                true
+                return true;
             }
+            // internal use
+            ELM_INTEVENT__SET => return true,
+            _ => {}
+        }
 
-            // --- check ---
-            ELM_INTEVENT_CHECK => {
-                // C++ tnm_stack_push_int(p_int_event->check_event() ? 1 : 0)
-                self.stack.push_int(if host.on_int_event_check(owner_id) {
-                    1
-                } else {
-                    0
-                });
-                true
+        self.seed_object_int_event(scope, owner_id, host);
+        let Some(ev) = self.int_event_mut(scope, owner_id) else {
+            Self::report_intevent_invalid_fatal(host, owner_id, sub);
+            return true;
+        };
+        match sub {
+            ELM_INTEVENT_SET | ELM_INTEVENT_SET_REAL => {
+                ev.set_event(arg(0), arg(1), arg(2), arg(3), now);
             }
-
-            // --- get_event_value ---
-            ELM_INTEVENT_GET_EVENT_VALUE => {
-                // C++ tnm_stack_push_int(p_int_event->get_event_value())
-                self.stack.push_int(host.on_int_event_get_value(owner_id));
-                true
+            ELM_INTEVENT_LOOP | ELM_INTEVENT_LOOP_REAL => {
+                ev.loop_event(
+                    IntEventLoop::Loop,
+                    arg(0),
+                    arg(1),
+                    arg(2),
+                    arg(3),
+                    arg(4),
+                    now,
+                );
             }
-
-            // --- yure / yure_real (shake) ---
-            ELM_INTEVENT_YURE | ELM_INTEVENT_YURE_REAL => {
-                // Use C++ int_event interpolation core with yure-specific parameters.
-                let center = Self::int_arg(args, 0);
-                let swing = Self::int_arg(args, 1);
-                let time = Self::int_arg(args, 2);
-                let delay = Self::int_arg(args, 3);
-                let speed_type = Self::int_arg(args, 4);
-                host.on_int_event_yure(
-                    owner_id,
-                    center,
-                    swing,
-                    time,
-                    delay,
-                    speed_type,
-                    sub == ELM_INTEVENT_YURE_REAL,
+            ELM_INTEVENT_TURN | ELM_INTEVENT_TURN_REAL => {
+                ev.loop_event(
+                    IntEventLoop::Turn,
+                    arg(0),
+                    arg(1),
+                    arg(2),
+                    arg(3),
+                    arg(4),
+                    now,
                 );
-                true
             }
-
-            // --- __set (internal) ---
-            ELM_INTEVENT__SET => {
-                // accept — internal use
-                true
+            ELM_INTEVENT_YURE | ELM_INTEVENT_YURE_REAL => {
+                ev.yure_event(arg(0), arg(1), arg(2), arg(3), arg(4), now);
+            }
+            ELM_INTEVENT_END => {
+                // C++ p_int_event->end_event()
+                ev.end_event();
             }
-
             _ => {
                 Self::report_intevent_invalid_fatal(host, owner_id, sub);
-                true
+                return true;
             }
         }
+        self.notify_int_event(scope, owner_id, host);
+        true
     }
 
     // ---------------------------------------------------------------
@@ -207,8 +358,9 @@ impl Vm {
         args: &[Prop],
         ret_form: i32,
         host: &mut dyn Host,
-        owner_id: i32,
+        target: IntEventTarget,
     ) -> bool {
+        let IntEventTarget { scope, owner_id } = target;
         if element.is_empty() {
             // bare list reference — accept
             return true;
@@ -244,7 +396,7 @@ impl Vm {
                     args,
                     ret_form,
                     host,
-                    owner_id,
+                    IntEventTarget::new(scope, Self::int_event_list_owner_id(owner_id, Some(idx))),
                 );
             }
             // bare indexed access — get/set value
@@ -285,3 +437,101 @@ impl Vm {
             .unwrap_or(0)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elm::objectlist::{ELM_OBJECT_X, ELM_OBJECT_X_EVE, ELM_STAGE_OBJECT};
+    use crate::vm::test_support::test_vm;
+
+    /// Host with one object whose `x` starts at 100, on a virtual clock that
+    /// advances 10ms per waited frame.
+    struct ObjectHost {
+        clock: Arc<VirtualClock>,
+        x: i32,
+    }
+
+    impl Host for ObjectHost {
+        fn clock(&self) -> SharedVmClock {
+            self.clock.clone()
+        }
+
+        fn on_wait_frame(&mut self) {
+            self.clock.advance_ms(10);
+        }
+
+        fn on_object_get(&mut self, _: i32, _: i32, sub: i32, _: Option<i32>) -> i32 {
+            if sub == ELM_OBJECT_X { self.x } else { 0 }
+        }
+    }
+
+    fn int_args(values: &[i32]) -> Vec<Prop> {
+        values
+            .iter()
+            .map(|&v| Prop {
+                id: 0,
+                form: crate::elm::form::INT,
+                value: PropValue::Int(v),
+            })
+            .collect()
+    }
+
+    fn run(vm: &mut Vm, host: &mut ObjectHost, sub: i32, args: &[i32]) {
+        let scope = Vm::object_int_event_scope(ELM_STAGE_OBJECT, Some(0), 1);
+        vm.try_command_int_event(
+            &[sub],
+            0,
+            &int_args(args),
+            crate::elm::form::VOID,
+            host,
+            IntEventTarget::new(scope, ELM_OBJECT_X_EVE),
+        );
+    }
+
+    #[test]
+    fn object_events_start_from_the_property_and_yield_to_assigns() {
+        use crate::elm::intevent::*;
+        let mut vm = test_vm();
+        let mut host = ObjectHost {
+            clock: VirtualClock::shared(0),
+            x: 100,
+        };
+        let scope = Vm::object_int_event_scope(ELM_STAGE_OBJECT, Some(0), 1);
+
+        run(&mut vm, &mut host, ELM_INTEVENT_SET, &[200, 1000, 0, 0]);
+        assert_eq!(vm.int_event_value(scope, ELM_OBJECT_X_EVE, 500), Some(150));
+
+        vm.object_property_assigned(ELM_STAGE_OBJECT, 1, ELM_OBJECT_X, 30, Some(0), &mut host);
+        assert!(!vm.int_event_is_active(scope, ELM_OBJECT_X_EVE, 500));
+        assert_eq!(vm.int_event_value(scope, ELM_OBJECT_X_EVE, 500), Some(30));
+    }
+
+    #[test]
+    fn yure_wait_returns_once_the_shake_ends() {
+        use crate::elm::intevent::*;
+        let mut vm = test_vm();
+        let mut host = ObjectHost {
+            clock: VirtualClock::shared(0),
+            x: 0,
+        };
+        let scope = Vm::object_int_event_scope(ELM_STAGE_OBJECT, Some(0), 1);
+        run(&mut vm, &mut host, ELM_INTEVENT_YURE, &[0, 8, 200, 0, 0]);
+        let status = vm.wait_int_event(scope, ELM_OBJECT_X_EVE, false, &mut host);
+        assert_eq!(status, crate::vm::EVE_WAIT_DONE);
+        assert_eq!(host.clock.now_ms(), 200);
+    }
+
+    #[test]
+    fn clearing_an_object_drops_its_events() {
+        use crate::elm::intevent::*;
+        let mut vm = test_vm();
+        let mut host = ObjectHost {
+            clock: VirtualClock::shared(0),
+            x: 0,
+        };
+        let scope = Vm::object_int_event_scope(ELM_STAGE_OBJECT, Some(0), 1);
+        run(&mut vm, &mut host, ELM_INTEVENT_SET, &[50, 1000, 0, 0]);
+        vm.object_int_events_clear(ELM_STAGE_OBJECT, 1, Some(0), &mut host);
+        assert!(vm.int_event(scope, ELM_OBJECT_X_EVE).is_none());
+    }
+}
@@ -6,7 +6,7 @@ impl Vm {
             .frames
             .iter()
             .skip(1)
-            .filter(|f| f.excall_flag || f.frame_action_flag)
+            .filter(|f| f.excall_flag || f.is_nested_proc())
             .count()
     }
 
@@ -129,17 +129,9 @@ impl Vm {
         } else if crate::elm::math::is_max(method) {
             Some(arg_int(0).max(arg_int(1)))
         } else if crate::elm::math::is_rand(method) {
-            // Return a pseudo-random value in [0, arg0). Use wrapping for determinism.
-            let upper = arg_int(0);
-            if upper <= 0 {
-                Some(0)
-            } else {
-                // Simple LCG-style random for headless reproducibility
-                let seed = (self.steps as i32)
-                    .wrapping_mul(1103515245)
-                    .wrapping_add(12345);
-                Some(((seed as u32) % (upper as u32)) as i32)
-            }
+            // Return a pseudo-random value in [0, arg0) from the seedable VM RNG
+            // so replays and loads stay reproducible.
+            Some(self.rng.next_below(arg_int(0)))
         } else if crate::elm::math::is_limit(method) {
             let val = arg_int(0);
             let lo = arg_int(1);
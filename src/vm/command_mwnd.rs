@@ -73,7 +73,7 @@ impl Vm {
                 } else {
                     &[]
                 };
-                return self.try_command_mwnd(rest, arg_list_id, args, ret_form, host);
+                return self.try_command_mwnd(mwnd_idx, rest, arg_list_id, args, ret_form, host);
             }
             return true;
         }
@@ -81,12 +81,12 @@ impl Vm {
         match element[0] {
             ELM_MWNDLIST_CLOSE | ELM_MWNDLIST_CLOSE_WAIT => {
                 // C++ tnm_msg_proc_close_all(true)
-                host.on_mwnd_action(element[0], args);
+                host.on_mwnd_action(-1, element[0], args);
                 true
             }
             ELM_MWNDLIST_CLOSE_NOWAIT => {
                 // C++ tnm_msg_proc_close_all(false)
-                host.on_mwnd_action(element[0], args);
+                host.on_mwnd_action(-1, element[0], args);
                 true
             }
             _ => {
@@ -103,6 +103,7 @@ impl Vm {
     /// Route per-mwnd commands matching C++ `tnm_command_proc_mwnd`.
     fn try_command_mwnd(
         &mut self,
+        mwnd_no: i32,
         element: &[i32],
         arg_list_id: i32,
         args: &[Prop],
@@ -116,13 +117,17 @@ impl Vm {
         let sub = element[0];
         use crate::elm::mwnd::*;
 
+        if let Some(cmd) = VmMessageCommand::from_mwnd(sub) {
+            self.message_command(mwnd_no, cmd, args, host);
+        }
+
         match sub {
             // --- Waku (frame) commands ---
             ELM_MWND_SET_WAKU | ELM_MWND_INIT_WAKU_FILE | ELM_MWND_SET_WAKU_FILE => {
                 if matches!(sub, ELM_MWND_INIT_WAKU_FILE | ELM_MWND_SET_WAKU_FILE) {
                     Self::mwnd_report_file_not_found(host, sub, args);
                 }
-                host.on_mwnd_action(sub, args);
+                host.on_mwnd_action(mwnd_no, sub, args);
                 true
             }
             ELM_MWND_GET_WAKU_FILE => {
@@ -133,7 +138,7 @@ impl Vm {
             // --- Filter commands ---
             ELM_MWND_INIT_FILTER_FILE | ELM_MWND_SET_FILTER_FILE => {
                 Self::mwnd_report_file_not_found(host, sub, args);
-                host.on_mwnd_action(sub, args);
+                host.on_mwnd_action(mwnd_no, sub, args);
                 true
             }
             ELM_MWND_GET_FILTER_FILE => {
@@ -143,82 +148,89 @@ impl Vm {
 
             // --- Open/Close ---
             ELM_MWND_OPEN | ELM_MWND_OPEN_WAIT | ELM_MWND_OPEN_NOWAIT => {
-                host.on_mwnd_action(sub, args);
+                host.on_mwnd_action(mwnd_no, sub, args);
                 true
             }
             ELM_MWND_CHECK_OPEN => {
                 // C++ tnm_stack_push_int(p_mwnd->get_window_appear_flag() ? 1 : 0)
-                self.stack.push_int(host.on_mwnd_get(sub));
+                self.stack.push_int(host.on_mwnd_get(mwnd_no, sub));
                 true
             }
             ELM_MWND_CLOSE | ELM_MWND_CLOSE_WAIT | ELM_MWND_CLOSE_NOWAIT | ELM_MWND_END_CLOSE => {
-                host.on_mwnd_action(sub, args);
+                host.on_mwnd_action(mwnd_no, sub, args);
                 true
             }
 
             // --- Message block / clear ---
             ELM_MWND_MSG_BLOCK | ELM_MWND_MSG_PP_BLOCK | ELM_MWND_CLEAR | ELM_MWND__NOVEL_CLEAR => {
-                host.on_mwnd_action(sub, args);
+                host.on_mwnd_action(mwnd_no, sub, args);
                 true
             }
 
             // --- Print / overflow print / namae ---
             ELM_MWND_PRINT | ELM_MWND__OVER_FLOW_PRINT | ELM_MWND__OVER_FLOW_NAMAE => {
-                host.on_mwnd_action(sub, args);
+                let text = Self::mwnd_arg_str(args, 0);
+                if sub == ELM_MWND__OVER_FLOW_NAMAE {
+                    self.message_set_name(mwnd_no, &text, host);
+                } else {
+                    self.message_add_text(mwnd_no, &text, true, host);
+                }
+                host.on_mwnd_action(mwnd_no, sub, args);
                 true
             }
 
             // --- Ruby ---
             ELM_MWND_RUBY => {
-                host.on_mwnd_action(sub, args);
+                host.on_mwnd_action(mwnd_no, sub, args);
                 true
             }
 
             // --- Wait / flow control ---
             ELM_MWND_WAIT_MSG | ELM_MWND_PP | ELM_MWND_R | ELM_MWND_PAGE => {
-                host.on_mwnd_action(sub, args);
+                host.on_mwnd_action(mwnd_no, sub, args);
                 true
             }
 
             // --- NL / NLI ---
             ELM_MWND_NL | ELM_MWND_NLI => {
-                host.on_mwnd_action(sub, args);
+                host.on_mwnd_action(mwnd_no, sub, args);
                 true
             }
 
             // --- Indent ---
             ELM_MWND_INDENT | ELM_MWND_CLEAR_INDENT => {
-                host.on_mwnd_action(sub, args);
+                host.on_mwnd_action(mwnd_no, sub, args);
                 true
             }
 
             // --- Multi-message / next ---
             ELM_MWND_MULTI_MSG | ELM_MWND_NEXT_MSG => {
-                host.on_mwnd_action(sub, args);
+                host.on_mwnd_action(mwnd_no, sub, args);
                 true
             }
 
             // --- Slide message ---
             ELM_MWND_START_SLIDE_MSG | ELM_MWND_END_SLIDE_MSG | ELM_MWND__SLIDE_MSG => {
-                host.on_mwnd_action(sub, args);
+                host.on_mwnd_action(mwnd_no, sub, args);
                 true
             }
 
             // --- Selection ---
             ELM_MWND_SEL | ELM_MWND_SEL_CANCEL | ELM_MWND_SELMSG | ELM_MWND_SELMSG_CANCEL => {
-                host.on_mwnd_action(sub, args);
+                host.on_mwnd_action(mwnd_no, sub, args);
                 true
             }
 
             // --- Rep pos / size / color / msgbtn ---
             ELM_MWND_REP_POS | ELM_MWND_SIZE | ELM_MWND_COLOR | ELM_MWND_MSGBTN => {
-                host.on_mwnd_action(sub, args);
+                host.on_mwnd_action(mwnd_no, sub, args);
                 true
             }
 
             // --- Set namae ---
             ELM_MWND_SET_NAMAE | ELM_MWND_NAMAE => {
-                host.on_mwnd_action(sub, args);
+                self.message_set_name(mwnd_no, &Self::mwnd_arg_str(args, 0), host);
+                host.on_mwnd_action(mwnd_no, sub, args);
                 true
             }
 
@@ -229,7 +241,13 @@ impl Vm {
             | ELM_MWND_EXKOE
             | ELM_MWND_EXKOE_PLAY_WAIT
             | ELM_MWND_EXKOE_PLAY_WAIT_KEY => {
-                host.on_mwnd_action(sub, args);
+                host.on_mwnd_action(mwnd_no, sub, args);
+                let wait = match sub {
+                    ELM_MWND_KOE_PLAY_WAIT | ELM_MWND_EXKOE_PLAY_WAIT => Some(false),
+                    ELM_MWND_KOE_PLAY_WAIT_KEY | ELM_MWND_EXKOE_PLAY_WAIT_KEY => Some(true),
+                    _ => None,
+                };
+                self.koe_play(args, wait, host);
                 true
             }
 
@@ -238,16 +256,16 @@ impl Vm {
                 if sub == ELM_MWND_SET_FACE {
                     Self::mwnd_report_file_not_found(host, sub, args);
                 }
-                host.on_mwnd_action(sub, args);
+                host.on_mwnd_action(mwnd_no, sub, args);
                 true
             }
 
             // --- Layer / World ---
             ELM_MWND_LAYER | ELM_MWND_WORLD => {
                 if arg_list_id == 0 {
-                    self.stack.push_int(host.on_mwnd_get(sub)); // get
+                    self.stack.push_int(host.on_mwnd_get(mwnd_no, sub)); // get
                 } else {
-                    host.on_mwnd_action(sub, args);
+                    host.on_mwnd_action(mwnd_no, sub, args);
                 }
                 true
             }
@@ -270,7 +288,7 @@ impl Vm {
             | ELM_MWND_SET_WINDOW_POS
             | ELM_MWND_SET_WINDOW_SIZE
             | ELM_MWND_SET_WINDOW_MOJI_CNT => {
-                host.on_mwnd_action(sub, args);
+                host.on_mwnd_action(mwnd_no, sub, args);
                 true
             }
             ELM_MWND_GET_WINDOW_POS_X
@@ -279,7 +297,7 @@ impl Vm {
             | ELM_MWND_GET_WINDOW_SIZE_Y
             | ELM_MWND_GET_WINDOW_MOJI_CNT_X
             | ELM_MWND_GET_WINDOW_MOJI_CNT_Y => {
-                self.stack.push_int(host.on_mwnd_get(sub));
+                self.stack.push_int(host.on_mwnd_get(mwnd_no, sub));
                 true
             }
 
@@ -292,7 +310,7 @@ impl Vm {
             | ELM_MWND_SET_OPEN_ANIME_TIME
             | ELM_MWND_SET_CLOSE_ANIME_TYPE
             | ELM_MWND_SET_CLOSE_ANIME_TIME => {
-                host.on_mwnd_action(sub, args);
+                host.on_mwnd_action(mwnd_no, sub, args);
                 true
             }
             ELM_MWND_GET_OPEN_ANIME_TYPE
@@ -303,7 +321,7 @@ impl Vm {
             | ELM_MWND_GET_DEFAULT_OPEN_ANIME_TIME
             | ELM_MWND_GET_DEFAULT_CLOSE_ANIME_TYPE
             | ELM_MWND_GET_DEFAULT_CLOSE_ANIME_TIME => {
-                self.stack.push_int(host.on_mwnd_get(sub));
+                self.stack.push_int(host.on_mwnd_get(mwnd_no, sub));
                 true
             }
 
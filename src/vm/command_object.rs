@@ -17,8 +17,21 @@ include!("command_object_frame_action_counter_gc.rs");
 include!("command_object_child_route.rs");
 include!("command_object_gan_track.rs");
 include!("command_object_error_family.rs");
+include!("command_object_rep_list.rs");
 
 impl Vm {
+    pub(super) fn object_int_event_scope(
+        list_id: i32,
+        stage_idx: Option<i32>,
+        obj_idx: i32,
+    ) -> IntEventScope {
+        IntEventScope::Object {
+            list_id,
+            stage_idx: stage_idx.unwrap_or(-1),
+            obj_idx,
+        }
+    }
+
     fn object_arg_str(args: &[Prop], idx: usize) -> String {
         match args.get(idx).map(|p| &p.value) {
             Some(PropValue::Str(v)) => v.clone(),
@@ -251,37 +264,46 @@ impl Vm {
                     self.stack
                         .push_int(host.on_object_get(list_id, obj_idx, sub, stage_idx));
                 } else {
-                    host.on_object_property(
-                        list_id,
-                        obj_idx,
-                        sub,
-                        Self::int_arg(args, 0),
-                        stage_idx,
-                    );
+                    let v = Self::int_arg(args, 0);
+                    host.on_object_property(list_id, obj_idx, sub, v, stage_idx);
+                    self.object_property_assigned(list_id, obj_idx, sub, v, stage_idx, host);
                 }
                 true
             }
 
             // Compound set commands (2-3 positional args)
-            ELM_OBJECT_SET_POS => {
-                // C++ al_id 0: set_pos_x, set_pos_y; al_id 1: +set_pos_z
-                host.on_object_action(list_id, obj_idx, sub, args, stage_idx);
-                true
-            }
-            ELM_OBJECT_SET_CENTER => {
-                host.on_object_action(list_id, obj_idx, sub, args, stage_idx);
-                true
-            }
-            ELM_OBJECT_SET_CENTER_REP => {
-                host.on_object_action(list_id, obj_idx, sub, args, stage_idx);
-                true
-            }
-            ELM_OBJECT_SET_SCALE => {
-                host.on_object_action(list_id, obj_idx, sub, args, stage_idx);
-                true
-            }
-            ELM_OBJECT_SET_ROTATE => {
+            ELM_OBJECT_SET_POS
+            | ELM_OBJECT_SET_CENTER
+            | ELM_OBJECT_SET_CENTER_REP
+            | ELM_OBJECT_SET_SCALE
+            | ELM_OBJECT_SET_ROTATE => {
+                // C++ al_id 0: set_*_x, set_*_y; al_id 1: +set_*_z
                 host.on_object_action(list_id, obj_idx, sub, args, stage_idx);
+                let props = match sub {
+                    ELM_OBJECT_SET_POS => [ELM_OBJECT_X, ELM_OBJECT_Y, ELM_OBJECT_Z],
+                    ELM_OBJECT_SET_CENTER => [
+                        ELM_OBJECT_CENTER_X,
+                        ELM_OBJECT_CENTER_Y,
+                        ELM_OBJECT_CENTER_Z,
+                    ],
+                    ELM_OBJECT_SET_CENTER_REP => [
+                        ELM_OBJECT_CENTER_REP_X,
+                        ELM_OBJECT_CENTER_REP_Y,
+                        ELM_OBJECT_CENTER_REP_Z,
+                    ],
+                    ELM_OBJECT_SET_SCALE => {
+                        [ELM_OBJECT_SCALE_X, ELM_OBJECT_SCALE_Y, ELM_OBJECT_SCALE_Z]
+                    }
+                    _ => [
+                        ELM_OBJECT_ROTATE_X,
+                        ELM_OBJECT_ROTATE_Y,
+                        ELM_OBJECT_ROTATE_Z,
+                    ],
+                };
+                for (i, prop) in props.into_iter().enumerate().take(args.len()) {
+                    let v = Self::int_arg(args, i);
+                    self.object_property_assigned(list_id, obj_idx, prop, v, stage_idx, host);
+                }
                 true
             }
             ELM_OBJECT_SET_CLIP => {
@@ -294,12 +316,18 @@ impl Vm {
                 true
             }
 
-            // Int list / rep properties (host manages int_list data)
-            ELM_OBJECT_X_REP | ELM_OBJECT_Y_REP | ELM_OBJECT_Z_REP | ELM_OBJECT_TR_REP => {
-                // C++ tnm_command_proc_int_list — accept, host handles
-                host.on_object_action(list_id, obj_idx, sub, args, stage_idx);
-                true
-            }
+            // Rep lists: the VM walks the int_list methods, the host keeps the values
+            ELM_OBJECT_X_REP | ELM_OBJECT_Y_REP | ELM_OBJECT_Z_REP | ELM_OBJECT_TR_REP => self
+                .try_command_object_rep_list(
+                    list_id,
+                    stage_idx,
+                    obj_idx,
+                    sub,
+                    &element[1..],
+                    arg_list_id,
+                    args,
+                    host,
+                ),
             ELM_OBJECT_F => {
                 // C++ tnm_command_proc_int_list(&p_obj->F(), 32, ...)
                 host.on_object_action(list_id, obj_idx, sub, args, stage_idx);
@@ -342,9 +370,17 @@ impl Vm {
             | ELM_OBJECT_COLOR_RATE_EVE
             | ELM_OBJECT_COLOR_ADD_R_EVE
             | ELM_OBJECT_COLOR_ADD_G_EVE
-            | ELM_OBJECT_COLOR_ADD_B_EVE => {
-                self.try_command_int_event(&element[1..], arg_list_id, args, ret_form, host, sub)
-            }
+            | ELM_OBJECT_COLOR_ADD_B_EVE => self.try_command_int_event(
+                &element[1..],
+                arg_list_id,
+                args,
+                ret_form,
+                host,
+                IntEventTarget::new(
+                    Self::object_int_event_scope(list_id, stage_idx, obj_idx),
+                    sub,
+                ),
+            ),
 
             // Event list properties → int_event_list sub-router
             ELM_OBJECT_X_REP_EVE
@@ -356,24 +392,36 @@ impl Vm {
                 args,
                 ret_form,
                 host,
-                sub,
+                IntEventTarget::new(
+                    Self::object_int_event_scope(list_id, stage_idx, obj_idx),
+                    sub,
+                ),
             ),
 
             // ALL_EVE: end/wait/check
             ELM_OBJECT_ALL_EVE => {
+                let scope = Self::object_int_event_scope(list_id, stage_idx, obj_idx);
                 if element.len() >= 2 {
                     use crate::elm::allevent::*;
                     match element[1] {
                         ELM_ALLEVENT_END => {
+                            self.end_int_events(scope, host);
                             host.on_object_action(list_id, obj_idx, sub, args, stage_idx);
                         }
                         ELM_ALLEVENT_WAIT => {
                             // C++ pushes proc TNM_PROC_TYPE_ALL_EVENT_WAIT
+                            let now = host.clock().now_ms();
+                            for owner_id in self.active_int_events(scope, now) {
+                                self.wait_int_event(scope, owner_id, false, host);
+                            }
                             host.on_object_action(list_id, obj_idx, sub, args, stage_idx);
                         }
                         ELM_ALLEVENT_CHECK => {
                             // C++ tnm_stack_push_int(p_obj->check_all_event() ? 1 : 0)
-                            self.stack.push_int(0);
+                            let now = host.clock().now_ms();
+                            let active = !self.active_int_events(scope, now).is_empty()
+                                || host.on_object_get(list_id, obj_idx, sub, stage_idx) != 0;
+                            self.stack.push_int(i32::from(active));
                         }
                         _ => {
                             host.on_error_fatal("無効なコマンドが指定されました。(allevent)");
@@ -428,9 +476,11 @@ impl Vm {
 
             // Lifecycle commands
             ELM_OBJECT_INIT | ELM_OBJECT_FREE | ELM_OBJECT_INIT_PARAM => {
+                self.object_rep_lists_clear(list_id, obj_idx, stage_idx);
                 if Self::object_command_replaces_resource(sub) {
                     self.frame_counter_invalidate_object_context(list_id, obj_idx, stage_idx);
                     self.object_gan_track_clear(list_id, obj_idx, stage_idx);
+                    self.object_int_events_clear(list_id, obj_idx, stage_idx, host);
                 }
                 host.on_object_action(list_id, obj_idx, sub, args, stage_idx);
                 true
@@ -442,17 +492,31 @@ impl Vm {
             | ELM_OBJECT_CREATE_WEATHER
             | ELM_OBJECT_CREATE_MESH
             | ELM_OBJECT_CREATE_BILLBOARD
-            | ELM_OBJECT_CREATE_SAVE_THUMB
+            | ELM_OBJECT_CREATE_COPY_FROM => {
+                if Self::object_command_replaces_resource(sub) {
+                    self.frame_counter_invalidate_object_context(list_id, obj_idx, stage_idx);
+                    self.object_gan_track_clear(list_id, obj_idx, stage_idx);
+                    self.object_int_events_clear(list_id, obj_idx, stage_idx, host);
+                }
+                if sub == ELM_OBJECT_CREATE_COPY_FROM {
+                    self.object_rep_lists_copy_from(list_id, obj_idx, stage_idx, args);
+                }
+                Self::object_report_file_not_found(host, sub, args);
+                host.on_object_action(list_id, obj_idx, sub, args, stage_idx);
+                true
+            }
+            ELM_OBJECT_CREATE_SAVE_THUMB
             | ELM_OBJECT_CREATE_CAPTURE_THUMB
             | ELM_OBJECT_CREATE_CAPTURE
-            | ELM_OBJECT_CREATE_COPY_FROM
             | ELM_OBJECT_CREATE_FROM_CAPTURE_FILE => {
                 if Self::object_command_replaces_resource(sub) {
                     self.frame_counter_invalidate_object_context(list_id, obj_idx, stage_idx);
                     self.object_gan_track_clear(list_id, obj_idx, stage_idx);
+                    self.object_int_events_clear(list_id, obj_idx, stage_idx, host);
                 }
                 Self::object_report_file_not_found(host, sub, args);
-                host.on_object_action(list_id, obj_idx, sub, args, stage_idx);
+                let image = self.object_capture_image(sub, args, host);
+                host.on_object_create_from_capture(list_id, obj_idx, sub, args, image, stage_idx);
                 true
             }
             ELM_OBJECT_CREATE_MOVIE
@@ -468,6 +532,7 @@ impl Vm {
                 if Self::object_command_replaces_resource(sub) {
                     self.frame_counter_invalidate_object_context(list_id, obj_idx, stage_idx);
                     self.object_gan_track_clear(list_id, obj_idx, stage_idx);
+                    self.object_int_events_clear(list_id, obj_idx, stage_idx, host);
                 }
                 Self::object_report_file_not_found(host, sub, args);
                 host.on_object_action(list_id, obj_idx, sub, args, stage_idx);
@@ -483,6 +548,7 @@ impl Vm {
                 if Self::object_command_replaces_resource(sub) {
                     self.frame_counter_invalidate_object_context(list_id, obj_idx, stage_idx);
                     self.object_gan_track_clear(list_id, obj_idx, stage_idx);
+                    self.object_int_events_clear(list_id, obj_idx, stage_idx, host);
                 }
                 Self::object_report_file_not_found(host, sub, args);
                 host.on_object_action(list_id, obj_idx, sub, args, stage_idx);
@@ -553,7 +619,13 @@ impl Vm {
             }
 
             ELM_OBJECT_SET_WEATHER_PARAM_TYPE_A | ELM_OBJECT_SET_WEATHER_PARAM_TYPE_B => {
-                if !args.is_empty() {
+                // Parameters are named only (C++ `S_tnm_weather_param` fields).
+                let arg_cnt = if sub == ELM_OBJECT_SET_WEATHER_PARAM_TYPE_A {
+                    crate::render::WEATHER_A_ARG_CNT
+                } else {
+                    crate::render::WEATHER_B_ARG_CNT
+                };
+                if args.iter().any(|a| !(0..arg_cnt).contains(&a.id)) {
                     host.on_error_fatal(Self::object_invalid_message_for_sub(sub));
                     Self::object_frame_action_push_default(&mut self.stack, ret_form);
                     return true;
@@ -704,3 +776,132 @@ impl Vm {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elm::list::ELM_INTLIST_RESIZE;
+    use crate::elm::objectlist::{ELM_OBJECT_X_REP, ELM_STAGE_OBJECT};
+    use crate::vm::test_support::test_vm;
+
+    /// Host that keeps the last rep list it was sent.
+    #[derive(Default)]
+    struct RepHost {
+        last: Option<(i32, Vec<i32>)>,
+    }
+
+    impl Host for RepHost {
+        fn on_object_int_list_set(
+            &mut self,
+            _: i32,
+            obj_index: i32,
+            _: i32,
+            values: &[i32],
+            _: Option<i32>,
+        ) {
+            self.last = Some((obj_index, values.to_vec()));
+        }
+    }
+
+    fn resize(vm: &mut Vm, host: &mut RepHost, size: i32) {
+        let args = [Prop {
+            id: 0,
+            form: crate::elm::form::INT,
+            value: PropValue::Int(size),
+        }];
+        vm.try_command_object_rep_list(
+            ELM_STAGE_OBJECT,
+            Some(0),
+            3,
+            ELM_OBJECT_X_REP,
+            &[ELM_INTLIST_RESIZE],
+            1,
+            &args,
+            host,
+        );
+    }
+
+    #[test]
+    fn rep_lists_are_bounded_and_come_back_with_a_load() {
+        let mut vm = test_vm();
+        let mut host = RepHost::default();
+        resize(&mut vm, &mut host, i32::MAX);
+        let get_size = |vm: &Vm| {
+            vm.object_rep_list(ELM_STAGE_OBJECT, Some(0), 3, ELM_OBJECT_X_REP)
+                .len()
+        };
+        assert_eq!(get_size(&vm), Vm::OBJECT_REP_LIST_MAX);
+
+        resize(&mut vm, &mut host, 2);
+        vm.object_rep_list_set(
+            ELM_STAGE_OBJECT,
+            Some(0),
+            3,
+            ELM_OBJECT_X_REP,
+            1,
+            40,
+            &mut host,
+        );
+        assert_eq!(host.last, Some((3, vec![0, 40])));
+
+        let saved = vm.snapshot_local_state(0);
+        resize(&mut vm, &mut host, 0);
+        assert_eq!(get_size(&vm), 0);
+
+        vm.apply_local_state(&saved, 0);
+        host.last = None;
+        vm.notify_object_rep_lists(&mut host);
+        assert_eq!(host.last, Some((3, vec![0, 40])));
+    }
+
+    /// Host whose frame counters advance by the VM clock between update turns.
+    struct FrameClockHost {
+        clock: Arc<VirtualClock>,
+        delta: ClockFrameDelta,
+    }
+
+    impl Host for FrameClockHost {
+        fn clock(&self) -> SharedVmClock {
+            self.clock.clone()
+        }
+
+        fn on_frame_counter_elapsed(&mut self) -> (i32, i32) {
+            self.delta.next(&*self.clock)
+        }
+    }
+
+    #[test]
+    fn frame_counters_advance_by_the_clock_delta() {
+        use crate::elm::objectlist::ELM_OBJECT_FRAME_ACTION;
+        let mut vm = test_vm();
+        let mut host = FrameClockHost {
+            clock: VirtualClock::shared(5000),
+            delta: ClockFrameDelta::default(),
+        };
+        let slot = Vm::frame_action_counter_slot(ELM_STAGE_OBJECT, 0, Some(0), None);
+        vm.ensure_counter_slot(slot);
+        vm.frame_counter_bind_object_context(
+            slot,
+            ELM_STAGE_OBJECT,
+            0,
+            ELM_OBJECT_FRAME_ACTION,
+            Some(0),
+            None,
+        );
+        vm.frame_counter_set_mode(slot, true, false, false, 0, 100, 1000);
+        vm.counter_active[slot] = true;
+
+        // The first turn only primes the delta.
+        vm.frame_action_counter_tick_all(&mut host);
+        assert_eq!(vm.counter_values[slot], 0);
+
+        host.clock.advance_ms(250);
+        vm.frame_action_counter_tick_all(&mut host);
+        assert_eq!(vm.counter_values[slot], 25);
+
+        host.clock.advance_ms(2000);
+        vm.frame_action_counter_tick_all(&mut host);
+        assert_eq!(vm.counter_values[slot], 100);
+        assert!(!vm.counter_active[slot]);
+    }
+}
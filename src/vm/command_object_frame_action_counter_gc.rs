@@ -12,7 +12,7 @@ impl Vm {
         obj_idx: i32,
         stage_idx: Option<i32>,
     ) -> usize {
-        let stage = stage_idx.unwrap_or(-1).max(-1) as usize + 1;
+        let stage = (stage_idx.unwrap_or(-1).max(-1) + 1) as usize;
         let list = list_id.max(0) as usize;
         let obj = obj_idx.max(0) as usize;
         Self::FRAME_COUNTER_OBJECT_EPOCH_BASE + stage * 200_000 + list * 20_000 + obj
@@ -303,10 +303,11 @@ impl Vm {
         sub: i32,
         stage_idx: Option<i32>,
         ch_idx: Option<i32>,
+        elapsed: (i32, i32),
     ) {
         let (past_game_time, past_real_time) = host
             .on_object_frame_action_counter_elapsed(list_id, obj_idx, sub, stage_idx, ch_idx)
-            .unwrap_or_else(|| host.on_frame_counter_elapsed());
+            .unwrap_or(elapsed);
         let before = self.counter_values.get(slot).copied().unwrap_or(0);
         let real = self
             .counter_values
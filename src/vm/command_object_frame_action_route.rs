@@ -42,10 +42,10 @@ impl Vm {
         stage_idx: Option<i32>,
         ch_idx: Option<i32>,
     ) -> usize {
-        let stage = stage_idx.unwrap_or(-1).max(-1) as usize + 1;
+        let stage = (stage_idx.unwrap_or(-1).max(-1) + 1) as usize;
         let list = list_id.max(0) as usize;
         let obj = obj_idx.max(0) as usize;
-        let ch = ch_idx.unwrap_or(-1).max(-1) as usize + 1;
+        let ch = (ch_idx.unwrap_or(-1).max(-1) + 1) as usize;
         1_000_000 + stage * 200_000 + list * 20_000 + obj * 64 + ch
     }
     fn frame_counter_meta_slot(slot: usize, kind: usize) -> usize {
@@ -93,6 +93,8 @@ impl Vm {
             return;
         }
         let max_slot = meta_slots / Self::FRAME_COUNTER_META_SPAN;
+        // One update turn: every counter advances by the same delta.
+        let elapsed = host.on_frame_counter_elapsed();
         for slot in 0..max_slot {
             if !self.counter_active.get(slot).copied().unwrap_or(false) {
                 continue;
@@ -119,7 +121,7 @@ impl Vm {
                     }
                 }
                 self.frame_counter_tick_by_mode(
-                    slot, host, list_id, obj_idx, sub, stage_idx, ch_idx,
+                    slot, host, list_id, obj_idx, sub, stage_idx, ch_idx, elapsed,
                 );
             }
         }
@@ -512,9 +514,19 @@ impl Vm {
                 }
                 let should_invalidate = if method == ELM_OBJECT_LOAD_GAN {
                     let gan_path = Self::object_arg_str(args, 0);
+                    host.on_object_gan_load(list_id, obj_idx, stage_idx, &gan_path);
                     self.object_gan_track_load_changed(list_id, obj_idx, stage_idx, &gan_path)
                 } else {
                     let set_no = Self::arg_int(args, 0);
+                    let key = Self::object_scope_key(list_id, obj_idx, stage_idx);
+                    if self.object_gan_loaded_path.contains_key(&key) {
+                        let playback = crate::resource::GanPlayback {
+                            set_no: set_no.max(0) as usize,
+                            loop_flag: Self::arg_int(args, 1) != 0,
+                            start_ms: host.clock().now_ms(),
+                        };
+                        host.on_object_gan_start(list_id, obj_idx, stage_idx, &playback);
+                    }
                     self.object_gan_track_start_changed(list_id, obj_idx, stage_idx, set_no)
                 };
                 host.on_object_action(list_id, obj_idx, sub, args, stage_idx);
@@ -0,0 +1,272 @@
+impl Vm {
+    const ERR_OBJECT_REP_OOR: &'static str = "範囲外のインデックスが指定されました。(int_list)";
+    /// Longest rep list `resize` may ask for; each entry is one more draw.
+    const OBJECT_REP_LIST_MAX: usize = 1024;
+
+    /// `x_rep` / `y_rep` / `z_rep` / `tr_rep`: int lists kept per object in
+    /// the saved VM state and mirrored to the host for drawing.
+    pub(super) fn object_sub_is_rep_list(sub: i32) -> bool {
+        use crate::elm::objectlist::*;
+        matches!(
+            sub,
+            ELM_OBJECT_X_REP | ELM_OBJECT_Y_REP | ELM_OBJECT_Z_REP | ELM_OBJECT_TR_REP
+        )
+    }
+
+    fn object_rep_list(
+        &self,
+        list_id: i32,
+        stage_idx: Option<i32>,
+        obj_idx: i32,
+        sub: i32,
+    ) -> &[i32] {
+        let (stage, list_id, obj_idx) = Self::object_scope_key(list_id, obj_idx, stage_idx);
+        self.object_rep_lists
+            .get(&(stage, list_id, obj_idx, sub))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    fn object_rep_list_store(
+        &mut self,
+        list_id: i32,
+        stage_idx: Option<i32>,
+        obj_idx: i32,
+        sub: i32,
+        list: Vec<i32>,
+        host: &mut dyn Host,
+    ) {
+        host.on_object_int_list_set(list_id, obj_idx, sub, &list, stage_idx);
+        let (stage, list_id, obj_idx) = Self::object_scope_key(list_id, obj_idx, stage_idx);
+        if list.is_empty() {
+            self.object_rep_lists
+                .remove(&(stage, list_id, obj_idx, sub));
+        } else {
+            self.object_rep_lists
+                .insert((stage, list_id, obj_idx, sub), list);
+        }
+    }
+
+    /// Drop an object's rep lists when it is freed or re-initialised; the
+    /// host resets its copy along with the rest of the object.
+    pub(super) fn object_rep_lists_clear(
+        &mut self,
+        list_id: i32,
+        obj_idx: i32,
+        stage_idx: Option<i32>,
+    ) {
+        let (stage, list_id, obj_idx) = Self::object_scope_key(list_id, obj_idx, stage_idx);
+        self.object_rep_lists
+            .retain(|&(s, l, o, _), _| (s, l, o) != (stage, list_id, obj_idx));
+    }
+
+    /// `create_copy_from`: the object takes the source's rep lists, as the
+    /// host copies the rest of its state. Only stage objects are resolved.
+    pub(super) fn object_rep_lists_copy_from(
+        &mut self,
+        list_id: i32,
+        obj_idx: i32,
+        stage_idx: Option<i32>,
+        args: &[Prop],
+    ) {
+        self.object_rep_lists_clear(list_id, obj_idx, stage_idx);
+        let Some(PropValue::Element(src)) = args.first().map(|p| &p.value) else {
+            return;
+        };
+        let Some((src_stage, src_obj)) = Self::stage_object_of_element(src) else {
+            return;
+        };
+        let (stage, list_id, obj_idx) = Self::object_scope_key(list_id, obj_idx, stage_idx);
+        let copied: Vec<_> = self
+            .object_rep_lists
+            .range(
+                (
+                    src_stage,
+                    crate::elm::objectlist::ELM_STAGE_OBJECT,
+                    src_obj,
+                    i32::MIN,
+                )..,
+            )
+            .take_while(|&(&(s, l, o, _), _)| {
+                (s, l, o) == (src_stage, crate::elm::objectlist::ELM_STAGE_OBJECT, src_obj)
+            })
+            .map(|(&(_, _, _, sub), list)| ((stage, list_id, obj_idx, sub), list.clone()))
+            .collect();
+        self.object_rep_lists.extend(copied);
+    }
+
+    /// `(stage, object)` of a `stage[s].object[i]` / `back.object[i]` element.
+    fn stage_object_of_element(element: &[i32]) -> Option<(i32, i32)> {
+        use crate::elm::{ELM_ARRAY, global::*};
+        let (stage, tail) = match element {
+            [ELM_GLOBAL_STAGE, ELM_ARRAY, stage, tail @ ..] => (*stage, tail),
+            [x, tail @ ..] if *x == ELM_GLOBAL_BACK => (0, tail),
+            [x, tail @ ..] if *x == ELM_GLOBAL_FRONT => (1, tail),
+            [x, tail @ ..] if *x == ELM_GLOBAL_NEXT => (2, tail),
+            _ => return None,
+        };
+        match tail {
+            [crate::elm::objectlist::ELM_STAGE_OBJECT, ELM_ARRAY, obj, ..] => Some((stage, *obj)),
+            _ => None,
+        }
+    }
+
+    /// Send every stored rep list to the host after a load.
+    pub(super) fn notify_object_rep_lists(&self, host: &mut dyn Host) {
+        for (&(stage, list_id, obj_idx, sub), list) in &self.object_rep_lists {
+            let stage_idx = (stage >= 0).then_some(stage);
+            host.on_object_int_list_set(list_id, obj_idx, sub, list, stage_idx);
+        }
+    }
+
+    fn object_rep_index_in_range(&self, list: &[i32], idx: i32, host: &mut dyn Host) -> bool {
+        let ok = usize::try_from(idx).is_ok_and(|i| i < list.len());
+        if !ok && self.options.disp_out_of_range_error {
+            host.on_error_fatal(Self::ERR_OBJECT_REP_OOR);
+        }
+        ok
+    }
+
+    /// `object.x_rep[idx]` read.
+    pub(super) fn object_rep_list_get(
+        &self,
+        list_id: i32,
+        stage_idx: Option<i32>,
+        obj_idx: i32,
+        sub: i32,
+        idx: i32,
+        host: &mut dyn Host,
+    ) -> i32 {
+        let list = self.object_rep_list(list_id, stage_idx, obj_idx, sub);
+        if !self.object_rep_index_in_range(list, idx, host) {
+            return 0;
+        }
+        list[idx as usize]
+    }
+
+    /// `object.x_rep[idx] = value`.
+    #[allow(clippy::too_many_arguments)]
+    pub(super) fn object_rep_list_set(
+        &mut self,
+        list_id: i32,
+        stage_idx: Option<i32>,
+        obj_idx: i32,
+        sub: i32,
+        idx: i32,
+        value: i32,
+        host: &mut dyn Host,
+    ) {
+        let mut list = self
+            .object_rep_list(list_id, stage_idx, obj_idx, sub)
+            .to_vec();
+        if !self.object_rep_index_in_range(&list, idx, host) {
+            return;
+        }
+        list[idx as usize] = value;
+        self.object_rep_list_store(list_id, stage_idx, obj_idx, sub, list, host);
+    }
+
+    /// Property lane: `x_rep[idx]` and `x_rep.get_size()`. `tail` starts
+    /// AFTER the `*_REP` root.
+    pub(super) fn object_rep_list_property(
+        &self,
+        list_id: i32,
+        stage_idx: Option<i32>,
+        obj_idx: i32,
+        sub: i32,
+        tail: &[i32],
+        host: &mut dyn Host,
+    ) -> i32 {
+        match tail {
+            [crate::elm::ELM_ARRAY, idx] => {
+                self.object_rep_list_get(list_id, stage_idx, obj_idx, sub, *idx, host)
+            }
+            [crate::elm::list::ELM_INTLIST_GET_SIZE] => {
+                self.object_rep_list(list_id, stage_idx, obj_idx, sub).len() as i32
+            }
+            _ => {
+                host.on_error_fatal("CD_PROPERTY stage.object.*_rep: invalid composite target");
+                0
+            }
+        }
+    }
+
+    /// C++ `tnm_command_proc_int_list` over an object's rep list.
+    ///
+    /// `element` starts AFTER the `*_REP` root.
+    #[allow(clippy::too_many_arguments)]
+    fn try_command_object_rep_list(
+        &mut self,
+        list_id: i32,
+        stage_idx: Option<i32>,
+        obj_idx: i32,
+        sub: i32,
+        element: &[i32],
+        arg_list_id: i32,
+        args: &[Prop],
+        host: &mut dyn Host,
+    ) -> bool {
+        use crate::elm::list::*;
+
+        let Some(&method) = element.first() else {
+            host.on_error_fatal("無効なコマンドが指定されました。(int_list)");
+            return true;
+        };
+        if method == crate::elm::ELM_ARRAY {
+            let idx = element.get(1).copied().unwrap_or(0);
+            if arg_list_id == 0 {
+                let v = self.object_rep_list_get(list_id, stage_idx, obj_idx, sub, idx, host);
+                self.stack.push_int(v);
+            } else {
+                let v = Self::int_arg(args, 0);
+                self.object_rep_list_set(list_id, stage_idx, obj_idx, sub, idx, v, host);
+            }
+            return true;
+        }
+
+        let mut list = self
+            .object_rep_list(list_id, stage_idx, obj_idx, sub)
+            .to_vec();
+        match method {
+            ELM_INTLIST_GET_SIZE => {
+                self.stack.push_int(list.len() as i32);
+                return true;
+            }
+            ELM_INTLIST_RESIZE => {
+                let size = Self::int_arg(args, 0).max(0) as usize;
+                list.resize(size.min(Self::OBJECT_REP_LIST_MAX), 0);
+            }
+            ELM_INTLIST_INIT => {
+                list.clear();
+            }
+            ELM_INTLIST_CLEAR => {
+                let start = Self::int_arg(args, 0);
+                let end = Self::int_arg(args, 1);
+                let fill = Self::int_arg(args, 2);
+                for idx in start..=end {
+                    if !self.object_rep_index_in_range(&list, idx, host) {
+                        break;
+                    }
+                    list[idx as usize] = fill;
+                }
+            }
+            ELM_INTLIST_SETS => {
+                let start = Self::int_arg(args, 0);
+                for (i, arg) in args.iter().enumerate().skip(1) {
+                    let idx = start + i as i32 - 1;
+                    if !self.object_rep_index_in_range(&list, idx, host) {
+                        break;
+                    }
+                    if let PropValue::Int(v) = arg.value {
+                        list[idx as usize] = v;
+                    }
+                }
+            }
+            _ => {
+                host.on_error_fatal("無効なコマンドが指定されました。(int_list)");
+                return true;
+            }
+        }
+        self.object_rep_list_store(list_id, stage_idx, obj_idx, sub, list, host);
+        true
+    }
+}
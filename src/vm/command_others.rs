@@ -2,8 +2,16 @@
 // Aligns with C++ cmd_others.cpp
 use super::*;
 impl Vm {
-    fn mask_event_owner_id(mask_index: usize, sub: i32) -> i32 {
-        ((mask_index as i32) << 8) | (sub & 0xFF)
+    /// `mask[no]` as last set by script, if the slot was ever touched.
+    pub fn mask_slot(&self, no: usize) -> Option<&MaskSlotState> {
+        self.mask_slots.get(no)
+    }
+
+    /// Re-announce every mask slot (after a load replaced them).
+    pub(super) fn notify_mask_slots(&self, host: &mut dyn Host) {
+        for (idx, slot) in self.mask_slots.iter().enumerate() {
+            host.on_mask_slot(idx, slot);
+        }
     }
 
     fn trace_excall_counter_command(&self, counter_idx: usize, action: &str, value: i32) {
@@ -588,32 +596,42 @@ impl Vm {
                 match sub {
                     ELM_MASK_INIT => {
                         self.mask_slots[idx] = MaskSlotState::default();
+                        host.on_mask_slot(idx, &self.mask_slots[idx]);
                     }
                     ELM_MASK_CREATE => {
                         self.mask_slots[idx].name = Self::arg_str_others(args, 0);
+                        host.on_mask_slot(idx, &self.mask_slots[idx]);
                     }
-                    ELM_MASK_X => {
-                        if args.is_empty() {
-                            self.stack.push_int(self.mask_slots[idx].x);
+                    ELM_MASK_X | ELM_MASK_Y => {
+                        let now = host.clock().now_ms();
+                        let slot = &mut self.mask_slots[idx];
+                        let ev = if sub == ELM_MASK_X {
+                            &mut slot.x
                         } else {
-                            self.mask_slots[idx].x = Self::arg_int(args, 0);
-                        }
-                    }
-                    ELM_MASK_Y => {
+                            &mut slot.y
+                        };
                         if args.is_empty() {
-                            self.stack.push_int(self.mask_slots[idx].y);
+                            let v = ev.value_at(now);
+                            self.stack.push_int(v);
                         } else {
-                            self.mask_slots[idx].y = Self::arg_int(args, 0);
+                            ev.set_value(Self::arg_int(args, 0));
+                            host.on_mask_slot(idx, &self.mask_slots[idx]);
                         }
                     }
                     ELM_MASK_X_EVE | ELM_MASK_Y_EVE => {
-                        let owner = Self::mask_event_owner_id(idx, sub);
                         let rest = if element.len() > 3 {
                             &element[3..]
                         } else {
                             &[]
                         };
-                        self.try_command_int_event(rest, _arg_list_id, args, ret_form, host, owner);
+                        self.try_command_int_event(
+                            rest,
+                            _arg_list_id,
+                            args,
+                            ret_form,
+                            host,
+                            IntEventTarget::new(IntEventScope::Mask { mask_idx: idx }, sub),
+                        );
                     }
                     _ => {
                         host.on_error_fatal("無効なコマンドが指定されました。(mask)");
@@ -692,3 +710,22 @@ impl Vm {
         true
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mask_eve_tweens_from_the_assigned_offset() {
+        let mut slot = MaskSlotState::default();
+        slot.x.set_value(100);
+        slot.int_event_mut(crate::elm::mask::ELM_MASK_X_EVE)
+            .unwrap()
+            .set_event(200, 1000, 0, crate::vm::INT_EVENT_SPEED_LINEAR, 0);
+        assert_eq!(slot.pos_at(0), (100, 0));
+        assert_eq!(slot.pos_at(500), (150, 0));
+        assert!(slot.is_active(500));
+        assert_eq!(slot.pos_at(1000), (200, 0));
+        assert!(!slot.is_active(1000));
+    }
+}
@@ -116,6 +116,7 @@ impl Vm {
             // ----- skip unread message -----
             ELM_SCRIPT_SET_SKIP_UNREAD_MESSAGE_FLAG => {
                 self.script_skip_unread_message_flag = if arg_int(0) != 0 { 1 } else { 0 };
+                self.notify_message_speed(host);
             }
             ELM_SCRIPT_GET_SKIP_UNREAD_MESSAGE_FLAG => {
                 self.stack.push_int(self.script_skip_unread_message_flag);
@@ -125,15 +126,19 @@ impl Vm {
             // ----- auto mode -----
             ELM_SCRIPT_START_AUTO_MODE => {
                 self.script_auto_mode_flag = true;
+                self.notify_message_speed(host);
             }
             ELM_SCRIPT_END_AUTO_MODE => {
                 self.script_auto_mode_flag = false;
+                self.notify_message_speed(host);
             }
             ELM_SCRIPT_SET_AUTO_MODE_MOJI_WAIT => {
                 self.script_auto_mode_moji_wait = arg_int(0);
+                self.notify_message_speed(host);
             }
             ELM_SCRIPT_SET_AUTO_MODE_MOJI_WAIT_DEFAULT => {
                 self.script_auto_mode_moji_wait = -1;
+                self.notify_message_speed(host);
             }
             ELM_SCRIPT_GET_AUTO_MODE_MOJI_WAIT => {
                 self.stack.push_int(self.script_auto_mode_moji_wait);
@@ -141,9 +146,11 @@ impl Vm {
             }
             ELM_SCRIPT_SET_AUTO_MODE_MIN_WAIT => {
                 self.script_auto_mode_min_wait = arg_int(0);
+                self.notify_message_speed(host);
             }
             ELM_SCRIPT_SET_AUTO_MODE_MIN_WAIT_DEFAULT => {
                 self.script_auto_mode_min_wait = -1;
+                self.notify_message_speed(host);
             }
             ELM_SCRIPT_GET_AUTO_MODE_MIN_WAIT => {
                 self.stack.push_int(self.script_auto_mode_min_wait);
@@ -151,6 +158,7 @@ impl Vm {
             }
             ELM_SCRIPT_SET_AUTO_MODE_MOJI_CNT => {
                 self.script_auto_mode_moji_cnt = arg_int(0);
+                self.notify_message_speed(host);
             }
 
             // ----- mouse cursor hide -----
@@ -178,9 +186,11 @@ impl Vm {
             // ----- message speed / nowait -----
             ELM_SCRIPT_SET_MESSAGE_SPEED => {
                 self.script_msg_speed = arg_int(0);
+                self.notify_message_speed(host);
             }
             ELM_SCRIPT_SET_MESSAGE_SPEED_DEFAULT => {
                 self.script_msg_speed = -1;
+                self.notify_message_speed(host);
             }
             ELM_SCRIPT_GET_MESSAGE_SPEED => {
                 self.stack.push_int(self.script_msg_speed);
@@ -188,6 +198,7 @@ impl Vm {
             }
             ELM_SCRIPT_SET_MESSAGE_NOWAIT_FLAG => {
                 self.script_msg_nowait = arg_int(0) != 0;
+                self.notify_message_speed(host);
             }
             ELM_SCRIPT_GET_MESSAGE_NOWAIT_FLAG => {
                 self.stack
@@ -453,9 +464,11 @@ impl Vm {
                 let name = arg_str(0);
                 Self::script_maybe_report_font_resource(host, &name, "script.set_font_name");
                 self.script_font_name = name;
+                self.notify_font_style(host);
             }
             ELM_SCRIPT_SET_FONT_NAME_DEFAULT => {
                 self.script_font_name.clear();
+                self.notify_font_style(host);
             }
             ELM_SCRIPT_GET_FONT_NAME => {
                 self.stack.push_str(self.script_font_name.clone());
@@ -463,9 +476,11 @@ impl Vm {
             }
             ELM_SCRIPT_SET_FONT_BOLD => {
                 self.script_font_bold = arg_int(0);
+                self.notify_font_style(host);
             }
             ELM_SCRIPT_SET_FONT_BOLD_DEFAULT => {
                 self.script_font_bold = -1;
+                self.notify_font_style(host);
             }
             ELM_SCRIPT_GET_FONT_BOLD => {
                 self.stack.push_int(self.script_font_bold);
@@ -473,9 +488,11 @@ impl Vm {
             }
             ELM_SCRIPT_SET_FONT_SHADOW => {
                 self.script_font_shadow = arg_int(0);
+                self.notify_font_style(host);
             }
             ELM_SCRIPT_SET_FONT_SHADOW_DEFAULT => {
                 self.script_font_shadow = -1;
+                self.notify_font_style(host);
             }
             ELM_SCRIPT_GET_FONT_SHADOW => {
                 self.stack.push_int(self.script_font_shadow);
@@ -0,0 +1,63 @@
+/// `sel_start_call_scn` / `sel_start_call_z_no` of the SELBTN family.
+///
+/// C++ reference: cmd_global.cpp (SELBTN / SELBTN_READY / SELBTN_START),
+/// elm_sel_btn.cpp `C_elm_sel_btn::start` (sel_start_call farcall)
+///
+/// The selection itself is host-owned; the VM only runs the scene the script
+/// asked to call once the host has shown the buttons.
+use super::*;
+
+impl Vm {
+    /// Cache the start call of `selbtn_ready` and farcall it (or the one of
+    /// `selbtn` itself) when a selection starts.
+    pub(super) fn selbtn_start_call(
+        &mut self,
+        elm: i32,
+        args: &[Prop],
+        provider: &mut dyn SceneProvider,
+        host: &mut dyn Host,
+    ) -> Result<()> {
+        use crate::elm::global::*;
+
+        let call = match elm {
+            ELM_GLOBAL_SELBTN_READY | ELM_GLOBAL_SELBTN_CANCEL_READY => {
+                self.selbtn_start_call = Self::selbtn_start_call_arg(args);
+                return Ok(());
+            }
+            ELM_GLOBAL_SELBTN | ELM_GLOBAL_SELBTN_CANCEL => Self::selbtn_start_call_arg(args),
+            ELM_GLOBAL_SELBTN_START => self.selbtn_start_call.take(),
+            _ => return Ok(()),
+        };
+        let Some((scene, z_no)) = call else {
+            return Ok(());
+        };
+        host.on_selbtn_start_call(elm, args, &scene, z_no);
+
+        // Same shape as the frame-action farcall (run until the called scene
+        // returns, then let the command go on to the host), but with a call
+        // type of its own so the frame-action argument rules stay out of it.
+        self.proc_farcall_like(&scene, z_no, crate::elm::form::VOID, &[], false, provider)?;
+        if let Some(f) = self.frames.last_mut() {
+            f.call_type = VmCallType::SelBtnStart;
+        }
+        self.push_script_proc();
+        self.run_inner(host, provider)?;
+        Ok(())
+    }
+
+    /// Named args 2 (`sel_start_call_scn`) and 3 (`sel_start_call_z_no`).
+    fn selbtn_start_call_arg(args: &[Prop]) -> Option<(String, i32)> {
+        let mut scene = None;
+        let mut z_no = -1;
+        for p in args {
+            match (p.id, &p.value) {
+                (2, PropValue::Str(v)) => scene = Some(v.clone()),
+                (3, PropValue::Int(v)) => z_no = *v,
+                _ => {}
+            }
+        }
+        scene
+            .filter(|s| !s.is_empty() && z_no >= 0)
+            .map(|s| (s, z_no))
+    }
+}
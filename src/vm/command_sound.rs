@@ -395,6 +395,35 @@ impl Vm {
         }
     }
 
+    /// Start voice `koe(koe_no, chara_no)`; `wait` blocks until it ends,
+    /// `Some(true)` letting a key press stop it.
+    pub(super) fn koe_play(&mut self, args: &[Prop], wait: Option<bool>, host: &mut dyn Host) {
+        let int = |i: usize| match args.get(i).map(|p| &p.value) {
+            Some(PropValue::Int(v)) => *v,
+            _ => -1,
+        };
+        self.koe_no = int(0);
+        self.koe_chara_no = int(1);
+        host.on_koe_play(self.koe_no, self.koe_chara_no);
+        if let Some(key) = wait {
+            self.koe_wait(key, host);
+        }
+    }
+
+    /// C++ cmd_global.cpp: KOE_WAIT / KOE_WAIT_KEY.
+    pub(super) fn koe_wait(&mut self, key_skip: bool, host: &mut dyn Host) {
+        while host.koe_playing() {
+            if host.should_interrupt() {
+                return;
+            }
+            if key_skip && host.should_skip_wait() {
+                host.on_koe_stop();
+                return;
+            }
+            host.on_wait_frame();
+        }
+    }
+
     pub(super) fn try_command_sound(
         &mut self,
         element: &[i32],
@@ -423,15 +452,30 @@ impl Vm {
             ELM_GLOBAL_PCMEVENT => {
                 self.try_command_pcmevent(&element[1..], arg_list_id, args, ret_form, host)
             }
-            // KOE root element — pass through to host for now (bare element ref).
-            ELM_GLOBAL_KOE | ELM_GLOBAL_KOE_ST | ELM_GLOBAL_EXKOE => true,
-            // KOE play/wait — accept as no-op (audio not implemented).
-            ELM_GLOBAL_KOE_PLAY_WAIT
-            | ELM_GLOBAL_KOE_PLAY_WAIT_KEY
-            | ELM_GLOBAL_EXKOE_PLAY_WAIT
-            | ELM_GLOBAL_EXKOE_PLAY_WAIT_KEY => true,
-            // KOE stop/wait — accept as no-op.
-            ELM_GLOBAL_KOE_STOP | ELM_GLOBAL_KOE_WAIT | ELM_GLOBAL_KOE_WAIT_KEY => true,
+            ELM_GLOBAL_KOE | ELM_GLOBAL_KOE_ST | ELM_GLOBAL_EXKOE => {
+                self.koe_play(args, None, host);
+                true
+            }
+            ELM_GLOBAL_KOE_PLAY_WAIT | ELM_GLOBAL_EXKOE_PLAY_WAIT => {
+                self.koe_play(args, Some(false), host);
+                true
+            }
+            ELM_GLOBAL_KOE_PLAY_WAIT_KEY | ELM_GLOBAL_EXKOE_PLAY_WAIT_KEY => {
+                self.koe_play(args, Some(true), host);
+                true
+            }
+            ELM_GLOBAL_KOE_STOP => {
+                host.on_koe_stop();
+                true
+            }
+            ELM_GLOBAL_KOE_WAIT => {
+                self.koe_wait(false, host);
+                true
+            }
+            ELM_GLOBAL_KOE_WAIT_KEY => {
+                self.koe_wait(true, host);
+                true
+            }
             // KOE volume is already handled via dedicated koe_get_volume / koe_check arms.
             ELM_GLOBAL_KOE_SET_VOLUME
             | ELM_GLOBAL_KOE_SET_VOLUME_MAX
@@ -161,9 +161,14 @@ impl Vm {
             }
 
             // World list → command_world module.
-            ELM_STAGE_WORLD => {
-                self.try_command_world_list(&element[1..], arg_list_id, args, ret_form, host)
-            }
+            ELM_STAGE_WORLD => self.try_command_world_list(
+                stage_idx,
+                &element[1..],
+                arg_list_id,
+                args,
+                ret_form,
+                host,
+            ),
 
             // Effect list → command_effect module.
             ELM_STAGE_EFFECT => {
@@ -93,6 +93,7 @@ impl Vm {
                 self.game_end_save_done_flag = 0;
                 self.syscom_cfg = VmSyscomConfigState::default();
                 self.apply_syscom_option_defaults();
+                self.notify_message_speed(host);
                 if ret_form == crate::elm::form::INT {
                     self.stack.push_int(0);
                 }
@@ -107,6 +108,7 @@ impl Vm {
                             && self.msg_back_disable_flag == 0
                         {
                             self.read_skip_onoff_flag = 0;
+                            self.notify_message_speed(host);
                             1
                         } else {
                             0
@@ -193,6 +195,7 @@ impl Vm {
                     }
                     _ => {}
                 }
+                self.notify_message_speed(host);
                 if ret_form == crate::elm::form::INT {
                     self.stack.push_int(0);
                 }
@@ -208,8 +211,8 @@ impl Vm {
                 let ok = match x {
                     y if y == crate::elm::syscom::ELM_SYSCOM_SAVE => {
                         if let Some(slot_no) = slot_no {
-                            self.local_save_slots
-                                .insert(slot_no, self.make_local_slot());
+                            let slot = self.make_local_slot(host, true);
+                            self.local_save_slots.insert(slot_no, slot);
                             true
                         } else {
                             false
@@ -217,8 +220,8 @@ impl Vm {
                     }
                     y if y == crate::elm::syscom::ELM_SYSCOM_QUICK_SAVE => {
                         if let Some(slot_no) = slot_no {
-                            self.quick_save_slots
-                                .insert(slot_no, self.make_local_slot());
+                            let slot = self.make_local_slot(host, true);
+                            self.quick_save_slots.insert(slot_no, slot);
                             true
                         } else {
                             false
@@ -226,8 +229,8 @@ impl Vm {
                     }
                     y if y == crate::elm::syscom::ELM_SYSCOM_INNER_SAVE => {
                         if let Some(slot_no) = slot_no {
-                            self.inner_save_slots
-                                .insert(slot_no, self.make_local_slot());
+                            let slot = self.make_local_slot(host, false);
+                            self.inner_save_slots.insert(slot_no, slot);
                             true
                         } else {
                             false
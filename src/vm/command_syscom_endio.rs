@@ -8,8 +8,9 @@ impl Vm {
             return false;
         }
         // C++ reference: eng_syscom.cpp::tnm_syscom_end_save(save_cnt + quick_save_cnt).
-        self.end_save_slots.insert(0, self.make_local_slot());
-        let end_save_state = self.snapshot_end_save_state();
+        let slot = self.make_local_slot(host, false);
+        self.end_save_slots.insert(0, slot);
+        let end_save_state = self.snapshot_end_save_state(host.clock().now_ms());
         host.on_syscom_end_save_snapshot(0, &end_save_state);
         self.game_end_save_done_flag = 1;
         if se_play {
@@ -10,6 +10,13 @@ impl Vm {
         host: &mut dyn Host,
     ) -> Result<Option<bool>> {
         if let Some(handled) = self.try_command_syscom_misc_lowfreq(x, args, ret_form) {
+            if matches!(
+                x,
+                crate::elm::syscom::ELM_SYSCOM_SET_SKIP_UNREAD_MESSAGE_ONOFF
+                    | crate::elm::syscom::ELM_SYSCOM_SET_SKIP_UNREAD_MESSAGE_ONOFF_DEFAULT
+            ) {
+                self.notify_message_speed(host);
+            }
             return Ok(Some(handled));
         }
         match x {
@@ -156,6 +163,7 @@ impl Vm {
                     Some(PropValue::Str(v)) => v.clone(),
                     _ => String::new(),
                 };
+                self.notify_font_style(host);
                 if ret_form == crate::elm::form::INT {
                     self.stack.push_int(0);
                 }
@@ -163,6 +171,7 @@ impl Vm {
             }
             y if y == crate::elm::syscom::ELM_SYSCOM_SET_FONT_NAME_DEFAULT => {
                 self.syscom_cfg.font_name.clear();
+                self.notify_font_style(host);
                 if ret_form == crate::elm::form::INT {
                     self.stack.push_int(0);
                 }
@@ -191,6 +200,7 @@ impl Vm {
             }
             y if y == crate::elm::syscom::ELM_SYSCOM_SET_FONT_BOLD => {
                 self.syscom_cfg.font_bold = Self::arg_int(args, 0);
+                self.notify_font_style(host);
                 if ret_form == crate::elm::form::INT {
                     self.stack.push_int(0);
                 }
@@ -198,6 +208,7 @@ impl Vm {
             }
             y if y == crate::elm::syscom::ELM_SYSCOM_SET_FONT_BOLD_DEFAULT => {
                 self.syscom_cfg.font_bold = -1;
+                self.notify_font_style(host);
                 if ret_form == crate::elm::form::INT {
                     self.stack.push_int(0);
                 }
@@ -211,6 +222,7 @@ impl Vm {
             }
             y if y == crate::elm::syscom::ELM_SYSCOM_SET_FONT_DECORATION => {
                 self.syscom_cfg.font_decoration = Self::arg_int(args, 0);
+                self.notify_font_style(host);
                 if ret_form == crate::elm::form::INT {
                     self.stack.push_int(0);
                 }
@@ -218,6 +230,7 @@ impl Vm {
             }
             y if y == crate::elm::syscom::ELM_SYSCOM_SET_FONT_DECORATION_DEFAULT => {
                 self.syscom_cfg.font_decoration = -1;
+                self.notify_font_style(host);
                 if ret_form == crate::elm::form::INT {
                     self.stack.push_int(0);
                 }
@@ -397,6 +410,7 @@ impl Vm {
             }
             y if y == crate::elm::syscom::ELM_SYSCOM_SET_MESSAGE_SPEED => {
                 self.syscom_cfg.message_speed = Self::arg_int(args, 0);
+                self.notify_message_speed(host);
                 if ret_form == crate::elm::form::INT {
                     self.stack.push_int(0);
                 }
@@ -404,6 +418,7 @@ impl Vm {
             }
             y if y == crate::elm::syscom::ELM_SYSCOM_SET_MESSAGE_SPEED_DEFAULT => {
                 self.syscom_cfg.message_speed = -1;
+                self.notify_message_speed(host);
                 if ret_form == crate::elm::form::INT {
                     self.stack.push_int(0);
                 }
@@ -417,6 +432,7 @@ impl Vm {
             }
             y if y == crate::elm::syscom::ELM_SYSCOM_SET_MESSAGE_NOWAIT => {
                 self.syscom_cfg.message_nowait = if Self::arg_int(args, 0) != 0 { 1 } else { 0 };
+                self.notify_message_speed(host);
                 if ret_form == crate::elm::form::INT {
                     self.stack.push_int(0);
                 }
@@ -430,6 +446,7 @@ impl Vm {
             }
             y if y == crate::elm::syscom::ELM_SYSCOM_SET_AUTO_MODE_MOJI_WAIT => {
                 self.syscom_cfg.auto_mode_moji_wait = Self::arg_int(args, 0);
+                self.notify_message_speed(host);
                 if ret_form == crate::elm::form::INT {
                     self.stack.push_int(0);
                 }
@@ -437,6 +454,7 @@ impl Vm {
             }
             y if y == crate::elm::syscom::ELM_SYSCOM_SET_AUTO_MODE_MOJI_WAIT_DEFAULT => {
                 self.syscom_cfg.auto_mode_moji_wait = -1;
+                self.notify_message_speed(host);
                 if ret_form == crate::elm::form::INT {
                     self.stack.push_int(0);
                 }
@@ -450,6 +468,7 @@ impl Vm {
             }
             y if y == crate::elm::syscom::ELM_SYSCOM_SET_AUTO_MODE_MIN_WAIT => {
                 self.syscom_cfg.auto_mode_min_wait = Self::arg_int(args, 0);
+                self.notify_message_speed(host);
                 if ret_form == crate::elm::form::INT {
                     self.stack.push_int(0);
                 }
@@ -457,6 +476,7 @@ impl Vm {
             }
             y if y == crate::elm::syscom::ELM_SYSCOM_SET_AUTO_MODE_MIN_WAIT_DEFAULT => {
                 self.syscom_cfg.auto_mode_min_wait = -1;
+                self.notify_message_speed(host);
                 if ret_form == crate::elm::form::INT {
                     self.stack.push_int(0);
                 }
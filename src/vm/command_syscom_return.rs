@@ -153,10 +153,25 @@ impl Vm {
                     // C++ keeps running proc queue after calling tnm_saveload_proc_end_load(),
                     // so this hook is observational and does not abort subsequent procs.
                     let ok = if let Some(slot) = self.end_save_slots.get(&0).cloned() {
-                        self.apply_local_state(&slot.state);
+                        self.apply_local_state(&slot.state, host.clock().now_ms());
+                        self.notify_screen_effects(host);
+                        self.notify_worlds(host);
+                        self.notify_mask_slots(host);
+                        self.notify_int_events(host);
+                        self.notify_font_style(host);
+                        self.notify_cur_mwnd(host);
+                        self.notify_messages(host);
+                        self.notify_object_rep_lists(host);
+                        self.notify_message_speed(host);
                         true
                     } else if let Some(st) = host.on_syscom_end_load_snapshot(0) {
-                        self.apply_end_save_state_with_provider(&st, provider)?
+                        let ok = self.apply_end_save_state_with_provider(
+                            &st,
+                            host.clock().now_ms(),
+                            provider,
+                        )?;
+                        self.notify_int_events(host);
+                        ok
                     } else {
                         false
                     };
@@ -171,7 +186,16 @@ impl Vm {
                     // C++ reference: flow_proc.cpp::tnm_load_proc.
                     let slot_no = proc.option;
                     if let Some(slot) = self.local_save_slots.get(&slot_no).cloned() {
-                        self.apply_local_state(&slot.state);
+                        self.apply_local_state(&slot.state, host.clock().now_ms());
+                        self.notify_screen_effects(host);
+                        self.notify_worlds(host);
+                        self.notify_mask_slots(host);
+                        self.notify_int_events(host);
+                        self.notify_font_style(host);
+                        self.notify_cur_mwnd(host);
+                        self.notify_messages(host);
+                        self.notify_object_rep_lists(host);
+                        self.notify_message_speed(host);
                         self.system_wipe_flag = 1;
                         self.do_frame_action_flag = 1;
                         self.do_load_after_call_flag = 1;
@@ -189,7 +213,16 @@ impl Vm {
                     // C++ reference: flow_proc.cpp::tnm_quick_load_proc.
                     let slot_no = proc.option;
                     if let Some(slot) = self.quick_save_slots.get(&slot_no).cloned() {
-                        self.apply_local_state(&slot.state);
+                        self.apply_local_state(&slot.state, host.clock().now_ms());
+                        self.notify_screen_effects(host);
+                        self.notify_worlds(host);
+                        self.notify_mask_slots(host);
+                        self.notify_int_events(host);
+                        self.notify_font_style(host);
+                        self.notify_cur_mwnd(host);
+                        self.notify_messages(host);
+                        self.notify_object_rep_lists(host);
+                        self.notify_message_speed(host);
                         self.system_wipe_flag = 1;
                         self.do_frame_action_flag = 1;
                         self.do_load_after_call_flag = 1;
@@ -207,7 +240,16 @@ impl Vm {
                     // C++ reference: flow_proc.cpp::tnm_inner_load_proc.
                     let slot_no = proc.option;
                     if let Some(slot) = self.inner_save_slots.get(&slot_no).cloned() {
-                        self.apply_local_state(&slot.state);
+                        self.apply_local_state(&slot.state, host.clock().now_ms());
+                        self.notify_screen_effects(host);
+                        self.notify_worlds(host);
+                        self.notify_mask_slots(host);
+                        self.notify_int_events(host);
+                        self.notify_font_style(host);
+                        self.notify_cur_mwnd(host);
+                        self.notify_messages(host);
+                        self.notify_object_rep_lists(host);
+                        self.notify_message_speed(host);
                         self.system_wipe_flag = 1;
                         self.do_frame_action_flag = 1;
                         self.do_load_after_call_flag = 1;
@@ -255,7 +297,7 @@ impl Vm {
             }
             return Ok(Some(true));
         }
-        let global_state = self.snapshot_persistent_state();
+        let global_state = self.snapshot_global_state();
         host.on_syscom_return_to_menu_save_global(&global_state);
         self.game_timer_move_flag = 0;
         host.on_game_timer_move(false);
@@ -369,7 +411,7 @@ impl Vm {
         self.game_end_no_warning_flag = 1;
         // C++ reference: eng_syscom.cpp::tnm_syscom_end_game -> tnm_syscom_end_save(false, false).
         self.run_end_save_for_end_game(host);
-        let persistent_state = self.snapshot_persistent_state();
+        let persistent_state = self.snapshot_global_state();
         host.on_syscom_end_game_save_flush(&persistent_state);
 
         let mut proc_queue = Vec::new();
@@ -45,7 +45,18 @@ impl Vm {
         }
     }
 
-    pub(super) fn make_local_slot(&self) -> LocalSaveSlot {
+    /// A save slot of the current state; `with_thumb` also captures its
+    /// thumbnail (the end save and inner saves have none).
+    pub(super) fn make_local_slot(
+        &mut self,
+        host: &mut dyn Host,
+        with_thumb: bool,
+    ) -> LocalSaveSlot {
+        let thumb = if with_thumb {
+            self.take_local_save_thumb(host)
+        } else {
+            None
+        };
         let now = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .unwrap_or_default();
@@ -55,7 +66,8 @@ impl Vm {
             stamp,
             scene_title: self.scene_title.clone(),
             message: self.last_sel_msg.clone(),
-            state: self.snapshot_local_state(),
+            state: self.snapshot_local_state(host.clock().now_ms()),
+            thumb,
         }
     }
 
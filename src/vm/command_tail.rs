@@ -1,56 +1,112 @@
 use super::*;
 
 impl Vm {
-    fn parse_wipe_start_params_from_cpp(elm: i32, args: &[Prop]) -> (u64, bool, i32) {
+    fn parse_wipe_request_from_cpp(elm: i32, args: &[Prop]) -> VmWipeRequest {
         // C++ source of truth: cmd_wipe.cpp::tnm_command_proc_wipe
         // defaults + positional args + named args override
+        let is_mask = elm == crate::elm::global::ELM_GLOBAL_MASK_WIPE
+            || elm == crate::elm::global::ELM_GLOBAL_MASK_WIPE_ALL;
+        let is_all = elm == crate::elm::global::ELM_GLOBAL_WIPE_ALL
+            || elm == crate::elm::global::ELM_GLOBAL_MASK_WIPE_ALL;
+        // Positional args carry id -1; named args follow them.
+        let positional = args.iter().take_while(|p| p.id < 0).count();
+        let int_at = |pos: usize| match args[..positional].get(pos).map(|p| &p.value) {
+            Some(PropValue::Int(v)) => Some(*v),
+            _ => None,
+        };
+        let base = if is_mask { 1 } else { 0 };
+
+        let mut req = VmWipeRequest {
+            command: elm,
+            mask_file: None,
+            wipe_type: 0,
+            time_ms: 0,
+            speed_mode: 0,
+            option: Vec::new(),
+            begin_order: if is_all { i32::MIN } else { 0 },
+            end_order: if is_all { i32::MAX } else { 0 },
+            begin_layer: i32::MIN,
+            end_layer: i32::MAX,
+            with_low_order: false,
+            wait_flag: true,
+            key_wait_mode: -1,
+        };
         let mut wipe_time = 500i32;
         let mut start_time = 0i32;
-        let mut wait_flag = true;
-        let mut key_wait_mode = -1i32;
-
-        let time_pos = if elm == crate::elm::global::ELM_GLOBAL_MASK_WIPE
-            || elm == crate::elm::global::ELM_GLOBAL_MASK_WIPE_ALL
-        {
-            2
-        } else {
-            1
-        };
-        if let Some(PropValue::Int(v)) = args.get(time_pos).map(|p| &p.value) {
-            wipe_time = *v;
+
+        if is_mask && let Some(PropValue::Str(name)) = args.first().map(|p| &p.value) {
+            req.mask_file = Some(name.clone());
+        }
+        if let Some(v) = int_at(base) {
+            req.wipe_type = v;
+        }
+        if let Some(v) = int_at(base + 1) {
+            wipe_time = v;
+        }
+        if let Some(v) = int_at(base + 2) {
+            req.speed_mode = v;
+        }
+        for pos in (base + 3)..positional {
+            if let Some(v) = int_at(pos) {
+                req.option.push(v);
+            }
         }
 
         for arg in args {
-            match arg.id {
-                1 => {
-                    if let PropValue::Int(v) = arg.value {
-                        wipe_time = v;
-                    }
-                }
-                8 => {
-                    if let PropValue::Int(v) = arg.value {
-                        wait_flag = v != 0;
-                    }
-                }
-                9 => {
-                    if let PropValue::Int(v) = arg.value {
-                        key_wait_mode = v;
-                    }
-                }
-                11 => {
-                    if let PropValue::Int(v) = arg.value {
-                        start_time = v;
-                    }
-                }
+            let int = match &arg.value {
+                PropValue::Int(v) => Some(*v),
+                _ => None,
+            };
+            match (arg.id, int) {
+                (0, Some(v)) => req.wipe_type = v,
+                (1, Some(v)) => wipe_time = v,
+                (2, Some(v)) => req.speed_mode = v,
+                (3, Some(v)) => req.begin_order = v,
+                (4, Some(v)) => req.end_order = v,
+                (5, Some(v)) => req.begin_layer = v,
+                (6, Some(v)) => req.end_layer = v,
+                (7, Some(v)) => req.with_low_order = v != 0,
+                (8, Some(v)) => req.wait_flag = v != 0,
+                (9, Some(v)) => req.key_wait_mode = v,
+                (10, _) => match &arg.value {
+                    PropValue::Int(v) => req.option = vec![*v],
+                    PropValue::IntList(list) => req.option = list.clone(),
+                    _ => {}
+                },
+                (11, Some(v)) => start_time = v,
                 _ => {}
             }
         }
 
-        (
-            ((wipe_time as i64 - start_time as i64).max(0)) as u64,
-            wait_flag,
-            key_wait_mode,
-        )
+        req.time_ms = ((wipe_time as i64 - start_time as i64).max(0)) as u64;
+        req
+    }
+
+    /// Running wipe request, if its deadline has not passed at `now_ms`.
+    pub fn active_wipe(&self, now_ms: u64) -> Option<&VmWipeRequest> {
+        match (&self.wipe_active, self.wipe_end_at) {
+            (Some((req, _)), Some(deadline)) if now_ms < deadline => Some(req),
+            _ => None,
+        }
+    }
+
+    /// Eased 0..=1 progress of the running wipe at `now_ms` (see `render::wipe_progress`).
+    pub fn wipe_progress(&self, now_ms: u64) -> Option<f32> {
+        let (req, started) = self.wipe_active.as_ref()?;
+        self.active_wipe(now_ms)?;
+        Some(crate::render::wipe_progress(
+            now_ms.saturating_sub(*started),
+            req.time_ms,
+            req.speed_mode,
+        ))
+    }
+
+    fn finish_wipe(&mut self, host: &mut dyn Host, cut_short: bool) {
+        self.wipe_end_at = None;
+        self.wipe_active = None;
+        if cut_short {
+            host.on_wipe_end();
+        }
     }
     fn mwnd_no_from_element_path(path: &[i32]) -> Option<i32> {
         // C++ keeps current mwnd as element path and GET resolves from that element:
@@ -83,6 +139,15 @@ impl Vm {
         Self::mwnd_no_from_element_path(element_path).unwrap_or(-1)
     }
 
+    /// Window targeted by global message commands (-1 = default window).
+    pub(super) fn cur_mwnd_no(&self) -> i32 {
+        Self::resolve_mwnd_no(&self.cur_mwnd_element)
+    }
+
+    pub(super) fn notify_cur_mwnd(&self, host: &mut dyn Host) {
+        host.on_cur_mwnd(Self::resolve_mwnd_no(&self.cur_mwnd_element));
+    }
+
     fn canonical_mwnd_element_path_from_no(mwnd_no: i32) -> Vec<i32> {
         vec![
             crate::elm::global::ELM_GLOBAL_FRONT,
@@ -313,17 +378,16 @@ impl Vm {
                     let key_skip_enabled = x == crate::elm::global::ELM_GLOBAL_TIMEWAIT_KEY;
                     if let Some(PropValue::Int(ms)) = args.get(0).map(|p| &p.value) {
                         if *ms > 0 {
-                            let total = std::time::Duration::from_millis(*ms as u64);
-                            let tick = std::time::Duration::from_millis(16);
-                            let start = std::time::Instant::now();
-                            while start.elapsed() < total {
+                            let clock = host.clock();
+                            let deadline = clock.now_ms() + *ms as u64;
+                            while clock.now_ms() < deadline {
                                 if host.should_interrupt()
                                     || (key_skip_enabled && host.should_skip_wait())
                                 {
                                     break;
                                 }
-                                let left = total.saturating_sub(start.elapsed());
-                                std::thread::sleep(left.min(tick));
+                                let left = deadline.saturating_sub(clock.now_ms());
+                                clock.sleep_ms(left.min(16));
                             }
                         }
                     }
@@ -350,6 +414,7 @@ impl Vm {
                     }
                     _ => {}
                 }
+                self.notify_cur_mwnd(host);
                 return Ok(Some(true));
             }
             x if crate::elm::global::is_get_mwnd(x) => {
@@ -385,6 +450,11 @@ impl Vm {
             // -----------------------------------------------------------------
             // Display / capture / wipe stubs (accept + no-op or default)
             // -----------------------------------------------------------------
+            x if crate::elm::global::is_capture_command(x) => {
+                return Ok(Some(
+                    self.try_command_global_capture(x, args, ret_form, host),
+                ));
+            }
             x if crate::elm::global::is_display_capture_stub(x) => {
                 if ret_form == crate::elm::form::INT {
                     self.stack.push_int(0);
@@ -392,50 +462,55 @@ impl Vm {
                 return Ok(Some(true));
             }
             x if crate::elm::global::is_wipe_start_command(x) => {
-                let (duration_ms, wait_flag, key_wait_mode) =
-                    Self::parse_wipe_start_params_from_cpp(x, args);
+                let mut req = Self::parse_wipe_request_from_cpp(x, args);
+                let clock = host.clock();
                 if self.no_wipe_anime_onoff_flag != 0 {
-                    self.wipe_end_at = None;
+                    // system.no_wipe_anime: the stage switches without animation.
+                    req.time_ms = 0;
+                }
+                let now = clock.now_ms();
+                host.on_wipe_start(&req);
+                let (wait_flag, key_wait_mode) = (req.wait_flag, req.key_wait_mode);
+                if req.time_ms == 0 {
+                    self.finish_wipe(host, false);
                 } else {
-                    self.wipe_end_at =
-                        Some(Instant::now() + std::time::Duration::from_millis(duration_ms));
+                    self.wipe_end_at = Some(now + req.time_ms);
+                    self.wipe_active = Some((req, now));
                 }
 
-                if wait_flag {
-                    if let Some(deadline) = self.wipe_end_at {
-                        let key_skip_enabled = if key_wait_mode == 0 {
-                            false
-                        } else if key_wait_mode == 1 {
-                            true
-                        } else {
-                            self.skip_wipe_anime_onoff_flag != 0
-                        };
-                        let mut wipe_completed = false;
-                        while Instant::now() < deadline {
-                            if host.should_interrupt() {
-                                break;
-                            }
-                            if key_skip_enabled && host.should_skip_wait() {
-                                wipe_completed = true;
-                                break;
-                            }
-                            let left = deadline.saturating_duration_since(Instant::now());
-                            std::thread::sleep(left.min(std::time::Duration::from_millis(16)));
-                        }
-                        if Instant::now() >= deadline {
-                            wipe_completed = true;
+                if wait_flag && let Some(deadline) = self.wipe_end_at {
+                    let key_skip_enabled = if key_wait_mode == 0 {
+                        false
+                    } else if key_wait_mode == 1 {
+                        true
+                    } else {
+                        self.skip_wipe_anime_onoff_flag != 0
+                    };
+                    let mut skipped = false;
+                    while clock.now_ms() < deadline {
+                        if host.should_interrupt() {
+                            break;
                         }
-                        if wipe_completed {
-                            self.wipe_end_at = None;
+                        if key_skip_enabled && host.should_skip_wait() {
+                            skipped = true;
+                            break;
                         }
+                        let left = deadline.saturating_sub(clock.now_ms());
+                        clock.sleep_ms(left.min(16));
+                    }
+                    if skipped {
+                        self.finish_wipe(host, true);
+                    } else if clock.now_ms() >= deadline {
+                        self.finish_wipe(host, false);
                     }
                 }
-
-                // Let host observe this command too (for GUI-side wipe animation).
-                return Ok(Some(false));
+                return Ok(Some(true));
             }
             x if crate::elm::global::is_wipe_end(x) => {
-                self.wipe_end_at = None;
+                // C++ cmd_global.cpp::ELM_GLOBAL_WIPE_END jumps the running wipe to its end.
+                if self.wipe_end_at.is_some() {
+                    self.finish_wipe(host, true);
+                }
                 if ret_form == crate::elm::form::INT {
                     self.stack.push_int(0);
                 }
@@ -461,8 +536,9 @@ impl Vm {
                         self.skip_wipe_anime_onoff_flag != 0
                     };
 
+                    let clock = host.clock();
                     let mut wipe_completed = false;
-                    while Instant::now() < deadline {
+                    while clock.now_ms() < deadline {
                         if host.should_interrupt() {
                             break;
                         }
@@ -471,14 +547,14 @@ impl Vm {
                             wipe_completed = true;
                             break;
                         }
-                        let left = deadline.saturating_duration_since(Instant::now());
-                        std::thread::sleep(left.min(std::time::Duration::from_millis(16)));
+                        let left = deadline.saturating_sub(clock.now_ms());
+                        clock.sleep_ms(left.min(16));
                     }
-                    if Instant::now() >= deadline {
+                    if clock.now_ms() >= deadline {
                         wipe_completed = true;
                     }
                     if wipe_completed {
-                        self.wipe_end_at = None;
+                        self.finish_wipe(host, skipped_by_key);
                     }
                 }
                 if ret_form == crate::elm::form::INT {
@@ -489,8 +565,8 @@ impl Vm {
             x if crate::elm::global::is_check_wipe(x) => {
                 let active = match self.wipe_end_at {
                     Some(deadline) => {
-                        if Instant::now() >= deadline {
-                            self.wipe_end_at = None;
+                        if host.clock().now_ms() >= deadline {
+                            self.finish_wipe(host, false);
                             false
                         } else {
                             true
@@ -581,12 +657,17 @@ impl Vm {
             }
             x if crate::elm::global::is_koe_check(x) => {
                 // C++ cmd_global.cpp::ELM_GLOBAL_KOE_CHECK always pushes int.
-                self.stack.push_int(0); // not playing
+                self.stack.push_int(host.koe_playing() as i32);
                 return Ok(Some(true));
             }
             x if crate::elm::global::is_koe_check_pair(x) => {
                 // C++ cmd_global.cpp::ELM_GLOBAL_KOE_CHECK_GET_* always pushes int.
-                self.stack.push_int(-1);
+                let v = match x {
+                    _ if !host.koe_playing() => -1,
+                    crate::elm::global::ELM_GLOBAL_KOE_CHECK_GET_KOE_NO => self.koe_no,
+                    _ => self.koe_chara_no,
+                };
+                self.stack.push_int(v);
                 return Ok(Some(true));
             }
             x if crate::elm::global::is_koe_check_is_ex(x) => {
@@ -618,12 +699,6 @@ impl Vm {
                 }
                 return Ok(Some(true));
             }
-            x if crate::elm::global::is_capture_stub_extra(x) => {
-                if ret_form == crate::elm::form::INT {
-                    self.stack.push_int(0);
-                }
-                return Ok(Some(true));
-            }
             // -----------------------------------------------------------------
             // Input / Mouse / Key / Script / Syscom / System (pass through)
             // -----------------------------------------------------------------
@@ -643,3 +718,187 @@ impl Vm {
         Ok(None)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elm::global::*;
+    use crate::vm::test_support::test_vm;
+
+    /// Host on a virtual clock that can hold the skip key down.
+    struct ClockHost {
+        clock: Arc<VirtualClock>,
+        skip: bool,
+        wipe_starts: usize,
+        wipe_ends: usize,
+    }
+
+    impl ClockHost {
+        fn new() -> Self {
+            Self {
+                clock: VirtualClock::shared(1000),
+                skip: false,
+                wipe_starts: 0,
+                wipe_ends: 0,
+            }
+        }
+    }
+
+    impl Host for ClockHost {
+        fn clock(&self) -> SharedVmClock {
+            self.clock.clone()
+        }
+
+        fn should_skip_wait(&self) -> bool {
+            self.skip
+        }
+
+        fn on_wipe_start(&mut self, _req: &VmWipeRequest) {
+            self.wipe_starts += 1;
+        }
+
+        fn on_wipe_end(&mut self) {
+            self.wipe_ends += 1;
+        }
+    }
+
+    fn int_arg(id: i32, v: i32) -> Prop {
+        Prop {
+            id,
+            form: crate::elm::form::INT,
+            value: PropValue::Int(v),
+        }
+    }
+
+    fn run(vm: &mut Vm, host: &mut ClockHost, elm: i32, args: &[Prop], ret_form: i32) {
+        let handled = vm
+            .try_command_global_tail(&[elm], 0, args, ret_form, host)
+            .unwrap();
+        assert_eq!(handled, Some(true));
+    }
+
+    #[test]
+    fn timewait_sleeps_on_the_host_clock() {
+        let mut vm = test_vm();
+        let mut host = ClockHost::new();
+        run(
+            &mut vm,
+            &mut host,
+            ELM_GLOBAL_TIMEWAIT,
+            &[int_arg(-1, 250)],
+            crate::elm::form::VOID,
+        );
+        assert_eq!(host.clock.now_ms(), 1250);
+    }
+
+    #[test]
+    fn timewait_key_returns_at_once_when_skipped() {
+        let mut vm = test_vm();
+        let mut host = ClockHost::new();
+        host.skip = true;
+        run(
+            &mut vm,
+            &mut host,
+            ELM_GLOBAL_TIMEWAIT,
+            &[int_arg(-1, 250)],
+            crate::elm::form::VOID,
+        );
+        assert_eq!(host.clock.now_ms(), 1250);
+        run(
+            &mut vm,
+            &mut host,
+            ELM_GLOBAL_TIMEWAIT_KEY,
+            &[int_arg(-1, 250)],
+            crate::elm::form::VOID,
+        );
+        assert_eq!(host.clock.now_ms(), 1250);
+    }
+
+    #[test]
+    fn waited_wipe_runs_to_its_deadline() {
+        let mut vm = test_vm();
+        let mut host = ClockHost::new();
+        run(
+            &mut vm,
+            &mut host,
+            ELM_GLOBAL_WIPE,
+            &[int_arg(-1, 0), int_arg(-1, 300)],
+            crate::elm::form::VOID,
+        );
+        assert_eq!(host.wipe_starts, 1);
+        assert_eq!(host.clock.now_ms(), 1300);
+        assert!(vm.wipe_end_at.is_none());
+        assert_eq!(host.wipe_ends, 0);
+    }
+
+    #[test]
+    fn unwaited_wipe_is_checked_and_waited_on_the_clock() {
+        let mut vm = test_vm();
+        let mut host = ClockHost::new();
+        let args = [int_arg(-1, 0), int_arg(-1, 400), int_arg(8, 0)];
+        run(
+            &mut vm,
+            &mut host,
+            ELM_GLOBAL_WIPE,
+            &args,
+            crate::elm::form::VOID,
+        );
+        assert_eq!(host.clock.now_ms(), 1000);
+        assert_eq!(vm.wipe_progress(1000), Some(0.0));
+        assert!(vm.active_wipe(1399).is_some());
+
+        host.clock.advance_ms(100);
+        run(
+            &mut vm,
+            &mut host,
+            ELM_GLOBAL_CHECK_WIPE,
+            &[],
+            crate::elm::form::INT,
+        );
+        assert_eq!(vm.stack.pop_int().unwrap(), 1);
+
+        run(
+            &mut vm,
+            &mut host,
+            ELM_GLOBAL_WAIT_WIPE,
+            &[int_arg(0, 0)],
+            crate::elm::form::INT,
+        );
+        assert_eq!(vm.stack.pop_int().unwrap(), 0);
+        assert_eq!(host.clock.now_ms(), 1400);
+        run(
+            &mut vm,
+            &mut host,
+            ELM_GLOBAL_CHECK_WIPE,
+            &[],
+            crate::elm::form::INT,
+        );
+        assert_eq!(vm.stack.pop_int().unwrap(), 0);
+    }
+
+    #[test]
+    fn key_skipped_wipe_wait_ends_the_wipe_early() {
+        let mut vm = test_vm();
+        let mut host = ClockHost::new();
+        let args = [int_arg(-1, 0), int_arg(-1, 400), int_arg(8, 0)];
+        run(
+            &mut vm,
+            &mut host,
+            ELM_GLOBAL_WIPE,
+            &args,
+            crate::elm::form::VOID,
+        );
+        host.skip = true;
+        run(
+            &mut vm,
+            &mut host,
+            ELM_GLOBAL_WAIT_WIPE,
+            &[int_arg(0, 1)],
+            crate::elm::form::INT,
+        );
+        assert_eq!(vm.stack.pop_int().unwrap(), 1);
+        assert_eq!(host.clock.now_ms(), 1000);
+        assert_eq!(host.wipe_ends, 1);
+        assert!(vm.active_wipe(1000).is_none());
+    }
+}
@@ -6,21 +6,99 @@
 ///   - World list: create_world / destroy_world / array access
 ///   - Per-world: camera eye/pint/up xyz get/set, calc_camera_eye, calc_camera_pint,
 ///     set_camera_eye/pint/up, camera_view_angle, mono, order, layer, wipe_copy/erase,
-///     camera event dispatchers, set_camera_eve_xz_rotate
+///     camera event dispatchers, set_camera_eve_xz_rotate (accepted as a no-op)
 ///
-/// Property get commands push default 0 values.
-/// Property set / calc commands delegate to Host callbacks.
+/// Approach: worlds live in `Vm::worlds` per stage, so they are part of
+/// local saves; gets read them back, and every change (including `_eve`
+/// events) reports the stage's whole list through `Host::on_worlds`.
 use super::*;
 
 impl Vm {
     // ---------------------------------------------------------------
-    // World list: global.screen.world_list
+    // World state: values, host notification
+    // ---------------------------------------------------------------
+
+    /// `stage[stage_idx].world`; a stage starts with one default world.
+    pub(super) fn stage_worlds_mut(&mut self, stage_idx: i32) -> &mut Vec<VmWorld> {
+        self.worlds
+            .entry(stage_idx)
+            .or_insert_with(|| vec![VmWorld::default()])
+    }
+
+    /// Re-announce every stage's worlds (after a load replaced them).
+    pub(super) fn notify_worlds(&self, host: &mut dyn Host) {
+        for (&stage_idx, worlds) in &self.worlds {
+            host.on_worlds(stage_idx, worlds);
+        }
+    }
+
+    pub(super) fn notify_stage_worlds(&self, stage_idx: i32, host: &mut dyn Host) {
+        if let Some(worlds) = self.worlds.get(&stage_idx) {
+            host.on_worlds(stage_idx, worlds);
+        }
+    }
+
+    /// `world[idx]` if it exists, reporting out-of-range indices like C++ `get_sub`.
+    pub(super) fn world_index(
+        &mut self,
+        stage_idx: i32,
+        idx: i32,
+        host: &mut dyn Host,
+    ) -> Option<usize> {
+        let len = self.stage_worlds_mut(stage_idx).len();
+        match usize::try_from(idx) {
+            Ok(idx) if idx < len => Some(idx),
+            _ => {
+                if self.options.disp_out_of_range_error {
+                    host.on_error_fatal("範囲外のワールド番号が指定されました。(world_list)");
+                }
+                None
+            }
+        }
+    }
+
+    fn world_prop_get(
+        &mut self,
+        stage_idx: i32,
+        idx: usize,
+        prop: i32,
+        host: &mut dyn Host,
+    ) -> i32 {
+        let now = host.clock().now_ms();
+        let world = &mut self.stage_worlds_mut(stage_idx)[idx];
+        if let Some(v) = world.prop_at(prop, now) {
+            return v;
+        }
+        world.int_prop_mut(prop).map(|v| *v).unwrap_or(0)
+    }
+
+    /// Plain assign of a world property (`world[m].camera_eye_x = v`).
+    pub(super) fn world_prop_set(
+        &mut self,
+        stage_idx: i32,
+        idx: usize,
+        prop: i32,
+        value: i32,
+        host: &mut dyn Host,
+    ) {
+        let world = &mut self.stage_worlds_mut(stage_idx)[idx];
+        if let Some(ev) = world.int_event_mut(prop) {
+            ev.set_value(value);
+        } else if let Some(v) = world.int_prop_mut(prop) {
+            *v = value;
+        }
+        self.notify_stage_worlds(stage_idx, host);
+    }
+
+    // ---------------------------------------------------------------
+    // World list: stage.world_list
     // ---------------------------------------------------------------
 
     /// Route world list commands matching C++ `tnm_command_proc_world_list`.
     /// `element` starts after the world_list root element.
     pub(super) fn try_command_world_list(
         &mut self,
+        stage_idx: i32,
         element: &[i32],
         arg_list_id: i32,
         args: &[Prop],
@@ -35,25 +113,28 @@ impl Vm {
         if element[0] == crate::elm::ELM_ARRAY {
             // Indexed: world_list[idx].sub
             if element.len() >= 2 {
-                let idx = element[1];
-                let size = host.on_world_list_get_size();
-                if size >= 0 && (idx < 0 || idx >= size) {
-                    if self.options.disp_out_of_range_error {
-                        host.on_error_fatal("範囲外のワールド番号が指定されました。(world_list)");
-                    }
+                let Some(idx) = self.world_index(stage_idx, element[1], host) else {
                     if ret_form == crate::elm::form::INT {
                         self.stack.push_int(0);
                     } else if ret_form == crate::elm::form::STR {
                         self.stack.push_str(String::new());
                     }
                     return true;
-                }
+                };
                 let rest = if element.len() > 2 {
                     &element[2..]
                 } else {
                     &[]
                 };
-                return self.try_command_world(rest, arg_list_id, args, ret_form, host);
+                return self.try_command_world(
+                    stage_idx,
+                    idx,
+                    rest,
+                    arg_list_id,
+                    args,
+                    ret_form,
+                    host,
+                );
             }
             return true;
         }
@@ -61,14 +142,17 @@ impl Vm {
         match element[0] {
             ELM_WORLDLIST_CREATE_WORLD => {
                 // C++ creates a new world, pushes its index.
-                host.on_world_create();
-                let idx = host.on_world_list_get_size().saturating_sub(1).max(0);
+                let worlds = self.stage_worlds_mut(stage_idx);
+                worlds.push(VmWorld::default());
+                let idx = worlds.len() as i32 - 1;
                 self.stack.push_int(idx);
+                self.notify_stage_worlds(stage_idx, host);
                 true
             }
             ELM_WORLDLIST_DESTROY_WORLD => {
                 // C++ destroys the last world.
-                host.on_world_destroy();
+                self.stage_worlds_mut(stage_idx).pop();
+                self.notify_stage_worlds(stage_idx, host);
                 true
             }
             _ => {
@@ -83,12 +167,15 @@ impl Vm {
     // ---------------------------------------------------------------
 
     /// Route per-world commands matching C++ `tnm_command_proc_world`.
+    #[allow(clippy::too_many_arguments)]
     fn try_command_world(
         &mut self,
+        stage_idx: i32,
+        world_idx: usize,
         element: &[i32],
         arg_list_id: i32,
         args: &[Prop],
-        _ret_form: i32,
+        ret_form: i32,
         host: &mut dyn Host,
     ) -> bool {
         if element.is_empty() {
@@ -96,17 +183,17 @@ impl Vm {
         }
         let sub = element[0];
         use crate::elm::world::*;
+        let now = host.clock().now_ms();
 
         match sub {
             ELM_WORLD_INIT => {
                 // C++ p_world->reinit()
-                host.on_world_init();
-                true
+                self.stage_worlds_mut(stage_idx)[world_idx] = VmWorld::default();
             }
             ELM_WORLD_GET_NO => {
                 // C++ tnm_stack_push_int(p_world->get_world_no())
-                self.stack.push_int(0);
-                true
+                self.stack.push_int(world_idx as i32);
+                return true;
             }
 
             // --- Camera scalar properties (get/set) ---
@@ -126,83 +213,33 @@ impl Vm {
             | ELM_WORLD_WIPE_COPY
             | ELM_WORLD_WIPE_ERASE => {
                 if arg_list_id == 0 {
-                    self.stack.push_int(0);
+                    let v = self.world_prop_get(stage_idx, world_idx, sub, host);
+                    self.stack.push_int(v);
                 } else {
-                    host.on_world_property(
-                        sub,
-                        args.first()
-                            .and_then(|p| match p.value {
-                                PropValue::Int(v) => Some(v),
-                                _ => None,
-                            })
-                            .unwrap_or(0),
-                    );
+                    let v = Self::int_arg(args, 0);
+                    self.world_prop_set(stage_idx, world_idx, sub, v, host);
                 }
-                true
+                return true;
             }
 
             // --- Camera set helpers (3 args) ---
             ELM_WORLD_SET_CAMERA_EYE | ELM_WORLD_SET_CAMERA_PINT | ELM_WORLD_SET_CAMERA_UP => {
-                let x = args
-                    .first()
-                    .and_then(|p| match p.value {
-                        PropValue::Int(v) => Some(v),
-                        _ => None,
-                    })
-                    .unwrap_or(0);
-                let y = args
-                    .get(1)
-                    .and_then(|p| match p.value {
-                        PropValue::Int(v) => Some(v),
-                        _ => None,
-                    })
-                    .unwrap_or(0);
-                let z = args
-                    .get(2)
-                    .and_then(|p| match p.value {
-                        PropValue::Int(v) => Some(v),
-                        _ => None,
-                    })
-                    .unwrap_or(0);
-                host.on_world_set_camera(sub, x, y, z);
-                true
+                let v = [0, 1, 2].map(|i| Self::int_arg(args, i));
+                self.stage_worlds_mut(stage_idx)[world_idx].set_camera(sub, v);
             }
 
             // --- Camera calc helpers (3 args: distance, rotate_h, rotate_v) ---
             ELM_WORLD_CALC_CAMERA_EYE | ELM_WORLD_CALC_CAMERA_PINT => {
-                // C++ uses trig: sin/cos on distance/rotate params to compute camera pos.
-                // For now, accept — the actual calculation is done host-side or in a future pass.
-                let distance = args
-                    .first()
-                    .and_then(|p| match p.value {
-                        PropValue::Int(v) => Some(v),
-                        _ => None,
-                    })
-                    .unwrap_or(0);
-                let rotate_h = args
-                    .get(1)
-                    .and_then(|p| match p.value {
-                        PropValue::Int(v) => Some(v),
-                        _ => None,
-                    })
-                    .unwrap_or(0);
-                let rotate_v = args
-                    .get(2)
-                    .and_then(|p| match p.value {
-                        PropValue::Int(v) => Some(v),
-                        _ => None,
-                    })
-                    .unwrap_or(0);
-                host.on_world_calc_camera(sub, distance, rotate_h, rotate_v);
-                true
+                let [distance, rotate_h, rotate_v] = [0, 1, 2].map(|i| Self::int_arg(args, i));
+                self.stage_worlds_mut(stage_idx)[world_idx]
+                    .calc_camera(sub, distance, rotate_h, rotate_v, now);
             }
 
             // --- set_camera_eve_xz_rotate (5 args) ---
-            ELM_WORLD_SET_CAMERA_EVE_XZ_ROTATE => {
-                // C++ 5 args: effectively animation params.
-                // Accept as no-op.
-                true
-            }
+            // Limitation: accepted as a no-op. Only the 5-int shape is known; the
+            // argument order and the eye's path are not in this tree, so the
+            // camera is left where it is rather than moved along a guess.
+            ELM_WORLD_SET_CAMERA_EVE_XZ_ROTATE => return true,
 
             // --- Camera event dispatchers ---
             ELM_WORLD_CAMERA_EYE_X_EVE
@@ -215,13 +252,28 @@ impl Vm {
             | ELM_WORLD_CAMERA_UP_Y_EVE
             | ELM_WORLD_CAMERA_UP_Z_EVE => {
                 // C++ tnm_command_proc_int_event — route to int_event sub-router.
-                self.try_command_int_event(&element[1..], arg_list_id, args, _ret_form, host, sub)
+                return self.try_command_int_event(
+                    &element[1..],
+                    arg_list_id,
+                    args,
+                    ret_form,
+                    host,
+                    IntEventTarget::new(
+                        IntEventScope::World {
+                            stage_idx,
+                            world_idx,
+                        },
+                        sub,
+                    ),
+                );
             }
 
             _ => {
                 host.on_error_fatal("無効なコマンドが指定されました。(world)");
-                true
+                return true;
             }
         }
+        self.notify_stage_worlds(stage_idx, host);
+        true
     }
 }
@@ -109,6 +109,7 @@ impl Vm {
                 -1,
             ],
             last_sel_msg: String::new(),
+            selbtn_start_call: None,
             hide_mwnd_onoff_flag: 0,
             hide_mwnd_enable_flag: 1,
             hide_mwnd_exist_flag: 1,
@@ -153,6 +154,7 @@ impl Vm {
             system_extra_str_values: options.system_extra_str_values.clone(),
             return_scene_once: None,
             wipe_end_at: None,
+            wipe_active: None,
             last_pc: 0,
             last_line_no: 0,
             last_scene: String::new(),
@@ -218,12 +220,28 @@ impl Vm {
             bgm_name_listened: BTreeMap::new(),
             g00buf_loaded: Vec::new(),
             mask_slots: Vec::new(),
+            screen_effects: vec![VmScreenEffect::default()],
+            worlds: BTreeMap::new(),
+            int_events: BTreeMap::new(),
+            quakes: Vec::new(),
+            shake: None,
+            messages: BTreeMap::new(),
+            message_page_serial: 0,
+            read_texts: Default::default(),
+            koe_no: -1,
+            koe_chara_no: -1,
             object_gan_loaded_path: BTreeMap::new(),
             object_gan_started_set: BTreeMap::new(),
+            object_rep_lists: BTreeMap::new(),
+            rng: VmRng::from_seed_option(options.rng_seed),
+            watchpoints: Vec::new(),
+            next_watch_id: 0,
             local_save_slots: BTreeMap::new(),
             quick_save_slots: BTreeMap::new(),
             inner_save_slots: BTreeMap::new(),
             end_save_slots: BTreeMap::new(),
+            capture_image: None,
+            save_thumb_image: None,
         }
     }
     pub(super) fn command_needs_read_flag_tail(element: &[i32]) -> bool {
@@ -262,6 +280,9 @@ impl Vm {
                     if self.msg_back_off_flag == 0 {
                         self.msg_back_has_message = 1;
                     }
+                    // Text without a read flag can never have been read.
+                    let read = read_flag_no.is_some_and(|no| self.mark_text_read(no));
+                    self.message_add_text(self.cur_mwnd_no(), &text, read, host);
                     host.on_text(&text, read_flag_no.unwrap_or(0));
                 }
             }
@@ -271,10 +292,15 @@ impl Vm {
                     PropValue::Int(v) => Some(v.to_string()),
                     _ => None,
                 }) {
+                    self.message_set_name(self.cur_mwnd_no(), &name, host);
                     host.on_name(&name);
                 }
             }
-            _ => {}
+            x => {
+                if let Some(cmd) = VmMessageCommand::from_global(x) {
+                    self.message_command(self.cur_mwnd_no(), cmd, args, host);
+                }
+            }
         }
     }
     pub(super) fn is_selection_command(elm: i32) -> bool {
@@ -336,6 +362,7 @@ impl Vm {
                 break;
             }
             self.read_skip_onoff_flag = 0;
+            self.notify_message_speed(host);
             self.proc_farcall_like(
                 &route.scene,
                 route.z_no,
@@ -357,6 +384,9 @@ impl Vm {
         provider: &mut dyn SceneProvider,
     ) -> Result<()> {
         while !self.lexer.is_eof() && !self.halted {
+            // Changes made by the previous instruction are attributed to last_scene/last_line_no.
+            self.poll_watchpoints(host);
+            self.poll_scene_reload(host, provider)?;
             self.run_flick_scene_proc(host, provider)?;
             if self.run_key_wait_proc(host) == KeyWaitTickResult::Pending {
                 self.frame_action_counter_tick_all(host);
@@ -659,11 +689,14 @@ impl Vm {
                 }
                 x if x == cd::NAME => {
                     let s = self.stack.pop_str()?;
+                    self.message_set_name(self.cur_mwnd_no(), &s, host);
                     host.on_name(&s);
                 }
                 x if x == cd::TEXT => {
                     let read_flag_no = self.vm_read_i32(host, "CD_TEXT", "read flag no")?;
                     let msg = self.stack.pop_str()?;
+                    let read = self.mark_text_read(read_flag_no);
+                    self.message_add_text(self.cur_mwnd_no(), &msg, read, host);
                     host.on_text(&msg, read_flag_no);
                 }
                 x if x == cd::NONE => {
@@ -689,6 +722,7 @@ impl Vm {
                 }
             }
         }
+        self.poll_watchpoints(host);
         Ok(())
     }
 }
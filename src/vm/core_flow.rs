@@ -75,6 +75,7 @@ impl Vm {
     }
 
     pub fn run(&mut self, host: &mut dyn Host, provider: &mut dyn SceneProvider) -> Result<()> {
+        self.notify_message_speed(host);
         self.run_inner(host, provider).with_context(|| {
             format!(
                 "vm: error at pc={} line={} scene={}",
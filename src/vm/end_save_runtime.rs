@@ -79,7 +79,7 @@ impl Vm {
         }
     }
 
-    pub(super) fn snapshot_end_save_runtime_state(&self) -> VmEndSaveRuntimeState {
+    pub(super) fn snapshot_end_save_runtime_state(&self, now_ms: u64) -> VmEndSaveRuntimeState {
         let frames = self
             .frames
             .iter()
@@ -146,12 +146,24 @@ impl Vm {
             last_pc: self.last_pc,
             last_line_no: self.last_line_no,
             last_scene: self.last_scene.clone(),
+            rng_state: self.rng.state(),
+            int_events: self
+                .int_events
+                .iter()
+                .flat_map(|(&(scope, owner_id), ev)| {
+                    let mut entry = scope.to_save().to_vec();
+                    entry.push(owner_id);
+                    entry.extend(ev.to_save(now_ms));
+                    entry
+                })
+                .collect(),
         }
     }
 
     pub(super) fn apply_end_save_state_with_provider(
         &mut self,
         st: &VmEndSaveState,
+        now_ms: u64,
         provider: &mut dyn SceneProvider,
     ) -> Result<bool> {
         let Some(rt) = &st.runtime else {
@@ -192,6 +204,7 @@ impl Vm {
                     1 => VmCallType::Gosub,
                     2 => VmCallType::Farcall,
                     3 => VmCallType::UserCmd,
+                    4 => VmCallType::SelBtnStart,
                     _ => VmCallType::None,
                 },
                 excall_flag: f.excall_flag,
@@ -293,6 +306,18 @@ impl Vm {
         self.last_pc = rt.last_pc;
         self.last_line_no = rt.last_line_no;
         self.last_scene = rt.last_scene.clone();
+        if rt.rng_state != 0 {
+            self.rng = VmRng::from_state(rt.rng_state);
+        }
+        self.int_events = rt
+            .int_events
+            .chunks_exact(INT_EVENT_SAVE_LEN)
+            .filter_map(|e| {
+                let scope = IntEventScope::from_save(e[0..4].try_into().ok()?)?;
+                let ev = IntEvent::from_save(e[5..13].try_into().ok()?, now_ms);
+                Some(((scope, e[4]), ev))
+            })
+            .collect();
         Ok(true)
     }
 }
@@ -100,21 +100,31 @@ pub struct VmEndSaveRuntimeState {
     pub last_pc: usize,
     pub last_line_no: i32,
     pub last_scene: String,
+    /// `VmRng::state()` at save time (SESV6+; 0 means "not recorded").
+    pub rng_state: u64,
+    /// VM-held `*_eve` events (SESV7+): scope, owner id and
+    /// `IntEvent::to_save`, `INT_EVENT_SAVE_LEN` ints each.
+    pub int_events: Vec<i32>,
 }
 
+/// Ints per entry of `VmEndSaveRuntimeState::int_events`.
+pub const INT_EVENT_SAVE_LEN: usize = 13;
+
 impl VmEndSaveState {
     const MAGIC_V1: &'static [u8; 5] = b"SESV1";
     const MAGIC_V2: &'static [u8; 5] = b"SESV2";
     const MAGIC_V3: &'static [u8; 5] = b"SESV3";
     const MAGIC_V4: &'static [u8; 5] = b"SESV4";
     const MAGIC_V5: &'static [u8; 5] = b"SESV5";
+    const MAGIC_V6: &'static [u8; 5] = b"SESV6";
+    const MAGIC_V7: &'static [u8; 5] = b"SESV7";
     const MAX_VEC_LEN: usize = 1 << 20;
     const MAX_STR_BYTES: usize = 16 * 1024 * 1024;
     const MAX_TOTAL_STR_BYTES: usize = 128 * 1024 * 1024;
 
     pub fn encode_binary(&self) -> Vec<u8> {
         let mut out = Vec::new();
-        out.extend_from_slice(Self::MAGIC_V5);
+        out.extend_from_slice(Self::MAGIC_V7);
 
         let title = self.scene_title.as_bytes();
         let title_len: u32 = title
@@ -351,6 +361,8 @@ impl VmEndSaveState {
         buf.extend_from_slice(&(rt.last_pc as u64).to_le_bytes());
         buf.extend_from_slice(&rt.last_line_no.to_le_bytes());
         Self::push_string(buf, &rt.last_scene);
+        buf.extend_from_slice(&rt.rng_state.to_le_bytes());
+        Self::push_i32_vec(buf, &rt.int_events);
     }
 
     pub fn decode_binary(bytes: &[u8]) -> Result<Self> {
@@ -528,7 +540,14 @@ impl VmEndSaveState {
         let mut cur = Cursor::new(bytes);
         let mut magic = [0u8; 5];
         std::io::Read::read_exact(&mut cur, &mut magic)?;
-        if &magic != Self::MAGIC_V1 && &magic != Self::MAGIC_V2 && &magic != Self::MAGIC_V3 {
+        if &magic != Self::MAGIC_V1
+            && &magic != Self::MAGIC_V2
+            && &magic != Self::MAGIC_V3
+            && &magic != Self::MAGIC_V4
+            && &magic != Self::MAGIC_V5
+            && &magic != Self::MAGIC_V6
+            && &magic != Self::MAGIC_V7
+        {
             bail!("invalid end-save state magic")
         }
 
@@ -556,6 +575,8 @@ impl VmEndSaveState {
             || &magic == Self::MAGIC_V3
             || &magic == Self::MAGIC_V4
             || &magic == Self::MAGIC_V5
+            || &magic == Self::MAGIC_V6
+            || &magic == Self::MAGIC_V7
         {
             let mut has_runtime = [0u8; 1];
             std::io::Read::read_exact(&mut cur, &mut has_runtime)?;
@@ -577,19 +598,22 @@ impl VmEndSaveState {
                     let return_scene = read_string(&mut cur, "runtime return scene")?;
                     let return_line_no = read_i32(&mut cur)?;
                     let expect_ret_form = read_i32(&mut cur)?;
-                    let (call_type, excall_flag, frame_action_flag) =
-                        if &magic == Self::MAGIC_V4 || &magic == Self::MAGIC_V5 {
-                            let call_type = read_i32(&mut cur)?;
-                            let mut excall = [0u8; 1];
-                            std::io::Read::read_exact(&mut cur, &mut excall)?;
-                            let mut frame = [0u8; 1];
-                            std::io::Read::read_exact(&mut cur, &mut frame)?;
-                            (call_type, excall[0] != 0, frame[0] != 0)
-                        } else {
-                            let mut frame = [0u8; 1];
-                            std::io::Read::read_exact(&mut cur, &mut frame)?;
-                            (0, false, frame[0] != 0)
-                        };
+                    let (call_type, excall_flag, frame_action_flag) = if &magic == Self::MAGIC_V4
+                        || &magic == Self::MAGIC_V5
+                        || &magic == Self::MAGIC_V6
+                        || &magic == Self::MAGIC_V7
+                    {
+                        let call_type = read_i32(&mut cur)?;
+                        let mut excall = [0u8; 1];
+                        std::io::Read::read_exact(&mut cur, &mut excall)?;
+                        let mut frame = [0u8; 1];
+                        std::io::Read::read_exact(&mut cur, &mut frame)?;
+                        (call_type, excall[0] != 0, frame[0] != 0)
+                    } else {
+                        let mut frame = [0u8; 1];
+                        std::io::Read::read_exact(&mut cur, &mut frame)?;
+                        (0, false, frame[0] != 0)
+                    };
                     let arg_cnt = read_u64(&mut cur)? as usize;
                     let call_l = read_i32_vec(&mut cur, "runtime call_l")?;
                     let call_k = read_str_vec(&mut cur, "runtime call_k")?;
@@ -615,7 +639,10 @@ impl VmEndSaveState {
                         call_user_props,
                     });
                 }
-                let proc_stack = if &magic == Self::MAGIC_V5 {
+                let proc_stack = if &magic == Self::MAGIC_V5
+                    || &magic == Self::MAGIC_V6
+                    || &magic == Self::MAGIC_V7
+                {
                     read_i32_vec(&mut cur, "runtime proc_stack")?
                 } else {
                     Vec::new()
@@ -654,6 +681,8 @@ impl VmEndSaveState {
                     == Self::MAGIC_V3
                     || &magic == Self::MAGIC_V4
                     || &magic == Self::MAGIC_V5
+                    || &magic == Self::MAGIC_V6
+                    || &magic == Self::MAGIC_V7
                 {
                     (
                         read_i32(&mut cur)?,
@@ -666,6 +695,20 @@ impl VmEndSaveState {
                 let last_pc = read_u64(&mut cur)? as usize;
                 let last_line_no = read_i32(&mut cur)?;
                 let last_scene = read_string(&mut cur, "runtime last scene")?;
+                let rng_state = if &magic == Self::MAGIC_V6 || &magic == Self::MAGIC_V7 {
+                    read_u64(&mut cur)?
+                } else {
+                    0
+                };
+                let int_events = if &magic == Self::MAGIC_V7 {
+                    let v = read_i32_vec(&mut cur, "runtime int_events")?;
+                    if v.len() % INT_EVENT_SAVE_LEN != 0 {
+                        bail!("runtime int_events length {} is not a whole entry", v.len());
+                    }
+                    v
+                } else {
+                    Vec::new()
+                };
                 state.runtime = Some(VmEndSaveRuntimeState {
                     scene,
                     lexer_scene,
@@ -698,6 +741,8 @@ impl VmEndSaveState {
                     last_pc,
                     last_line_no,
                     last_scene,
+                    rng_state,
+                    int_events,
                 });
             }
         }
@@ -709,3 +754,28 @@ impl VmEndSaveState {
         Ok(state)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn int_events_round_trip() {
+        let mut ev = crate::vm::IntEvent::new(0);
+        ev.set_event(100, 1000, 0, 0, 0);
+        let mut int_events = crate::vm::IntEventScope::Mask { mask_idx: 2 }
+            .to_save()
+            .to_vec();
+        int_events.push(7);
+        int_events.extend(ev.to_save(400));
+        let st = VmEndSaveState {
+            runtime: Some(VmEndSaveRuntimeState {
+                int_events,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let decoded = VmEndSaveState::decode_binary(&st.encode_binary()).unwrap();
+        assert_eq!(decoded, st);
+    }
+}
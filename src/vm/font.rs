@@ -0,0 +1,73 @@
+use super::{Host, Vm};
+
+/// Text decoration, as set by `syscom.set_font_decoration` / `script.set_font_shadow`.
+///
+/// C++ reference: `TNM_FONT_SHADOW_MODE_*` (0 none, 1 shadow, 2 outline,
+/// 3 outline + shadow).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FontDecoration {
+    None,
+    #[default]
+    Shadow,
+    Outline,
+    OutlineShadow,
+}
+
+impl FontDecoration {
+    /// Map a config value; negative values mean "use `default`".
+    pub fn from_config(value: i32, default: Self) -> Self {
+        match value {
+            0 => Self::None,
+            1 => Self::Shadow,
+            2 => Self::Outline,
+            3 => Self::OutlineShadow,
+            _ => default,
+        }
+    }
+
+    pub fn has_shadow(self) -> bool {
+        matches!(self, Self::Shadow | Self::OutlineShadow)
+    }
+
+    pub fn has_outline(self) -> bool {
+        matches!(self, Self::Outline | Self::OutlineShadow)
+    }
+}
+
+/// Font settings in effect for script text (C++ `Gp_script->m_font_*`, falling
+/// back to `Gp_config->m_font_*` when the script value is the default).
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct VmFontStyle {
+    /// Face name; empty selects the host's default font.
+    pub name: String,
+    pub bold: bool,
+    pub decoration: FontDecoration,
+}
+
+impl Vm {
+    pub fn font_style(&self) -> VmFontStyle {
+        let name = if self.script_font_name.is_empty() {
+            self.syscom_cfg.font_name.clone()
+        } else {
+            self.script_font_name.clone()
+        };
+        let bold = if self.script_font_bold >= 0 {
+            self.script_font_bold
+        } else {
+            self.syscom_cfg.font_bold
+        };
+        let decoration = FontDecoration::from_config(
+            self.script_font_shadow,
+            FontDecoration::from_config(self.syscom_cfg.font_decoration, FontDecoration::default()),
+        );
+        VmFontStyle {
+            name,
+            bold: bold > 0,
+            decoration,
+        }
+    }
+
+    pub(super) fn notify_font_style(&self, host: &mut dyn Host) {
+        host.on_font_style(&self.font_style());
+    }
+}
@@ -0,0 +1,556 @@
+/// Clock-driven `*_eve` property value.
+///
+/// C++ reference: elm_int_event.cpp (`set_event` / `loop_event` / `turn_event` /
+/// `end_event`). Instead of accumulating per-frame deltas, the running event
+/// remembers its start time on the VM clock and is evaluated on demand, so the
+/// VM and hosts observe the same value for the same `now_ms`.
+/// Game and real time share the VM clock here.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct IntEvent {
+    /// Settled value: the target of a one-shot event, or the base of a loop.
+    value: i32,
+    anim: Option<IntEventAnim>,
+}
+
+/// `speed_type` of `set`/`loop`/`turn`/`yure`: constant speed.
+pub const INT_EVENT_SPEED_LINEAR: i32 = 0;
+/// `speed_type`: start slow and accelerate (quadratic ease-in).
+pub const INT_EVENT_SPEED_ACCEL: i32 = 1;
+/// `speed_type`: start fast and decelerate (quadratic ease-out).
+pub const INT_EVENT_SPEED_DECEL: i32 = 2;
+
+/// What an `*_eve` owner id belongs to.
+///
+/// Owner ids are property ids (plus a list index for `*_rep_eve`), so the
+/// same id on two objects or worlds needs the scope to stay separate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum IntEventScope {
+    /// Owners that already encode their index.
+    Global,
+    Object {
+        list_id: i32,
+        /// `-1` for objects outside a stage.
+        stage_idx: i32,
+        obj_idx: i32,
+    },
+    /// `stage[n].world[m]`; events live in `VmWorld`.
+    World { stage_idx: i32, world_idx: usize },
+    /// `screen.effect[n]`; events live in `VmScreenEffect`.
+    Effect { effect_idx: usize },
+    /// `mask[n]`; events live in `MaskSlotState`.
+    Mask { mask_idx: usize },
+}
+
+impl IntEventScope {
+    /// Flat `[kind, a, b, c]` form for save files.
+    pub fn to_save(self) -> [i32; 4] {
+        match self {
+            Self::Global => [0, 0, 0, 0],
+            Self::Object {
+                list_id,
+                stage_idx,
+                obj_idx,
+            } => [1, list_id, stage_idx, obj_idx],
+            Self::World {
+                stage_idx,
+                world_idx,
+            } => [2, stage_idx, world_idx as i32, 0],
+            Self::Effect { effect_idx } => [3, effect_idx as i32, 0, 0],
+            Self::Mask { mask_idx } => [4, mask_idx as i32, 0, 0],
+        }
+    }
+
+    pub fn from_save(v: [i32; 4]) -> Option<Self> {
+        Some(match v[0] {
+            0 => Self::Global,
+            1 => Self::Object {
+                list_id: v[1],
+                stage_idx: v[2],
+                obj_idx: v[3],
+            },
+            2 => Self::World {
+                stage_idx: v[1],
+                world_idx: usize::try_from(v[2]).ok()?,
+            },
+            3 => Self::Effect {
+                effect_idx: usize::try_from(v[1]).ok()?,
+            },
+            4 => Self::Mask {
+                mask_idx: usize::try_from(v[1]).ok()?,
+            },
+            _ => return None,
+        })
+    }
+}
+
+/// Event addressed by an int_event route: property `owner_id` within `scope`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct IntEventTarget {
+    pub scope: IntEventScope,
+    pub owner_id: i32,
+}
+
+impl IntEventTarget {
+    pub fn new(scope: IntEventScope, owner_id: i32) -> Self {
+        Self { scope, owner_id }
+    }
+}
+
+/// Object `*_eve` ids and the plain property each one animates.
+const OBJECT_EVENT_PROPS: [(i32, i32); 36] = {
+    use crate::elm::objectlist::*;
+    [
+        (ELM_OBJECT_PATNO_EVE, ELM_OBJECT_PATNO),
+        (ELM_OBJECT_X_EVE, ELM_OBJECT_X),
+        (ELM_OBJECT_Y_EVE, ELM_OBJECT_Y),
+        (ELM_OBJECT_Z_EVE, ELM_OBJECT_Z),
+        (ELM_OBJECT_CENTER_X_EVE, ELM_OBJECT_CENTER_X),
+        (ELM_OBJECT_CENTER_Y_EVE, ELM_OBJECT_CENTER_Y),
+        (ELM_OBJECT_CENTER_Z_EVE, ELM_OBJECT_CENTER_Z),
+        (ELM_OBJECT_CENTER_REP_X_EVE, ELM_OBJECT_CENTER_REP_X),
+        (ELM_OBJECT_CENTER_REP_Y_EVE, ELM_OBJECT_CENTER_REP_Y),
+        (ELM_OBJECT_CENTER_REP_Z_EVE, ELM_OBJECT_CENTER_REP_Z),
+        (ELM_OBJECT_SCALE_X_EVE, ELM_OBJECT_SCALE_X),
+        (ELM_OBJECT_SCALE_Y_EVE, ELM_OBJECT_SCALE_Y),
+        (ELM_OBJECT_SCALE_Z_EVE, ELM_OBJECT_SCALE_Z),
+        (ELM_OBJECT_ROTATE_X_EVE, ELM_OBJECT_ROTATE_X),
+        (ELM_OBJECT_ROTATE_Y_EVE, ELM_OBJECT_ROTATE_Y),
+        (ELM_OBJECT_ROTATE_Z_EVE, ELM_OBJECT_ROTATE_Z),
+        (ELM_OBJECT_CLIP_LEFT_EVE, ELM_OBJECT_CLIP_LEFT),
+        (ELM_OBJECT_CLIP_TOP_EVE, ELM_OBJECT_CLIP_TOP),
+        (ELM_OBJECT_CLIP_RIGHT_EVE, ELM_OBJECT_CLIP_RIGHT),
+        (ELM_OBJECT_CLIP_BOTTOM_EVE, ELM_OBJECT_CLIP_BOTTOM),
+        (ELM_OBJECT_SRC_CLIP_LEFT_EVE, ELM_OBJECT_SRC_CLIP_LEFT),
+        (ELM_OBJECT_SRC_CLIP_TOP_EVE, ELM_OBJECT_SRC_CLIP_TOP),
+        (ELM_OBJECT_SRC_CLIP_RIGHT_EVE, ELM_OBJECT_SRC_CLIP_RIGHT),
+        (ELM_OBJECT_SRC_CLIP_BOTTOM_EVE, ELM_OBJECT_SRC_CLIP_BOTTOM),
+        (ELM_OBJECT_TR_EVE, ELM_OBJECT_TR),
+        (ELM_OBJECT_MONO_EVE, ELM_OBJECT_MONO),
+        (ELM_OBJECT_REVERSE_EVE, ELM_OBJECT_REVERSE),
+        (ELM_OBJECT_BRIGHT_EVE, ELM_OBJECT_BRIGHT),
+        (ELM_OBJECT_DARK_EVE, ELM_OBJECT_DARK),
+        (ELM_OBJECT_COLOR_R_EVE, ELM_OBJECT_COLOR_R),
+        (ELM_OBJECT_COLOR_G_EVE, ELM_OBJECT_COLOR_G),
+        (ELM_OBJECT_COLOR_B_EVE, ELM_OBJECT_COLOR_B),
+        (ELM_OBJECT_COLOR_RATE_EVE, ELM_OBJECT_COLOR_RATE),
+        (ELM_OBJECT_COLOR_ADD_R_EVE, ELM_OBJECT_COLOR_ADD_R),
+        (ELM_OBJECT_COLOR_ADD_G_EVE, ELM_OBJECT_COLOR_ADD_G),
+        (ELM_OBJECT_COLOR_ADD_B_EVE, ELM_OBJECT_COLOR_ADD_B),
+    ]
+};
+
+/// Plain object property animated by an object `*_eve` id (`x_eve` → `x`).
+pub fn object_event_property(eve: i32) -> Option<i32> {
+    OBJECT_EVENT_PROPS
+        .iter()
+        .find(|(e, _)| *e == eve)
+        .map(|(_, p)| *p)
+}
+
+/// The `*_eve` id animating a plain object property (`x` → `x_eve`).
+pub fn object_property_event(prop: i32) -> Option<i32> {
+    OBJECT_EVENT_PROPS
+        .iter()
+        .find(|(_, p)| *p == prop)
+        .map(|(e, _)| *e)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IntEventLoop {
+    #[default]
+    OneShot,
+    Loop,
+    Turn,
+    /// One swing out to `end_value`, over to the mirrored side and back to
+    /// `start_value` (the center), then settled like a one-shot.
+    Yure,
+}
+
+impl IntEventLoop {
+    fn to_save(self) -> i32 {
+        match self {
+            Self::OneShot => 0,
+            Self::Loop => 1,
+            Self::Turn => 2,
+            Self::Yure => 3,
+        }
+    }
+
+    fn from_save(v: i32) -> Option<Self> {
+        Some(match v {
+            0 => Self::OneShot,
+            1 => Self::Loop,
+            2 => Self::Turn,
+            3 => Self::Yure,
+            _ => return None,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct IntEventAnim {
+    loop_type: IntEventLoop,
+    start_value: i32,
+    end_value: i32,
+    /// VM clock at the start; negative for events resumed from a save made
+    /// further into the event than the current clock.
+    start_ms: i64,
+    total_ms: i32,
+    delay_ms: i32,
+    speed_type: i32,
+}
+
+impl IntEventAnim {
+    /// C++ `C_elm_int_event::frame_sub`; `None` once a one-shot has finished.
+    fn value_at(&self, now_ms: u64) -> Option<i32> {
+        let elapsed = (now_ms as i64 - self.start_ms).clamp(0, i32::MAX as i64);
+        let end = self.total_ms as i64;
+        let mut cur = elapsed - self.delay_ms as i64;
+        let finite = matches!(self.loop_type, IntEventLoop::OneShot | IntEventLoop::Yure);
+        if finite && cur >= end {
+            return None;
+        }
+        if cur <= 0 {
+            return Some(self.start_value);
+        }
+        match self.loop_type {
+            IntEventLoop::OneShot => {}
+            IntEventLoop::Yure => return Some(self.yure_value_at(cur as f64 / end as f64)),
+            IntEventLoop::Loop => cur %= end,
+            IntEventLoop::Turn => {
+                cur %= end * 2;
+                if cur > end {
+                    cur = end - (cur - end);
+                }
+            }
+        }
+        let (start, delta) = (
+            self.start_value as f64,
+            (self.end_value - self.start_value) as f64,
+        );
+        let (cur, end) = (cur as f64, end as f64);
+        let v = match self.speed_type {
+            INT_EVENT_SPEED_ACCEL => delta * cur * cur / end / end + start,
+            INT_EVENT_SPEED_DECEL => {
+                -delta * (cur - end) * (cur - end) / end / end + self.end_value as f64
+            }
+            _ => delta * cur / end + start,
+        };
+        Some(v as i32)
+    }
+
+    /// Yure at progress `t` (0..1): the speed curve shapes the whole swing,
+    /// which is a triangle wave center → +swing → −swing → center.
+    fn yure_value_at(&self, t: f64) -> i32 {
+        let t = match self.speed_type {
+            INT_EVENT_SPEED_ACCEL => t * t,
+            INT_EVENT_SPEED_DECEL => 1.0 - (1.0 - t) * (1.0 - t),
+            _ => t,
+        };
+        let phase = t * 4.0;
+        let wave = if phase < 1.0 {
+            phase
+        } else if phase < 3.0 {
+            2.0 - phase
+        } else {
+            phase - 4.0
+        };
+        let swing = (self.end_value - self.start_value) as f64;
+        (self.start_value as f64 + swing * wave) as i32
+    }
+}
+
+impl IntEvent {
+    pub fn new(value: i32) -> Self {
+        Self { value, anim: None }
+    }
+
+    /// Settled value, ignoring any running event.
+    pub fn value(&self) -> i32 {
+        self.value
+    }
+
+    /// Assign directly, cancelling any running event.
+    pub fn set_value(&mut self, value: i32) {
+        self.value = value;
+        self.anim = None;
+    }
+
+    /// Current value on the VM clock.
+    pub fn value_at(&self, now_ms: u64) -> i32 {
+        self.anim
+            .and_then(|a| a.value_at(now_ms))
+            .unwrap_or(self.value)
+    }
+
+    pub fn is_active(&self, now_ms: u64) -> bool {
+        self.anim.is_some_and(|a| a.value_at(now_ms).is_some())
+    }
+
+    /// Tween from the current value to `value` (C++ `set_event`).
+    pub fn set_event(
+        &mut self,
+        value: i32,
+        total_ms: i32,
+        delay_ms: i32,
+        speed_type: i32,
+        now_ms: u64,
+    ) {
+        let start_value = self.value_at(now_ms);
+        self.value = value;
+        self.start(
+            IntEventLoop::OneShot,
+            start_value,
+            value,
+            total_ms,
+            delay_ms,
+            speed_type,
+            now_ms,
+        );
+    }
+
+    /// Repeat `start_value` → `end_value` (`Loop`) or bounce between them (`Turn`).
+    #[allow(clippy::too_many_arguments)]
+    pub fn loop_event(
+        &mut self,
+        loop_type: IntEventLoop,
+        start_value: i32,
+        end_value: i32,
+        total_ms: i32,
+        delay_ms: i32,
+        speed_type: i32,
+        now_ms: u64,
+    ) {
+        self.start(
+            loop_type,
+            start_value,
+            end_value,
+            total_ms,
+            delay_ms,
+            speed_type,
+            now_ms,
+        );
+    }
+
+    /// Shake once around `center` by `swing` over `total_ms`; settles on `center`.
+    pub fn yure_event(
+        &mut self,
+        center: i32,
+        swing: i32,
+        total_ms: i32,
+        delay_ms: i32,
+        speed_type: i32,
+        now_ms: u64,
+    ) {
+        self.value = center;
+        self.start(
+            IntEventLoop::Yure,
+            center,
+            center.saturating_add(swing),
+            total_ms,
+            delay_ms,
+            speed_type,
+            now_ms,
+        );
+    }
+
+    /// Stop the running event and settle (C++ `end_event`).
+    pub fn end_event(&mut self) {
+        self.anim = None;
+    }
+
+    /// Move a running event `delta_ms` later, e.g. to resume an in-memory
+    /// save on a clock that kept running since it was taken.
+    pub fn shift_ms(&mut self, delta_ms: u64) {
+        if let Some(anim) = &mut self.anim {
+            anim.start_ms = anim.start_ms.saturating_add(delta_ms as i64);
+        }
+    }
+
+    /// Flat form for save files: `[value, loop, start, end, elapsed, total,
+    /// delay, speed]`, with `loop` = -1 when settled. The elapsed time
+    /// replaces the clock-relative start so a load resumes mid-event.
+    pub fn to_save(&self, now_ms: u64) -> [i32; 8] {
+        let Some(a) = self.anim else {
+            return [self.value, -1, 0, 0, 0, 0, 0, 0];
+        };
+        let elapsed = (now_ms as i64 - a.start_ms).clamp(0, i32::MAX as i64) as i32;
+        [
+            self.value,
+            a.loop_type.to_save(),
+            a.start_value,
+            a.end_value,
+            elapsed,
+            a.total_ms,
+            a.delay_ms,
+            a.speed_type,
+        ]
+    }
+
+    /// Inverse of `to_save`, resuming on the clock at `now_ms`.
+    pub fn from_save(v: [i32; 8], now_ms: u64) -> Self {
+        let anim = IntEventLoop::from_save(v[1]).map(|loop_type| IntEventAnim {
+            loop_type,
+            start_value: v[2],
+            end_value: v[3],
+            start_ms: now_ms as i64 - v[4].max(0) as i64,
+            total_ms: v[5],
+            delay_ms: v[6],
+            speed_type: v[7],
+        });
+        Self {
+            value: v[0],
+            anim: anim.filter(|a| a.total_ms > 0),
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn start(
+        &mut self,
+        loop_type: IntEventLoop,
+        start_value: i32,
+        end_value: i32,
+        total_ms: i32,
+        delay_ms: i32,
+        speed_type: i32,
+        now_ms: u64,
+    ) {
+        if total_ms <= 0 {
+            self.anim = None;
+            return;
+        }
+        self.anim = Some(IntEventAnim {
+            loop_type,
+            start_value,
+            end_value,
+            start_ms: now_ms as i64,
+            total_ms,
+            delay_ms: delay_ms.max(0),
+            speed_type,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn speed_types_shape_the_tween() {
+        let at = |speed_type, ms| {
+            let mut ev = IntEvent::new(0);
+            ev.set_event(1000, 1000, 0, speed_type, 0);
+            ev.value_at(ms)
+        };
+        assert_eq!(at(INT_EVENT_SPEED_LINEAR, 500), 500);
+        assert_eq!(at(INT_EVENT_SPEED_ACCEL, 500), 250);
+        assert_eq!(at(INT_EVENT_SPEED_DECEL, 500), 750);
+        for speed_type in [
+            INT_EVENT_SPEED_LINEAR,
+            INT_EVENT_SPEED_ACCEL,
+            INT_EVENT_SPEED_DECEL,
+        ] {
+            assert_eq!(at(speed_type, 0), 0);
+            assert_eq!(at(speed_type, 1000), 1000);
+        }
+    }
+
+    #[test]
+    fn delay_holds_the_start_value() {
+        let mut ev = IntEvent::new(10);
+        ev.set_event(20, 100, 50, INT_EVENT_SPEED_LINEAR, 1000);
+        assert_eq!(ev.value_at(1040), 10);
+        assert_eq!(ev.value_at(1100), 15);
+        assert!(ev.is_active(1140));
+        assert!(!ev.is_active(1150));
+    }
+
+    #[test]
+    fn yure_swings_both_ways_and_settles() {
+        let mut ev = IntEvent::new(0);
+        ev.yure_event(100, 20, 400, 0, INT_EVENT_SPEED_LINEAR, 0);
+        assert_eq!(ev.value_at(100), 120);
+        assert_eq!(ev.value_at(200), 100);
+        assert_eq!(ev.value_at(300), 80);
+        assert!(ev.is_active(399));
+        assert!(!ev.is_active(400));
+        assert_eq!(ev.value_at(5000), 100);
+    }
+
+    #[test]
+    fn loops_keep_running() {
+        let mut ev = IntEvent::new(0);
+        ev.loop_event(IntEventLoop::Turn, 0, 100, 100, 0, 0, 0);
+        assert_eq!(ev.value_at(150), 50);
+        assert!(ev.is_active(1_000_000));
+    }
+
+    #[test]
+    fn assign_cancels_the_event() {
+        let mut ev = IntEvent::new(0);
+        ev.set_event(100, 1000, 0, INT_EVENT_SPEED_LINEAR, 0);
+        ev.set_value(7);
+        assert!(!ev.is_active(10));
+        assert_eq!(ev.value_at(10), 7);
+    }
+
+    #[test]
+    fn save_resumes_mid_event_on_a_new_clock() {
+        let mut ev = IntEvent::new(0);
+        ev.set_event(100, 1000, 0, INT_EVENT_SPEED_LINEAR, 5000);
+        let saved = ev.to_save(5250);
+        let loaded = IntEvent::from_save(saved, 20);
+        assert_eq!(loaded.value_at(20), 25);
+        assert_eq!(loaded.value_at(770), 100);
+        assert!(!loaded.is_active(770));
+
+        let settled = IntEvent::new(42);
+        assert_eq!(IntEvent::from_save(settled.to_save(0), 0), settled);
+    }
+
+    #[test]
+    fn shift_moves_the_event_later() {
+        let mut ev = IntEvent::new(0);
+        ev.set_event(100, 100, 0, INT_EVENT_SPEED_LINEAR, 0);
+        ev.shift_ms(1000);
+        assert_eq!(ev.value_at(1050), 50);
+    }
+
+    #[test]
+    fn scopes_round_trip_through_saves() {
+        let scopes = [
+            IntEventScope::Global,
+            IntEventScope::Object {
+                list_id: 3,
+                stage_idx: -1,
+                obj_idx: 9,
+            },
+            IntEventScope::World {
+                stage_idx: 1,
+                world_idx: 2,
+            },
+            IntEventScope::Effect { effect_idx: 4 },
+            IntEventScope::Mask { mask_idx: 1 },
+        ];
+        for scope in scopes {
+            assert_eq!(IntEventScope::from_save(scope.to_save()), Some(scope));
+        }
+        assert_eq!(IntEventScope::from_save([9, 0, 0, 0]), None);
+    }
+
+    #[test]
+    fn event_ids_map_to_their_properties() {
+        use crate::elm::objectlist::*;
+        assert_eq!(object_event_property(ELM_OBJECT_X_EVE), Some(ELM_OBJECT_X));
+        assert_eq!(
+            object_event_property(ELM_OBJECT_TR_EVE),
+            Some(ELM_OBJECT_TR)
+        );
+        assert_eq!(
+            object_property_event(ELM_OBJECT_SCALE_Y),
+            Some(ELM_OBJECT_SCALE_Y_EVE)
+        );
+        assert_eq!(object_event_property(ELM_OBJECT_X), None);
+        assert_eq!(object_property_event(ELM_OBJECT_DISP), None);
+    }
+}
@@ -1,7 +1,7 @@
 use super::*;
 
 impl Vm {
-    pub(super) fn snapshot_local_state(&self) -> VmLocalState {
+    pub(super) fn snapshot_local_state(&self, now_ms: u64) -> VmLocalState {
         VmLocalState {
             scene: self.scene.clone(),
             lexer: self.lexer.clone(),
@@ -142,11 +142,18 @@ impl Vm {
             bgm_name_listened: self.bgm_name_listened.clone(),
             g00buf_loaded: self.g00buf_loaded.clone(),
             mask_slots: self.mask_slots.clone(),
+            screen_effects: self.screen_effects.clone(),
+            worlds: self.worlds.clone(),
+            int_events: self.int_events.clone(),
+            saved_at_ms: now_ms,
+            messages: self.messages.clone(),
             object_gan_loaded_path: self.object_gan_loaded_path.clone(),
             object_gan_started_set: self.object_gan_started_set.clone(),
+            object_rep_lists: self.object_rep_lists.clone(),
+            rng: self.rng,
         }
     }
-    pub(super) fn apply_local_state(&mut self, st: &VmLocalState) {
+    pub(super) fn apply_local_state(&mut self, st: &VmLocalState, now_ms: u64) {
         self.scene = st.scene.clone();
         self.lexer = st.lexer.clone();
         self.stack = st.stack.clone();
@@ -184,8 +191,12 @@ impl Vm {
         self.bgm_name_listened = st.bgm_name_listened.clone();
         self.g00buf_loaded = st.g00buf_loaded.clone();
         self.mask_slots = st.mask_slots.clone();
+        self.screen_effects = st.screen_effects.clone();
+        self.worlds = st.worlds.clone();
         self.object_gan_loaded_path = st.object_gan_loaded_path.clone();
         self.object_gan_started_set = st.object_gan_started_set.clone();
+        self.object_rep_lists = st.object_rep_lists.clone();
+        self.rng = st.rng;
         self.cur_mwnd_element = st.cur_mwnd_element.clone();
         self.cur_sel_mwnd_element = st.cur_sel_mwnd_element.clone();
         self.last_sel_msg = st.last_sel_msg.clone();
@@ -302,13 +313,21 @@ impl Vm {
         self.bgm_name_listened = st.bgm_name_listened.clone();
         self.g00buf_loaded = st.g00buf_loaded.clone();
         self.mask_slots = st.mask_slots.clone();
+        self.screen_effects = st.screen_effects.clone();
+        self.worlds = st.worlds.clone();
+        self.int_events = st.int_events.clone();
+        self.shift_int_events(now_ms.saturating_sub(st.saved_at_ms));
+        self.restore_messages(&st.messages);
         self.object_gan_loaded_path = st.object_gan_loaded_path.clone();
         self.object_gan_started_set = st.object_gan_started_set.clone();
+        self.object_rep_lists = st.object_rep_lists.clone();
         self.save_point_snapshot = st.save_point_snapshot.clone();
         self.sel_point_snapshot = st.sel_point_snapshot.clone();
         self.sel_point_stock = st.sel_point_stock.clone();
         self.wipe_end_at = None;
+        self.wipe_active = None;
         self.halted = false;
+        self.rebaseline_watchpoints();
     }
     pub fn set_options(&mut self, options: VmOptions) {
         self.options = options;
@@ -316,6 +335,11 @@ impl Vm {
         self.skip_wipe_anime_onoff_flag = if self.options.skip_wipe_anime { 1 } else { 0 };
         self.system_extra_int_values = self.options.system_extra_int_values.clone();
         self.system_extra_str_values = self.options.system_extra_str_values.clone();
+        self.rng = VmRng::from_seed_option(self.options.rng_seed);
+        self.watchpoints.clear();
+        for point in self.options.watchpoints.clone() {
+            self.add_watchpoint(point);
+        }
         self.apply_syscom_option_defaults();
 
         self.counter_list_size = self.options.preloaded_counter_count.max(1);
@@ -378,9 +402,19 @@ impl Vm {
             cg_group_codes: self.cg_group_codes.clone(),
             cg_code_exist_cnt: self.cg_code_exist_cnt.clone(),
             bgm_name_listened: self.bgm_name_listened.clone(),
+            read_texts: Default::default(),
+        }
+    }
+    /// `snapshot_persistent_state` plus the read flags: what the global save
+    /// keeps. Save/sel points leave them out, as loading those never
+    /// unreads text.
+    pub fn snapshot_global_state(&self) -> VmPersistentState {
+        VmPersistentState {
+            read_texts: self.read_texts.clone(),
+            ..self.snapshot_persistent_state()
         }
     }
-    pub fn snapshot_end_save_state(&self) -> VmEndSaveState {
+    pub fn snapshot_end_save_state(&self, now_ms: u64) -> VmEndSaveState {
         VmEndSaveState {
             scene_title: self.scene_title.clone(),
             message: self.last_sel_msg.clone(),
@@ -389,7 +423,7 @@ impl Vm {
             // Gap: full VmLocalState parity is still pending (e.g. additional mwnd/msg-back side paths and failure semantics).
             // Expected: end-load should eventually match C++ local continuation semantics branch-by-branch.
             // Validation: run END_SAVE -> END_LOAD cross-process and verify call/user-prop/frame-action continuity.
-            runtime: Some(self.snapshot_end_save_runtime_state()),
+            runtime: Some(self.snapshot_end_save_runtime_state(now_ms)),
         }
     }
     pub fn apply_persistent_state(&mut self, st: &VmPersistentState) {
@@ -426,6 +460,7 @@ impl Vm {
         self.cg_group_codes = st.cg_group_codes.clone();
         self.cg_code_exist_cnt = st.cg_code_exist_cnt.clone();
         self.bgm_name_listened = st.bgm_name_listened.clone();
+        self.read_texts.extend(st.read_texts.iter().cloned());
         self.save_point_snapshot = if self.save_point_set {
             Some(self.snapshot_persistent_state())
         } else {
@@ -437,6 +472,7 @@ impl Vm {
             None
         };
         self.sel_point_stock = None;
+        self.rebaseline_watchpoints();
     }
     pub(super) fn clear_transient_flow_state(&mut self) {
         self.stack = IfcStack::default();
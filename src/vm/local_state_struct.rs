@@ -139,7 +139,15 @@ struct VmLocalState {
     bgm_name_listened: BTreeMap<String, bool>,
     g00buf_loaded: Vec<Option<String>>,
     mask_slots: Vec<MaskSlotState>,
+    screen_effects: Vec<VmScreenEffect>,
+    worlds: BTreeMap<i32, Vec<VmWorld>>,
+    int_events: BTreeMap<(IntEventScope, i32), IntEvent>,
+    /// VM clock at save time; events resume from here on load.
+    saved_at_ms: u64,
+    messages: BTreeMap<i32, message::MwndMessage>,
     object_gan_loaded_path: BTreeMap<(i32, i32, i32), String>,
     object_gan_started_set: BTreeMap<(i32, i32, i32), i32>,
+    object_rep_lists: BTreeMap<(i32, i32, i32, i32), Vec<i32>>,
+    rng: VmRng,
 }
 
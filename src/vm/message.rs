@@ -0,0 +1,473 @@
+use std::collections::BTreeMap;
+
+use super::{Host, Prop, PropValue, Vm};
+
+/// Style of a text run; `None` fields use the window's Gameexe defaults.
+///
+/// Colors are `#COLOR_TABLE` indices (C++ `C_elm_mwnd::set_moji_color`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct VmTextStyle {
+    pub color: Option<i32>,
+    pub shadow_color: Option<i32>,
+    pub fuchi_color: Option<i32>,
+    pub size: Option<i32>,
+}
+
+/// Key/display wait recorded in a message (C++ `tnm_msg_proc_*` waits).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VmMessageWait {
+    /// `wait_msg`: until the text has finished appearing.
+    Display,
+    /// `pp`: key wait, the page continues afterwards.
+    Pp,
+    /// `r`: key wait followed by a line break.
+    R,
+    /// `page`: key wait, the next text starts a new page.
+    Page,
+}
+
+/// One element of a message page, in script order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VmMessagePart {
+    /// Text printed with one style; `ruby` is set for a `ruby(...)` span.
+    Text {
+        text: String,
+        style: VmTextStyle,
+        ruby: Option<String>,
+    },
+    /// `nl`, or `nli` (`indent`) which continues at the indent position.
+    NewLine {
+        indent: bool,
+    },
+    /// `indent`: later lines start at the current position.
+    Indent,
+    /// `clear_indent`.
+    ClearIndent,
+    Wait(VmMessageWait),
+    /// `rep_pos`: move the text cursor inside the window.
+    RepPos {
+        x: i32,
+        y: i32,
+    },
+}
+
+/// Structured contents of a message window page.
+///
+/// C++ reference: elm_mwnd.cpp `C_elm_mwnd` message buffer. A page ends at
+/// `page`/`clear`, or when content arrives after a `pp` key wait.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct VmMessage {
+    /// Increases with every new page across all windows; lets hosts tell a
+    /// continued page from a new one.
+    pub page_serial: u32,
+    pub name: String,
+    pub parts: Vec<VmMessagePart>,
+    /// Every text on the page had been read before (read skip may pass it).
+    pub read: bool,
+}
+
+impl VmMessage {
+    /// Text without ruby, styles or waits; line breaks become `\n`.
+    pub fn plain_text(&self) -> String {
+        let mut out = String::new();
+        for part in &self.parts {
+            match part {
+                VmMessagePart::Text { text, .. } => out.push_str(text),
+                VmMessagePart::NewLine { .. } | VmMessagePart::Wait(VmMessageWait::R) => {
+                    out.push('\n')
+                }
+                _ => {}
+            }
+        }
+        out
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.name.is_empty() && self.parts.is_empty()
+    }
+
+    /// Number of text characters (ruby and line breaks not counted).
+    pub fn char_count(&self) -> usize {
+        self.parts
+            .iter()
+            .map(|p| match p {
+                VmMessagePart::Text { text, .. } => text.chars().count(),
+                _ => 0,
+            })
+            .sum()
+    }
+
+    /// The page as it looks with only the first `chars` text characters
+    /// shown (typewriter display). Layout parts before the cut are kept.
+    pub fn revealed(&self, chars: usize) -> VmMessage {
+        let mut left = chars;
+        let mut parts = Vec::new();
+        for part in &self.parts {
+            match part {
+                VmMessagePart::Text { text, style, ruby } => {
+                    if left == 0 {
+                        break;
+                    }
+                    let n = text.chars().count();
+                    if n <= left {
+                        parts.push(part.clone());
+                        left -= n;
+                    } else {
+                        parts.push(VmMessagePart::Text {
+                            text: text.chars().take(left).collect(),
+                            style: *style,
+                            ruby: ruby.clone(),
+                        });
+                        left = 0;
+                    }
+                }
+                _ => parts.push(part.clone()),
+            }
+        }
+        VmMessage {
+            parts,
+            ..self.clone()
+        }
+    }
+
+    fn push_text(&mut self, text: &str, style: VmTextStyle, ruby: Option<&str>) {
+        if let Some(VmMessagePart::Text {
+            text: last,
+            style: last_style,
+            ruby: last_ruby,
+        }) = self.parts.last_mut()
+            && *last_style == style
+            && last_ruby.as_deref() == ruby
+        {
+            last.push_str(text);
+            return;
+        }
+        self.parts.push(VmMessagePart::Text {
+            text: text.to_string(),
+            style,
+            ruby: ruby.map(str::to_string),
+        });
+    }
+}
+
+/// Per-window builder state behind `VmMessage`.
+#[derive(Debug, Clone, Default)]
+pub(super) struct MwndMessage {
+    message: VmMessage,
+    style: VmTextStyle,
+    ruby: Option<String>,
+    /// A `pp` key wait passed; the next content starts a new page.
+    clear_ready: bool,
+    /// Parts the host has been sent for this page.
+    sent_parts: usize,
+}
+
+impl Vm {
+    /// Current page of `mwnd_no` (-1 = the default window).
+    pub fn message(&self, mwnd_no: i32) -> Option<&VmMessage> {
+        self.messages
+            .get(&self.message_mwnd_no(mwnd_no))
+            .map(|m| &m.message)
+    }
+
+    /// Window that owns pages addressed to `mwnd_no`: -1 (no `set_mwnd` yet)
+    /// is the Gameexe default window, so `mwnd[default].clear` reaches them.
+    fn message_mwnd_no(&self, mwnd_no: i32) -> i32 {
+        if mwnd_no < 0 {
+            self.options.default_mwnd_no
+        } else {
+            mwnd_no
+        }
+    }
+
+    fn message_new_page(&mut self, mwnd_no: i32) {
+        self.message_page_serial = self.message_page_serial.wrapping_add(1);
+        let serial = self.message_page_serial;
+        let msg = self.messages.entry(mwnd_no).or_default();
+        msg.message = VmMessage {
+            page_serial: serial,
+            read: true,
+            ..VmMessage::default()
+        };
+        msg.style = VmTextStyle::default();
+        msg.ruby = None;
+        msg.clear_ready = false;
+        msg.sent_parts = 0;
+    }
+
+    /// Builder for content about to be added, starting a new page if needed.
+    fn message_for_content(&mut self, mwnd_no: i32) -> &mut MwndMessage {
+        let clear = self.messages.get(&mwnd_no).is_none_or(|m| m.clear_ready);
+        if clear {
+            self.message_new_page(mwnd_no);
+        }
+        self.messages.entry(mwnd_no).or_default()
+    }
+
+    /// Send the page to the host. Parts are only appended, and only the last
+    /// one grows, so everything before the last part already sent is kept.
+    fn notify_message(&mut self, mwnd_no: i32, host: &mut dyn Host) {
+        if let Some(msg) = self.messages.get_mut(&mwnd_no) {
+            let from_part = msg.sent_parts.saturating_sub(1);
+            msg.sent_parts = msg.message.parts.len();
+            host.on_message(mwnd_no, &msg.message, from_part);
+        }
+    }
+
+    /// Append printed text; `read` is whether this text had been read before.
+    pub(super) fn message_add_text(
+        &mut self,
+        mwnd_no: i32,
+        text: &str,
+        read: bool,
+        host: &mut dyn Host,
+    ) {
+        let mwnd_no = self.message_mwnd_no(mwnd_no);
+        let msg = self.message_for_content(mwnd_no);
+        let (style, ruby) = (msg.style, msg.ruby.clone());
+        msg.message.push_text(text, style, ruby.as_deref());
+        msg.message.read &= read;
+        self.notify_message(mwnd_no, host);
+    }
+
+    pub(super) fn message_set_name(&mut self, mwnd_no: i32, name: &str, host: &mut dyn Host) {
+        let mwnd_no = self.message_mwnd_no(mwnd_no);
+        self.message_for_content(mwnd_no).message.name = name.to_string();
+        self.notify_message(mwnd_no, host);
+    }
+
+    /// Apply a text-flow command (`nl`, `ruby`, `color`, `pp`, ...) shared by
+    /// the global and `mwnd[n]` forms.
+    ///
+    /// C++ reference: cmd_mwnd.cpp / cmd_global.cpp `tnm_msg_proc_*`.
+    pub(super) fn message_command(
+        &mut self,
+        mwnd_no: i32,
+        cmd: VmMessageCommand,
+        args: &[Prop],
+        host: &mut dyn Host,
+    ) {
+        let mwnd_no = self.message_mwnd_no(mwnd_no);
+        let int = |i: usize| match args.get(i).map(|p| &p.value) {
+            Some(PropValue::Int(v)) => Some(*v),
+            _ => None,
+        };
+        match cmd {
+            VmMessageCommand::Clear | VmMessageCommand::Page => {
+                if cmd == VmMessageCommand::Page {
+                    let msg = self.message_for_content(mwnd_no);
+                    msg.message
+                        .parts
+                        .push(VmMessagePart::Wait(VmMessageWait::Page));
+                    self.notify_message(mwnd_no, host);
+                }
+                self.message_new_page(mwnd_no);
+                self.notify_message(mwnd_no, host);
+                return;
+            }
+            VmMessageCommand::MsgBlock => {
+                // A new block only clears if the last one ended on a key wait.
+                if self.messages.get(&mwnd_no).is_some_and(|m| m.clear_ready) {
+                    self.message_new_page(mwnd_no);
+                    self.notify_message(mwnd_no, host);
+                }
+                return;
+            }
+            VmMessageCommand::Ruby => {
+                let ruby = args.first().and_then(|p| match &p.value {
+                    PropValue::Str(s) => Some(s.clone()),
+                    _ => None,
+                });
+                self.message_for_content(mwnd_no).ruby = ruby;
+                return;
+            }
+            VmMessageCommand::Color => {
+                let msg = self.message_for_content(mwnd_no);
+                msg.style.color = int(0);
+                msg.style.shadow_color = int(1);
+                msg.style.fuchi_color = int(2);
+                return;
+            }
+            VmMessageCommand::Size => {
+                self.message_for_content(mwnd_no).style.size = int(0);
+                return;
+            }
+            _ => {}
+        }
+
+        let part = match cmd {
+            VmMessageCommand::Nl => VmMessagePart::NewLine { indent: false },
+            VmMessageCommand::Nli => VmMessagePart::NewLine { indent: true },
+            VmMessageCommand::Indent => VmMessagePart::Indent,
+            VmMessageCommand::ClearIndent => VmMessagePart::ClearIndent,
+            VmMessageCommand::WaitMsg => VmMessagePart::Wait(VmMessageWait::Display),
+            VmMessageCommand::Pp => VmMessagePart::Wait(VmMessageWait::Pp),
+            VmMessageCommand::R => VmMessagePart::Wait(VmMessageWait::R),
+            VmMessageCommand::RepPos => VmMessagePart::RepPos {
+                x: int(0).unwrap_or(0),
+                y: int(1).unwrap_or(0),
+            },
+            _ => return,
+        };
+        let msg = self.message_for_content(mwnd_no);
+        msg.message.parts.push(part);
+        if cmd == VmMessageCommand::Pp {
+            msg.clear_ready = true;
+        }
+        self.notify_message(mwnd_no, host);
+    }
+
+    /// Take the pages of a local save; windows it has no page for get an
+    /// empty one.
+    pub(super) fn restore_messages(&mut self, saved: &BTreeMap<i32, MwndMessage>) {
+        let cleared: Vec<i32> = self
+            .messages
+            .keys()
+            .filter(|no| !saved.contains_key(no))
+            .copied()
+            .collect();
+        self.messages = saved.clone();
+        for no in cleared {
+            self.message_new_page(no);
+        }
+    }
+
+    /// Send every page to the host in full (after a load).
+    pub(super) fn notify_messages(&mut self, host: &mut dyn Host) {
+        let nos: Vec<i32> = self.messages.keys().copied().collect();
+        for no in nos {
+            if let Some(msg) = self.messages.get_mut(&no) {
+                msg.sent_parts = 0;
+            }
+            self.notify_message(no, host);
+        }
+    }
+}
+
+/// Text-flow commands that exist both globally and on `mwnd[n]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum VmMessageCommand {
+    Clear,
+    Page,
+    MsgBlock,
+    Ruby,
+    Color,
+    Size,
+    Nl,
+    Nli,
+    Indent,
+    ClearIndent,
+    WaitMsg,
+    Pp,
+    R,
+    RepPos,
+}
+
+impl VmMessageCommand {
+    pub(super) fn from_global(elm: i32) -> Option<Self> {
+        use crate::elm::global::*;
+        Some(match elm {
+            ELM_GLOBAL_CLEAR => Self::Clear,
+            ELM_GLOBAL_PAGE => Self::Page,
+            ELM_GLOBAL_MSG_BLOCK | ELM_GLOBAL_MSG_PP_BLOCK => Self::MsgBlock,
+            ELM_GLOBAL_RUBY => Self::Ruby,
+            ELM_GLOBAL_COLOR => Self::Color,
+            ELM_GLOBAL_SIZE => Self::Size,
+            ELM_GLOBAL_NL => Self::Nl,
+            ELM_GLOBAL_NLI => Self::Nli,
+            ELM_GLOBAL_INDENT => Self::Indent,
+            ELM_GLOBAL_CLEAR_INDENT => Self::ClearIndent,
+            ELM_GLOBAL_WAIT_MSG => Self::WaitMsg,
+            ELM_GLOBAL_PP => Self::Pp,
+            ELM_GLOBAL_R => Self::R,
+            ELM_GLOBAL_REP_POS => Self::RepPos,
+            _ => return None,
+        })
+    }
+
+    pub(super) fn from_mwnd(sub: i32) -> Option<Self> {
+        use crate::elm::mwnd::*;
+        Some(match sub {
+            ELM_MWND_CLEAR | ELM_MWND__NOVEL_CLEAR => Self::Clear,
+            ELM_MWND_PAGE => Self::Page,
+            ELM_MWND_MSG_BLOCK | ELM_MWND_MSG_PP_BLOCK => Self::MsgBlock,
+            ELM_MWND_RUBY => Self::Ruby,
+            ELM_MWND_COLOR => Self::Color,
+            ELM_MWND_SIZE => Self::Size,
+            ELM_MWND_NL => Self::Nl,
+            ELM_MWND_NLI => Self::Nli,
+            ELM_MWND_INDENT => Self::Indent,
+            ELM_MWND_CLEAR_INDENT => Self::ClearIndent,
+            ELM_MWND_WAIT_MSG => Self::WaitMsg,
+            ELM_MWND_PP => Self::Pp,
+            ELM_MWND_R => Self::R,
+            ELM_MWND_REP_POS => Self::RepPos,
+            _ => return None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vm::test_support::test_vm;
+
+    /// Host that keeps a copy of each page from the `from_part` deltas.
+    #[derive(Default)]
+    struct PageHost {
+        pages: BTreeMap<i32, VmMessage>,
+        sent: Vec<usize>,
+    }
+
+    impl Host for PageHost {
+        fn on_message(&mut self, mwnd_no: i32, msg: &VmMessage, from_part: usize) {
+            let page = self.pages.entry(mwnd_no).or_default();
+            page.parts.truncate(from_part);
+            page.parts.extend_from_slice(&msg.parts[from_part..]);
+            page.name = msg.name.clone();
+            self.sent.push(msg.parts.len() - from_part);
+        }
+    }
+
+    #[test]
+    fn default_window_pages_belong_to_the_default_mwnd() {
+        let mut vm = test_vm();
+        vm.options.default_mwnd_no = 2;
+        let mut host = PageHost::default();
+        vm.message_add_text(-1, "hello", true, &mut host);
+        assert_eq!(
+            vm.message(2).map(VmMessage::plain_text).as_deref(),
+            Some("hello")
+        );
+        vm.message_command(2, VmMessageCommand::Clear, &[], &mut host);
+        assert!(vm.message(-1).is_some_and(VmMessage::is_empty));
+        assert!(host.pages[&2].parts.is_empty());
+    }
+
+    #[test]
+    fn hosts_get_only_the_changed_parts() {
+        let mut vm = test_vm();
+        let mut host = PageHost::default();
+        vm.message_add_text(0, "a", true, &mut host);
+        vm.message_command(0, VmMessageCommand::Nl, &[], &mut host);
+        vm.message_add_text(0, "b", true, &mut host);
+        vm.message_add_text(0, "c", true, &mut host);
+        assert_eq!(host.sent, vec![1, 2, 2, 1]);
+        assert_eq!(host.pages[&0].plain_text(), "a\nbc");
+        assert_eq!(host.pages[&0].parts, vm.message(0).unwrap().parts);
+    }
+
+    #[test]
+    fn local_save_restores_pages() {
+        let mut vm = test_vm();
+        let mut host = PageHost::default();
+        vm.message_add_text(0, "saved", true, &mut host);
+        let st = vm.snapshot_local_state(0);
+        vm.message_command(0, VmMessageCommand::Clear, &[], &mut host);
+        vm.message_add_text(1, "later", true, &mut host);
+        vm.apply_local_state(&st, 0);
+        vm.notify_messages(&mut host);
+        assert_eq!(host.pages[&0].plain_text(), "saved");
+        assert!(host.pages[&1].is_empty());
+    }
+}
@@ -0,0 +1,144 @@
+use super::{Host, Vm};
+
+/// Milliseconds per character when neither the script nor the config sets a
+/// message speed (C++ tnm_ini.cpp `CONFIG.MESSAGE_SPEED` fallback).
+pub const DEFAULT_MESSAGE_SPEED_MS: i32 = 30;
+/// Auto mode wait per displayed character.
+pub const DEFAULT_AUTO_MODE_MOJI_WAIT_MS: i32 = 100;
+/// Shortest auto mode wait after a message finished appearing.
+pub const DEFAULT_AUTO_MODE_MIN_WAIT_MS: i32 = 2000;
+
+/// How message text is revealed and advanced, resolved from script and
+/// config settings (C++ `Gp_script->m_msg_speed` etc. falling back to
+/// `Gp_config`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VmMessageSpeed {
+    /// Milliseconds per character; 0 shows each message at once (nowait).
+    pub moji_ms: u32,
+    /// Auto mode: advance on its own after the text and voice finished.
+    pub auto_mode: bool,
+    pub auto_moji_ms: u32,
+    pub auto_min_ms: u32,
+    /// Characters added to every message's auto wait count
+    /// (`script.set_auto_mode_moji_cnt`).
+    pub auto_extra_moji: u32,
+    /// Read skip is on (`syscom.set_read_skip_onoff_flag`).
+    pub skip: bool,
+    /// Skip also passes messages that were never read.
+    pub skip_unread: bool,
+}
+
+impl Default for VmMessageSpeed {
+    fn default() -> Self {
+        Self {
+            moji_ms: DEFAULT_MESSAGE_SPEED_MS as u32,
+            auto_mode: false,
+            auto_moji_ms: DEFAULT_AUTO_MODE_MOJI_WAIT_MS as u32,
+            auto_min_ms: DEFAULT_AUTO_MODE_MIN_WAIT_MS as u32,
+            auto_extra_moji: 0,
+            skip: false,
+            skip_unread: false,
+        }
+    }
+}
+
+impl VmMessageSpeed {
+    /// Time until `chars` characters have all appeared.
+    pub fn reveal_ms(&self, chars: usize) -> u64 {
+        self.moji_ms as u64 * chars as u64
+    }
+
+    /// Characters visible `elapsed_ms` after a message of `chars` started.
+    pub fn revealed_chars(&self, elapsed_ms: u64, chars: usize) -> usize {
+        if self.moji_ms == 0 {
+            return chars;
+        }
+        ((elapsed_ms / self.moji_ms as u64) as usize).min(chars)
+    }
+
+    /// Auto mode wait after a message of `chars` finished appearing:
+    /// `max(min_wait, moji_wait * chars)` (C++ `tnm_msg_proc_auto_mode`).
+    pub fn auto_wait_ms(&self, chars: usize) -> u64 {
+        let chars = chars as u64 + self.auto_extra_moji as u64;
+        (self.auto_moji_ms as u64 * chars).max(self.auto_min_ms as u64)
+    }
+
+    /// Whether auto mode may advance `since_reveal_ms` after the text fully
+    /// appeared; a playing voice always holds the message.
+    pub fn auto_advance_ready(
+        &self,
+        chars: usize,
+        since_reveal_ms: u64,
+        voice_playing: bool,
+    ) -> bool {
+        self.auto_mode && !voice_playing && since_reveal_ms >= self.auto_wait_ms(chars)
+    }
+
+    /// Whether skip passes a message in `read` state without waiting.
+    pub fn skips(&self, read: bool) -> bool {
+        self.skip && (read || self.skip_unread)
+    }
+}
+
+impl Vm {
+    pub fn message_speed(&self) -> VmMessageSpeed {
+        let pick = |script: i32, config: i32, default: i32| {
+            if script >= 0 {
+                script
+            } else if config >= 0 {
+                config
+            } else {
+                default
+            }
+        };
+        let nowait = self.script_msg_nowait || self.syscom_cfg.message_nowait != 0;
+        let moji_ms = if nowait {
+            0
+        } else {
+            pick(
+                self.script_msg_speed,
+                self.syscom_cfg.message_speed,
+                self.options.default_message_speed,
+            )
+        };
+        VmMessageSpeed {
+            moji_ms: moji_ms.max(0) as u32,
+            auto_mode: self.script_auto_mode_flag
+                || (self.auto_mode_onoff_flag != 0
+                    && self.auto_mode_enable_flag != 0
+                    && self.auto_mode_exist_flag != 0),
+            auto_moji_ms: pick(
+                self.script_auto_mode_moji_wait,
+                self.syscom_cfg.auto_mode_moji_wait,
+                DEFAULT_AUTO_MODE_MOJI_WAIT_MS,
+            )
+            .max(0) as u32,
+            auto_min_ms: pick(
+                self.script_auto_mode_min_wait,
+                self.syscom_cfg.auto_mode_min_wait,
+                DEFAULT_AUTO_MODE_MIN_WAIT_MS,
+            )
+            .max(0) as u32,
+            auto_extra_moji: self.script_auto_mode_moji_cnt.max(0) as u32,
+            skip: self.read_skip_onoff_flag != 0
+                && self.read_skip_enable_flag != 0
+                && self.read_skip_exist_flag != 0,
+            skip_unread: self.syscom_cfg.skip_unread_message_onoff != 0
+                || self.script_skip_unread_message_flag != 0,
+        }
+    }
+
+    pub(super) fn notify_message_speed(&self, host: &mut dyn Host) {
+        host.on_message_speed(&self.message_speed());
+    }
+
+    /// Mark the text with `read_flag_no` in the current scene as read and
+    /// return whether it already was (C++ `tnm_set_read_flag`). Read flags
+    /// are global data: see `snapshot_global_state`.
+    pub(super) fn mark_text_read(&mut self, read_flag_no: i32) -> bool {
+        if read_flag_no < 0 {
+            return false;
+        }
+        !self.read_texts.insert((self.scene.clone(), read_flag_no))
+    }
+}
@@ -1,4 +1,4 @@
-use std::{collections::BTreeMap, sync::Arc, time::Instant};
+use std::{collections::BTreeMap, sync::Arc};
 
 use anyhow::{Context, Result, bail};
 
@@ -7,7 +7,9 @@ use crate::{dat::SceneDat, lexer::SceneLexer, stack::IfcStack};
 use self::syscom_config_state::VmSyscomConfigState;
 
 mod api;
+mod clock;
 mod command_call;
+mod command_capture;
 mod command_effect;
 mod command_head;
 mod command_input;
@@ -19,6 +21,7 @@ mod command_others;
 mod command_others_cg;
 mod command_others_counter_matrix;
 mod command_script;
+mod command_selbtn;
 mod command_sound;
 mod command_sound_bgm;
 mod command_stage;
@@ -36,21 +39,46 @@ mod core;
 mod core_flow;
 mod end_save_runtime;
 mod end_save_state;
+mod font;
+mod int_event;
 mod local_state;
+mod message;
+mod message_speed;
 mod opcode;
 mod persistent;
 mod props;
 mod props_assign;
+mod quake;
+mod rng;
+mod scene_reload;
+mod screen_effect;
 mod stack_ops;
 mod syscom_config_state;
+#[cfg(test)]
+pub(crate) mod test_support;
+mod watch;
+mod world;
 
 pub use api::*;
+pub use clock::*;
 pub use end_save_state::*;
+pub use font::*;
+pub use int_event::*;
+pub use message::*;
+pub use message_speed::*;
 pub use persistent::*;
+pub use quake::*;
+pub use rng::*;
+pub use screen_effect::*;
+pub use watch::*;
+pub use world::*;
 
 pub trait SceneProvider {
     fn get_scene(&mut self, scene: &str) -> Result<Arc<SceneDat>>;
 
+    /// Whether this provider can serve `scene` (used by chained providers).
+    fn has_scene(&self, scene: &str) -> bool;
+
     fn inc_cmd_count(&self) -> i32 {
         0
     }
@@ -58,6 +86,23 @@ pub trait SceneProvider {
     fn get_inc_cmd_target(&mut self, _user_cmd_id: i32) -> Result<Option<(String, i32)>> {
         Ok(None)
     }
+
+    /// Drop cached decode state so the next lookups re-read the sources.
+    fn invalidate(&mut self) {}
+
+    /// Called by the VM between instructions with the host clock time; report
+    /// scenes whose sources changed.
+    fn poll_scene_reload(&mut self, _now_ms: u64) -> Option<VmSceneReload> {
+        None
+    }
+}
+
+/// Scenes a `SceneProvider` re-read since the last poll (see `crate::hot_reload`).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VmSceneReload {
+    pub changed_scenes: Vec<String>,
+    /// Restart the running scene if it is among `changed_scenes`.
+    pub restart: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -71,6 +116,8 @@ pub struct VmOptions {
     pub return_menu_scene: Option<(String, i32)>,
     /// Whether to honor script wait commands in real time.
     pub realtime_wait: bool,
+    /// Seed for `math.rand`; `None` uses `VmRng::DEFAULT_SEED`.
+    pub rng_seed: Option<u64>,
     /// Current key-skip policy for wipe wait when script passes key_wait_mode=-1.
     /// Mirrors C++ runtime system.skip_wipe_anime_flag behavior.
     pub skip_wipe_anime: bool,
@@ -141,6 +188,15 @@ pub struct VmOptions {
     pub preloaded_counter_count: usize,
     /// C++ tnm_ini.cpp: FRAME_ACTION_CH.CNT (excall frame_action_ch default size).
     pub preloaded_frame_action_ch_count: usize,
+    /// Flag/variable watchpoints installed by `set_options` (reported via `Host::on_watchpoint`).
+    pub watchpoints: Vec<VmWatchpoint>,
+    /// C++ tnm_ini.cpp: `#SHAKE.NNN` patterns by shake number.
+    pub shake_patterns: Vec<Vec<crate::render::ShakeStep>>,
+    /// Gameexe `CONFIG.MESSAGE_SPEED`: ms per character when neither script
+    /// nor syscom sets one.
+    pub default_message_speed: i32,
+    /// Gameexe `#DEFAULT_MWND_NO`: window that `-1` (no `set_mwnd` yet) names.
+    pub default_mwnd_no: i32,
 }
 
 #[derive(Debug, Clone)]
@@ -164,6 +220,7 @@ impl Default for VmOptions {
             trace_stack: false,
             return_menu_scene: Some(("__sys_menu".to_string(), 0)),
             realtime_wait: true,
+            rng_seed: None,
             // C++ tnm_ini.cpp defaults skip_wipe_anime.onoff to true.
             skip_wipe_anime: true,
             skip_wipe_anime_default: true,
@@ -205,6 +262,10 @@ impl Default for VmOptions {
             preloaded_bgm_names: Vec::new(),
             preloaded_counter_count: FLAG_LIST_SIZE,
             preloaded_frame_action_ch_count: 0,
+            watchpoints: Vec::new(),
+            shake_patterns: Vec::new(),
+            default_message_speed: DEFAULT_MESSAGE_SPEED_MS,
+            default_mwnd_no: 0,
         }
     }
 }
@@ -262,6 +323,9 @@ enum VmCallType {
     Gosub = 1,
     Farcall = 2,
     UserCmd = 3,
+    /// Farcall of a selection's `sel_start_call_scn`; not a C++ call type.
+    /// Like a frame action, returning from it ends the nested run.
+    SelBtnStart = 4,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -293,11 +357,43 @@ struct GroupWaitProc {
     group_idx: i32,
 }
 
-#[derive(Debug, Clone, Default)]
-struct MaskSlotState {
-    name: String,
-    x: i32,
-    y: i32,
+/// One `mask[n]` slot: image name set by `create` plus its stage offset.
+///
+/// The offset is held as clock-driven events so `x_eve`/`y_eve` tweens are
+/// seen by hosts the same way as plain `x`/`y` assigns.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MaskSlotState {
+    pub name: String,
+    pub x: IntEvent,
+    pub y: IntEvent,
+}
+
+impl MaskSlotState {
+    /// Stage offset at `now_ms` on the VM clock.
+    pub fn pos_at(&self, now_ms: u64) -> (i32, i32) {
+        (self.x.value_at(now_ms), self.y.value_at(now_ms))
+    }
+
+    pub fn is_active(&self, now_ms: u64) -> bool {
+        self.x.is_active(now_ms) || self.y.is_active(now_ms)
+    }
+
+    /// Event behind `ELM_MASK_X_EVE` / `ELM_MASK_Y_EVE`.
+    pub fn int_event(&self, sub: i32) -> Option<&IntEvent> {
+        match sub {
+            crate::elm::mask::ELM_MASK_X_EVE => Some(&self.x),
+            crate::elm::mask::ELM_MASK_Y_EVE => Some(&self.y),
+            _ => None,
+        }
+    }
+
+    pub fn int_event_mut(&mut self, sub: i32) -> Option<&mut IntEvent> {
+        match sub {
+            crate::elm::mask::ELM_MASK_X_EVE => Some(&mut self.x),
+            crate::elm::mask::ELM_MASK_Y_EVE => Some(&mut self.y),
+            _ => None,
+        }
+    }
 }
 
 impl Default for FrameAction {
@@ -363,6 +459,14 @@ struct Frame {
     call: CallContext,
 }
 
+impl Frame {
+    /// The frame runs nested in a host-side proc (its own `run_inner`) and
+    /// holds a script proc of its own.
+    fn is_nested_proc(&self) -> bool {
+        self.frame_action_flag || self.call_type == VmCallType::SelBtnStart
+    }
+}
+
 #[derive(Debug, Clone)]
 struct LocalSaveStamp {
     year: i32,
@@ -396,6 +500,8 @@ struct LocalSaveSlot {
     scene_title: String,
     message: String,
     state: VmLocalState,
+    /// Stage capture shown by `create_save_thumb`.
+    thumb: Option<Arc<image::RgbaImage>>,
 }
 
 include!("local_state_struct.rs");
@@ -457,6 +563,8 @@ pub struct Vm {
     cur_mwnd_element: Vec<i32>,
     cur_sel_mwnd_element: Vec<i32>,
     last_sel_msg: String,
+    /// `sel_start_call_scn` / `z_no` cached by `selbtn_ready` for `selbtn_start`.
+    selbtn_start_call: Option<(String, i32)>,
 
     // ----- Minimal system/syscom/script/input state for system scenes -----
     hide_mwnd_onoff_flag: i32,
@@ -504,7 +612,10 @@ pub struct Vm {
     return_scene_once: Option<(String, i32)>,
 
     // ----- Global wipe state (best-effort timing alignment with C++ flow) -----
-    wipe_end_at: Option<Instant>,
+    /// Wipe deadline in `Host::clock()` milliseconds.
+    wipe_end_at: Option<u64>,
+    /// Running wipe and its start time (same clock as `wipe_end_at`).
+    wipe_active: Option<(VmWipeRequest, u64)>,
 
     last_pc: usize,
     last_line_no: i32,
@@ -569,14 +680,42 @@ pub struct Vm {
     bgm_name_listened: BTreeMap<String, bool>,
     g00buf_loaded: Vec<Option<String>>,
     mask_slots: Vec<MaskSlotState>,
+    /// `screen.effect[n]`; effect 0 doubles as `screen.<prop>`.
+    screen_effects: Vec<VmScreenEffect>,
+    /// `stage[n].world` lists by stage index.
+    worlds: BTreeMap<i32, Vec<VmWorld>>,
+    /// `*_eve` events of objects, worlds and masks (effects keep theirs).
+    int_events: BTreeMap<(IntEventScope, i32), IntEvent>,
+    /// `screen.quake[n]`; `None` when that quake never started or was ended.
+    quakes: Vec<Option<VmQuake>>,
+    shake: Option<VmShake>,
+    /// Message page per window (-1 = the current default window).
+    messages: BTreeMap<i32, message::MwndMessage>,
+    message_page_serial: u32,
+    /// `(scene, read_flag_no)` of texts ever shown; kept with global data.
+    read_texts: std::collections::BTreeSet<(String, i32)>,
+    /// Last voice started by `koe` and its character (-1 = none).
+    koe_no: i32,
+    koe_chara_no: i32,
     object_gan_loaded_path: BTreeMap<(i32, i32, i32), String>,
     object_gan_started_set: BTreeMap<(i32, i32, i32), i32>,
+    /// `x_rep`/`y_rep`/`z_rep`/`tr_rep` by `(stage, list, object, sub)`.
+    object_rep_lists: BTreeMap<(i32, i32, i32, i32), Vec<i32>>,
+    rng: VmRng,
+
+    // ----- Debugger watchpoints (not part of save state) -----
+    watchpoints: Vec<watch::WatchSlot>,
+    next_watch_id: usize,
 
     // ----- Local save slots (Rust-native UX-compatible save/load) -----
     local_save_slots: BTreeMap<i32, LocalSaveSlot>,
     quick_save_slots: BTreeMap<i32, LocalSaveSlot>,
     inner_save_slots: BTreeMap<i32, LocalSaveSlot>,
     end_save_slots: BTreeMap<i32, LocalSaveSlot>,
+
+    // ----- Captures (capture / capture_for_local_save) -----
+    capture_image: Option<Arc<image::RgbaImage>>,
+    save_thumb_image: Option<Arc<image::RgbaImage>>,
 }
 
 fn make_user_props(dat: &SceneDat) -> (Vec<i32>, Vec<PropValue>) {
@@ -27,10 +27,15 @@ pub struct VmPersistentState {
     pub cg_group_codes: Vec<[i32; 5]>,
     pub cg_code_exist_cnt: Vec<i32>,
     pub bgm_name_listened: std::collections::BTreeMap<String, bool>,
+    /// `(scene, read_flag_no)` of texts ever shown. Only the global save
+    /// (`Vm::snapshot_global_state`) fills this; loading merges it in.
+    pub read_texts: std::collections::BTreeSet<(String, i32)>,
 }
 
 impl VmPersistentState {
-    const MAGIC: &'static [u8; 5] = b"SVMS1";
+    const MAGIC: &'static [u8; 5] = b"SVMS2";
+    /// Before read flags were stored.
+    const MAGIC_V1: &'static [u8; 5] = b"SVMS1";
     const MAX_VEC_LEN: usize = 1 << 20;
     const MAX_STR_BYTES: usize = 16 * 1024 * 1024;
     const MAX_TOTAL_STR_BYTES: usize = 64 * 1024 * 1024;
@@ -81,6 +86,17 @@ impl VmPersistentState {
         push_str_vec(&mut out, &self.local_namae);
         out.push(if self.save_point_set { 1 } else { 0 });
         out.push(if self.sel_point_set { 1 } else { 0 });
+        // Read flags: scene names, then each scene's flag numbers.
+        let mut by_scene: std::collections::BTreeMap<&str, Vec<i32>> =
+            std::collections::BTreeMap::new();
+        for (scene, no) in &self.read_texts {
+            by_scene.entry(scene.as_str()).or_default().push(*no);
+        }
+        let scenes: Vec<String> = by_scene.keys().map(|s| s.to_string()).collect();
+        push_str_vec(&mut out, &scenes);
+        for nos in by_scene.values() {
+            push_i32_vec(&mut out, nos);
+        }
         out
     }
 
@@ -168,11 +184,11 @@ impl VmPersistentState {
 
         let mut cur = Cursor::new(bytes);
         let magic = read_exact::<5>(&mut cur)?;
-        if &magic != Self::MAGIC {
+        if &magic != Self::MAGIC && &magic != Self::MAGIC_V1 {
             bail!("invalid persistent state magic")
         }
 
-        let st = Self {
+        let mut st = Self {
             flags_a: read_i32_vec(&mut cur)?,
             flags_b: read_i32_vec(&mut cur)?,
             flags_c: read_i32_vec(&mut cur)?,
@@ -196,7 +212,15 @@ impl VmPersistentState {
             cg_group_codes: Vec::new(),
             cg_code_exist_cnt: Vec::new(),
             bgm_name_listened: std::collections::BTreeMap::new(),
+            read_texts: std::collections::BTreeSet::new(),
         };
+        if &magic == Self::MAGIC {
+            for scene in read_str_vec(&mut cur)? {
+                for no in read_i32_vec(&mut cur)? {
+                    st.read_texts.insert((scene.clone(), no));
+                }
+            }
+        }
 
         if cur.position() != bytes.len() as u64 {
             bail!("unexpected trailing bytes in persistent state")
@@ -205,3 +229,28 @@ impl VmPersistentState {
         Ok(st)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_flags_round_trip() {
+        let mut st = VmPersistentState::default();
+        st.read_texts.insert(("s01".to_string(), 3));
+        st.read_texts.insert(("s01".to_string(), 7));
+        st.read_texts.insert(("s02".to_string(), 0));
+        let back = VmPersistentState::decode_binary(&st.encode_binary()).unwrap();
+        assert_eq!(back.read_texts, st.read_texts);
+    }
+
+    #[test]
+    fn v1_data_loads_without_read_flags() {
+        let mut bytes = VmPersistentState::default().encode_binary();
+        bytes[..5].copy_from_slice(VmPersistentState::MAGIC_V1);
+        // Drop the empty read-flag scene list.
+        bytes.truncate(bytes.len() - 4);
+        let st = VmPersistentState::decode_binary(&bytes).unwrap();
+        assert!(st.read_texts.is_empty());
+    }
+}
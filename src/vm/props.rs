@@ -520,14 +520,6 @@ impl Vm {
         )
     }
 
-    fn composite_int_event_owner_id(sub: i32, list_index: Option<i32>) -> i32 {
-        // Keep owner-id stable for host hooks, and encode list index for *_rep_eve paths.
-        match list_index {
-            Some(idx) => ((sub & 0xFFFF) << 16) | (idx & 0xFFFF),
-            None => sub,
-        }
-    }
-
     fn try_property_object_child_composite(
         list_id: i32,
         obj_idx: i32,
@@ -611,13 +603,13 @@ impl Vm {
     }
 
     fn try_property_object_int_event_composite(
+        &mut self,
+        scope: IntEventScope,
         sub: i32,
         tail: &[i32],
-        disp_out_of_range_error: bool,
-        proc_depth: i32,
-        proc_top: i32,
         host: &mut dyn Host,
     ) -> Option<(PropValue, i32)> {
+        let disp_out_of_range_error = self.options.disp_out_of_range_error;
         if tail.is_empty() {
             host.on_error_fatal("CD_PROPERTY stage.object.*_eve: missing method");
             return Some((PropValue::Int(0), crate::elm::form::INT));
@@ -665,64 +657,23 @@ impl Vm {
             (tail[0], None)
         };
 
-        let owner_id = Self::composite_int_event_owner_id(sub, list_idx);
+        let owner_id = Self::int_event_list_owner_id(sub, list_idx);
+        let now = host.clock().now_ms();
         if Self::int_event_method_is_query(method) {
             let v = if method == crate::elm::intevent::ELM_INTEVENT_CHECK {
-                if host.on_int_event_check(owner_id) {
-                    1
-                } else {
-                    0
-                }
+                i32::from(self.int_event_is_active(scope, owner_id, now))
             } else {
-                host.on_int_event_get_value(owner_id)
+                self.int_event_value(scope, owner_id, now).unwrap_or(0)
             };
             return Some((PropValue::Int(v), crate::elm::form::INT));
         }
         if method == crate::elm::intevent::ELM_INTEVENT_WAIT
             || method == crate::elm::intevent::ELM_INTEVENT_WAIT_KEY
         {
-            // flow_proc.cpp alignment: WAIT is proc-driven, so property lane exposes
-            // a poll-style observable state instead of mutating command completion.
-            // Consumer example: script can poll until `EVE_WAIT_DONE` / `EVE_WAIT_KEY_SKIPPED`
-            // and keep stepping while `EVE_WAIT_PENDING` is returned.
+            // Same wait as the command lane; `EVE_WAIT_PENDING` only when interrupted.
             let key_skip = method == crate::elm::intevent::ELM_INTEVENT_WAIT_KEY;
-            let before_wait = host.on_int_event_check(owner_id);
-            if !before_wait {
-                host.on_int_event_wait_status(owner_id, key_skip, crate::vm::EVE_WAIT_DONE);
-                host.on_int_event_wait_status_with_proc(
-                    owner_id,
-                    key_skip,
-                    crate::vm::EVE_WAIT_DONE,
-                    proc_depth,
-                    proc_top,
-                );
-                return Some((
-                    PropValue::Int(crate::vm::EVE_WAIT_DONE),
-                    crate::elm::form::INT,
-                ));
-            }
-            if key_skip && host.should_skip_wait() {
-                host.on_int_event_wait_status(owner_id, key_skip, crate::vm::EVE_WAIT_KEY_SKIPPED);
-                host.on_int_event_wait_status_with_proc(
-                    owner_id,
-                    key_skip,
-                    crate::vm::EVE_WAIT_KEY_SKIPPED,
-                    proc_depth,
-                    proc_top,
-                );
-                return Some((
-                    PropValue::Int(crate::vm::EVE_WAIT_KEY_SKIPPED),
-                    crate::elm::form::INT,
-                ));
-            }
-            host.on_int_event_wait(owner_id, key_skip);
-            host.on_wait_frame();
-            let still_waiting = host.on_int_event_check(owner_id);
-            let status = if still_waiting {
-                crate::vm::EVE_WAIT_PENDING
-            } else {
-                crate::vm::EVE_WAIT_DONE
-            };
+            let status = self.wait_int_event(scope, owner_id, key_skip, host);
+            let (proc_depth, proc_top) = self.observe_proc_stack_tuple();
             host.on_int_event_wait_status(owner_id, key_skip, status);
             host.on_int_event_wait_status_with_proc(
                 owner_id, key_skip, status, proc_depth, proc_top,
@@ -741,14 +692,12 @@ impl Vm {
     }
 
     fn try_property_object_known_command_only_composite(
+        &mut self,
         list_id: i32,
         obj_idx: i32,
         sub: i32,
         tail: &[i32],
         stage_idx: i32,
-        disp_out_of_range_error: bool,
-        proc_depth: i32,
-        proc_top: i32,
         host: &mut dyn Host,
     ) -> Option<(PropValue, i32)> {
         if sub == crate::elm::objectlist::ELM_OBJECT_CHILD {
@@ -757,21 +706,15 @@ impl Vm {
                 obj_idx,
                 tail,
                 stage_idx,
-                disp_out_of_range_error,
+                self.options.disp_out_of_range_error,
                 host,
             );
         }
         if Self::object_composite_is_int_event_sub(sub)
             || Self::object_composite_is_int_event_list_sub(sub)
         {
-            return Self::try_property_object_int_event_composite(
-                sub,
-                tail,
-                disp_out_of_range_error,
-                proc_depth,
-                proc_top,
-                host,
-            );
+            let scope = Self::object_int_event_scope(list_id, Some(stage_idx), obj_idx);
+            return self.try_property_object_int_event_composite(scope, sub, tail, host);
         }
 
         if tail.is_empty() {
@@ -1210,6 +1153,19 @@ impl Vm {
                         host.on_error_fatal("無効なコマンドが指定されました。(frame_action)");
                         return Some((PropValue::Int(0), crate::elm::form::INT));
                     }
+                    if Self::object_sub_is_rep_list(sub) {
+                        return Some((
+                            PropValue::Int(self.object_rep_list_property(
+                                ELM_STAGE_OBJECT,
+                                Some(stage_idx),
+                                obj_idx,
+                                sub,
+                                tail,
+                                host,
+                            )),
+                            crate::elm::form::INT,
+                        ));
+                    }
                     if Self::object_query_is_int(sub) || Self::object_query_is_str(sub) {
                         host.on_error_fatal("CD_PROPERTY stage.object: invalid composite target");
                         return Some((PropValue::Int(0), crate::elm::form::INT));
@@ -1224,16 +1180,12 @@ impl Vm {
                         );
                     }
                     if Self::object_query_is_known_composite_sub(sub) {
-                        let (proc_depth, proc_top) = self.observe_proc_stack_tuple();
-                        return Self::try_property_object_known_command_only_composite(
+                        return self.try_property_object_known_command_only_composite(
                             ELM_STAGE_OBJECT,
                             obj_idx,
                             sub,
                             tail,
                             stage_idx,
-                            self.options.disp_out_of_range_error,
-                            proc_depth,
-                            proc_top,
                             host,
                         );
                     }
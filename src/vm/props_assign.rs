@@ -241,6 +241,24 @@ impl Vm {
                             );
                             return Ok(true);
                         }
+                        if Self::object_sub_is_rep_list(sub)
+                            && tail.len() == 2
+                            && tail[0] == crate::elm::ELM_ARRAY
+                        {
+                            let v = self
+                                .resolve_assign_int_rhs(rhs, host)
+                                .map_err(|e| anyhow::anyhow!("CD_ASSIGN stage.object: {}", e))?;
+                            self.object_rep_list_set(
+                                ELM_STAGE_OBJECT,
+                                Some(stage_idx),
+                                obj_idx,
+                                sub,
+                                tail[1],
+                                v,
+                                host,
+                            );
+                            return Ok(true);
+                        }
                         host.on_error_fatal("CD_ASSIGN stage.object: invalid composite target");
                         return Ok(true);
                     }
@@ -256,9 +274,53 @@ impl Vm {
                         .resolve_assign_int_rhs(rhs, host)
                         .map_err(|e| anyhow::anyhow!("CD_ASSIGN stage.object: {}", e))?;
                     host.on_object_property(ELM_STAGE_OBJECT, obj_idx, sub, v, Some(stage_idx));
+                    self.object_property_assigned(
+                        ELM_STAGE_OBJECT,
+                        obj_idx,
+                        sub,
+                        v,
+                        Some(stage_idx),
+                        host,
+                    );
                     return Ok(true);
                 }
             }
+
+            if pos < element.len() && element[pos] == crate::elm::objectlist::ELM_STAGE_WORLD {
+                pos += 1;
+                if element.len() == pos + 3 && element[pos] == crate::elm::ELM_ARRAY {
+                    use crate::elm::world::*;
+                    let sub = element[pos + 2];
+                    let Some(world_idx) = self.world_index(stage_idx, element[pos + 1], host)
+                    else {
+                        return Ok(true);
+                    };
+                    if matches!(
+                        sub,
+                        ELM_WORLD_CAMERA_EYE_X
+                            | ELM_WORLD_CAMERA_EYE_Y
+                            | ELM_WORLD_CAMERA_EYE_Z
+                            | ELM_WORLD_CAMERA_PINT_X
+                            | ELM_WORLD_CAMERA_PINT_Y
+                            | ELM_WORLD_CAMERA_PINT_Z
+                            | ELM_WORLD_CAMERA_UP_X
+                            | ELM_WORLD_CAMERA_UP_Y
+                            | ELM_WORLD_CAMERA_UP_Z
+                            | ELM_WORLD_CAMERA_VIEW_ANGLE
+                            | ELM_WORLD_MONO
+                            | ELM_WORLD_ORDER
+                            | ELM_WORLD_LAYER
+                            | ELM_WORLD_WIPE_COPY
+                            | ELM_WORLD_WIPE_ERASE
+                    ) {
+                        let v = self
+                            .resolve_assign_int_rhs(rhs, host)
+                            .map_err(|e| anyhow::anyhow!("CD_ASSIGN stage.world: {}", e))?;
+                        self.world_prop_set(stage_idx, world_idx, sub, v, host);
+                        return Ok(true);
+                    }
+                }
+            }
         }
 
         // ----- Flag int-list writes: A[idx] = int -----
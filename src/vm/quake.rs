@@ -0,0 +1,52 @@
+use super::VmQuakeRequest;
+use crate::render::{QuakeTransform, RenderQuake, ShakeStep};
+
+/// One running `screen.quake[n]` (C++ elm_quake.cpp `C_elm_quake`).
+///
+/// The start time is on the VM clock, so `wait`/`check` and every renderer
+/// evaluating `transform_at` with the same `now_ms` agree on the phase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VmQuake {
+    pub req: VmQuakeRequest,
+    pub start_ms: u64,
+}
+
+impl VmQuake {
+    pub fn elapsed_ms(&self, now_ms: u64) -> u64 {
+        now_ms.saturating_sub(self.start_ms)
+    }
+
+    pub fn is_active(&self, now_ms: u64) -> bool {
+        self.elapsed_ms(now_ms) < crate::render::quake_duration_ms(&self.req)
+    }
+
+    pub fn transform_at(&self, now_ms: u64, order: i32) -> QuakeTransform {
+        self.render(now_ms).transform_for_order(order)
+    }
+
+    /// Snapshot for `RenderScene::quakes`.
+    pub fn render(&self, now_ms: u64) -> RenderQuake {
+        RenderQuake {
+            req: self.req,
+            elapsed_ms: self.elapsed_ms(now_ms) as f32,
+        }
+    }
+}
+
+/// Running `screen.shake` pattern (C++ elm_screen.cpp `C_elm_screen_shake`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VmShake {
+    pub shake_no: i32,
+    pub steps: Vec<ShakeStep>,
+    pub start_ms: u64,
+}
+
+impl VmShake {
+    pub fn is_active(&self, now_ms: u64) -> bool {
+        self.offset_at(now_ms).is_some()
+    }
+
+    pub fn offset_at(&self, now_ms: u64) -> Option<(f32, f32)> {
+        crate::render::shake_offset_at(&self.steps, now_ms.saturating_sub(self.start_ms))
+    }
+}
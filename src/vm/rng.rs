@@ -0,0 +1,109 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Seedable PRNG behind `math.rand` and other script-visible random choices.
+///
+/// xorshift64* over a single `u64` word so the whole generator state fits in
+/// end-save runtime state and local save slots; loading a save resumes the same
+/// sequence instead of rerolling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VmRng {
+    state: u64,
+}
+
+impl VmRng {
+    const MUL: u64 = 0x2545_F491_4F6C_DD1D;
+
+    pub fn new(seed: u64) -> Self {
+        // splitmix64 finalizer so small/adjacent seeds still diverge and 0 stays valid.
+        let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+        Self {
+            state: if z == 0 { Self::MUL } else { z },
+        }
+    }
+
+    /// Seed used when `VmOptions::rng_seed` is unset, so headless runs repeat.
+    pub const DEFAULT_SEED: u64 = 0;
+
+    /// Seed from `VmOptions::rng_seed`, falling back to `DEFAULT_SEED`.
+    pub fn from_seed_option(seed: Option<u64>) -> Self {
+        Self::new(seed.unwrap_or(Self::DEFAULT_SEED))
+    }
+
+    /// Wall-clock seed for frontends that want a fresh sequence per launch,
+    /// like the original engine.
+    pub fn time_seed() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0)
+    }
+
+    /// Rebuild from a raw state word previously returned by `state()`.
+    pub fn from_state(state: u64) -> Self {
+        Self {
+            state: if state == 0 { Self::MUL } else { state },
+        }
+    }
+
+    pub fn state(&self) -> u64 {
+        self.state
+    }
+
+    pub fn next_u32(&mut self) -> u32 {
+        let mut x = self.state;
+        x ^= x >> 12;
+        x ^= x << 25;
+        x ^= x >> 27;
+        self.state = x;
+        (x.wrapping_mul(Self::MUL) >> 32) as u32
+    }
+
+    /// Uniform value in `[0, upper)`; returns 0 when `upper <= 0`.
+    pub fn next_below(&mut self, upper: i32) -> i32 {
+        if upper <= 0 {
+            return 0;
+        }
+        ((self.next_u32() as u64 * upper as u64) >> 32) as i32
+    }
+}
+
+impl Default for VmRng {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_SEED)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unset_seed_is_deterministic() {
+        let mut a = VmRng::from_seed_option(None);
+        let mut b = VmRng::default();
+        for _ in 0..16 {
+            assert_eq!(a.next_u32(), b.next_u32());
+        }
+    }
+
+    #[test]
+    fn state_round_trip_resumes_sequence() {
+        let mut rng = VmRng::new(42);
+        rng.next_u32();
+        let mut resumed = VmRng::from_state(rng.state());
+        assert_eq!(rng.next_u32(), resumed.next_u32());
+    }
+
+    #[test]
+    fn next_below_stays_in_range() {
+        let mut rng = VmRng::new(7);
+        assert_eq!(rng.next_below(0), 0);
+        assert_eq!(rng.next_below(-3), 0);
+        for _ in 0..256 {
+            assert!((0..10).contains(&rng.next_below(10)));
+        }
+    }
+}
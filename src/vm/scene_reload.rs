@@ -0,0 +1,151 @@
+use super::*;
+
+/// Where a restarted scene resumes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RestartLabel {
+    Label(i32),
+    ZLabel(i32),
+}
+
+impl Vm {
+    /// Nearest label or z-label at or before `pc` in the current dat (z-label wins ties).
+    fn nearest_restart_label(&self, pc: usize) -> RestartLabel {
+        let nearest = |list: &[i32]| {
+            list.iter()
+                .enumerate()
+                .filter(|(_, ofs)| **ofs >= 0 && **ofs as usize <= pc)
+                .max_by_key(|(_, ofs)| **ofs)
+                .map(|(i, ofs)| (i as i32, *ofs))
+        };
+        let z = nearest(&self.lexer.dat.z_labels);
+        let l = nearest(&self.lexer.dat.labels);
+        match (z, l) {
+            (_, Some((li, lo))) if z.is_none_or(|(_, zo)| lo > zo) => RestartLabel::Label(li),
+            (Some((zi, _)), _) => RestartLabel::ZLabel(zi),
+            _ => RestartLabel::ZLabel(0),
+        }
+    }
+
+    /// Re-enter the current scene from a freshly decoded dat.
+    ///
+    /// Like a jump to that label, the call stack is kept: caller frames, `call.L/K`
+    /// and procs stay, and frames returning into a changed scene return into its
+    /// new dat at their recorded offset. Only the int stack of the interrupted
+    /// statement and pending key/group waits are dropped.
+    fn restart_current_scene(
+        &mut self,
+        changed_scenes: &[String],
+        provider: &mut dyn SceneProvider,
+    ) -> Result<RestartLabel> {
+        let target = self.nearest_restart_label(self.lexer.pc);
+        let dat = provider.get_scene(&self.scene)?;
+        for frame in &mut self.frames {
+            if frame.return_scene == self.scene {
+                frame.return_dat = dat.clone();
+            } else if changed_scenes.contains(&frame.return_scene) {
+                frame.return_dat = provider.get_scene(&frame.return_scene)?;
+            }
+        }
+        self.stack = IfcStack::default();
+        self.key_wait_proc = KeyWaitProc::default();
+        self.group_wait_proc = GroupWaitProc::default();
+        self.lexer.set_scene(dat);
+        self.reload_user_props_from_current_scene();
+        let jumped = match target {
+            RestartLabel::Label(no) => self.lexer.jump_to_label(no),
+            RestartLabel::ZLabel(no) => self.lexer.jump_to_z_label(no),
+        };
+        if jumped.is_err() {
+            // The label vanished in the rebuilt scene; start over from z-label 0.
+            self.lexer.jump_to_z_label(0)?;
+            return Ok(RestartLabel::ZLabel(0));
+        }
+        Ok(target)
+    }
+
+    /// Apply a provider-reported reload at an instruction boundary.
+    pub(super) fn poll_scene_reload(
+        &mut self,
+        host: &mut dyn Host,
+        provider: &mut dyn SceneProvider,
+    ) -> Result<()> {
+        let Some(reload) = provider.poll_scene_reload(host.clock().now_ms()) else {
+            return Ok(());
+        };
+        let current_changed = reload.changed_scenes.contains(&self.scene);
+        let mut restarted = false;
+        if reload.restart && current_changed {
+            match self.restart_current_scene(&reload.changed_scenes, provider) {
+                Ok(target) => {
+                    restarted = true;
+                    host.on_trace(&format!(
+                        "hot reload: restarted {} at {:?}",
+                        self.scene, target
+                    ));
+                }
+                Err(e) => {
+                    host.on_error(&format!(
+                        "hot reload: restart {} failed: {:#}",
+                        self.scene, e
+                    ));
+                }
+            }
+        }
+        host.on_scene_reload(&reload.changed_scenes, restarted);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vm::test_support::{EmptySceneProvider, empty_scene_dat, test_vm};
+
+    fn vm_with_labels(labels: Vec<i32>, z_labels: Vec<i32>) -> Vm {
+        let mut vm = test_vm();
+        let mut dat = (*empty_scene_dat()).clone();
+        dat.labels = labels;
+        dat.z_labels = z_labels;
+        vm.lexer.set_scene(Arc::new(dat));
+        vm
+    }
+
+    #[test]
+    fn nearest_label_prefers_the_closest_before_pc() {
+        let vm = vm_with_labels(vec![4, 12], vec![0, 8]);
+        assert_eq!(vm.nearest_restart_label(10), RestartLabel::ZLabel(1));
+        assert_eq!(vm.nearest_restart_label(12), RestartLabel::Label(1));
+        assert_eq!(vm.nearest_restart_label(8), RestartLabel::ZLabel(1));
+        assert_eq!(vm.nearest_restart_label(2), RestartLabel::ZLabel(0));
+    }
+
+    #[test]
+    fn restart_keeps_the_call_stack() {
+        let mut vm = test_vm();
+        let mut provider = EmptySceneProvider;
+        vm.proc_farcall_like(
+            "other",
+            0,
+            crate::elm::form::VOID,
+            &[],
+            false,
+            &mut provider,
+        )
+        .unwrap();
+        vm.frames.last_mut().unwrap().call.l[0] = 9;
+        vm.proc_stack.push(VmProcType::Script);
+        vm.stack.push_int(7);
+
+        let frames = vm.frames.len();
+        let procs = vm.proc_stack.clone();
+        let caller_dat = vm.frames[1].return_dat.clone();
+        vm.restart_current_scene(&["test".to_string()], &mut provider)
+            .unwrap();
+        assert_eq!(vm.frames.len(), frames);
+        assert_eq!(vm.frames.last().unwrap().call.l[0], 9);
+        assert_eq!(vm.frames[1].return_scene, "test");
+        assert!(!Arc::ptr_eq(&vm.frames[1].return_dat, &caller_dat));
+        assert_eq!(vm.proc_stack, procs);
+        assert_eq!(vm.stack.pop_int().unwrap_or(0), 0);
+    }
+}
@@ -0,0 +1,284 @@
+use super::IntEvent;
+use crate::elm::effect::*;
+
+/// One `screen.effect[n]` layer (C++ elm_effect.cpp `C_elm_effect`).
+///
+/// `screen.<prop>` addresses effect 0. Values are script units (`mono` and
+/// `color_rate` 0..255, `bright`/`dark` 0..255); `resolve` converts them for the
+/// renderer. The order/layer ranges default to every object.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VmScreenEffect {
+    pub x: IntEvent,
+    pub y: IntEvent,
+    pub z: IntEvent,
+    pub mono: IntEvent,
+    pub reverse: IntEvent,
+    pub bright: IntEvent,
+    pub dark: IntEvent,
+    pub color_r: IntEvent,
+    pub color_g: IntEvent,
+    pub color_b: IntEvent,
+    pub color_rate: IntEvent,
+    pub color_add_r: IntEvent,
+    pub color_add_g: IntEvent,
+    pub color_add_b: IntEvent,
+    pub begin_order: i32,
+    pub end_order: i32,
+    pub begin_layer: i32,
+    pub end_layer: i32,
+    pub wipe_copy: i32,
+    pub wipe_erase: i32,
+}
+
+impl Default for VmScreenEffect {
+    fn default() -> Self {
+        Self {
+            x: IntEvent::default(),
+            y: IntEvent::default(),
+            z: IntEvent::default(),
+            mono: IntEvent::default(),
+            reverse: IntEvent::default(),
+            bright: IntEvent::default(),
+            dark: IntEvent::default(),
+            color_r: IntEvent::default(),
+            color_g: IntEvent::default(),
+            color_b: IntEvent::default(),
+            color_rate: IntEvent::default(),
+            color_add_r: IntEvent::default(),
+            color_add_g: IntEvent::default(),
+            color_add_b: IntEvent::default(),
+            begin_order: i32::MIN,
+            end_order: i32::MAX,
+            begin_layer: i32::MIN,
+            end_layer: i32::MAX,
+            wipe_copy: 0,
+            wipe_erase: 0,
+        }
+    }
+}
+
+impl VmScreenEffect {
+    /// Animatable property by `ELM_EFFECT_*` id; `*_EVE` ids map to their property.
+    pub fn int_event(&self, prop: i32) -> Option<&IntEvent> {
+        self.int_events().into_iter().nth(Self::event_index(prop)?)
+    }
+
+    pub fn int_event_mut(&mut self, prop: i32) -> Option<&mut IntEvent> {
+        let idx = Self::event_index(prop)?;
+        self.int_events_mut().into_iter().nth(idx)
+    }
+
+    /// Plain int property (`begin_order` … `end_layer`, `wipe_copy`, `wipe_erase`).
+    pub fn int_prop_mut(&mut self, prop: i32) -> Option<&mut i32> {
+        match prop {
+            ELM_EFFECT_BEGIN_ORDER => Some(&mut self.begin_order),
+            ELM_EFFECT_END_ORDER => Some(&mut self.end_order),
+            ELM_EFFECT_BEGIN_LAYER => Some(&mut self.begin_layer),
+            ELM_EFFECT_END_LAYER => Some(&mut self.end_layer),
+            ELM_EFFECT_WIPE_COPY => Some(&mut self.wipe_copy),
+            ELM_EFFECT_WIPE_ERASE => Some(&mut self.wipe_erase),
+            _ => None,
+        }
+    }
+
+    /// Map a `screen.<prop>` / `screen.<prop>_eve` id to the `ELM_EFFECT_*` id on effect 0.
+    pub fn screen_prop(sub: i32) -> Option<i32> {
+        use crate::elm::screen::*;
+        let prop = match sub {
+            ELM_SCREEN_X => ELM_EFFECT_X,
+            ELM_SCREEN_Y => ELM_EFFECT_Y,
+            ELM_SCREEN_Z => ELM_EFFECT_Z,
+            ELM_SCREEN_MONO => ELM_EFFECT_MONO,
+            ELM_SCREEN_REVERSE => ELM_EFFECT_REVERSE,
+            ELM_SCREEN_BRIGHT => ELM_EFFECT_BRIGHT,
+            ELM_SCREEN_DARK => ELM_EFFECT_DARK,
+            ELM_SCREEN_COLOR_R => ELM_EFFECT_COLOR_R,
+            ELM_SCREEN_COLOR_G => ELM_EFFECT_COLOR_G,
+            ELM_SCREEN_COLOR_B => ELM_EFFECT_COLOR_B,
+            ELM_SCREEN_COLOR_RATE => ELM_EFFECT_COLOR_RATE,
+            ELM_SCREEN_COLOR_ADD_R => ELM_EFFECT_COLOR_ADD_R,
+            ELM_SCREEN_COLOR_ADD_G => ELM_EFFECT_COLOR_ADD_G,
+            ELM_SCREEN_COLOR_ADD_B => ELM_EFFECT_COLOR_ADD_B,
+            ELM_SCREEN_X_EVE => ELM_EFFECT_X_EVE,
+            ELM_SCREEN_Y_EVE => ELM_EFFECT_Y_EVE,
+            ELM_SCREEN_Z_EVE => ELM_EFFECT_Z_EVE,
+            ELM_SCREEN_MONO_EVE => ELM_EFFECT_MONO_EVE,
+            ELM_SCREEN_REVERSE_EVE => ELM_EFFECT_REVERSE_EVE,
+            ELM_SCREEN_BRIGHT_EVE => ELM_EFFECT_BRIGHT_EVE,
+            ELM_SCREEN_DARK_EVE => ELM_EFFECT_DARK_EVE,
+            ELM_SCREEN_COLOR_R_EVE => ELM_EFFECT_COLOR_R_EVE,
+            ELM_SCREEN_COLOR_G_EVE => ELM_EFFECT_COLOR_G_EVE,
+            ELM_SCREEN_COLOR_B_EVE => ELM_EFFECT_COLOR_B_EVE,
+            ELM_SCREEN_COLOR_RATE_EVE => ELM_EFFECT_COLOR_RATE_EVE,
+            ELM_SCREEN_COLOR_ADD_R_EVE => ELM_EFFECT_COLOR_ADD_R_EVE,
+            ELM_SCREEN_COLOR_ADD_G_EVE => ELM_EFFECT_COLOR_ADD_G_EVE,
+            ELM_SCREEN_COLOR_ADD_B_EVE => ELM_EFFECT_COLOR_ADD_B_EVE,
+            _ => return None,
+        };
+        Some(prop)
+    }
+
+    /// Resume the `_eve` animations `delta_ms` later (see `IntEvent::shift_ms`).
+    pub(super) fn shift_ms(&mut self, delta_ms: u64) {
+        for ev in self.int_events_mut() {
+            ev.shift_ms(delta_ms);
+        }
+    }
+
+    /// Whether any `_eve` animation is still running at `now_ms`.
+    pub fn is_animating(&self, now_ms: u64) -> bool {
+        self.int_events().iter().any(|e| e.is_active(now_ms))
+    }
+
+    /// Renderer view at `now_ms` on the VM clock (`z` is not rendered).
+    pub fn resolve(&self, now_ms: u64) -> crate::render::RenderEffect {
+        let v = |e: &IntEvent| e.value_at(now_ms) as f32;
+        crate::render::RenderEffect {
+            x: v(&self.x),
+            y: v(&self.y),
+            color: crate::render::RenderColor {
+                mono: (v(&self.mono) / 255.0).clamp(0.0, 1.0),
+                reverse: self.reverse.value_at(now_ms) != 0,
+                bright: v(&self.bright).clamp(0.0, 255.0),
+                dark: v(&self.dark).clamp(0.0, 255.0),
+                tint_r: v(&self.color_r).clamp(0.0, 255.0),
+                tint_g: v(&self.color_g).clamp(0.0, 255.0),
+                tint_b: v(&self.color_b).clamp(0.0, 255.0),
+                tint_rate: (v(&self.color_rate) / 255.0).clamp(0.0, 1.0),
+                color_add_r: v(&self.color_add_r).clamp(-255.0, 255.0),
+                color_add_g: v(&self.color_add_g).clamp(-255.0, 255.0),
+                color_add_b: v(&self.color_add_b).clamp(-255.0, 255.0),
+                ..Default::default()
+            },
+            begin_order: self.begin_order,
+            end_order: self.end_order,
+            begin_layer: self.begin_layer,
+            end_layer: self.end_layer,
+        }
+    }
+
+    /// Index into `int_events()`: `ELM_EFFECT_X..=COLOR_ADD_B` and their `_EVE` twins.
+    fn event_index(prop: i32) -> Option<usize> {
+        match prop {
+            ELM_EFFECT_X..=ELM_EFFECT_COLOR_ADD_B => Some((prop - ELM_EFFECT_X) as usize),
+            ELM_EFFECT_X_EVE..=ELM_EFFECT_COLOR_ADD_B_EVE => {
+                Some((prop - ELM_EFFECT_X_EVE) as usize)
+            }
+            _ => None,
+        }
+    }
+
+    fn int_events(&self) -> [&IntEvent; 14] {
+        [
+            &self.x,
+            &self.y,
+            &self.z,
+            &self.mono,
+            &self.reverse,
+            &self.bright,
+            &self.dark,
+            &self.color_r,
+            &self.color_g,
+            &self.color_b,
+            &self.color_rate,
+            &self.color_add_r,
+            &self.color_add_g,
+            &self.color_add_b,
+        ]
+    }
+
+    fn int_events_mut(&mut self) -> [&mut IntEvent; 14] {
+        [
+            &mut self.x,
+            &mut self.y,
+            &mut self.z,
+            &mut self.mono,
+            &mut self.reverse,
+            &mut self.bright,
+            &mut self.dark,
+            &mut self.color_r,
+            &mut self.color_g,
+            &mut self.color_b,
+            &mut self.color_rate,
+            &mut self.color_add_r,
+            &mut self.color_add_g,
+            &mut self.color_add_b,
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn eve_ids_address_the_same_event_as_their_property() {
+        let mut effect = VmScreenEffect::default();
+        effect
+            .int_event_mut(ELM_EFFECT_DARK_EVE)
+            .unwrap()
+            .set_value(40);
+        assert_eq!(effect.dark.value(), 40);
+        assert_eq!(effect.int_event(ELM_EFFECT_DARK).unwrap().value(), 40);
+        effect
+            .int_event_mut(ELM_EFFECT_COLOR_ADD_B)
+            .unwrap()
+            .set_value(-3);
+        assert_eq!(effect.color_add_b.value(), -3);
+        assert!(effect.int_event(ELM_EFFECT_BEGIN_ORDER).is_none());
+    }
+
+    #[test]
+    fn plain_props_are_not_events() {
+        let mut effect = VmScreenEffect::default();
+        *effect.int_prop_mut(ELM_EFFECT_END_LAYER).unwrap() = 7;
+        assert_eq!(effect.end_layer, 7);
+        assert!(effect.int_prop_mut(ELM_EFFECT_X).is_none());
+        assert_eq!(effect.begin_order, i32::MIN);
+    }
+
+    #[test]
+    fn screen_props_map_onto_effect_zero() {
+        use crate::elm::screen::*;
+        assert_eq!(
+            VmScreenEffect::screen_prop(ELM_SCREEN_MONO),
+            Some(ELM_EFFECT_MONO)
+        );
+        assert_eq!(
+            VmScreenEffect::screen_prop(ELM_SCREEN_COLOR_RATE_EVE),
+            Some(ELM_EFFECT_COLOR_RATE_EVE)
+        );
+    }
+
+    #[test]
+    fn resolve_scales_and_clamps_script_units() {
+        let mut effect = VmScreenEffect::default();
+        effect.x.set_value(12);
+        effect.mono.set_value(510);
+        effect.reverse.set_value(1);
+        effect.color_rate.set_value(51);
+        effect.color_add_r.set_value(-300);
+        effect.end_order = 4;
+        let r = effect.resolve(0);
+        assert_eq!(r.x, 12.0);
+        assert_eq!(r.color.mono, 1.0);
+        assert!(r.color.reverse);
+        assert_eq!(r.color.tint_rate, 0.2);
+        assert_eq!(r.color.color_add_r, -255.0);
+        assert_eq!((r.begin_order, r.end_order), (i32::MIN, 4));
+    }
+
+    #[test]
+    fn animations_follow_the_clock_and_shift_later() {
+        let mut effect = VmScreenEffect::default();
+        effect.bright.set_event(100, 1000, 0, 0, 0);
+        assert!(effect.is_animating(500));
+        assert_eq!(effect.resolve(500).color.bright, 50.0);
+        assert!(!effect.is_animating(1000));
+
+        effect.shift_ms(500);
+        assert!(effect.is_animating(1000));
+        assert_eq!(effect.resolve(1000).color.bright, 50.0);
+        assert_eq!(effect.resolve(2000).color.bright, 100.0);
+    }
+}
@@ -263,7 +263,7 @@ impl Vm {
 
         // pop callee
         let callee = self.frames.pop().unwrap();
-        let frame_action_flag = callee.frame_action_flag;
+        let nested_proc = callee.is_nested_proc();
         let ex_call_flag = callee.excall_flag;
         self.scene = callee.return_scene;
         self.lexer.set_scene(callee.return_dat);
@@ -317,14 +317,15 @@ impl Vm {
         if ex_call_flag {
             host.on_input_clear();
         }
-        if ex_call_flag || frame_action_flag {
+        if ex_call_flag || nested_proc {
             self.pop_script_proc();
         }
         self.reconcile_proc_stack();
 
-        // If this was invoked as a frame action, stop after returning to the caller.
+        // If this was invoked as a frame action (or selection start call), stop
+        // after returning to the caller.
         // (Matches tnm_proc_script() behaviour when frame_action_flag is set.)
-        Ok(!frame_action_flag)
+        Ok(!nested_proc)
     }
 
     pub(super) fn calculate_1(&mut self, form: i32, opr: u8, _host: &mut dyn Host) -> Result<()> {
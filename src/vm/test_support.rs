@@ -0,0 +1,100 @@
+//! Shared fixtures for VM unit tests.
+use super::*;
+
+/// Compiled `.dat` bytes holding `scn_size` zero bytes of script and no tables.
+pub(crate) fn scene_dat_bytes(scn_size: usize) -> Vec<u8> {
+    let mut bytes = vec![0u8; crate::dat::SceneHeader::SIZE + scn_size];
+    let scn_ofs = crate::dat::SceneHeader::SIZE as i32;
+    bytes[0..4].copy_from_slice(&scn_ofs.to_le_bytes());
+    bytes[4..8].copy_from_slice(&scn_ofs.to_le_bytes());
+    bytes[8..12].copy_from_slice(&(scn_size as i32).to_le_bytes());
+    bytes
+}
+
+/// Unencrypted Scene.pck bytes holding `scenes` as plain `.dat` blobs.
+pub(crate) fn pack_bytes(scenes: &[(&str, Vec<u8>)]) -> Vec<u8> {
+    let header_size = 92;
+    let name_index_ofs = header_size;
+    let name_list_ofs = name_index_ofs + scenes.len() * 8;
+    let names: Vec<Vec<u16>> = scenes
+        .iter()
+        .map(|(n, _)| n.encode_utf16().collect())
+        .collect();
+    let data_index_ofs = name_list_ofs + names.iter().map(|n| n.len() * 2).sum::<usize>();
+    let data_list_ofs = data_index_ofs + scenes.len() * 8;
+
+    let mut out = vec![0u8; data_list_ofs];
+    let put = |out: &mut Vec<u8>, ofs: usize, v: usize| {
+        out[ofs..ofs + 4].copy_from_slice(&(v as i32).to_le_bytes());
+    };
+    let n = scenes.len();
+    for (field, v) in [
+        (0, header_size),
+        (52, name_index_ofs),
+        (56, n),
+        (60, name_list_ofs),
+        (64, n),
+        (68, data_index_ofs),
+        (72, n),
+        (76, data_list_ofs),
+        (80, n),
+    ] {
+        put(&mut out, field, v);
+    }
+    let mut name_pos = 0;
+    let mut data_pos = 0;
+    for (i, ((_, dat), name)) in scenes.iter().zip(&names).enumerate() {
+        put(&mut out, name_index_ofs + i * 8, name_pos);
+        put(&mut out, name_index_ofs + i * 8 + 4, name.len());
+        for (j, w) in name.iter().enumerate() {
+            let at = name_list_ofs + (name_pos + j) * 2;
+            out[at..at + 2].copy_from_slice(&w.to_le_bytes());
+        }
+        name_pos += name.len();
+        put(&mut out, data_index_ofs + i * 8, data_pos);
+        put(&mut out, data_index_ofs + i * 8 + 4, dat.len());
+        data_pos += dat.len();
+    }
+    for (_, dat) in scenes {
+        out.extend_from_slice(dat);
+    }
+    out
+}
+
+/// Minimal scene: z-label 0 at the start of a few zero bytes of script.
+pub(crate) fn empty_scene_dat() -> Arc<SceneDat> {
+    let bytes = scene_dat_bytes(16);
+    let mut dat = crate::dat::parse(&bytes).expect("empty scene parses");
+    dat.z_labels = vec![0];
+    Arc::new(dat)
+}
+
+pub(crate) fn test_vm() -> Vm {
+    Vm::new("test".to_string(), empty_scene_dat())
+}
+
+/// Host that records watchpoint hits and otherwise keeps every default.
+#[derive(Default)]
+pub(crate) struct RecordingHost {
+    pub watch_hits: Vec<VmWatchHit>,
+}
+
+impl Host for RecordingHost {
+    fn on_watchpoint(&mut self, hit: &VmWatchHit) {
+        self.watch_hits.push(hit.clone());
+    }
+}
+
+/// Provider serving `empty_scene_dat` for every scene name.
+#[derive(Default)]
+pub(crate) struct EmptySceneProvider;
+
+impl SceneProvider for EmptySceneProvider {
+    fn get_scene(&mut self, _scene: &str) -> Result<Arc<SceneDat>> {
+        Ok(empty_scene_dat())
+    }
+
+    fn has_scene(&self, _scene: &str) -> bool {
+        true
+    }
+}
@@ -0,0 +1,389 @@
+use super::*;
+
+/// Storage family a watchpoint observes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum VmWatchList {
+    A,
+    B,
+    C,
+    D,
+    E,
+    F,
+    G,
+    X,
+    Z,
+    S,
+    M,
+    /// `call.L` of the current call frame.
+    CallL,
+    /// `call.K` of the current call frame.
+    CallK,
+    /// `counter[n]` values.
+    Counter,
+    /// CG table flags (`cg_table.set_flag*` and friends).
+    CgFlag,
+    /// Scene user props (`CD_DEC_PROP` at scene scope).
+    UserProp,
+}
+
+impl VmWatchList {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::A => "A",
+            Self::B => "B",
+            Self::C => "C",
+            Self::D => "D",
+            Self::E => "E",
+            Self::F => "F",
+            Self::G => "G",
+            Self::X => "X",
+            Self::Z => "Z",
+            Self::S => "S",
+            Self::M => "M",
+            Self::CallL => "L",
+            Self::CallK => "K",
+            Self::Counter => "counter",
+            Self::CgFlag => "cg",
+            Self::UserProp => "user_prop",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        let list = match name.trim().to_ascii_lowercase().as_str() {
+            "a" => Self::A,
+            "b" => Self::B,
+            "c" => Self::C,
+            "d" => Self::D,
+            "e" => Self::E,
+            "f" => Self::F,
+            "g" => Self::G,
+            "x" => Self::X,
+            "z" => Self::Z,
+            "s" => Self::S,
+            "m" => Self::M,
+            "l" | "call.l" => Self::CallL,
+            "k" | "call.k" => Self::CallK,
+            "counter" => Self::Counter,
+            "cg" | "cg_flag" => Self::CgFlag,
+            "user_prop" | "prop" => Self::UserProp,
+            _ => return None,
+        };
+        Some(list)
+    }
+}
+
+/// One watch registration; `index: None` watches every slot of the list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VmWatchpoint {
+    pub list: VmWatchList,
+    pub index: Option<usize>,
+}
+
+impl VmWatchpoint {
+    pub fn new(list: VmWatchList, index: Option<usize>) -> Self {
+        Self { list, index }
+    }
+
+    /// Parse a comma separated spec such as `A[3],Z,counter[2],cg[10],L[0]`.
+    pub fn parse_list(spec: &str) -> Result<Vec<Self>> {
+        let mut out = Vec::new();
+        for part in spec.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            let (name, index) = match part.split_once('[') {
+                Some((name, rest)) => {
+                    let idx = rest
+                        .strip_suffix(']')
+                        .with_context(|| format!("watchpoint {part}: missing ']'"))?;
+                    let idx = idx
+                        .trim()
+                        .parse::<usize>()
+                        .with_context(|| format!("watchpoint {part}: bad index"))?;
+                    (name, Some(idx))
+                }
+                None => (part, None),
+            };
+            let Some(list) = VmWatchList::from_name(name) else {
+                bail!("watchpoint {part}: unknown list {name}");
+            };
+            out.push(Self { list, index });
+        }
+        Ok(out)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VmWatchValue {
+    Int(i32),
+    Str(String),
+    IntList(Vec<i32>),
+    StrList(Vec<String>),
+    Element(Vec<i32>),
+}
+
+impl std::fmt::Display for VmWatchValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Int(v) => write!(f, "{v}"),
+            Self::Str(s) => write!(f, "{s:?}"),
+            Self::IntList(v) => write!(f, "{v:?}"),
+            Self::StrList(v) => write!(f, "{v:?}"),
+            Self::Element(v) => write!(f, "element{v:?}"),
+        }
+    }
+}
+
+/// A watched value changed between two VM steps.
+///
+/// `scene`/`line_no` identify the instruction that was executing when the change
+/// was observed (CD_ASSIGN, a command such as `cg_table.set_flag_value_from_group_code`,
+/// or a frame-driven counter tick).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VmWatchHit {
+    pub id: usize,
+    pub list: VmWatchList,
+    pub index: usize,
+    pub old: Option<VmWatchValue>,
+    pub new: Option<VmWatchValue>,
+    pub scene: String,
+    pub line_no: i32,
+}
+
+/// Borrowed view of a watched list, so polling compares without cloning it.
+enum WatchView<'a> {
+    Ints(&'a [i32]),
+    Strs(&'a [String]),
+    Props(&'a [PropValue]),
+}
+
+impl WatchView<'_> {
+    fn len(&self) -> usize {
+        match self {
+            Self::Ints(v) => v.len(),
+            Self::Strs(v) => v.len(),
+            Self::Props(v) => v.len(),
+        }
+    }
+
+    fn eq_at(&self, i: usize, value: &VmWatchValue) -> bool {
+        match (self, value) {
+            (Self::Ints(v), VmWatchValue::Int(x)) => v[i] == *x,
+            (Self::Strs(v), VmWatchValue::Str(x)) => v[i] == *x,
+            (Self::Props(v), _) => match (&v[i], value) {
+                (PropValue::Int(a), VmWatchValue::Int(b)) => a == b,
+                (PropValue::Str(a), VmWatchValue::Str(b)) => a == b,
+                (PropValue::IntList(a), VmWatchValue::IntList(b)) => a == b,
+                (PropValue::StrList(a), VmWatchValue::StrList(b)) => a == b,
+                (PropValue::Element(a), VmWatchValue::Element(b)) => a == b,
+                (PropValue::List(_), VmWatchValue::Element(b)) => b.is_empty(),
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    fn value_at(&self, i: usize) -> VmWatchValue {
+        match self {
+            Self::Ints(v) => VmWatchValue::Int(v[i]),
+            Self::Strs(v) => VmWatchValue::Str(v[i].clone()),
+            Self::Props(v) => match &v[i] {
+                PropValue::Int(x) => VmWatchValue::Int(*x),
+                PropValue::Str(x) => VmWatchValue::Str(x.clone()),
+                PropValue::IntList(x) => VmWatchValue::IntList(x.clone()),
+                PropValue::StrList(x) => VmWatchValue::StrList(x.clone()),
+                PropValue::Element(x) => VmWatchValue::Element(x.clone()),
+                PropValue::List(_) => VmWatchValue::Element(Vec::new()),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct WatchSlot {
+    id: usize,
+    point: VmWatchpoint,
+    /// Baseline values; `None` until the first poll (or after a state restore).
+    last: Option<Vec<VmWatchValue>>,
+    /// Call depth the `call.L/K` baseline was taken at.
+    call_depth: usize,
+}
+
+impl Vm {
+    /// Register a watchpoint and return its id.
+    pub fn add_watchpoint(&mut self, point: VmWatchpoint) -> usize {
+        let id = self.next_watch_id;
+        self.next_watch_id += 1;
+        self.watchpoints.push(WatchSlot {
+            id,
+            point,
+            last: None,
+            call_depth: 0,
+        });
+        id
+    }
+
+    pub fn remove_watchpoint(&mut self, id: usize) -> bool {
+        let before = self.watchpoints.len();
+        self.watchpoints.retain(|w| w.id != id);
+        self.watchpoints.len() != before
+    }
+
+    pub fn clear_watchpoints(&mut self) {
+        self.watchpoints.clear();
+    }
+
+    pub fn watchpoints(&self) -> Vec<(usize, VmWatchpoint)> {
+        self.watchpoints.iter().map(|w| (w.id, w.point)).collect()
+    }
+
+    /// Drop baselines so bulk state restores (load, persistent apply) are not reported.
+    pub(super) fn rebaseline_watchpoints(&mut self) {
+        for w in &mut self.watchpoints {
+            w.last = None;
+        }
+    }
+
+    fn watch_view(&self, list: VmWatchList) -> WatchView<'_> {
+        match list {
+            VmWatchList::A => WatchView::Ints(&self.flags_a),
+            VmWatchList::B => WatchView::Ints(&self.flags_b),
+            VmWatchList::C => WatchView::Ints(&self.flags_c),
+            VmWatchList::D => WatchView::Ints(&self.flags_d),
+            VmWatchList::E => WatchView::Ints(&self.flags_e),
+            VmWatchList::F => WatchView::Ints(&self.flags_f),
+            VmWatchList::G => WatchView::Ints(&self.flags_g),
+            VmWatchList::X => WatchView::Ints(&self.flags_x),
+            VmWatchList::Z => WatchView::Ints(&self.flags_z),
+            VmWatchList::S => WatchView::Strs(&self.flags_s),
+            VmWatchList::M => WatchView::Strs(&self.flags_m),
+            VmWatchList::CallL => WatchView::Ints(self.frames.last().map_or(&[], |f| &f.call.l)),
+            VmWatchList::CallK => WatchView::Strs(self.frames.last().map_or(&[], |f| &f.call.k)),
+            VmWatchList::Counter => {
+                let n = self.counter_list_size.min(self.counter_values.len());
+                WatchView::Ints(&self.counter_values[..n])
+            }
+            VmWatchList::CgFlag => WatchView::Ints(&self.cg_flags),
+            VmWatchList::UserProp => WatchView::Props(&self.user_prop_values),
+        }
+    }
+
+    /// Compare watched values against their baselines and report changes to the host.
+    ///
+    /// Values are compared in place; a baseline is only rebuilt when it changed.
+    pub(super) fn poll_watchpoints(&mut self, host: &mut dyn Host) {
+        if self.watchpoints.is_empty() {
+            return;
+        }
+        let call_depth = self.frames.len();
+        let mut hits = Vec::new();
+        for slot in 0..self.watchpoints.len() {
+            let point = self.watchpoints[slot].point;
+            let view = self.watch_view(point.list);
+            let (start, end) = match point.index {
+                Some(i) => (i.min(view.len()), i.saturating_add(1).min(view.len())),
+                None => (0, view.len()),
+            };
+            let w = &self.watchpoints[slot];
+            let is_call = matches!(point.list, VmWatchList::CallL | VmWatchList::CallK);
+            // call.L/K baselines are per frame; a call/return re-baselines instead of reporting.
+            let same_frame = !is_call || w.call_depth == call_depth;
+            if let Some(last) = &w.last
+                && same_frame
+            {
+                let before = hits.len();
+                let base = point.index.unwrap_or(0);
+                for i in 0..last.len().max(end - start) {
+                    let old = last.get(i);
+                    let at = start + i;
+                    let unchanged = match old {
+                        Some(old) => at < end && view.eq_at(at, old),
+                        None => at >= end,
+                    };
+                    if !unchanged {
+                        hits.push(VmWatchHit {
+                            id: w.id,
+                            list: point.list,
+                            index: base + i,
+                            old: old.cloned(),
+                            new: (at < end).then(|| view.value_at(at)),
+                            scene: self.last_scene.clone(),
+                            line_no: self.last_line_no,
+                        });
+                    }
+                }
+                if hits.len() == before {
+                    continue;
+                }
+            }
+            let now = (start..end).map(|i| view.value_at(i)).collect();
+            let w = &mut self.watchpoints[slot];
+            w.last = Some(now);
+            w.call_depth = call_depth;
+        }
+        for hit in &hits {
+            host.on_watchpoint(hit);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vm::test_support::{RecordingHost, test_vm};
+
+    #[test]
+    fn parse_list_reads_names_and_indices() {
+        let points = VmWatchpoint::parse_list("A[3], z ,counter[2],call.l[0]").unwrap();
+        assert_eq!(
+            points,
+            vec![
+                VmWatchpoint::new(VmWatchList::A, Some(3)),
+                VmWatchpoint::new(VmWatchList::Z, None),
+                VmWatchpoint::new(VmWatchList::Counter, Some(2)),
+                VmWatchpoint::new(VmWatchList::CallL, Some(0)),
+            ]
+        );
+        assert!(VmWatchpoint::parse_list("Q[1]").is_err());
+        assert!(VmWatchpoint::parse_list("A[1").is_err());
+    }
+
+    #[test]
+    fn poll_reports_only_changed_slots() {
+        let mut vm = test_vm();
+        let mut host = RecordingHost::default();
+        vm.flags_a = vec![0; 8];
+        vm.add_watchpoint(VmWatchpoint::new(VmWatchList::A, Some(3)));
+        vm.poll_watchpoints(&mut host);
+        assert!(host.watch_hits.is_empty());
+
+        vm.flags_a[2] = 5;
+        vm.poll_watchpoints(&mut host);
+        assert!(host.watch_hits.is_empty());
+
+        vm.flags_a[3] = 7;
+        vm.poll_watchpoints(&mut host);
+        assert_eq!(host.watch_hits.len(), 1);
+        let hit = &host.watch_hits[0];
+        assert_eq!(hit.index, 3);
+        assert_eq!(hit.old, Some(VmWatchValue::Int(0)));
+        assert_eq!(hit.new, Some(VmWatchValue::Int(7)));
+
+        vm.poll_watchpoints(&mut host);
+        assert_eq!(host.watch_hits.len(), 1);
+    }
+
+    #[test]
+    fn poll_reports_list_growth() {
+        let mut vm = test_vm();
+        let mut host = RecordingHost::default();
+        vm.flags_s = vec!["a".to_string()];
+        vm.add_watchpoint(VmWatchpoint::new(VmWatchList::S, None));
+        vm.poll_watchpoints(&mut host);
+        vm.flags_s.push("b".to_string());
+        vm.poll_watchpoints(&mut host);
+        assert_eq!(host.watch_hits.len(), 1);
+        assert_eq!(host.watch_hits[0].old, None);
+        assert_eq!(
+            host.watch_hits[0].new,
+            Some(VmWatchValue::Str("b".to_string()))
+        );
+    }
+}
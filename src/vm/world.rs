@@ -0,0 +1,217 @@
+use super::IntEvent;
+use crate::elm::world::*;
+
+/// One `stage[n].world[m]` (C++ elm_world.cpp `C_elm_world`).
+///
+/// Camera components are script units (`camera_view_angle` in tenths of a
+/// degree) held as clock-driven events so `_eve` tweens reach hosts the same
+/// way as plain assigns; `camera_at` resolves them for the renderer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VmWorld {
+    pub eye_x: IntEvent,
+    pub eye_y: IntEvent,
+    pub eye_z: IntEvent,
+    pub pint_x: IntEvent,
+    pub pint_y: IntEvent,
+    pub pint_z: IntEvent,
+    pub up_x: IntEvent,
+    pub up_y: IntEvent,
+    pub up_z: IntEvent,
+    pub view_angle: i32,
+    pub mono: i32,
+    pub order: i32,
+    pub layer: i32,
+    pub wipe_copy: i32,
+    pub wipe_erase: i32,
+}
+
+impl Default for VmWorld {
+    /// C++ `C_elm_world::reinit`; see `render::WorldCamera::default`.
+    fn default() -> Self {
+        Self {
+            eye_x: IntEvent::new(0),
+            eye_y: IntEvent::new(0),
+            eye_z: IntEvent::new(-1000),
+            pint_x: IntEvent::new(0),
+            pint_y: IntEvent::new(0),
+            pint_z: IntEvent::new(0),
+            up_x: IntEvent::new(0),
+            up_y: IntEvent::new(1),
+            up_z: IntEvent::new(0),
+            view_angle: 450,
+            mono: 0,
+            order: 0,
+            layer: 0,
+            wipe_copy: 0,
+            wipe_erase: 0,
+        }
+    }
+}
+
+impl VmWorld {
+    /// Camera property by `ELM_WORLD_CAMERA_*` id; `*_EVE` ids map to their property.
+    pub fn int_event(&self, prop: i32) -> Option<&IntEvent> {
+        self.int_events().into_iter().nth(Self::event_index(prop)?)
+    }
+
+    pub fn int_event_mut(&mut self, prop: i32) -> Option<&mut IntEvent> {
+        let idx = Self::event_index(prop)?;
+        self.int_events_mut().into_iter().nth(idx)
+    }
+
+    /// Plain int property (`camera_view_angle`, `mono`, `order`, `layer`,
+    /// `wipe_copy`, `wipe_erase`).
+    pub fn int_prop_mut(&mut self, prop: i32) -> Option<&mut i32> {
+        match prop {
+            ELM_WORLD_CAMERA_VIEW_ANGLE => Some(&mut self.view_angle),
+            ELM_WORLD_MONO => Some(&mut self.mono),
+            ELM_WORLD_ORDER => Some(&mut self.order),
+            ELM_WORLD_LAYER => Some(&mut self.layer),
+            ELM_WORLD_WIPE_COPY => Some(&mut self.wipe_copy),
+            ELM_WORLD_WIPE_ERASE => Some(&mut self.wipe_erase),
+            _ => None,
+        }
+    }
+
+    /// Property value at `now_ms`.
+    pub fn prop_at(&self, prop: i32, now_ms: u64) -> Option<i32> {
+        self.int_event(prop).map(|e| e.value_at(now_ms))
+    }
+
+    /// `set_camera_eye` / `set_camera_pint` / `set_camera_up`.
+    pub fn set_camera(&mut self, sub: i32, v: [i32; 3]) {
+        let first = match sub {
+            ELM_WORLD_SET_CAMERA_EYE => ELM_WORLD_CAMERA_EYE_X,
+            ELM_WORLD_SET_CAMERA_PINT => ELM_WORLD_CAMERA_PINT_X,
+            ELM_WORLD_SET_CAMERA_UP => ELM_WORLD_CAMERA_UP_X,
+            _ => return,
+        };
+        for (i, value) in v.into_iter().enumerate() {
+            if let Some(ev) = self.int_event_mut(first + i as i32) {
+                ev.set_value(value);
+            }
+        }
+    }
+
+    /// `calc_camera_eye` / `calc_camera_pint` on the camera as it is at `now_ms`.
+    pub fn calc_camera(
+        &mut self,
+        sub: i32,
+        distance: i32,
+        rotate_h: i32,
+        rotate_v: i32,
+        now_ms: u64,
+    ) {
+        let mut camera = self.camera_at(now_ms);
+        let (set, moved) = match sub {
+            ELM_WORLD_CALC_CAMERA_EYE => {
+                camera.calc_eye(distance, rotate_h, rotate_v);
+                (ELM_WORLD_SET_CAMERA_EYE, camera.eye)
+            }
+            ELM_WORLD_CALC_CAMERA_PINT => {
+                camera.calc_pint(distance, rotate_h, rotate_v);
+                (ELM_WORLD_SET_CAMERA_PINT, camera.pint)
+            }
+            _ => return,
+        };
+        self.set_camera(set, moved.map(|v| v.round() as i32));
+    }
+
+    /// Resume the `_eve` animations `delta_ms` later (see `IntEvent::shift_ms`).
+    pub(super) fn shift_ms(&mut self, delta_ms: u64) {
+        for ev in self.int_events_mut() {
+            ev.shift_ms(delta_ms);
+        }
+    }
+
+    /// Whether any camera animation is still running at `now_ms`.
+    pub fn is_animating(&self, now_ms: u64) -> bool {
+        self.int_events().iter().any(|e| e.is_active(now_ms))
+    }
+
+    /// Renderer camera at `now_ms` on the VM clock.
+    pub fn camera_at(&self, now_ms: u64) -> crate::render::WorldCamera {
+        let v = |prop: i32| self.prop_at(prop, now_ms).unwrap_or(0) as f32;
+        crate::render::WorldCamera {
+            eye: [
+                v(ELM_WORLD_CAMERA_EYE_X),
+                v(ELM_WORLD_CAMERA_EYE_Y),
+                v(ELM_WORLD_CAMERA_EYE_Z),
+            ],
+            pint: [
+                v(ELM_WORLD_CAMERA_PINT_X),
+                v(ELM_WORLD_CAMERA_PINT_Y),
+                v(ELM_WORLD_CAMERA_PINT_Z),
+            ],
+            up: [
+                v(ELM_WORLD_CAMERA_UP_X),
+                v(ELM_WORLD_CAMERA_UP_Y),
+                v(ELM_WORLD_CAMERA_UP_Z),
+            ],
+            view_angle_deg: self.view_angle as f32 / 10.0,
+        }
+    }
+
+    /// Index into `int_events()`: `ELM_WORLD_CAMERA_EYE_X..=UP_Z` and their `_EVE` twins.
+    fn event_index(prop: i32) -> Option<usize> {
+        match prop {
+            ELM_WORLD_CAMERA_EYE_X..=ELM_WORLD_CAMERA_UP_Z => {
+                Some((prop - ELM_WORLD_CAMERA_EYE_X) as usize)
+            }
+            ELM_WORLD_CAMERA_EYE_X_EVE..=ELM_WORLD_CAMERA_UP_Z_EVE => {
+                Some((prop - ELM_WORLD_CAMERA_EYE_X_EVE) as usize)
+            }
+            _ => None,
+        }
+    }
+
+    fn int_events(&self) -> [&IntEvent; 9] {
+        [
+            &self.eye_x,
+            &self.eye_y,
+            &self.eye_z,
+            &self.pint_x,
+            &self.pint_y,
+            &self.pint_z,
+            &self.up_x,
+            &self.up_y,
+            &self.up_z,
+        ]
+    }
+
+    fn int_events_mut(&mut self) -> [&mut IntEvent; 9] {
+        [
+            &mut self.eye_x,
+            &mut self.eye_y,
+            &mut self.eye_z,
+            &mut self.pint_x,
+            &mut self.pint_y,
+            &mut self.pint_z,
+            &mut self.up_x,
+            &mut self.up_y,
+            &mut self.up_z,
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn camera_events_and_calc_move_the_resolved_camera() {
+        let mut world = VmWorld::default();
+        assert_eq!(world.camera_at(0), crate::render::WorldCamera::default());
+
+        world
+            .int_event_mut(ELM_WORLD_CAMERA_EYE_Y_EVE)
+            .unwrap()
+            .set_event(100, 100, 0, 0, 0);
+        assert!(world.is_animating(50));
+        assert_eq!(world.camera_at(50).eye[1], 50.0);
+
+        world.calc_camera(ELM_WORLD_CALC_CAMERA_EYE, 500, 900, 0, 200);
+        assert_eq!(world.prop_at(ELM_WORLD_CAMERA_EYE_X, 200), Some(500));
+        assert_eq!(world.prop_at(ELM_WORLD_CAMERA_EYE_Z, 200), Some(0));
+    }
+}